@@ -0,0 +1,598 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use leptos::*;
+use web_sys::wasm_bindgen::JsCast;
+
+use super::Color;
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 48.0;
+const MARGIN_RIGHT: f64 = 12.0;
+const MARGIN_TOP: f64 = 8.0;
+const MARGIN_BOTTOM: f64 = 24.0;
+const MAX_POINTS: usize = 120;
+const SPARKLINE_MAX_POINTS: usize = 60;
+const X_TICKS: usize = 5;
+const Y_TICKS: usize = 4;
+const PALETTE: [Color; 5] = [
+    Color::Blue,
+    Color::Green,
+    Color::Yellow,
+    Color::Red,
+    Color::Gray,
+];
+
+pub type Points = Vec<(DateTime<Utc>, f64)>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChartKind {
+    #[default]
+    Line,
+    Bar,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartSeries {
+    pub label: String,
+    pub points: Points,
+    pub color: Option<Color>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ChartData {
+    timestamps: Vec<DateTime<Utc>>,
+    series: Vec<(String, Color, Vec<Option<f64>>)>,
+    max: f64,
+}
+
+#[component]
+pub fn Chart(
+    #[prop(into)] series: Signal<Vec<ChartSeries>>,
+    #[prop(optional)] kind: ChartKind,
+    #[prop(optional, into)] format_value: Option<Callback<f64, String>>,
+    #[prop(optional, into)] empty_text: Option<String>,
+) -> impl IntoView {
+    let data = create_memo(move |_| series.with(|series| ChartData::new(series, kind)));
+    let hovered = create_rw_signal(None::<usize>);
+    let format_value = move |value: f64| match format_value {
+        Some(format_value) => format_value.call(value),
+        None => format_compact(value),
+    };
+    let empty_text = empty_text.unwrap_or_else(|| "No data for this period.".to_string());
+
+    let on_pointer = move |ev: ev::MouseEvent| {
+        let Some(element) = ev
+            .current_target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        else {
+            return;
+        };
+        let rect = element.get_bounding_client_rect();
+        if rect.width() > 0.0 {
+            let x = (ev.client_x() as f64 - rect.left()) * WIDTH / rect.width();
+            hovered.set(data.with(|data| data.slot_at(x, kind)));
+        }
+    };
+
+    view! {
+        <div class="w-full">
+            <Show
+                when=move || data.with(|data| !data.timestamps.is_empty())
+                fallback=move || {
+                    let empty_text = empty_text.clone();
+                    view! {
+                        <div class="flex items-center justify-center h-40 text-sm text-gray-500 dark:text-gray-400">
+                            {empty_text}
+                        </div>
+                    }
+                }
+            >
+
+                <div class="relative">
+                    <svg
+                        class="w-full h-auto text-gray-500 dark:text-gray-400"
+                        viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+                        role="img"
+                        on:mousemove=on_pointer
+                        on:mouseleave=move |_| hovered.set(None)
+                    >
+                        <g class="text-[10px]" fill="currentColor">
+                            {move || {
+                                data.with(|data| {
+                                    (0..=Y_TICKS)
+                                        .map(|tick| {
+                                            let value = data.max * tick as f64 / Y_TICKS as f64;
+                                            let y = data.y(value);
+                                            view! {
+                                                <line
+                                                    x1=MARGIN_LEFT
+                                                    x2=WIDTH - MARGIN_RIGHT
+                                                    y1=y
+                                                    y2=y
+                                                    class="stroke-gray-200 dark:stroke-gray-700"
+                                                    stroke-width="1"
+                                                ></line>
+                                                <text
+                                                    x=MARGIN_LEFT - 6.0
+                                                    y=y + 3.0
+                                                    text-anchor="end"
+                                                >
+                                                    {format_value(value)}
+                                                </text>
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                            }}
+                            {move || {
+                                data.with(|data| {
+                                    data.x_ticks(kind)
+                                        .into_iter()
+                                        .map(|(x, label)| {
+                                            view! {
+                                                <text x=x y=HEIGHT - 6.0 text-anchor="middle">
+                                                    {label}
+                                                </text>
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                            }}
+
+                        </g>
+                        {move || {
+                            data.with(|data| {
+                                data.series
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(series_idx, (_, color, values))| {
+                                        let shapes = match kind {
+                                            ChartKind::Line => data.line(values).into_view(),
+                                            ChartKind::Bar => {
+                                                data.bars(series_idx, values).into_view()
+                                            }
+                                        };
+                                        view! {
+                                            <g class=color.chart_class() fill="currentColor">
+                                                {shapes}
+                                            </g>
+                                        }
+                                    })
+                                    .collect_view()
+                            })
+                        }}
+                        {move || {
+                            hovered
+                                .get()
+                                .map(|slot| {
+                                    let x = data.with(|data| data.x(slot, kind));
+                                    view! {
+                                        <line
+                                            x1=x
+                                            x2=x
+                                            y1=MARGIN_TOP
+                                            y2=HEIGHT - MARGIN_BOTTOM
+                                            class="stroke-gray-400 dark:stroke-gray-500"
+                                            stroke-width="1"
+                                            stroke-dasharray="3 3"
+                                        ></line>
+                                    }
+                                })
+                        }}
+
+                    </svg>
+                    {move || {
+                        hovered
+                            .get()
+                            .and_then(|slot| {
+                                data.with(|data| {
+                                    let timestamp = *data.timestamps.get(slot)?;
+                                    let left = data.x(slot, kind) * 100.0 / WIDTH;
+                                    let rows = data
+                                        .series
+                                        .iter()
+                                        .map(|(label, color, values)| {
+                                            let value = values
+                                                .get(slot)
+                                                .copied()
+                                                .flatten()
+                                                .map(format_value)
+                                                .unwrap_or_else(|| "—".to_string());
+                                            view! {
+                                                <div class="flex items-center gap-x-2">
+                                                    <span class=format!(
+                                                        "size-2 rounded-full {}",
+                                                        color.legend_class(),
+                                                    )></span>
+                                                    <span class="grow">{label.clone()}</span>
+                                                    <span class="font-medium">{value}</span>
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view();
+                                    Some(
+                                        view! {
+                                            <div
+                                                class="pointer-events-none absolute top-0 -translate-x-1/2 min-w-32 py-1.5 px-2.5 rounded-md bg-gray-900 text-xs text-white shadow-md dark:bg-slate-700"
+                                                style=format!("left: {left:.2}%")
+                                                role="tooltip"
+                                            >
+                                                <p class="mb-1 text-gray-300">
+                                                    {format_timestamp(timestamp, data.span(), true)}
+                                                </p>
+                                                {rows}
+                                            </div>
+                                        },
+                                    )
+                                })
+                            })
+                    }}
+
+                </div>
+                <div class="flex flex-wrap justify-center gap-x-4 gap-y-1 mt-2">
+                    {move || {
+                        data.with(|data| {
+                            data.series
+                                .iter()
+                                .map(|(label, color, _)| {
+                                    view! {
+                                        <span class="inline-flex items-center gap-x-1.5 text-xs text-gray-600 dark:text-gray-400">
+                                            <span class=format!(
+                                                "size-2.5 rounded-sm {}",
+                                                color.legend_class(),
+                                            )></span>
+                                            {label.clone()}
+                                        </span>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                    }}
+
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+pub fn Sparkline(#[prop(into)] series: Signal<Points>) -> impl IntoView {
+    const WIDTH: f64 = 100.0;
+    const HEIGHT: f64 = 24.0;
+
+    let points = create_memo(move |_| {
+        series.with(|series| {
+            let series = downsample(series, SPARKLINE_MAX_POINTS, false);
+            let (Some((first, _)), Some((last, _))) = (series.first(), series.last()) else {
+                return String::new();
+            };
+            let span = (*last - *first).num_seconds().max(1) as f64;
+            let max = series
+                .iter()
+                .map(|(_, value)| *value)
+                .fold(0.0, f64::max)
+                .max(1.0);
+            series
+                .iter()
+                .map(|(timestamp, value)| {
+                    let x = if series.len() > 1 {
+                        (*timestamp - *first).num_seconds() as f64 * WIDTH / span
+                    } else {
+                        WIDTH / 2.0
+                    };
+                    format!("{x:.2},{:.2}", HEIGHT - value * HEIGHT / max)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    });
+
+    view! {
+        <svg
+            class="mt-3 w-full h-8 text-blue-600 dark:text-blue-500"
+            class:hidden=move || points.get().is_empty()
+            viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+            preserveAspectRatio="none"
+            aria-hidden="true"
+        >
+            <polyline
+                points=move || points.get()
+                fill="none"
+                stroke="currentColor"
+                stroke-width="1.5"
+                stroke-linejoin="round"
+                vector-effect="non-scaling-stroke"
+            ></polyline>
+        </svg>
+    }
+}
+
+impl ChartSeries {
+    pub fn new(label: impl Into<String>, points: Points) -> Self {
+        ChartSeries {
+            label: label.into(),
+            points,
+            color: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl ChartData {
+    fn new(series: &[ChartSeries], kind: ChartKind) -> Self {
+        let mut timestamps = series
+            .iter()
+            .flat_map(|series| series.points.iter().map(|(timestamp, _)| *timestamp))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let (Some(first), Some(last)) = (timestamps.first().copied(), timestamps.last().copied())
+        else {
+            return ChartData::default();
+        };
+
+        // Large series are bucketed into equal time intervals
+        let span = (last - first).num_milliseconds().max(1);
+        let buckets = timestamps.len().min(MAX_POINTS);
+        let bucketed = buckets < timestamps.len();
+        if bucketed {
+            timestamps = (0..buckets)
+                .map(|bucket| {
+                    first + TimeDelta::milliseconds(span * bucket as i64 / buckets as i64)
+                })
+                .collect();
+        }
+        let slot = |timestamp: &DateTime<Utc>| {
+            if bucketed {
+                (((*timestamp - first).num_milliseconds() * buckets as i64 / (span + 1)) as usize)
+                    .min(buckets - 1)
+            } else {
+                timestamps.binary_search(timestamp).unwrap_or_default()
+            }
+        };
+
+        let mut max: f64 = 0.0;
+        let series = series
+            .iter()
+            .enumerate()
+            .map(|(idx, series)| {
+                let mut sums = vec![(0.0, 0usize); timestamps.len()];
+                for (timestamp, value) in &series.points {
+                    let (sum, count) = &mut sums[slot(timestamp)];
+                    *sum += value;
+                    *count += 1;
+                }
+                let values = sums
+                    .into_iter()
+                    .map(|(sum, count)| {
+                        (count > 0).then(|| match kind {
+                            ChartKind::Line => sum / count as f64,
+                            ChartKind::Bar => sum,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                max = values
+                    .iter()
+                    .flatten()
+                    .fold(max, |max, value| max.max(*value));
+                (
+                    series.label.clone(),
+                    series.color.unwrap_or(PALETTE[idx % PALETTE.len()]),
+                    values,
+                )
+            })
+            .collect();
+
+        ChartData {
+            timestamps,
+            series,
+            max: nice_ceiling(max),
+        }
+    }
+
+    fn span(&self) -> TimeDelta {
+        match (self.timestamps.first(), self.timestamps.last()) {
+            (Some(first), Some(last)) => *last - *first,
+            _ => TimeDelta::zero(),
+        }
+    }
+
+    fn plot_width(&self) -> f64 {
+        WIDTH - MARGIN_LEFT - MARGIN_RIGHT
+    }
+
+    fn x(&self, slot: usize, kind: ChartKind) -> f64 {
+        let slots = self.timestamps.len();
+        match kind {
+            // Lines are placed by time, bars get equally sized slots
+            ChartKind::Line if slots > 1 => {
+                let span = self.span().num_milliseconds().max(1) as f64;
+                let offset = (self.timestamps[slot] - self.timestamps[0]).num_milliseconds() as f64;
+                MARGIN_LEFT + offset * self.plot_width() / span
+            }
+            ChartKind::Line => MARGIN_LEFT + self.plot_width() / 2.0,
+            ChartKind::Bar => {
+                MARGIN_LEFT + (slot as f64 + 0.5) * self.plot_width() / slots.max(1) as f64
+            }
+        }
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        HEIGHT - MARGIN_BOTTOM - value * height / self.max
+    }
+
+    fn slot_at(&self, x: f64, kind: ChartKind) -> Option<usize> {
+        (0..self.timestamps.len()).min_by(|a, b| {
+            (self.x(*a, kind) - x)
+                .abs()
+                .total_cmp(&(self.x(*b, kind) - x).abs())
+        })
+    }
+
+    fn x_ticks(&self, kind: ChartKind) -> Vec<(f64, String)> {
+        let slots = self.timestamps.len();
+        let ticks = X_TICKS.min(slots);
+        let span = self.span();
+        let mut labels: Vec<(f64, String)> = Vec::with_capacity(ticks);
+        for tick in 0..ticks {
+            let slot = if ticks > 1 {
+                tick * (slots - 1) / (ticks - 1)
+            } else {
+                0
+            };
+            let label = format_timestamp(self.timestamps[slot], span, false);
+            if labels.last().is_none_or(|(_, last)| *last != label) {
+                labels.push((self.x(slot, kind), label));
+            }
+        }
+        labels
+    }
+
+    fn line(&self, values: &[Option<f64>]) -> impl IntoView {
+        let points = values
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, value)| {
+                value.map(|value| (self.x(slot, ChartKind::Line), self.y(value)))
+            })
+            .collect::<Vec<_>>();
+        let polyline = points
+            .iter()
+            .map(|(x, y)| format!("{x:.2},{y:.2}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        // A lone sample has no line to draw, so it is marked with a dot
+        let dot = (points.len() == 1).then(|| {
+            let (x, y) = points[0];
+            view! { <circle cx=x cy=y r="3"></circle> }
+        });
+
+        view! {
+            <polyline
+                points=polyline
+                fill="none"
+                stroke="currentColor"
+                stroke-width="2"
+                stroke-linejoin="round"
+            ></polyline>
+            {dot}
+        }
+    }
+
+    fn bars(&self, series_idx: usize, values: &[Option<f64>]) -> impl IntoView {
+        let slot_width = self.plot_width() / self.timestamps.len().max(1) as f64;
+        let bar_width = slot_width * 0.8 / self.series.len().max(1) as f64;
+        let baseline = self.y(0.0);
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, value)| {
+                let value = (*value)?;
+                let x =
+                    self.x(slot, ChartKind::Bar) - slot_width * 0.4 + bar_width * series_idx as f64;
+                let y = self.y(value);
+                Some(view! {
+                    <rect x=x y=y width=bar_width height=baseline - y rx="1"></rect>
+                })
+            })
+            .collect_view()
+    }
+}
+
+impl Color {
+    fn chart_class(&self) -> &'static str {
+        match self {
+            Color::Blue => "text-blue-600 dark:text-blue-500",
+            Color::Gray => "text-gray-400 dark:text-gray-500",
+            Color::Red => "text-red-500 dark:text-red-400",
+            Color::Yellow => "text-yellow-500 dark:text-yellow-400",
+            Color::Green => "text-teal-500 dark:text-teal-400",
+        }
+    }
+
+    fn legend_class(&self) -> &'static str {
+        match self {
+            Color::Blue => "bg-blue-600 dark:bg-blue-500",
+            Color::Gray => "bg-gray-400 dark:bg-gray-500",
+            Color::Red => "bg-red-500 dark:bg-red-400",
+            Color::Yellow => "bg-yellow-500 dark:bg-yellow-400",
+            Color::Green => "bg-teal-500 dark:bg-teal-400",
+        }
+    }
+}
+
+pub fn downsample(points: &[(DateTime<Utc>, f64)], max_points: usize, sum: bool) -> Points {
+    if points.len() <= max_points || max_points == 0 {
+        return points.to_vec();
+    }
+    points
+        .chunks(points.len().div_ceil(max_points))
+        .map(|chunk| {
+            let total = chunk.iter().map(|(_, value)| value).sum::<f64>();
+            (
+                chunk[0].0,
+                if sum {
+                    total
+                } else {
+                    total / chunk.len() as f64
+                },
+            )
+        })
+        .collect()
+}
+
+fn nice_ceiling(value: f64) -> f64 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(value.log10().floor());
+    let fraction = value / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+pub fn format_compact(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1_000_000_000.0 {
+        format!("{:.1}G", value / 1_000_000_000.0)
+    } else if abs >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if abs >= 1_000.0 {
+        format!("{:.1}k", value / 1_000.0)
+    } else if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>, span: TimeDelta, detailed: bool) -> String {
+    let timestamp = timestamp.with_timezone(&Local);
+    let format = match (span > TimeDelta::days(1), detailed) {
+        (false, _) => "%H:%M",
+        (true, false) => "%b %d",
+        (true, true) => "%b %d %H:%M",
+    };
+    timestamp.format(format).to_string()
+}
@@ -0,0 +1,108 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Duration;
+
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{wasm_bindgen::JsCast, HtmlInputElement, HtmlTextAreaElement};
+
+use crate::components::{
+    icon::{IconCheck, IconClipboard},
+    messages::alert::{use_alerts, Alert},
+};
+
+const COPIED_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Falls back to selecting the text of `target` when clipboard access is refused
+#[component]
+pub fn CopyButton(
+    #[prop(into)] value: MaybeSignal<String>,
+    #[prop(optional, into)] target: Option<String>,
+) -> impl IntoView {
+    let alert = use_alerts();
+    let copied = create_rw_signal(false);
+    let target = store_value(target);
+
+    let on_click = move |_| {
+        let value = value.get();
+        spawn_local(async move {
+            if write_clipboard(&value).await {
+                copied.set(true);
+                set_timeout(move || copied.set(false), COPIED_TIMEOUT);
+            } else {
+                let details =
+                    if target.with_value(|target| target.as_deref().is_some_and(select_text)) {
+                        "The text has been selected, press Ctrl+C to copy it.".to_string()
+                    } else {
+                        format!("Copy the value manually: {value}")
+                    };
+                alert.set(Alert::warning("Clipboard access denied").with_details(details));
+            }
+        });
+    };
+
+    view! {
+        <span class="relative inline-flex">
+            <button
+                type="button"
+                class="p-1 inline-flex items-center rounded-md text-gray-500 hover:text-blue-600 hover:bg-gray-100 focus:outline-none focus:ring-2 focus:ring-blue-500 dark:text-gray-400 dark:hover:text-blue-500 dark:hover:bg-gray-800"
+                title="Copy to clipboard"
+                on:click=on_click
+            >
+                <Show
+                    when=move || copied.get()
+                    fallback=|| view! { <IconClipboard attr:class="flex-shrink-0 size-4"/> }
+                >
+                    <IconCheck attr:class="flex-shrink-0 size-4 text-teal-500"/>
+                </Show>
+                <span class="sr-only">Copy to clipboard</span>
+            </button>
+            <span
+                class="absolute bottom-full start-1/2 -translate-x-1/2 mb-1 py-1 px-2 whitespace-nowrap rounded-md bg-gray-900 text-xs font-medium text-white shadow-sm dark:bg-slate-700"
+                class:hidden=move || !copied.get()
+                role="status"
+            >
+                Copied!
+            </span>
+        </span>
+    }
+}
+
+pub async fn write_clipboard(value: &str) -> bool {
+    // The Clipboard API is only exposed on secure origins
+    let clipboard = window().navigator().clipboard();
+    if clipboard.is_undefined() {
+        return false;
+    }
+    JsFuture::from(clipboard.write_text(value)).await.is_ok()
+}
+
+fn select_text(id: &str) -> bool {
+    let Some(element) = document().get_element_by_id(id) else {
+        return false;
+    };
+    if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+        let _ = input.focus();
+        input.select();
+    } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+        let _ = textarea.focus();
+        textarea.select();
+    } else {
+        let Some(selection) = window().get_selection().ok().flatten() else {
+            return false;
+        };
+        let Ok(range) = document().create_range() else {
+            return false;
+        };
+        if range.select_node_contents(&element).is_err() {
+            return false;
+        }
+        let _ = selection.remove_all_ranges();
+        let _ = selection.add_range(&range);
+    }
+    true
+}
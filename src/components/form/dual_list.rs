@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use crate::components::icon::{IconArrowLeft, IconArrowRight};
+
+use super::FormElement;
+
+#[component]
+pub fn DualList(
+    element: FormElement,
+    options: Memo<Vec<(String, String)>>,
+    #[prop(into)] available_title: String,
+    #[prop(into)] selected_title: String,
+) -> impl IntoView {
+    let filter = create_rw_signal(String::new());
+    let selected = create_memo(move |_| {
+        element.data.with(|data| {
+            data.array_value(element.id)
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+        })
+    });
+    let label = move |id: &str| {
+        options.with(|options| {
+            options
+                .iter()
+                .find(|(option_id, _)| option_id == id)
+                .map(|(_, label)| label.clone())
+                .unwrap_or_else(|| id.to_string())
+        })
+    };
+    let available = create_memo(move |_| {
+        let filter = filter.get().trim().to_lowercase();
+        let selected = selected.get();
+        options
+            .get()
+            .into_iter()
+            .filter(|(id, label)| {
+                !selected.contains(id)
+                    && (filter.is_empty()
+                        || id.to_lowercase().contains(&filter)
+                        || label.to_lowercase().contains(&filter))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    view! {
+        <div class="grid sm:grid-cols-2 gap-4">
+            <div class="border border-gray-200 rounded-lg dark:border-gray-700">
+                <div class="px-3 py-2 border-b border-gray-200 dark:border-gray-700">
+                    <span class="block mb-2 text-xs font-semibold uppercase text-gray-500 dark:text-gray-400">
+                        {available_title}
+                    </span>
+                    <input
+                        type="text"
+                        class="py-1.5 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                        placeholder="Search"
+                        prop:value=filter
+                        on:input=move |ev| {
+                            filter.set(event_target_value(&ev));
+                        }
+                    />
+
+                </div>
+                <ul class="max-h-60 overflow-y-auto divide-y divide-gray-200 dark:divide-gray-700">
+                    <For
+                        each=move || available.get()
+                        key=|(id, _)| id.clone()
+                        children=move |(id, label)| {
+                            view! {
+                                <li>
+                                    <button
+                                        type="button"
+                                        class="w-full flex justify-between items-center gap-x-2 py-2 px-3 text-sm text-start text-gray-800 hover:bg-gray-50 dark:text-gray-200 dark:hover:bg-gray-800"
+                                        on:click=move |_| {
+                                            element
+                                                .data
+                                                .update(|data| {
+                                                    data.array_push(element.id, id.clone(), true);
+                                                });
+                                        }
+                                    >
+
+                                        <span class="truncate">{label}</span>
+                                        <IconArrowRight attr:class="flex-shrink-0 size-3.5 text-gray-400"/>
+                                    </button>
+                                </li>
+                            }
+                        }
+                    />
+
+                    <Show when=move || available.with(|available| available.is_empty())>
+                        <li class="py-2 px-3 text-sm text-gray-500">No matches</li>
+                    </Show>
+                </ul>
+            </div>
+
+            <div class="border border-gray-200 rounded-lg dark:border-gray-700">
+                <div class="px-3 py-2 border-b border-gray-200 dark:border-gray-700">
+                    <span class="block text-xs font-semibold uppercase text-gray-500 dark:text-gray-400">
+                        {selected_title}
+                        " ("
+                        {move || selected.get().len()}
+                        ")"
+                    </span>
+                </div>
+                <ul class="max-h-60 overflow-y-auto divide-y divide-gray-200 dark:divide-gray-700">
+                    <For
+                        each=move || selected.get()
+                        key=|id| id.clone()
+                        children=move |id| {
+                            let text = label(&id);
+                            view! {
+                                <li>
+                                    <button
+                                        type="button"
+                                        class="w-full flex items-center gap-x-2 py-2 px-3 text-sm text-start text-gray-800 hover:bg-gray-50 dark:text-gray-200 dark:hover:bg-gray-800"
+                                        on:click=move |_| {
+                                            element
+                                                .data
+                                                .update(|data| {
+                                                    data.array_delete_item(element.id, &id);
+                                                });
+                                        }
+                                    >
+
+                                        <IconArrowLeft attr:class="flex-shrink-0 size-3.5 text-gray-400"/>
+                                        <span class="truncate">{text}</span>
+                                    </button>
+                                </li>
+                            }
+                        }
+                    />
+
+                </ul>
+            </div>
+        </div>
+    }
+}
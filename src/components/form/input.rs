@@ -8,9 +8,9 @@ use std::str::FromStr;
 
 use leptos::*;
 
-use crate::components::icon::{IconClock, IconExclamationCircle, IconInfo};
+use crate::components::icon::{IconClock, IconExclamationCircle};
 
-use super::FormElement;
+use super::{FormElement, HelpTooltip};
 
 #[component]
 pub fn InputText(
@@ -82,6 +82,8 @@ pub fn InputPassword(
     element: FormElement,
     #[prop(optional, into)] placeholder: Option<MaybeSignal<String>>,
     #[prop(optional, into)] disabled: MaybeSignal<bool>,
+    #[prop(optional, into)] on_input: Option<Callback<String>>,
+    #[prop(optional)] revealed: bool,
     #[prop(attrs)] attrs: Vec<(&'static str, Attribute)>,
 ) -> impl IntoView {
     let value = create_memo(move |_| {
@@ -98,7 +100,7 @@ pub fn InputPassword(
             .error_string(element.id)
             .map(|s| s.to_string())
     });
-    let show_password = create_rw_signal(false);
+    let show_password = create_rw_signal(revealed);
 
     view! {
         <div class="relative">
@@ -107,15 +109,20 @@ pub fn InputPassword(
                 type=move || if show_password.get() { "text" } else { "password" }
                 class=move || {
                     if error.get().is_none() {
-                        "py-2 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        "py-2 ps-3 pe-11 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
                     } else {
-                        "py-2 px-3 block w-full border-red-500 rounded-lg text-sm focus:border-red-500 focus:ring-red-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        "py-2 ps-3 pe-11 block w-full border-red-500 rounded-lg text-sm focus:border-red-500 focus:ring-red-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
                     }
                 }
 
                 placeholder=placeholder.map(|p| move || p.get())
                 prop:value=move || value.get()
                 disabled=move || disabled.get()
+                on:input=move |ev| {
+                    if let Some(on_input) = on_input {
+                        on_input.call(event_target_value(&ev));
+                    }
+                }
                 on:change=move |ev| {
                     element
                         .data
@@ -125,66 +132,10 @@ pub fn InputPassword(
                 }
             />
 
-            <button
-                type="button"
-                class="absolute top-0 end-0 p-3.5 rounded-e-md dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                on:click=move |_| {
-                    show_password.update(|v| *v = !*v);
-                }
-            >
-
-                <svg
-                    class=move || {
-                        let color = if error.get().is_none() { "gray-400" } else { "red-500" };
-                        format!("flex-shrink-0 size-3.5 text-{color} dark:text-neutral-600")
-                    }
-
-                    width="24"
-                    height="24"
-                    viewBox="0 0 24 24"
-                    fill="none"
-                    stroke="currentColor"
-                    stroke-width="2"
-                    stroke-linecap="round"
-                    stroke-linejoin="round"
-                >
-                    <path
-                        class="hs-password-active:hidden"
-                        class:hidden=move || show_password.get()
-                        d="M9.88 9.88a3 3 0 1 0 4.24 4.24"
-                    ></path>
-                    <path
-                        class="hs-password-active:hidden"
-                        class:hidden=move || show_password.get()
-                        d="M10.73 5.08A10.43 10.43 0 0 1 12 5c7 0 10 7 10 7a13.16 13.16 0 0 1-1.67 2.68"
-                    ></path>
-                    <path
-                        class="hs-password-active:hidden"
-                        class:hidden=move || show_password.get()
-                        d="M6.61 6.61A13.526 13.526 0 0 0 2 12s3 7 10 7a9.74 9.74 0 0 0 5.39-1.61"
-                    ></path>
-                    <line
-                        class="hs-password-active:hidden"
-                        class:hidden=move || show_password.get()
-                        x1="2"
-                        x2="22"
-                        y1="2"
-                        y2="22"
-                    ></line>
-                    <path
-                        class:hidden=move || !show_password.get()
-                        class="hs-password-active:block"
-                        d="M2 12s3-7 10-7 10 7 10 7-3 7-10 7-10-7-10-7Z"
-                    ></path>
-                    <circle
-                        class="hs-password-active:block"
-                        class:hidden=move || !show_password.get()
-                        cx="12"
-                        cy="12"
-                        r="3"
-                    ></circle>
-                </svg>
-            </button>
+            <RevealToggle
+                revealed=show_password
+                has_error=Signal::derive(move || error.get().is_some())
+            />
         </div>
 
         {move || {
@@ -197,8 +148,80 @@ pub fn InputPassword(
     }
 }
 
+#[component]
+pub fn RevealToggle(
+    revealed: RwSignal<bool>,
+    #[prop(into)] has_error: Signal<bool>,
+) -> impl IntoView {
+    view! {
+        <button
+            type="button"
+            class="absolute top-0 end-0 p-3.5 rounded-e-md dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+            aria-label=move || if revealed.get() { "Hide password" } else { "Show password" }
+            aria-pressed=move || revealed.get().to_string()
+            on:click=move |_| {
+                revealed.update(|v| *v = !*v);
+            }
+        >
+
+            <svg
+                class=move || {
+                    let color = if has_error.get() { "red-500" } else { "gray-400" };
+                    format!("flex-shrink-0 size-3.5 text-{color} dark:text-neutral-600")
+                }
+
+                width="24"
+                height="24"
+                viewBox="0 0 24 24"
+                fill="none"
+                stroke="currentColor"
+                stroke-width="2"
+                stroke-linecap="round"
+                stroke-linejoin="round"
+            >
+                <path
+                    class="hs-password-active:hidden"
+                    class:hidden=move || revealed.get()
+                    d="M9.88 9.88a3 3 0 1 0 4.24 4.24"
+                ></path>
+                <path
+                    class="hs-password-active:hidden"
+                    class:hidden=move || revealed.get()
+                    d="M10.73 5.08A10.43 10.43 0 0 1 12 5c7 0 10 7 10 7a13.16 13.16 0 0 1-1.67 2.68"
+                ></path>
+                <path
+                    class="hs-password-active:hidden"
+                    class:hidden=move || revealed.get()
+                    d="M6.61 6.61A13.526 13.526 0 0 0 2 12s3 7 10 7a9.74 9.74 0 0 0 5.39-1.61"
+                ></path>
+                <line
+                    class="hs-password-active:hidden"
+                    class:hidden=move || revealed.get()
+                    x1="2"
+                    x2="22"
+                    y1="2"
+                    y2="22"
+                ></line>
+                <path
+                    class:hidden=move || !revealed.get()
+                    class="hs-password-active:block"
+                    d="M2 12s3-7 10-7 10 7 10 7-3 7-10 7-10-7-10-7Z"
+                ></path>
+                <circle
+                    class="hs-password-active:block"
+                    class:hidden=move || !revealed.get()
+                    cx="12"
+                    cy="12"
+                    r="3"
+                ></circle>
+            </svg>
+        </button>
+    }
+}
+
 const UNIT_GB: u64 = 1024 * 1024 * 1024;
 const UNIT_MB: u64 = 1024 * 1024;
+const UNIT_KB: u64 = 1024;
 
 #[component]
 pub fn InputSize(
@@ -210,7 +233,8 @@ pub fn InputSize(
         element
             .data
             .get()
-            .value::<u64>(element.id)
+            .value::<Size>(element.id)
+            .map(|size| size.0)
             .unwrap_or_default()
     });
 
@@ -222,6 +246,8 @@ pub fn InputSize(
             UNIT_GB
         } else if raw_value % UNIT_MB == 0 {
             UNIT_MB
+        } else if raw_value % UNIT_KB == 0 {
+            UNIT_KB
         } else {
             1
         }
@@ -245,7 +271,10 @@ pub fn InputSize(
     view! {
         <div class="relative">
             <input
-                type="text"
+                type="number"
+                min="1"
+                step="1"
+                inputmode="numeric"
                 class="py-2 px-3 block w-full border-gray-200 shadow-sm rounded-lg text-sm focus:z-10 focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
                 prop:value=move || {
                     match display_value.get() {
@@ -266,7 +295,10 @@ pub fn InputSize(
                                     );
                                 }
                                 _ => {
-                                    data.new_error(element.id, "Invalid size".to_string());
+                                    data.new_error(
+                                        element.id,
+                                        "Size must be a whole number greater than zero".to_string(),
+                                    );
                                 }
                             }
                         });
@@ -305,6 +337,9 @@ pub fn InputSize(
                     <option selected=move || multiplier.get() == 1 value="1">
                         bytes
                     </option>
+                    <option selected=move || multiplier.get() == UNIT_KB value=UNIT_KB.to_string()>
+                        KB
+                    </option>
                     <option selected=move || multiplier.get() == UNIT_MB value=UNIT_MB.to_string()>
                         MB
                     </option>
@@ -349,7 +384,10 @@ pub fn InputDuration(
     view! {
         <div class="relative">
             <input
-                type="text"
+                type="number"
+                min="1"
+                step="1"
+                inputmode="numeric"
                 class="py-2 px-3 block w-full border-gray-200 shadow-sm rounded-lg text-sm focus:z-10 focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
                 prop:value=move || { value.get().value }
 
@@ -357,15 +395,26 @@ pub fn InputDuration(
                     element
                         .data
                         .update(|data| {
-                            match event_target_value(&ev).trim().parse::<u64>() {
+                            let new_value = event_target_value(&ev);
+                            let new_value = new_value.trim();
+                            match new_value.parse::<u64>() {
                                 Ok(new_value) if new_value > 0 => {
                                     data.update(
                                         element.id,
                                         value.get().value(new_value).to_string(),
                                     );
                                 }
+                                _ if new_value.starts_with('-') || new_value == "0" => {
+                                    data.new_error(
+                                        element.id,
+                                        "Duration must be greater than zero".to_string(),
+                                    );
+                                }
                                 _ => {
-                                    data.new_error(element.id, "Invalid duration".to_string());
+                                    data.new_error(
+                                        element.id,
+                                        "Duration must be a whole number".to_string(),
+                                    );
                                 }
                             }
                         });
@@ -585,6 +634,7 @@ pub fn InputSwitch(
     element: FormElement,
     #[prop(optional, into)] label: Option<MaybeSignal<String>>,
     #[prop(optional)] tooltip: Option<&'static str>,
+    #[prop(default = None)] description: Option<&'static str>,
     #[prop(optional, into)] disabled: MaybeSignal<bool>,
     #[prop(attrs)] attrs: Vec<(&'static str, Attribute)>,
 ) -> impl IntoView {
@@ -601,7 +651,7 @@ pub fn InputSwitch(
             <input
                 type="checkbox"
                 {..attrs}
-                class="relative w-11 h-6 p-px bg-gray-100 border-transparent text-transparent rounded-full cursor-pointer transition-colors ease-in-out duration-200 focus:ring-blue-600 disabled:opacity-50 disabled:pointer-events-none checked:bg-none checked:text-blue-600 checked:border-blue-600 focus:checked:border-blue-600 dark:bg-gray-800 dark:border-gray-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-600 before:inline-block before:size-5 before:bg-white checked:before:bg-blue-200 before:translate-x-0 checked:before:translate-x-full before:rounded-full before:shadow before:transform before:ring-0 before:transition before:ease-in-out before:duration-200 dark:before:bg-gray-400 dark:checked:before:bg-blue-200"
+                class="relative w-11 h-6 p-px bg-gray-100 border-transparent text-transparent rounded-full cursor-pointer motion-safe:transition-colors ease-in-out duration-200 focus:ring-blue-600 disabled:opacity-50 disabled:pointer-events-none checked:bg-none checked:text-blue-600 checked:border-blue-600 focus:checked:border-blue-600 dark:bg-gray-800 dark:border-gray-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-600 before:inline-block before:size-5 before:bg-white checked:before:bg-blue-200 before:translate-x-0 checked:before:translate-x-full before:rounded-full before:shadow before:transform before:ring-0 motion-safe:before:transition before:ease-in-out before:duration-200 dark:before:bg-gray-400 dark:checked:before:bg-blue-200"
                 prop:checked=move || value.get()
                 on:input=move |_| {
                     element
@@ -625,40 +675,7 @@ pub fn InputSwitch(
 
             {tooltip
                 .filter(|s| !s.is_empty())
-                .map(|tooltip| {
-                    let is_mouse_over = create_rw_signal(false);
-                    view! {
-                        <div class="hs-tooltip inline-block">
-                            <button
-                                type="button"
-                                class="hs-tooltip-toggle ms-1"
-                                on:mouseover=move |_| {
-                                    is_mouse_over.set(true);
-                                }
-
-                                on:mouseleave=move |_| {
-                                    is_mouse_over.set(false);
-                                }
-                            >
-
-                                <IconInfo
-                                    size=16
-                                    attr:stroke-width="1"
-                                    attr:class="inline-block size-3 text-gray-400 dark:text-gray-600"
-                                />
-                            </button>
-                            <span
-                                class="hs-tooltip-content hs-tooltip-shown:opacity-100 hs-tooltip-shown:visible opacity-70 transition-opacity inline-block absolute w-40 text-center z-10 py-1 px-2 bg-gray-900 text-xs font-medium text-white rounded shadow-sm dark:bg-slate-700"
-                                role="tooltip"
-                                class:hidden=move || !is_mouse_over.get()
-                                class:show=move || is_mouse_over.get()
-                            >
-                                {tooltip}
-                            </span>
-
-                        </div>
-                    }
-                })}
+                .map(|tooltip| view! { <HelpTooltip help=tooltip description/> })}
 
         </div>
     }
@@ -773,6 +790,48 @@ impl std::fmt::Display for Duration {
     }
 }
 
+// The k, m and g suffixes, optionally followed by b, are powers of 1024
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
+pub struct Size(pub u64);
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "0"),
+            size if size % UNIT_GB == 0 => write!(f, "{}G", size / UNIT_GB),
+            size if size % UNIT_MB == 0 => write!(f, "{}M", size / UNIT_MB),
+            size if size % UNIT_KB == 0 => write!(f, "{}K", size / UNIT_KB),
+            size => write!(f, "{size}"),
+        }
+    }
+}
+
+impl FromStr for Size {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_ascii_lowercase();
+        let value = value.strip_suffix('b').unwrap_or(&value);
+        let (amount, multiplier) = if let Some(amount) = value.strip_suffix('k') {
+            (amount, UNIT_KB)
+        } else if let Some(amount) = value.strip_suffix('m') {
+            (amount, UNIT_MB)
+        } else if let Some(amount) = value.strip_suffix('g') {
+            (amount, UNIT_GB)
+        } else {
+            (value, 1)
+        };
+
+        amount
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .and_then(|amount| amount.checked_mul(multiplier))
+            .map(Size)
+            .ok_or(())
+    }
+}
+
 #[derive(Default, PartialEq, Eq, Clone)]
 pub struct Rate {
     pub amount: String,
@@ -5,18 +5,24 @@
  */
 
 pub mod button;
+pub mod dual_list;
 pub mod expression;
 pub mod input;
 pub mod select;
 pub mod stacked_badge;
 pub mod stacked_input;
 pub mod tab;
+pub mod unsaved;
+
+use std::cell::Cell;
 
 use leptos::*;
+use leptos_router::{use_location, use_navigate, NavigateOptions};
+use web_sys::KeyboardEvent;
 
 use crate::{
-    components::{icon::IconInfo, messages::alert::Alerts},
-    core::form::FormData,
+    components::icon::IconInfo,
+    core::{form::FormData, oauth::use_authorization},
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +33,32 @@ pub struct FormElement {
 
 pub type ValidateCb = Callback<Result<String, String>, ()>;
 
+// Viewers following a link to the /edit route are sent to the /view one
+pub fn use_read_only_form() -> Memo<bool> {
+    let location = use_location();
+    let path = location.pathname.get_untracked();
+
+    if let Some(base) = path
+        .strip_suffix("/edit")
+        .filter(|_| use_authorization().get_untracked().is_viewer())
+    {
+        let search = location.search.get_untracked();
+        use_navigate()(
+            &if search.is_empty() {
+                format!("{base}/view")
+            } else {
+                format!("{base}/view?{search}")
+            },
+            NavigateOptions {
+                replace: true,
+                ..Default::default()
+            },
+        );
+    }
+
+    create_memo(move |_| location.pathname.with(|path| path.ends_with("/view")))
+}
+
 #[component]
 pub fn Form(
     #[prop(optional, into)] title: MaybeSignal<String>,
@@ -45,8 +77,6 @@ pub fn Form(
                     <p class="text-sm text-gray-600 dark:text-gray-400">{move || subtitle.get()}</p>
                 </div>
 
-                <Alerts/>
-
                 <form>{children()}</form>
             </div>
         </div>
@@ -95,47 +125,15 @@ pub fn FormSection(
 pub fn FormItem(
     #[prop(into)] label: MaybeSignal<String>,
     #[prop(optional)] tooltip: Option<&'static str>,
+    #[prop(default = None)] description: Option<&'static str>,
     #[prop(optional, into)] hide: MaybeSignal<bool>,
     #[prop(optional, into)] is_optional: MaybeSignal<bool>,
     #[prop(optional)] stacked: bool,
     children: Children,
 ) -> impl IntoView {
     let tooltip = tooltip
-    .filter(|s| !s.is_empty())
-    .map(|tooltip| {
-        let is_mouse_over = create_rw_signal(false);
-        view! {
-            <div class="hs-tooltip inline-block">
-                <button
-                    type="button"
-                    class="hs-tooltip-toggle ms-1"
-                    on:mouseover=move |_| {
-                        is_mouse_over.set(true);
-                    }
-
-                    on:mouseleave=move |_| {
-                        is_mouse_over.set(false);
-                    }
-                >
-
-                    <IconInfo
-                        size=16
-                        attr:stroke-width="1"
-                        attr:class="inline-block size-3 text-gray-400 dark:text-gray-600"
-                    />
-                </button>
-                <span
-                    class="hs-tooltip-content hs-tooltip-shown:opacity-100 hs-tooltip-shown:visible opacity-70 transition-opacity inline-block absolute w-40 text-center z-10 py-1 px-2 bg-gray-900 text-xs font-medium text-white rounded shadow-sm dark:bg-slate-700"
-                    role="tooltip"
-                    class:hidden=move || !is_mouse_over.get()
-                    class:show=move || is_mouse_over.get()
-                >
-                    {tooltip}
-                </span>
-
-            </div>
-        }
-    });
+        .filter(|s| !s.is_empty())
+        .map(|tooltip| view! { <HelpTooltip help=tooltip description/> });
 
     let is_optional = move || {
         if is_optional.get() {
@@ -178,3 +176,69 @@ impl FormElement {
         FormElement { id, data }
     }
 }
+
+thread_local! {
+    static NEXT_TOOLTIP_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+#[component]
+pub fn HelpTooltip(
+    help: &'static str,
+    #[prop(default = None)] description: Option<&'static str>,
+) -> impl IntoView {
+    let id = NEXT_TOOLTIP_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        format!("help-tooltip-{id}")
+    });
+    let is_hovered = create_rw_signal(false);
+    let is_focused = create_rw_signal(false);
+    let is_shown = move || is_hovered.get() || is_focused.get();
+
+    view! {
+        <div class="hs-tooltip inline-block">
+            <button
+                type="button"
+                class="hs-tooltip-toggle ms-1 rounded focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500"
+                aria-label="Help"
+                aria-describedby=id.clone()
+                on:mouseover=move |_| is_hovered.set(true)
+                on:mouseleave=move |_| is_hovered.set(false)
+                on:focus=move |_| is_focused.set(true)
+                on:blur=move |_| is_focused.set(false)
+                on:keydown=move |ev: KeyboardEvent| {
+                    if ev.key() == "Escape" {
+                        is_hovered.set(false);
+                        is_focused.set(false);
+                    }
+                }
+            >
+
+                <IconInfo
+                    size=16
+                    attr:stroke-width="1"
+                    attr:class="inline-block size-3 text-gray-400 dark:text-gray-600"
+                    attr:aria-hidden="true"
+                />
+            </button>
+            <span
+                id=id
+                class="hs-tooltip-content hs-tooltip-shown:opacity-100 hs-tooltip-shown:visible opacity-70 motion-safe:transition-opacity inline-block absolute z-10 py-1 px-2 bg-gray-900 text-xs font-medium text-white rounded shadow-sm dark:bg-slate-700"
+                class=("w-40", description.is_none())
+                class=("w-72", description.is_some())
+                class=("text-center", description.is_none())
+                role="tooltip"
+                class:hidden=move || !is_shown()
+                class:show=is_shown
+            >
+                {help}
+                {description
+                    .map(|description| {
+                        view! { <span class="block mt-1 font-normal text-gray-300">{description}</span> }
+                    })}
+
+            </span>
+
+        </div>
+    }
+}
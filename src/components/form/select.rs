@@ -182,6 +182,99 @@ pub fn CheckboxGroup(
     }
 }
 
+#[component]
+pub fn RadioGroup(
+    element: FormElement,
+    #[prop(optional, into)] disabled: MaybeSignal<bool>,
+) -> impl IntoView {
+    let options = create_memo(move |_| {
+        let data = element.data.get();
+        let help = data
+            .schema
+            .fields
+            .get(element.id)
+            .map(|field| field.option_help)
+            .unwrap_or_default();
+        data.select_sources(element.id)
+            .into_iter()
+            .map(|(id, label)| {
+                let help = help
+                    .iter()
+                    .find_map(|(help_id, help)| (*help_id == id).then_some(*help));
+                (id, label, help)
+            })
+            .collect::<Vec<_>>()
+    });
+    let value = create_memo(move |_| {
+        element
+            .data
+            .get()
+            .value::<String>(element.id)
+            .unwrap_or_default()
+    });
+    let error = create_memo(move |_| {
+        element
+            .data
+            .get()
+            .error_string(element.id)
+            .map(|s| s.to_string())
+    });
+
+    view! {
+        <div class="grid sm:grid-cols-2 gap-2" role="radiogroup">
+            <For
+                each=move || options.get()
+                key=move |(id, _, _)| id.clone()
+                children=move |(id, label, help)| {
+                    let id_ = id.clone();
+                    view! {
+                        <label class="flex p-3 w-full bg-white border border-gray-200 rounded-lg text-sm focus-within:border-blue-500 focus-within:ring-1 focus-within:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400">
+                            <input
+                                type="radio"
+                                name=element.id
+                                class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-gray-800 dark:border-gray-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                                prop:checked=move || value.get() == id_
+                                disabled=move || disabled.get()
+                                on:change=move |_| {
+                                    element
+                                        .data
+                                        .update(|data| {
+                                            data.update(element.id, id.clone());
+                                        });
+                                }
+                            />
+
+                            <span class="ms-3">
+                                <span class="block text-sm font-semibold text-gray-800 dark:text-gray-300">
+                                    {label}
+                                </span>
+                                {help
+                                    .map(|help| {
+                                        view! {
+                                            <span class="block text-sm text-gray-500 dark:text-gray-500">
+                                                {help}
+                                            </span>
+                                        }
+                                    })}
+
+                            </span>
+                        </label>
+                    }
+                }
+            />
+
+        </div>
+
+        {move || {
+            error
+                .get()
+                .map(|error| {
+                    view! { <p class="text-xs text-red-600 mt-2">{error}</p> }
+                })
+        }}
+    }
+}
+
 #[component]
 pub fn SelectCron(
     element: FormElement,
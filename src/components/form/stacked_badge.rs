@@ -135,7 +135,7 @@ pub fn StackedBadge(
         </div>
 
         <span
-            class="hs-tooltip-content hs-tooltip-shown:opacity-100 hs-tooltip-shown:visible transition-opacity inline-block absolute z-10 py-3 px-4 bg-white border text-sm text-gray-600 rounded-lg shadow-md dark:bg-gray-900 dark:border-gray-700 dark:text-gray-400"
+            class="hs-tooltip-content hs-tooltip-shown:opacity-100 hs-tooltip-shown:visible motion-safe:transition-opacity inline-block absolute z-10 py-3 px-4 bg-white border text-sm text-gray-600 rounded-lg shadow-md dark:bg-gray-900 dark:border-gray-700 dark:text-gray-400"
             role="tooltip"
             class:hidden=move || !show_tooltip.get()
             class:invisible=move || !show_tooltip.get()
@@ -216,7 +216,7 @@ pub fn StackedBadge(
                     </button>
                     <button
                         type="button"
-                        class="-ms-px py-2 px-3 inline-flex justify-center items-center gap-2 border font-medium bg-white text-gray-700 rounded-e-md shadow-sm align-middle hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-blue-600 transition-all text-sm dark:bg-gray-800 dark:hover:bg-slate-800 dark:border-gray-700 dark:text-gray-400 dark:hover:text-white"
+                        class="-ms-px py-2 px-3 inline-flex justify-center items-center gap-2 border font-medium bg-white text-gray-700 rounded-e-md shadow-sm align-middle hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-blue-600 motion-safe:transition-all text-sm dark:bg-gray-800 dark:hover:bg-slate-800 dark:border-gray-700 dark:text-gray-400 dark:hover:text-white"
                         on:click=move |_| {
                             show_tooltip.set(false);
                         }
@@ -5,9 +5,10 @@
  */
 
 use leptos::*;
+use web_sys::DragEvent;
 
 use crate::{
-    components::icon::{IconPlus, IconXMark},
+    components::icon::{IconGripVertical, IconPlus, IconXMark},
     core::form::FormErrorType,
 };
 
@@ -40,6 +41,8 @@ pub fn StackedInput(
             })
             .collect::<Vec<_>>()
     });
+    let dragging = create_rw_signal(None::<usize>);
+    let drop_target = create_rw_signal(None::<usize>);
     let error = create_memo(move |_| {
         element.data.get().error(element.id).and_then(|e| {
             if e.id == FormErrorType::None {
@@ -67,44 +70,92 @@ pub fn StackedInput(
                     let is_err = error.is_some();
                     let error = error.unwrap_or_default();
                     view! {
-                        <div class="space-y-3">
-                            <div class="relative">
-                                <input
-                                    type="text"
-                                    class=move || {
-                                        if !is_err {
-                                            "py-2 px-3 pe-11 block w-full border-gray-200 shadow-sm text-sm rounded-lg focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
-                                        } else {
-                                            "py-2 px-3 pe-11 block w-full border-red-500 shadow-sm text-sm rounded-lg focus:border-red-500 focus:ring-red-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        <div
+                            class="space-y-3"
+                            class:opacity-50=move || dragging.get() == Some(idx)
+                            on:dragover=move |ev: DragEvent| {
+                                if dragging.get_untracked().is_some() {
+                                    ev.prevent_default();
+                                    drop_target.set(Some(idx));
+                                }
+                            }
+
+                            on:drop=move |ev: DragEvent| {
+                                ev.prevent_default();
+                                if let Some(from) = dragging.get_untracked() {
+                                    element
+                                        .data
+                                        .update(|data| {
+                                            data.array_move(element.id, from, idx);
+                                        });
+                                }
+                                dragging.set(None);
+                                drop_target.set(None);
+                            }
+                        >
+                            <div
+                                class="flex items-center gap-x-2 rounded-lg ring-blue-500"
+                                class:ring-2=move || {
+                                    drop_target.get() == Some(idx) && dragging.get() != Some(idx)
+                                }
+                            >
+                                <span
+                                    class="flex-shrink-0 cursor-move text-gray-400 dark:text-gray-500"
+                                    draggable="true"
+                                    title="Drag to reorder"
+                                    on:dragstart=move |ev: DragEvent| {
+                                        if let Some(transfer) = ev.data_transfer() {
+                                            transfer.set_effect_allowed("move");
+                                            let _ = transfer.set_data("text/plain", &idx.to_string());
                                         }
+                                        dragging.set(Some(idx));
                                     }
 
-                                    prop:value=item
-                                    placeholder=placeholder.clone().map(|p| move || p.get())
-                                    on:change=move |ev| {
-                                        element
-                                            .data
-                                            .update(|data| {
-                                                data.array_update(element.id, idx, event_target_value(&ev));
-                                            });
-                                    }
-                                />
-
-                                <button
-                                    type="button"
-                                    class="absolute top-0 end-0 p-2.5 rounded-e-md dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                                    on:click=move |_| {
-                                        element
-                                            .data
-                                            .update(|data| {
-                                                data.array_delete(element.id, idx);
-                                            });
+                                    on:dragend=move |_| {
+                                        dragging.set(None);
+                                        drop_target.set(None);
                                     }
                                 >
+                                    <IconGripVertical/>
+                                </span>
+                                <div class="relative grow">
+                                    <input
+                                        type="text"
+                                        class=move || {
+                                            if !is_err {
+                                                "py-2 px-3 pe-11 block w-full border-gray-200 shadow-sm text-sm rounded-lg focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                                            } else {
+                                                "py-2 px-3 pe-11 block w-full border-red-500 shadow-sm text-sm rounded-lg focus:border-red-500 focus:ring-red-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                                            }
+                                        }
+
+                                        prop:value=item
+                                        placeholder=placeholder.clone().map(|p| move || p.get())
+                                        on:change=move |ev| {
+                                            element
+                                                .data
+                                                .update(|data| {
+                                                    data.array_update(element.id, idx, event_target_value(&ev));
+                                                });
+                                        }
+                                    />
+
+                                    <button
+                                        type="button"
+                                        class="absolute top-0 end-0 p-2.5 rounded-e-md dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                                        on:click=move |_| {
+                                            element
+                                                .data
+                                                .update(|data| {
+                                                    data.array_delete(element.id, idx);
+                                                });
+                                        }
+                                    >
 
-                                    <IconXMark/>
+                                        <IconXMark/>
 
-                                </button>
+                                    </button>
+                                </div>
                             </div>
                             <p class="text-xs text-red-600 mt-2" class:hidden=!is_err>
                                 {error}
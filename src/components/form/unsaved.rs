@@ -0,0 +1,166 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use ahash::AHashMap;
+use leptos::*;
+use leptos_router::use_navigate;
+use leptos_use::{use_event_listener, use_event_listener_with_options, UseEventListenerOptions};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+use crate::{
+    components::messages::modal::{use_modals, Modal, ModalStack},
+    core::form::{FormData, FormValue},
+};
+
+#[derive(Clone, Copy)]
+pub struct UnsavedChanges {
+    data: RwSignal<FormData>,
+    baseline: RwSignal<Option<AHashMap<String, FormValue>>>,
+    pub is_dirty: Memo<bool>,
+    modal: ModalStack,
+}
+
+pub fn use_unsaved_changes(data: RwSignal<FormData>) -> UnsavedChanges {
+    let modal = use_modals();
+    let baseline = create_rw_signal(None::<AHashMap<String, FormValue>>);
+    let is_dirty = create_memo(move |_| {
+        baseline.with(|baseline| {
+            baseline
+                .as_ref()
+                .is_some_and(|baseline| data.with(|data| &data.values != baseline))
+        })
+    });
+
+    let unsaved = UnsavedChanges {
+        data,
+        baseline,
+        is_dirty,
+        modal,
+    };
+
+    let _ = use_event_listener(window(), ev::beforeunload, move |ev| {
+        if is_dirty.get_untracked() {
+            ev.prevent_default();
+            ev.set_return_value("");
+        }
+    });
+
+    // Registered in the capture phase so that it runs before the router's own
+    // click handler has a chance to navigate.
+    let _ = use_event_listener_with_options(
+        document(),
+        ev::click,
+        move |ev| {
+            if !is_dirty.get_untracked()
+                || ev.default_prevented()
+                || ev.button() != 0
+                || ev.meta_key()
+                || ev.alt_key()
+                || ev.ctrl_key()
+                || ev.shift_key()
+            {
+                return;
+            }
+            let Some(anchor) = ev
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .and_then(|target| target.closest("a[href]").ok().flatten())
+            else {
+                return;
+            };
+            if anchor.has_attribute("download")
+                || anchor
+                    .get_attribute("target")
+                    .is_some_and(|target| !target.is_empty() && target != "_self")
+            {
+                return;
+            }
+            let Some(url) = anchor
+                .get_attribute("href")
+                .and_then(|href| web_sys::Url::new_with_base(&href, &location_href()).ok())
+            else {
+                return;
+            };
+            let location = window().location();
+            if location.origin().ok().as_deref() != Some(url.origin().as_str())
+                || location.pathname().ok().as_deref() == Some(url.pathname().as_str())
+            {
+                return;
+            }
+
+            ev.prevent_default();
+            ev.stop_propagation();
+            unsaved.navigate(&format!("{}{}{}", url.pathname(), url.search(), url.hash()));
+        },
+        UseEventListenerOptions::default().capture(true),
+    );
+
+    // Back and forward buttons. Capturing on the window runs before the
+    // router's own popstate listener, which is then skipped.
+    let form_path = location_path();
+    let _ = use_event_listener_with_options(
+        window(),
+        ev::popstate,
+        move |ev| {
+            let path = location_path();
+            if !is_dirty.get_untracked() || path == form_path {
+                return;
+            }
+            ev.stop_immediate_propagation();
+            // Show the form's address again until the user decides
+            if let Ok(history) = window().history() {
+                let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&form_path));
+            }
+            unsaved.navigate(&path);
+        },
+        UseEventListenerOptions::default().capture(true),
+    );
+
+    unsaved
+}
+
+impl UnsavedChanges {
+    pub fn mark_clean(&self) {
+        self.baseline
+            .set(Some(self.data.with_untracked(|data| data.values.clone())));
+    }
+
+    pub fn navigate(&self, path: &str) {
+        if !self.is_dirty.get_untracked() {
+            use_navigate()(path, Default::default());
+            return;
+        }
+        let baseline = self.baseline;
+        let path = path.to_string();
+        self.modal.set(
+            Modal::with_title("Unsaved changes")
+                .with_message("You have unsaved changes — leave anyway?")
+                .with_button("Leave page")
+                .with_dangerous_callback(move || {
+                    baseline.set(None);
+                    use_navigate()(&path, Default::default());
+                }),
+        );
+    }
+
+    pub fn baseline(&self) -> Option<AHashMap<String, FormValue>> {
+        self.baseline.get_untracked()
+    }
+}
+
+fn location_href() -> String {
+    window().location().href().unwrap_or_default()
+}
+
+fn location_path() -> String {
+    let location = window().location();
+    format!(
+        "{}{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default(),
+        location.hash().unwrap_or_default()
+    )
+}
@@ -0,0 +1,39 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use crate::{
+    components::icon::IconExclamationTriangle,
+    core::{
+        connectivity::{use_connectivity, ConnectionState},
+        i18n::t,
+    },
+};
+
+#[component]
+pub fn ConnectivityBanner() -> impl IntoView {
+    let state = use_connectivity()
+        .map(|connectivity| connectivity.state.into())
+        .unwrap_or_else(|| Signal::derive(|| ConnectionState::Online));
+
+    view! {
+        <Show when=move || state.get() != ConnectionState::Online>
+            <div
+                class="fixed bottom-0 inset-x-0 z-[70] flex justify-center items-center gap-x-2 py-2 px-4 bg-yellow-50 border-t border-yellow-200 text-sm text-yellow-800 dark:bg-yellow-800/10 dark:border-yellow-900 dark:text-yellow-500"
+                role="status"
+                aria-live="polite"
+            >
+                <IconExclamationTriangle attr:class="flex-shrink-0 size-4" attr:aria-hidden="true"/>
+                {move || match state.get() {
+                    ConnectionState::Offline => t("connectivity.offline"),
+                    _ => t("connectivity.unreachable"),
+                }}
+
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,20 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use crate::VERSION_NAME;
+
+#[component]
+pub fn VersionFooter() -> impl IntoView {
+    view! {
+        <footer class="w-full py-6 text-xs text-gray-500 dark:text-gray-400">
+            <div class="flex flex-wrap justify-center items-center gap-x-3 gap-y-1">
+                <span>{VERSION_NAME}</span>
+            </div>
+        </footer>
+    }
+}
@@ -6,25 +6,56 @@
 
 use gloo_storage::{SessionStorage, Storage};
 use leptos::*;
-use leptos_router::use_navigate;
+use leptos_router::{use_location, use_navigate, NavigateOptions};
 
 use crate::{
-    components::icon::{
-        IconAdjustmentsHorizontal, IconHeart, IconPower, IconServer, IconUserCircle,
+    components::{
+        icon::{IconAdjustmentsHorizontal, IconHeart, IconPower, IconServer, IconUserCircle},
+        layout::{health::HealthIndicator, sidebar::SidebarState},
+    },
+    core::{
+        api::clear_response_cache,
+        debounce::{use_debounce, SEARCH_DEBOUNCE},
+        i18n::{t, use_i18n, LANGUAGES},
+        oauth::use_authorization,
+        url::UrlBuilder,
+        AccessToken, Permission, Permissions,
     },
-    core::{oauth::use_authorization, url::UrlBuilder, AccessToken, Permission, Permissions},
     pages::config::edit::DEFAULT_SETTINGS_URL,
     STATE_STORAGE_KEY, VERSION_NAME,
 };
 use web_sys::wasm_bindgen::JsCast;
 
 #[component]
-pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
+pub fn Header(permissions: Memo<Option<Permissions>>, sidebar: SidebarState) -> impl IntoView {
+    let i18n = use_i18n();
+    let location = use_location();
+    let show_results = move |filter: String| {
+        let query = filter.trim();
+        if !query.is_empty() {
+            // Typing refines the results in place rather than adding to the history
+            let replace = location.pathname.get_untracked() == "/settings/search";
+            use_navigate()(
+                &UrlBuilder::new("/settings/search")
+                    .with_parameter("query", query)
+                    .finish(),
+                NavigateOptions {
+                    replace,
+                    ..Default::default()
+                },
+            );
+        }
+    };
+    let search = use_debounce(SEARCH_DEBOUNCE, show_results);
+
     view! {
-        <header class="sticky top-0 inset-x-0 flex flex-wrap sm:justify-start sm:flex-nowrap z-[48] w-full bg-white border-b text-sm py-2.5 sm:py-4 lg:ps-64 dark:bg-gray-800 dark:border-gray-700">
+        <header
+            class="sticky top-0 inset-x-0 flex flex-wrap sm:justify-start sm:flex-nowrap z-[48] w-full bg-white border-b text-sm py-2.5 sm:py-4 dark:bg-gray-800 dark:border-gray-700"
+            class=("lg:ps-64", move || !sidebar.collapsed.get())
+        >
             <nav class="flex basis-full items-center w-full mx-auto px-4 sm:px-6 md:px-8">
 
-                <div class="me-5 lg:me-0 lg:hidden">
+                <div class="me-5" class=(["lg:me-0", "lg:hidden"], move || !sidebar.collapsed.get())>
                     <img src="/logo.svg" title=VERSION_NAME/>
                 </div>
 
@@ -78,22 +109,15 @@ pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
                                 <input
                                     type="text"
                                     class="py-2 px-4 ps-11 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
-                                    placeholder="Search settings"
+                                    placeholder=move || t("header.search-settings")
+                                    on:input=move |ev| search.call(event_target_value(&ev))
                                     on:keyup=move |ev| {
                                         let key_code = ev
                                             .unchecked_ref::<web_sys::KeyboardEvent>()
                                             .key_code();
                                         if key_code == 13 {
-                                            let filter = event_target_value(&ev);
-                                            let query = filter.trim();
-                                            if !query.is_empty() {
-                                                use_navigate()(
-                                                    &UrlBuilder::new("/settings/search")
-                                                        .with_parameter("query", query)
-                                                        .finish(),
-                                                    Default::default(),
-                                                );
-                                            }
+                                            search.cancel();
+                                            show_results(event_target_value(&ev));
                                         }
                                     }
                                 />
@@ -104,11 +128,47 @@ pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
                     </Show>
 
                     <div class="flex flex-row items-center justify-end gap-2">
+                        <label for="header-language" class="sr-only">
+                            {move || t("header.language")}
+                        </label>
+                        <select
+                            id="header-language"
+                            class="py-2 px-3 pe-9 block border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                            on:change=move |ev| {
+                                let code = event_target_value(&ev);
+                                if let Some(language) = LANGUAGES
+                                    .iter()
+                                    .find(|language| language.code == code)
+                                {
+                                    i18n.language.set(language.code);
+                                }
+                            }
+                        >
+
+                            {LANGUAGES
+                                .iter()
+                                .map(|language| {
+                                    view! {
+                                        <option
+                                            value=language.code
+                                            selected=move || i18n.language.get() == language.code
+                                        >
+                                            {language.name}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                        <Show when=move || {
+                            permissions.get().is_some_and(|p| p.has_admin_access())
+                        }>
+                            <HealthIndicator permissions/>
+                        </Show>
                         <a
                             href=move || { permissions.get().map(|p| { p.default_url(false) }) }
 
                             class="w-[2.375rem] h-[2.375rem] inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                            title="Management"
+                            title=move || t("header.management")
                             class:hidden=move || {
                                 permissions.get().map_or(true, |p| { !p.has_admin_access() })
                             }
@@ -120,7 +180,7 @@ pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
                         <a
                             class="w-[2.375rem] h-[2.375rem] inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
                             href=DEFAULT_SETTINGS_URL
-                            title="Settings"
+                            title=move || t("header.settings")
                             class:hidden=move || {
                                 permissions
                                     .get()
@@ -139,26 +199,16 @@ pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
                                     .map(|p| {
                                         if p.has_access(Permission::ManageEncryption) {
                                             "/account/crypto"
-                                        } else {
+                                        } else if p.has_access(Permission::ManagePasswords) {
                                             "/account/password"
+                                        } else {
+                                            "/account/preferences"
                                         }
                                     })
                             }
 
-                            title="Account"
-                            class:hidden=move || {
-                                permissions
-                                    .get()
-                                    .map_or(
-                                        true,
-                                        |p| {
-                                            !p
-                                                .has_access_any(
-                                                    &[Permission::ManageEncryption, Permission::ManagePasswords],
-                                                )
-                                        },
-                                    )
-                            }
+                            title=move || t("header.account")
+                            class:hidden=move || permissions.get().is_none()
                         >
 
                             <IconUserCircle/>
@@ -168,16 +218,17 @@ pub fn Header(permissions: Memo<Option<Permissions>>) -> impl IntoView {
                             class="w-[2.375rem] h-[2.375rem] inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
                             href="https://github.com/sponsors/stalwartlabs"
                             target="_blank"
-                            title="Sponsor Stalwart open source"
+                            title=move || t("header.sponsor")
                         >
                             <IconHeart/>
 
                         </a>
                         <a
                             class="w-[2.375rem] h-[2.375rem] inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                            title="Logout"
+                            title=move || t("header.logout")
                             on:click=move |_| {
                                 SessionStorage::delete(STATE_STORAGE_KEY);
+                                clear_response_cache();
                                 use_authorization().set(AccessToken::default());
                                 use_navigate()("/login", Default::default());
                             }
@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Duration;
+
+use leptos::{leptos_dom::helpers::TimeoutHandle, *};
+use web_sys::{AbortController, AbortSignal};
+
+use crate::core::{
+    http::{self, HttpRequest},
+    i18n::t,
+    oauth::use_authorization,
+    AccessToken, Permission, Permissions,
+};
+
+// Checks back off up to the maximum while the server is unreachable
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HealthStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+#[derive(Clone, Copy)]
+struct HealthCheck {
+    auth: RwSignal<AccessToken>,
+    abort_signal: StoredValue<Option<AbortSignal>>,
+    status: RwSignal<Option<HealthStatus>>,
+    failures: StoredValue<u32>,
+    timer: StoredValue<Option<TimeoutHandle>>,
+}
+
+// Dot in the header showing whether the server is ready, linking to the overview
+// dashboard when metrics are available.
+#[component]
+pub fn HealthIndicator(permissions: Memo<Option<Permissions>>) -> impl IntoView {
+    let abort_controller = AbortController::new().ok();
+    let check = HealthCheck {
+        auth: use_authorization(),
+        abort_signal: store_value(abort_controller.as_ref().map(AbortController::signal)),
+        status: create_rw_signal(None),
+        failures: store_value(0),
+        timer: store_value(None),
+    };
+    check.run();
+    on_cleanup(move || {
+        if let Some(abort_controller) = abort_controller {
+            abort_controller.abort();
+        }
+        if let Some(handle) = check.timer.try_get_value().flatten() {
+            handle.clear();
+        }
+    });
+    let status = check.status;
+
+    view! {
+        <a
+            class="w-[2.375rem] h-[2.375rem] inline-flex justify-center items-center rounded-full border border-transparent hover:bg-gray-100 dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+            href=move || {
+                permissions
+                    .get()
+                    .filter(|p| {
+                        p.has_access_all(&[Permission::MetricsList, Permission::MetricsLive])
+                    })
+                    .map(|_| "/manage/dashboard/overview")
+            }
+
+            title=move || {
+                match status.get() {
+                    Some(HealthStatus::Ok) => "Server is ready".to_string(),
+                    Some(HealthStatus::Degraded) => "Server is up but not ready".to_string(),
+                    Some(HealthStatus::Down) => "Server is unreachable".to_string(),
+                    None => t("header.health"),
+                }
+            }
+        >
+            <span
+                class="size-2.5 rounded-full"
+                class=move || {
+                    match status.get() {
+                        Some(HealthStatus::Ok) => "bg-green-500",
+                        Some(HealthStatus::Degraded) => "bg-yellow-500",
+                        Some(HealthStatus::Down) => "bg-red-500",
+                        None => "bg-gray-300 dark:bg-gray-600",
+                    }
+                }
+            >
+            </span>
+            <span class="sr-only">{move || t("header.health")}</span>
+        </a>
+    }
+}
+
+impl HealthCheck {
+    fn run(self) {
+        let Some(abort_signal) = self.abort_signal.try_get_value().flatten() else {
+            return;
+        };
+        let base_url = self.auth.get_untracked().base_url;
+
+        spawn_local(async move {
+            // Only a server that cannot be reached at all is down
+            let status = match HttpRequest::get(format!("{base_url}/healthz/ready"))
+                .with_abort_signal(abort_signal)
+                .send_raw()
+                .await
+            {
+                Ok(_) => HealthStatus::Ok,
+                Err(http::Error::Aborted) => return,
+                Err(http::Error::Network(err)) => {
+                    log::debug!("Health check failed: {err}");
+                    HealthStatus::Down
+                }
+                Err(err) => {
+                    log::debug!("Server is not ready: {err:?}");
+                    HealthStatus::Degraded
+                }
+            };
+
+            let failures = if status == HealthStatus::Down {
+                self.failures.try_update_value(|failures| {
+                    *failures += 1;
+                    *failures
+                })
+            } else {
+                self.failures.try_update_value(|failures| {
+                    *failures = 0;
+                    0
+                })
+            };
+            let Some(failures) = failures else {
+                return;
+            };
+            self.status.try_set(Some(status));
+
+            let delay = CHECK_INTERVAL
+                .saturating_mul(1 << failures.min(4))
+                .min(MAX_CHECK_INTERVAL);
+            match set_timeout_with_handle(move || self.run(), delay) {
+                Ok(handle) => {
+                    if self.timer.try_set_value(Some(handle)).is_some() {
+                        // Unmounted in the meantime
+                        handle.clear();
+                    }
+                }
+                Err(err) => log::warn!("Failed to schedule health check: {err:?}"),
+            }
+        });
+    }
+}
@@ -4,7 +4,11 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod connectivity;
+pub mod footer;
 pub mod header;
+pub mod health;
+pub mod shortcuts;
 pub mod sidebar;
 pub mod toggle;
 
@@ -16,10 +20,18 @@ use leptos_router::Outlet;
 
 use crate::{
     components::{
-        layout::{header::Header, sidebar::SideBar, toggle::ToggleNavigation},
-        messages::modal::Modal,
+        layout::{
+            connectivity::ConnectivityBanner,
+            footer::VersionFooter,
+            header::Header,
+            shortcuts::ShortcutsHelp,
+            sidebar::{SideBar, SidebarState},
+            toggle::ToggleNavigation,
+        },
+        messages::{alert::Alerts, error::PageErrorBoundary, modal::Modal},
     },
     core::{
+        i18n::t,
         schema::{Schema, SchemaType},
         Permissions,
     },
@@ -40,6 +52,8 @@ pub struct Route {
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct MenuItem {
     pub name: String,
+    // Does not change with the language, unlike the name
+    pub key: String,
     pub route: Option<String>,
     pub icon: Option<View>,
     pub children: Vec<MenuItem>,
@@ -47,20 +61,35 @@ pub struct MenuItem {
 
 #[component]
 pub fn Layout(
-    menu_items: Vec<MenuItem>,
+    #[prop(into)] menu_items: Signal<Vec<MenuItem>>,
     #[prop(into)] permissions: Memo<Option<Permissions>>,
 ) -> impl IntoView {
-    let menu_items_toggle = menu_items.clone();
-    let show_sidebar = create_rw_signal(false);
+    let sidebar = SidebarState::from_storage();
 
     view! {
         <Body class="bg-gray-50 dark:bg-slate-900"/>
         <Modal/>
-        <Header permissions/>
-        <ToggleNavigation menu_items show_sidebar/>
-        <SideBar menu_items=menu_items_toggle show_sidebar/>
-        <div class="w-full pt-10 px-4 sm:px-6 md:px-8 lg:ps-72">
-            <Outlet/>
+        <Alerts/>
+        <ShortcutsHelp/>
+        <ConnectivityBanner/>
+        <Header permissions sidebar/>
+        // Rebuilt when the language or the permissions change
+        {move || {
+            let menu_items = menu_items.get();
+            let menu_items_toggle = menu_items.clone();
+            view! {
+                <ToggleNavigation menu_items sidebar/>
+                <SideBar menu_items=menu_items_toggle sidebar/>
+            }
+        }}
+        <div
+            class="w-full pt-10 px-4 sm:px-6 md:px-8"
+            class=("lg:ps-72", move || !sidebar.collapsed.get())
+        >
+            <PageErrorBoundary>
+                <Outlet/>
+            </PageErrorBoundary>
+            <VersionFooter/>
         </div>
     }
 }
@@ -86,15 +115,21 @@ impl LayoutBuilder {
         };
         self.menu_items.push(MenuItem {
             name: schema.list.title.into(),
+            key: route.clone(),
             route: route.into(),
             ..Default::default()
         });
         self
     }
 
-    pub fn create(mut self, name: impl Into<String>) -> Self {
+    pub fn create(self, key: &str) -> Self {
+        self.create_named(key, t(key))
+    }
+
+    pub fn create_named(mut self, key: impl Into<String>, name: impl Into<String>) -> Self {
         self.chain.push(MenuItem {
             name: name.into(),
+            key: key.into(),
             ..Default::default()
         });
         self
@@ -138,4 +173,16 @@ impl MenuItem {
         self.children.len().hash(&mut hasher);
         hasher.finish().to_string()
     }
+
+    pub fn contains_route(&self, route: &str) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.route.as_deref() == Some(route) || child.contains_route(route))
+    }
+
+    // `filter` must already be lowercase
+    pub fn matches(&self, filter: &str) -> bool {
+        self.name.to_lowercase().contains(filter)
+            || self.children.iter().any(|child| child.matches(filter))
+    }
 }
@@ -0,0 +1,59 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use crate::{
+    components::messages::modal::{Modal, ModalStack},
+    core::shortcuts::{use_shortcut, Shortcut, ShortcutRegistry},
+};
+
+#[component]
+pub fn ShortcutsHelp() -> impl IntoView {
+    let registry = expect_context::<ShortcutRegistry>();
+    let modals = expect_context::<ModalStack>();
+
+    use_shortcut(Shortcut::new("?", "Show keyboard shortcuts"), move |_| {
+        let groups = registry.grouped();
+        modals.set(
+            Modal::with_title("Keyboard shortcuts")
+                .without_button()
+                .with_content(move || {
+                    groups
+                        .iter()
+                        .map(|(context, shortcuts)| {
+                            view! {
+                                <div class="mb-4 last:mb-0">
+                                    <h4 class="mb-2 text-xs font-semibold uppercase text-gray-500 dark:text-gray-400">
+                                        {context.title()}
+                                    </h4>
+                                    <dl class="divide-y divide-gray-200 dark:divide-gray-700">
+                                        {shortcuts
+                                            .iter()
+                                            .map(|shortcut| {
+                                                view! {
+                                                    <div class="flex justify-between items-center py-2">
+                                                        <dt class="text-sm text-gray-800 dark:text-gray-200">
+                                                            {shortcut.description}
+                                                        </dt>
+                                                        <dd>
+                                                            <kbd class="min-h-[30px] inline-flex justify-center items-center py-1 px-1.5 bg-white border border-gray-200 font-mono text-sm text-gray-800 shadow-[0px_2px_0px_0px_rgba(0,0,0,0.08)] rounded-md dark:bg-slate-900 dark:border-gray-700 dark:text-gray-200">
+                                                                {shortcut.label()}
+                                                            </kbd>
+                                                        </dd>
+                                                    </div>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </dl>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }),
+        );
+    });
+}
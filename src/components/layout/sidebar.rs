@@ -4,30 +4,138 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use ahash::AHashMap;
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
 use leptos::*;
 use leptos_router::use_location;
+use leptos_use::use_media_query;
 
-use crate::VERSION_NAME;
+use crate::{components::icon::IconXMark, core::shortcuts::move_focus, VERSION_NAME};
 
 use super::MenuItem;
 
+const SIDEBAR_COLLAPSED_KEY: &str = "webadmin_sidebar_collapsed";
+const SIDEBAR_GROUPS_KEY: &str = "webadmin_sidebar_groups";
+
+#[derive(Clone, Copy)]
+pub struct SidebarState {
+    pub open: RwSignal<bool>,
+    pub collapsed: RwSignal<bool>,
+    expanded: RwSignal<AHashMap<String, bool>>,
+    is_desktop: Signal<bool>,
+}
+
+impl SidebarState {
+    pub fn from_storage() -> Self {
+        let state = SidebarState {
+            open: create_rw_signal(false),
+            collapsed: create_rw_signal(LocalStorage::get(SIDEBAR_COLLAPSED_KEY).unwrap_or(false)),
+            expanded: create_rw_signal(SessionStorage::get(SIDEBAR_GROUPS_KEY).unwrap_or_default()),
+            is_desktop: use_media_query("(min-width: 1024px)"),
+        };
+        create_effect(move |_| {
+            let _ = LocalStorage::set(SIDEBAR_COLLAPSED_KEY, state.collapsed.get());
+        });
+        create_effect(move |_| {
+            state
+                .expanded
+                .with(|expanded| SessionStorage::set(SIDEBAR_GROUPS_KEY, expanded))
+                .ok();
+        });
+        state
+    }
+
+    pub fn toggle(&self) {
+        if self.is_desktop.get_untracked() {
+            self.collapsed.update(|v| *v = !*v);
+        } else {
+            self.open.update(|v| *v = !*v);
+        }
+    }
+
+    fn is_expanded(&self, group: &str) -> Option<bool> {
+        self.expanded.with(|expanded| expanded.get(group).copied())
+    }
+
+    fn toggle_group(&self, group: &str, is_expanded: bool) {
+        self.expanded.update(|expanded| {
+            expanded.insert(group.to_string(), !is_expanded);
+        });
+    }
+}
+
 #[component]
-pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl IntoView {
+pub fn SideBar(menu_items: Vec<MenuItem>, sidebar: SidebarState) -> impl IntoView {
     let current_route = create_memo(move |_| use_location().pathname.get());
+    let menu_routes = menu_items.clone();
+    let active_route =
+        create_memo(move |_| current_route.with(|path| best_route(&menu_routes, path)));
+
+    let filter = create_rw_signal(String::new());
+    let nav_ref = create_node_ref::<html::Nav>();
+    let query = create_memo(move |_| filter.with(|filter| filter.trim().to_lowercase()));
+
+    // Close the drawer after following a link
+    create_effect(move |_| {
+        current_route.track();
+        sidebar.open.set(false);
+    });
 
     view! {
         <div
-            class="hs-overlay hs-overlay-open:translate-x-0 -translate-x-full transition-all duration-300 transform fixed top-0 start-0 bottom-0 z-[60] w-64 bg-white border-e border-gray-200 pt-7 pb-10 overflow-y-auto lg:block lg:translate-x-0 lg:end-auto lg:bottom-0 [&::-webkit-scrollbar]:w-2 [&::-webkit-scrollbar-thumb]:rounded-full [&::-webkit-scrollbar-track]:bg-gray-100 [&::-webkit-scrollbar-thumb]:bg-gray-300 dark:[&::-webkit-scrollbar-track]:bg-slate-700 dark:[&::-webkit-scrollbar-thumb]:bg-slate-500 dark:bg-gray-800 dark:border-gray-700"
-            class:hidden=move || !show_sidebar.get()
-            class:open=move || show_sidebar.get()
+            class="fixed inset-0 z-[59] bg-gray-900 bg-opacity-50 lg:hidden dark:bg-opacity-80"
+            class:hidden=move || !sidebar.open.get()
+            on:click=move |_| sidebar.open.set(false)
+        ></div>
+        <div
+            class="hs-overlay hs-overlay-open:translate-x-0 -translate-x-full motion-safe:transition-all duration-300 transform fixed top-0 start-0 bottom-0 z-[60] w-64 bg-white border-e border-gray-200 pt-7 pb-10 overflow-y-auto lg:end-auto lg:bottom-0 [&::-webkit-scrollbar]:w-2 [&::-webkit-scrollbar-thumb]:rounded-full [&::-webkit-scrollbar-track]:bg-gray-100 [&::-webkit-scrollbar-thumb]:bg-gray-300 dark:[&::-webkit-scrollbar-track]:bg-slate-700 dark:[&::-webkit-scrollbar-thumb]:bg-slate-500 dark:bg-gray-800 dark:border-gray-700"
+            class=(["lg:block", "lg:translate-x-0"], move || !sidebar.collapsed.get())
+            class:hidden=move || !sidebar.open.get()
+            class:open=move || sidebar.open.get()
         >
-            <div class="px-8">
+            <div class="px-8 flex justify-between items-center">
                 <img src="/logo.svg" style="height: 25px;" title=VERSION_NAME/>
+                <button
+                    type="button"
+                    class="flex justify-center items-center size-7 text-sm font-semibold rounded-full border border-transparent text-gray-500 hover:bg-gray-100 dark:text-gray-400 dark:hover:bg-gray-700 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600"
+                    aria-label="Hide navigation"
+                    on:click=move |_| sidebar.toggle()
+                >
+                    <span class="sr-only">Hide navigation</span>
+                    <IconXMark/>
+                </button>
+            </div>
+
+            <div class="px-6 pt-6">
+                <label for="sidebar-filter" class="sr-only">
+                    Filter menu
+                </label>
+                <input
+                    id="sidebar-filter"
+                    type="search"
+                    class="py-2 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                    placeholder="Filter menu"
+                    autocomplete="off"
+                    prop:value=filter
+                    on:input=move |ev| filter.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Escape" {
+                            filter.set(String::new());
+                        }
+                    }
+                />
             </div>
 
             <nav
                 class="hs-accordion-group p-6 w-full flex flex-col flex-wrap"
                 data-hs-accordion-always-open
+                aria-label="Main navigation"
+                node_ref=nav_ref
+                on:keydown=move |ev| {
+                    if let Some(nav) = nav_ref.get_untracked() {
+                        move_focus(&nav, "a[href], button", &ev);
+                    }
+                }
             >
 
                 <ul class="space-y-1.5">
@@ -40,34 +148,45 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                 .unwrap()
                                 .children
                                 .is_empty();
-                            let children = item.children.clone();
-                            let is_displayed = create_rw_signal(false);
+                            let is_match = match_memo(&item, query);
+                            let group = item.key.clone();
+                            let item_ = item.clone();
                             let is_active = create_memo(move |_| {
-                                is_displayed.get()
-                                    || children
-                                        .iter()
-                                        .any(|i| {
-                                            i
-                                                .route
-                                                .as_ref()
-                                                .map_or(false, |f| current_route.get().starts_with(f))
-                                                || i
-                                                    .children
-                                                    .iter()
-                                                    .any(|i| {
-                                                        i.route
-                                                            .as_ref()
-                                                            .map_or(false, |f| current_route.get().starts_with(f))
-                                                    })
+                                query.with(|query| !query.is_empty()) && is_match.get()
+                                    || sidebar
+                                        .is_expanded(&group)
+                                        .unwrap_or_else(|| {
+                                            active_route
+                                                .with(|route| {
+                                                    route
+                                                        .as_deref()
+                                                        .is_some_and(|route| item_.contains_route(route))
+                                                })
                                         })
                             });
+                            let group = item.key.clone();
+                            let content_id = format!("menu-{}", item.id());
                             view! {
-                                <li class="hs-accordion">
+                                <li class="hs-accordion" class:opacity-40=move || !is_match.get()>
                                     <button
                                         type="button"
-                                        class="hs-accordion-toggle w-full text-start flex items-center gap-x-3.5 py-2 px-2.5 hs-accordion-active:text-blue-600 hs-accordion-active:hover:bg-transparent text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 dark:hs-accordion-active:text-white dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                                        aria-expanded=move || is_active.get().to_string()
+                                        aria-controls=content_id.clone()
+                                        class="hs-accordion-toggle w-full text-start flex items-center gap-x-3.5 py-2 px-2.5 hs-accordion-active:text-blue-600 hs-accordion-active:hover:bg-transparent text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 dark:hs-accordion-active:text-white focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600"
                                         class:active=move || is_active.get()
-                                        on:click=move |_| is_displayed.update(|v| *v = !*v)
+                                        on:click={
+                                            let group = group.clone();
+                                            move |_| {
+                                                sidebar.toggle_group(&group, is_active.get_untracked())
+                                            }
+                                        }
+
+                                        on:keydown=move |ev| {
+                                            if expand_key(&ev.key(), is_active.get_untracked()) {
+                                                ev.prevent_default();
+                                                sidebar.toggle_group(&group, is_active.get_untracked());
+                                            }
+                                        }
                                     >
 
                                         {item.icon}
@@ -75,6 +194,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                         {item.name}
 
                                         <svg
+                                            aria-hidden="true"
                                             class="hs-accordion-active:block ms-auto size-4"
                                             class:hidden=move || !is_active.get()
                                             class:block=move || is_active.get()
@@ -92,6 +212,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                         </svg>
 
                                         <svg
+                                            aria-hidden="true"
                                             class="hs-accordion-active:hidden ms-auto size-4"
                                             class:hidden=move || is_active.get()
                                             class:block=move || !is_active.get()
@@ -110,7 +231,8 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                     </button>
 
                                     <div
-                                        class="hs-accordion-content w-full overflow-hidden transition-[height] duration-300"
+                                        id=content_id
+                                        class="hs-accordion-content w-full overflow-hidden motion-safe:transition-[height] duration-300"
                                         hidden=move || !is_active.get()
                                     >
                                         <ul class=move || {
@@ -128,30 +250,56 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                             >
 
                                                 {if !item.children.is_empty() {
-                                                    let is_displayed = create_rw_signal(false);
-                                                    let children = item.children.clone();
+                                                    let is_match = match_memo(&item, query);
+                                                    let group = item.key.clone();
+                                                    let item_ = item.clone();
                                                     let is_active = create_memo(move |_| {
-                                                        children
-                                                            .iter()
-                                                            .any(|i| {
-                                                                is_displayed.get()
-                                                                    || i
-                                                                        .route
-                                                                        .as_ref()
-                                                                        .map_or(false, |f| current_route.get().starts_with(f))
-                                                            })
+                                                        query.with(|query| !query.is_empty())
+                                                            && is_match.get()
+                                                            || sidebar
+                                                                .is_expanded(&group)
+                                                                .unwrap_or_else(|| {
+                                                                    active_route
+                                                                        .with(|route| {
+                                                                            route
+                                                                                .as_deref()
+                                                                                .is_some_and(|route| {
+                                                                                    item_.contains_route(route)
+                                                                                })
+                                                                        })
+                                                                })
                                                     });
+                                                    let group = item.key.clone();
+                                                    let content_id = format!("menu-{}", item.id());
                                                     view! {
-                                                        <li class="hs-accordion">
+                                                        <li
+                                                            class="hs-accordion"
+                                                            class:opacity-40=move || !is_match.get()
+                                                        >
                                                             <button
                                                                 type="button"
-                                                                class="hs-accordion-toggle w-full text-start flex items-center gap-x-3.5 py-2 px-2.5 hs-accordion-active:text-blue-600 hs-accordion-active:hover:bg-transparent text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 dark:hs-accordion-active:text-white dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                                                                on:click=move |_| is_displayed.update(|v| *v = !*v)
+                                                                aria-expanded=move || is_active.get().to_string()
+                                                                aria-controls=content_id.clone()
+                                                                class="hs-accordion-toggle w-full text-start flex items-center gap-x-3.5 py-2 px-2.5 hs-accordion-active:text-blue-600 hs-accordion-active:hover:bg-transparent text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 dark:hs-accordion-active:text-white focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600"
+                                                                on:click={
+                                                                    let group = group.clone();
+                                                                    move |_| {
+                                                                        sidebar.toggle_group(&group, is_active.get_untracked())
+                                                                    }
+                                                                }
+
+                                                                on:keydown=move |ev| {
+                                                                    if expand_key(&ev.key(), is_active.get_untracked()) {
+                                                                        ev.prevent_default();
+                                                                        sidebar.toggle_group(&group, is_active.get_untracked());
+                                                                    }
+                                                                }
                                                             >
 
                                                                 {item.name}
 
                                                                 <svg
+                                                                    aria-hidden="true"
                                                                     class="hs-accordion-active:block ms-auto size-4"
                                                                     class:hidden=move || !is_active.get()
                                                                     class:block=move || is_active.get()
@@ -169,6 +317,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                                 </svg>
 
                                                                 <svg
+                                                                    aria-hidden="true"
                                                                     class="hs-accordion-active:hidden ms-auto size-4"
                                                                     class:hidden=move || is_active.get()
                                                                     class:block=move || !is_active.get()
@@ -187,7 +336,8 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                             </button>
 
                                                             <div
-                                                                class="hs-accordion-content w-full overflow-hidden transition-[height] duration-300"
+                                                                id=content_id
+                                                                class="hs-accordion-content w-full overflow-hidden motion-safe:transition-[height] duration-300"
                                                                 hidden=move || !is_active.get()
                                                             >
                                                                 <ul class="pt-2 ps-2">
@@ -198,14 +348,15 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                                     >
 
                                                                         {
-                                                                            let route = item.route.clone().unwrap();
+                                                                            let is_active = route_memo(&item, active_route);
+                                                                            let is_match = match_memo(&item, query);
                                                                             view! {
-                                                                                <li>
+                                                                                <li class:opacity-40=move || !is_match.get()>
                                                                                     <a
                                                                                         class=move || {
                                                                                             format!(
-                                                                                                "flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:text-slate-400 dark:hover:text-slate-300 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600{}",
-                                                                                                if current_route.get().starts_with(&route) {
+                                                                                                "flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:text-slate-400 dark:hover:text-slate-300 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600{}",
+                                                                                                if is_active.get() {
                                                                                                     " bg-gray-100"
                                                                                                 } else {
                                                                                                     ""
@@ -214,6 +365,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                                                         }
 
                                                                                         href=move || item.route.clone().unwrap()
+                                                                                        aria-current=move || is_active.get().then_some("page")
                                                                                     >
                                                                                         {item.name}
                                                                                     </a>
@@ -230,14 +382,15 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                     }
                                                         .into_view()
                                                 } else {
-                                                    let route = item.route.clone().unwrap();
+                                                    let is_active = route_memo(&item, active_route);
+                                                    let is_match = match_memo(&item, query);
                                                     view! {
-                                                        <li>
+                                                        <li class:opacity-40=move || !is_match.get()>
                                                             <a
                                                                 class=move || {
                                                                     format!(
-                                                                        "flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:text-slate-400 dark:hover:text-slate-300 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600{}",
-                                                                        if current_route.get().starts_with(&route) {
+                                                                        "flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:bg-gray-800 dark:text-slate-400 dark:hover:text-slate-300 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600{}",
+                                                                        if is_active.get() {
                                                                             " bg-gray-100"
                                                                         } else {
                                                                             ""
@@ -246,6 +399,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                                                 }
 
                                                                 href=move || item.route.clone().unwrap()
+                                                                aria-current=move || is_active.get().then_some("page")
                                                             >
                                                                 {item.name}
                                                             </a>
@@ -264,14 +418,15 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                 .into_view()
                         } else {
                             let route = item.route.clone().unwrap();
-                            let route_ = route.clone();
+                            let is_active = route_memo(&item, active_route);
+                            let is_match = match_memo(&item, query);
                             view! {
-                                <li>
+                                <li class:opacity-40=move || !is_match.get()>
                                     <a
                                         class=move || {
                                             format!(
-                                                "w-full flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600{}",
-                                                if route_ == current_route.get() {
+                                                "w-full flex items-center gap-x-3.5 py-2 px-2.5 text-sm text-slate-700 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-900 dark:text-slate-400 dark:hover:text-slate-300 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600{}",
+                                                if is_active.get() {
                                                     " bg-gray-100 active"
                                                 } else {
                                                     ""
@@ -280,6 +435,7 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
                                         }
 
                                         href=route
+                                        aria-current=move || is_active.get().then_some("page")
                                     >
                                         {item.icon}
                                         {item.name}
@@ -296,3 +452,68 @@ pub fn SideBar(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl
         </div>
     }
 }
+
+fn match_memo(item: &MenuItem, query: Memo<String>) -> Memo<bool> {
+    let item = item.clone();
+    create_memo(move |_| query.with(|query| query.is_empty() || item.matches(query)))
+}
+
+fn route_memo(item: &MenuItem, active_route: Memo<Option<String>>) -> Memo<bool> {
+    let route = item.route.clone();
+    create_memo(move |_| active_route.with(|active| active.is_some() && *active == route))
+}
+
+// Detail pages select the list they belong to, the longest matching route wins
+fn best_route(items: &[MenuItem], path: &str) -> Option<String> {
+    fn visit<'x>(items: &'x [MenuItem], path: &[&str], best: &mut Option<(usize, &'x str)>) {
+        for item in items {
+            if let Some(route) = item.route.as_deref() {
+                let segments = route_segments(route);
+                let matches = segments.len() <= path.len()
+                    && segments.iter().zip(path).all(|(segment, part)| {
+                        segment == part
+                            || segment
+                                .strip_suffix('s')
+                                .is_some_and(|singular| singular == *part)
+                    });
+                if matches && best.is_none_or(|(len, _)| segments.len() > len) {
+                    *best = Some((segments.len(), route));
+                }
+            }
+            visit(&item.children, path, best);
+        }
+    }
+
+    let mut best = None;
+    visit(items, &route_segments(path), &mut best);
+    best.map(|(_, route)| route.to_string())
+}
+
+fn route_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn expand_key(key: &str, is_expanded: bool) -> bool {
+    match key {
+        "ArrowRight" => !is_expanded,
+        "ArrowLeft" => is_expanded,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_key;
+
+    #[test]
+    fn arrows_toggle_groups() {
+        assert!(expand_key("ArrowRight", false));
+        assert!(!expand_key("ArrowRight", true));
+        assert!(expand_key("ArrowLeft", true));
+        assert!(!expand_key("ArrowLeft", false));
+        assert!(!expand_key("Enter", false));
+        assert!(!expand_key("ArrowDown", true));
+    }
+}
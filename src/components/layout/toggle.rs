@@ -7,7 +7,7 @@
 use leptos::*;
 use leptos_router::use_location;
 
-use super::MenuItem;
+use super::{sidebar::SidebarState, MenuItem};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct BreadCrumb {
@@ -16,7 +16,7 @@ struct BreadCrumb {
 }
 
 #[component]
-pub fn ToggleNavigation(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>) -> impl IntoView {
+pub fn ToggleNavigation(menu_items: Vec<MenuItem>, sidebar: SidebarState) -> impl IntoView {
     let build_path = move || {
         let current_route = use_location().pathname.get();
         let mut path = Vec::new();
@@ -61,7 +61,10 @@ pub fn ToggleNavigation(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>)
     };
 
     view! {
-        <div class="sticky top-0 inset-x-0 z-20 bg-white border-y px-4 sm:px-6 md:px-8 lg:hidden dark:bg-gray-800 dark:border-gray-700">
+        <div
+            class="sticky top-0 inset-x-0 z-20 bg-white border-y px-4 sm:px-6 md:px-8 dark:bg-gray-800 dark:border-gray-700"
+            class=("lg:hidden", move || !sidebar.collapsed.get())
+        >
             <div class="flex items-center py-4">
                 <button
                     type="button"
@@ -69,7 +72,7 @@ pub fn ToggleNavigation(menu_items: Vec<MenuItem>, show_sidebar: RwSignal<bool>)
                     data-hs-overlay="#application-sidebar"
                     aria-controls="application-sidebar"
                     aria-label="Toggle navigation"
-                    on:click=move |_| show_sidebar.update(|v| *v = !*v)
+                    on:click=move |_| sidebar.toggle()
                 >
                     <span class="sr-only">Toggle Navigation</span>
                     <svg
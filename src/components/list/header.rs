@@ -8,10 +8,25 @@ use std::collections::HashSet;
 
 use leptos::*;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortOrder {
+    pub column: String,
+    pub ascending: bool,
+}
+
+#[derive(Clone)]
+pub struct ColumnSort {
+    pub columns: Vec<(&'static str, &'static str)>,
+    pub current: Signal<Option<SortOrder>>,
+    pub on_sort: Callback<SortOrder, ()>,
+}
+
 #[component]
 pub fn ColumnList(
     #[prop(into)] headers: MaybeSignal<Vec<String>>,
     #[prop(into, optional)] select_all: Option<Callback<(), Vec<String>>>,
+    #[prop(optional)] sort: Option<ColumnSort>,
+    #[prop(optional)] sticky: bool,
     children: Children,
 ) -> impl IntoView {
     let headers_ = headers.clone();
@@ -19,7 +34,12 @@ pub fn ColumnList(
     let has_select_all = select_all.is_some();
 
     view! {
-        <thead class="bg-gray-50 dark:bg-slate-800">
+        <thead
+            class="bg-gray-50 dark:bg-slate-800"
+            class=("sticky", sticky)
+            class=("top-0", sticky)
+            class=("z-10", sticky)
+        >
             <tr>
                 {select_all
                     .map(|select_all| {
@@ -62,14 +82,82 @@ pub fn ColumnList(
                         } else {
                             "px-6 py-3 text-start"
                         };
-                        view! {
-                            <th scope="col" class=class>
-                                <div class="flex items-center gap-x-2">
-                                    <span class="text-xs font-semibold uppercase tracking-wide text-gray-800 dark:text-gray-200">
-                                        {header}
-                                    </span>
-                                </div>
-                            </th>
+                        let sort_column = sort
+                            .as_ref()
+                            .and_then(|sort| {
+                                sort.columns
+                                    .iter()
+                                    .find(|(label, _)| *label == header)
+                                    .map(|(_, column)| (*column, sort.current, sort.on_sort))
+                            });
+                        match sort_column {
+                            Some((column, current, on_sort)) => {
+                                let direction = Signal::derive(move || {
+                                    current
+                                        .get()
+                                        .filter(|order| order.column == column)
+                                        .map(|order| order.ascending)
+                                });
+                                view! {
+                                    <th
+                                        scope="col"
+                                        class=class
+                                        aria-sort=move || match direction.get() {
+                                            Some(true) => "ascending",
+                                            Some(false) => "descending",
+                                            None => "none",
+                                        }
+                                    >
+                                        <button
+                                            type="button"
+                                            class="inline-flex items-center gap-x-1 text-xs font-semibold uppercase tracking-wide text-gray-800 hover:text-blue-600 dark:text-gray-200 dark:hover:text-blue-500"
+                                            on:click=move |_| {
+                                                on_sort
+                                                    .call(SortOrder {
+                                                        column: column.to_string(),
+                                                        ascending: direction.get() != Some(true),
+                                                    });
+                                            }
+                                        >
+
+                                            {header}
+                                            <svg
+                                                class="flex-shrink-0 size-3.5"
+                                                class:text-gray-400=move || direction.get().is_none()
+                                                xmlns="http://www.w3.org/2000/svg"
+                                                width="24"
+                                                height="24"
+                                                viewBox="0 0 24 24"
+                                                fill="none"
+                                                stroke="currentColor"
+                                                stroke-width="2"
+                                                stroke-linecap="round"
+                                                stroke-linejoin="round"
+                                            >
+                                                <path
+                                                    class:hidden=move || direction.get() == Some(true)
+                                                    d="m7 15 5 5 5-5"
+                                                ></path>
+                                                <path
+                                                    class:hidden=move || direction.get() == Some(false)
+                                                    d="m7 9 5-5 5 5"
+                                                ></path>
+                                            </svg>
+                                        </button>
+                                    </th>
+                                }
+                            }
+                            None => {
+                                view! {
+                                    <th scope="col" class=class>
+                                        <div class="flex items-center gap-x-2">
+                                            <span class="text-xs font-semibold uppercase tracking-wide text-gray-800 dark:text-gray-200">
+                                                {header}
+                                            </span>
+                                        </div>
+                                    </th>
+                                }
+                            }
                         }
                     }
                 />
@@ -82,3 +170,28 @@ pub fn ColumnList(
         </tbody>
     }
 }
+
+impl SortOrder {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (column, ascending) = match value.strip_prefix('-') {
+            Some(column) => (column, false),
+            None => (value, true),
+        };
+        if !column.is_empty() {
+            Some(SortOrder {
+                column: column.to_string(),
+                ascending,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn to_param(&self) -> String {
+        if self.ascending {
+            self.column.clone()
+        } else {
+            format!("-{}", self.column)
+        }
+    }
+}
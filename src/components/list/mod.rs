@@ -6,13 +6,14 @@
 
 pub mod header;
 pub mod pagination;
+pub mod refresh;
 pub mod row;
 pub mod table;
 pub mod toolbar;
 
 use leptos::*;
 
-use crate::components::{icon::IconPlus, messages::alert::Alerts};
+use crate::components::icon::IconPlus;
 
 #[slot]
 pub struct Toolbar {
@@ -24,6 +25,13 @@ pub struct Footer {
     children: Children,
 }
 
+#[slot]
+pub struct Filters {
+    #[prop(optional, into)]
+    hide: MaybeSignal<bool>,
+    children: Children,
+}
+
 #[component]
 pub fn ListTable(
     #[prop(optional, into)] title: MaybeSignal<String>,
@@ -31,6 +39,7 @@ pub fn ListTable(
     children: Children,
     toolbar: Toolbar,
     footer: Footer,
+    #[prop(optional)] filters: Option<Filters>,
 ) -> impl IntoView {
     view! {
         <div class="flex flex-col">
@@ -64,6 +73,19 @@ pub fn ListTable(
 
                         </div>
 
+                        {filters
+                            .map(|filters| {
+                                let hide = filters.hide;
+                                view! {
+                                    <div
+                                        class="px-6 py-4 grid gap-3 sm:grid-cols-2 lg:grid-cols-4 border-b border-gray-200 dark:border-gray-700"
+                                        class:hidden=move || hide.get()
+                                    >
+                                        {(filters.children)()}
+                                    </div>
+                                }
+                            })}
+
                         <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
                             {children()}
                         </table>
@@ -80,7 +102,6 @@ pub fn ListTable(
 pub fn ListSection(children: Children) -> impl IntoView {
     view! {
         <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
-            <Alerts/>
             {children()}
         </div>
     }
@@ -111,9 +132,10 @@ pub fn ListTextItem(children: Children) -> impl IntoView {
 }
 
 #[component]
-pub fn ZeroResults(
+pub fn EmptyState(
     #[prop(into)] title: MaybeSignal<String>,
     #[prop(into)] subtitle: MaybeSignal<String>,
+    #[prop(default = None)] icon: Option<View>,
     #[prop(into, optional)] button_text: MaybeSignal<String>,
     #[prop(into, optional)] button_action: Option<Callback<(), ()>>,
 ) -> impl IntoView {
@@ -121,22 +143,8 @@ pub fn ZeroResults(
 
     view! {
         <div class="max-w-sm w-full min-h-[400px] flex flex-col justify-center mx-auto px-6 py-4">
-            <div class="flex justify-center items-center size-[46px] bg-gray-100 rounded-lg dark:bg-gray-800">
-                <svg
-                    class="size-4 text-gray-400"
-                    xmlns="http://www.w3.org/2000/svg"
-                    width="16"
-                    height="16"
-                    fill="currentColor"
-                    viewBox="0 0 16 16"
-                >
-                    <path d=concat!(
-                        "M11.742 10.344a6.5 6.5 0 1 0-1.397 1.398h-.001c.03.04.062.078.098.",
-                        "115l3.85 3.85a1 1 0 0 0 1.415-1.414l-3.85-3.85a1.007 ",
-                        "1.007 0 0 0-.115-.1zM12 6.5a5.5 5.5 0 1 1-11 ",
-                        "0 5.5 5.5 0 0 1 11 0z",
-                    )></path>
-                </svg>
+            <div class="flex justify-center items-center size-[46px] bg-gray-100 rounded-lg text-gray-400 dark:bg-gray-800">
+                {icon.unwrap_or_else(|| view! { <SearchIcon/> }.into_view())}
 
             </div>
 
@@ -162,3 +170,40 @@ pub fn ZeroResults(
         </div>
     }
 }
+
+#[component]
+pub fn ZeroResults(
+    #[prop(into)] title: MaybeSignal<String>,
+    #[prop(into)] subtitle: MaybeSignal<String>,
+    #[prop(into, optional)] button_text: MaybeSignal<String>,
+    #[prop(into, optional)] button_action: Option<Callback<(), ()>>,
+) -> impl IntoView {
+    EmptyState(EmptyStateProps {
+        title,
+        subtitle,
+        icon: None,
+        button_text,
+        button_action,
+    })
+}
+
+#[component]
+fn SearchIcon() -> impl IntoView {
+    view! {
+        <svg
+            class="size-4"
+            xmlns="http://www.w3.org/2000/svg"
+            width="16"
+            height="16"
+            fill="currentColor"
+            viewBox="0 0 16 16"
+        >
+            <path d=concat!(
+                "M11.742 10.344a6.5 6.5 0 1 0-1.397 1.398h-.001c.03.04.062.078.098.",
+                "115l3.85 3.85a1 1 0 0 0 1.415-1.414l-3.85-3.85a1.007 ",
+                "1.007 0 0 0-.115-.1zM12 6.5a5.5 5.5 0 1 1-11 ",
+                "0 5.5 5.5 0 0 1 11 0z",
+            )></path>
+        </svg>
+    }
+}
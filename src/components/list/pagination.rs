@@ -8,12 +8,16 @@ use leptos::*;
 
 use crate::components::icon::{IconArrowLeft, IconArrowRight};
 
+pub const PAGE_SIZES: [u32; 3] = [25, 50, 100];
+
 #[component]
 pub fn Pagination(
     #[prop(into)] current_page: MaybeSignal<u32>,
     #[prop(into)] total_results: MaybeSignal<Option<u32>>,
     #[prop(into)] page_size: MaybeSignal<u32>,
     #[prop(into)] on_page_change: Callback<u32, ()>,
+    #[prop(into, optional)] on_page_size_change: Option<Callback<u32, ()>>,
+    #[prop(optional)] jump_to_page: bool,
 ) -> impl IntoView {
     let total_pages = create_memo(move |_| {
         (total_results.get().unwrap_or(0) as f64 / page_size.get() as f64).ceil() as u32
@@ -32,24 +36,89 @@ pub fn Pagination(
 
                     </p>
                     <div class="max-w-sm space-y-3">
-                        <select
-                            class="py-2 px-3 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
-                            on:change=move |ev| {
-                                on_page_change.call(event_target_value(&ev).parse().unwrap_or(1));
+                        {if jump_to_page {
+                            view! {
+                                <input
+                                    type="number"
+                                    min="1"
+                                    max=move || total_pages.get().to_string()
+                                    class="py-2 px-3 block w-20 border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                                    prop:value=move || current_page.get().to_string()
+                                    on:change=move |ev| {
+                                        let page = event_target_value(&ev)
+                                            .trim()
+                                            .parse::<u32>()
+                                            .unwrap_or(1)
+                                            .clamp(1, std::cmp::max(total_pages.get(), 1));
+                                        if page != current_page.get() {
+                                            on_page_change.call(page);
+                                        }
+                                    }
+                                />
                             }
-                        >
-
-                            <For each=move || (1..=total_pages.get()) key=|page| *page let:page>
-                                <option selected=move || current_page.get() == page>{page}</option>
-
-                            </For>
+                                .into_view()
+                        } else {
+                            view! {
+                                <select
+                                    class="py-2 px-3 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                                    on:change=move |ev| {
+                                        on_page_change
+                                            .call(event_target_value(&ev).parse().unwrap_or(1));
+                                    }
+                                >
+
+                                    <For
+                                        each=move || 1..=total_pages.get()
+                                        key=|page| *page
+                                        let:page
+                                    >
+                                        <option selected=move || {
+                                            current_page.get() == page
+                                        }>{page}</option>
+
+                                    </For>
+
+                                </select>
+                            }
+                                .into_view()
+                        }}
 
-                        </select>
                     </div>
 
                     <p class="text-sm text-gray-600 dark:text-gray-400">
                         "of " {total_pages}
                     </p>
+
+                    {on_page_size_change
+                        .map(|on_page_size_change| {
+                            view! {
+                                <select
+                                    class="py-2 px-3 pe-9 block border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                                    on:change=move |ev| {
+                                        if let Ok(size) = event_target_value(&ev).parse::<u32>() {
+                                            on_page_size_change.call(size);
+                                        }
+                                    }
+                                >
+
+                                    {PAGE_SIZES
+                                        .into_iter()
+                                        .map(|size| {
+                                            view! {
+                                                <option
+                                                    value=size.to_string()
+                                                    selected=move || page_size.get() == size
+                                                >
+                                                    {format!("{size} per page")}
+                                                </option>
+                                            }
+                                        })
+                                        .collect_view()}
+
+                                </select>
+                            }
+                        })}
+
                 </div>
 
             </Show>
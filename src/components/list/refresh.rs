@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Duration;
+
+use gloo_storage::{SessionStorage, Storage};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+const AUTO_REFRESH_INTERVALS: [u32; 4] = [5, 15, 30, 60];
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct AutoRefresh {
+    enabled: bool,
+    interval: u32,
+}
+
+#[component]
+pub fn AutoRefreshToggle(
+    storage_key: &'static str,
+    #[prop(optional)] enabled: bool,
+    #[prop(into)] paused: Signal<bool>,
+    #[prop(into)] on_refresh: Callback<(), ()>,
+) -> impl IntoView {
+    let settings = create_rw_signal(SessionStorage::get::<AutoRefresh>(storage_key).unwrap_or(
+        AutoRefresh {
+            enabled,
+            ..Default::default()
+        },
+    ));
+    let countdown = create_rw_signal(settings.get_untracked().interval);
+
+    create_effect(move |_| {
+        let settings = settings.get();
+        countdown.set(settings.interval);
+        if let Err(err) = SessionStorage::set(storage_key, settings) {
+            log::warn!("Failed to save auto-refresh settings: {err}");
+        }
+    });
+
+    match set_interval_with_handle(
+        move || {
+            let settings = settings.get_untracked();
+            if settings.enabled && !paused.get_untracked() {
+                let remaining = countdown.get_untracked().saturating_sub(1);
+                if remaining == 0 {
+                    countdown.set(settings.interval);
+                    on_refresh.call(());
+                } else {
+                    countdown.set(remaining);
+                }
+            }
+        },
+        Duration::from_secs(1),
+    ) {
+        Ok(handle) => on_cleanup(move || handle.clear()),
+        Err(err) => log::warn!("Failed to start auto-refresh timer: {err:?}"),
+    }
+
+    view! {
+        <div class="inline-flex items-center gap-x-2">
+            <label class="inline-flex items-center gap-x-2 text-sm text-gray-800 whitespace-nowrap dark:text-gray-200">
+                <input
+                    type="checkbox"
+                    class="shrink-0 border-gray-300 rounded text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-600 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                    prop:checked=move || settings.get().enabled
+                    on:input=move |_| {
+                        settings.update(|s| s.enabled = !s.enabled);
+                    }
+                />
+
+                "Auto-refresh"
+            </label>
+            <select
+                class="py-2 px-3 pe-9 block border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                on:change=move |ev| {
+                    if let Ok(interval) = event_target_value(&ev).parse::<u32>() {
+                        settings.update(|s| s.interval = interval);
+                    }
+                }
+            >
+
+                {AUTO_REFRESH_INTERVALS
+                    .into_iter()
+                    .map(|interval| {
+                        view! {
+                            <option
+                                value=interval.to_string()
+                                selected=move || settings.get().interval == interval
+                            >
+                                {format!("{interval}s")}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+
+            </select>
+            <span
+                class="text-xs text-gray-500 tabular-nums w-16"
+                class:invisible=move || !settings.get().enabled
+            >
+                {move || {
+                    if paused.get() {
+                        "Paused".to_string()
+                    } else {
+                        format!("in {}s", countdown.get())
+                    }
+                }}
+
+            </span>
+        </div>
+    }
+}
+
+impl Default for AutoRefresh {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 15,
+        }
+    }
+}
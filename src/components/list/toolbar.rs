@@ -4,10 +4,20 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use std::time::Duration;
+
 use leptos::*;
 use web_sys::wasm_bindgen::JsCast;
 
-use crate::components::Color;
+use crate::{
+    components::{icon::IconXMark, Color},
+    core::{
+        api::ApiClient,
+        debounce::use_debounce,
+        i18n::t,
+        shortcuts::{use_shortcut, Shortcut, ShortcutContext},
+    },
+};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ButtonIcon {
@@ -20,6 +30,7 @@ pub fn ToolbarButton(
     #[prop(into)] text: MaybeSignal<String>,
     color: Color,
     #[prop(into)] on_click: Callback<(), ()>,
+    #[prop(optional, into)] hide: MaybeSignal<bool>,
     #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
     let class = match color {
@@ -30,7 +41,7 @@ pub fn ToolbarButton(
     };
 
     view! {
-        <button class=class on:click=move |_| on_click.call(())>
+        <button class=class class:hidden=move || hide.get() on:click=move |_| on_click.call(())>
 
             {children.map(|children| children())}
 
@@ -43,35 +54,87 @@ pub fn ToolbarButton(
 pub fn SearchBox(
     #[prop(into)] value: MaybeSignal<Option<String>>,
     #[prop(into)] on_search: Callback<String, ()>,
+    #[prop(optional)] debounce: Option<Duration>,
+    // Results still loading for the previous search are dropped when a new one starts
+    #[prop(optional)] latest: Option<ApiClient>,
 ) -> impl IntoView {
-    let value_ = value.clone();
+    let value = Signal::derive(move || value.get());
+    let input_ref = create_node_ref::<html::Input>();
+    let text = create_rw_signal(String::new());
+    use_shortcut(
+        Shortcut::new("/", "Focus the search box").in_context(ShortcutContext::Lists),
+        move |_| {
+            if let Some(input) = input_ref.get_untracked() {
+                let _ = input.focus();
+            }
+        },
+    );
+    create_effect(move |_| text.set(value.get().unwrap_or_default()));
+    let submit = move |filter: String| {
+        if filter.trim() != value.get_untracked().unwrap_or_default() {
+            if let Some(latest) = latest {
+                latest.abort_latest();
+            }
+            on_search.call(filter.trim().to_string());
+        }
+    };
+    let debounced = debounce.map(|delay| use_debounce(delay, submit));
+    let search = move |filter: String| {
+        if let Some(debounced) = debounced {
+            debounced.cancel();
+        }
+        submit(filter);
+    };
+
     view! {
         <div class="sm:col-span-1">
             <label for="hs-as-table-product-review-search" class="sr-only">
-                Search
+                {move || t("button.search")}
             </label>
             <div class="relative">
                 <input
                     type="text"
+                    node_ref=input_ref
                     class=concat!(
-                        "py-2 px-3 ps-11 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 ",
+                        "py-2 px-3 ps-11 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 ",
                         "disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 ",
                         "dark:text-gray-400 dark:focus:ring-gray-600",
                     )
 
-                    placeholder="Search"
-                    prop:value=move || value_.get().unwrap_or_default()
+                    placeholder=move || t("button.search")
+                    prop:value=text
+                    on:input=move |ev| {
+                        text.set(event_target_value(&ev));
+                        if let Some(debounced) = debounced {
+                            debounced.call(text.get_untracked());
+                        }
+                    }
+
                     on:keyup=move |ev| {
                         let key_code = ev.unchecked_ref::<web_sys::KeyboardEvent>().key_code();
                         if key_code == 13 {
-                            let filter = event_target_value(&ev);
-                            if filter != value.get().unwrap_or_default() {
-                                on_search.call(filter);
-                            }
+                            search(event_target_value(&ev));
                         }
                     }
                 />
 
+                <button
+                    type="button"
+                    class="absolute inset-y-0 end-0 flex items-center pe-3 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300"
+                    class:hidden=move || text.with(|text| text.is_empty())
+                    on:click=move |_| {
+                        text.set(String::new());
+                        search(String::new());
+                        if let Some(input) = input_ref.get_untracked() {
+                            let _ = input.focus();
+                        }
+                    }
+                >
+
+                    <span class="sr-only">{move || t("button.clear")}</span>
+                    <IconXMark attr:class="flex-shrink-0 size-3.5"/>
+                </button>
+
                 <div class="absolute inset-y-0 start-0 flex items-center pointer-events-none ps-4">
                     <svg
                         class="size-4 text-gray-400"
@@ -93,3 +156,81 @@ pub fn SearchBox(
         </div>
     }
 }
+
+#[component]
+pub fn FilterInput(
+    #[prop(into)] label: String,
+    #[prop(into)] value: MaybeSignal<Option<String>>,
+    #[prop(optional)] input_type: Option<&'static str>,
+    #[prop(optional, into)] placeholder: Option<String>,
+    #[prop(into)] on_change: Callback<String, ()>,
+) -> impl IntoView {
+    let value_ = value.clone();
+    let has_value = Signal::derive(move || value_.get().is_some_and(|v| !v.is_empty()));
+
+    view! {
+        <div>
+            <label class="block text-xs font-medium text-gray-500 mb-1 dark:text-gray-400">
+                {label}
+            </label>
+            <div class="relative">
+                <input
+                    type=input_type.unwrap_or("text")
+                    class="py-2 px-3 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                    placeholder=placeholder
+                    autocapitalize="off"
+                    prop:value=move || value.get().unwrap_or_default()
+                    on:change=move |ev| {
+                        on_change.call(event_target_value(&ev).trim().to_string());
+                    }
+                />
+
+                <button
+                    type="button"
+                    class="absolute inset-y-0 end-0 flex items-center pe-3 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300"
+                    class:hidden=move || !has_value.get()
+                    on:click=move |_| on_change.call(String::new())
+                >
+                    <span class="sr-only">Clear</span>
+                    <IconXMark attr:class="flex-shrink-0 size-3.5"/>
+                </button>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn FilterSelect(
+    #[prop(into)] label: String,
+    #[prop(into)] value: MaybeSignal<String>,
+    options: Vec<(&'static str, &'static str)>,
+    #[prop(into)] on_change: Callback<String, ()>,
+) -> impl IntoView {
+    view! {
+        <div>
+            <label class="block text-xs font-medium text-gray-500 mb-1 dark:text-gray-400">
+                {label}
+            </label>
+            <select
+                class="py-2 px-3 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                on:change=move |ev| {
+                    on_change.call(event_target_value(&ev));
+                }
+            >
+
+                {options
+                    .into_iter()
+                    .map(|(option, text)| {
+                        let value = value.clone();
+                        view! {
+                            <option value=option selected=move || value.get() == option>
+                                {text}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+
+            </select>
+        </div>
+    }
+}
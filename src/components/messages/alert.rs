@@ -32,135 +32,191 @@ pub struct Alert {
     pub timeout: Option<Duration>,
 }
 
+const MAX_TOASTS: usize = 5;
+const EXIT_DURATION: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy)]
+pub struct AlertQueue {
+    toasts: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<u64>,
+    owner: Owner,
+}
+
+#[derive(Clone)]
+struct Toast {
+    id: u64,
+    alert: Alert,
+    leaving: RwSignal<bool>,
+}
+
 pub fn init_alerts() {
-    provide_context(create_rw_signal(Alert::disabled()));
+    provide_context(AlertQueue {
+        toasts: create_rw_signal(Vec::new()),
+        next_id: store_value(0),
+        owner: Owner::current().expect("alerts initialized outside of a reactive owner"),
+    });
+}
+
+pub fn use_alerts() -> AlertQueue {
+    expect_context::<AlertQueue>()
 }
 
-pub fn use_alerts() -> RwSignal<Alert> {
-    let signal = expect_context::<RwSignal<Alert>>();
-    signal.set(Alert::disabled());
-    signal
+pub fn alert_success(message: impl Into<String>) {
+    use_alerts().set(Alert::success(message));
+}
+
+pub fn alert_error(message: impl Into<String>) {
+    use_alerts().set(Alert::error(message));
+}
+
+pub fn alert_warning(message: impl Into<String>) {
+    use_alerts().set(Alert::warning(message));
+}
+
+impl AlertQueue {
+    pub fn set(&self, alert: Alert) {
+        if alert.is_closed() {
+            return;
+        }
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        let timeout = alert.timeout();
+        // Callers may be disposed before the toast is, so it belongs to the queue
+        let leaving = with_owner(self.owner, || create_rw_signal(false));
+
+        self.toasts.update(|toasts| {
+            toasts.push(Toast { id, alert, leaving });
+            // Errors stay until dismissed, only toasts that expire are evicted
+            let mut excess = toasts.len().saturating_sub(MAX_TOASTS);
+            toasts.retain(|toast| {
+                if excess > 0 && toast.alert.timeout.is_some() {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        });
+
+        if let Some(timeout) = timeout {
+            let queue = *self;
+            set_timeout(move || queue.dismiss(id), timeout);
+        }
+    }
+
+    pub fn dismiss(&self, id: u64) {
+        let Some(leaving) = self.toasts.with_untracked(|toasts| {
+            toasts
+                .iter()
+                .find(|toast| toast.id == id)
+                .map(|toast| toast.leaving)
+        }) else {
+            return;
+        };
+        leaving.set(true);
+
+        let toasts = self.toasts;
+        set_timeout(
+            move || toasts.update(|toasts| toasts.retain(|toast| toast.id != id)),
+            EXIT_DURATION,
+        );
+    }
 }
 
 #[component]
 pub fn Alerts() -> impl IntoView {
-    let alert = expect_context::<RwSignal<Alert>>();
-
-    create_effect(move |_| {
-        let alert_data = alert.get();
-        if alert_data.is_open() {
-            if let Some(timeout) = alert_data.timeout() {
-                set_timeout(
-                    move || {
-                        alert.update(|alert| {
-                            alert.close();
-                        });
-                    },
-                    timeout,
-                );
-            }
-        }
-    });
+    let queue = use_alerts();
 
     view! {
-        <div class=move || {
-            match alert.get().typ {
-                AlertType::None => "hidden",
-                _ => "pb-5",
-            }
-        }>
-            <div
-                class=move || {
-                    match alert.get().typ {
-                        AlertType::Success => {
-                            "bg-teal-50 border border-teal-200 text-sm text-teal-800 rounded-lg p-4 dark:bg-teal-800/10 dark:border-teal-900 dark:text-teal-500"
-                        }
-                        AlertType::Error => {
-                            "bg-red-50 border border-red-200 text-sm text-red-800 rounded-lg p-4 dark:bg-red-800/10 dark:border-red-900 dark:text-red-500"
-                        }
-                        AlertType::Warning => {
-                            "bg-yellow-50 border border-yellow-200 text-sm text-yellow-800 rounded-lg p-4 dark:bg-yellow-800/10 dark:border-yellow-900 dark:text-yellow-500"
-                        }
-                        AlertType::None => "hidden",
-                    }
-                }
+        <div
+            class="fixed top-4 end-4 z-[80] w-full max-w-sm space-y-3 px-4 sm:px-0"
+            aria-label="Notifications"
+        >
+            <For
+                each=move || queue.toasts.get().into_iter().rev()
+                key=|toast| toast.id
+                children=move |toast| view! { <AlertToast toast queue/> }
+            />
+        </div>
+    }
+}
 
-                role="alert"
-            >
-                <div class="flex">
-                    <div class="flex-shrink-0">
-                        {move || {
-                            match alert.get().typ {
-                                AlertType::Success | AlertType::None => {
-                                    view! {
-                                        <IconCheckCircle
-                                            attr:class="flex-shrink-0 size-4 text-blue-600 mt-1"
-                                            attr:stroke="teal"
-                                        />
-                                    }
-                                        .into_view()
-                                }
-                                AlertType::Error => {
-                                    view! {
-                                        <IconExclamationCircle
-                                            attr:class="flex-shrink-0 size-4 mt-0.5"
-                                            attr:stroke="red"
-                                        />
-                                    }
-                                        .into_view()
-                                }
-                                AlertType::Warning => {
-                                    view! {
-                                        <IconExclamationTriangle
-                                            attr:class="flex-shrink-0 size-4 mt-0.5"
-                                            attr:stroke="#854d0e"
-                                        />
-                                    }
-                                        .into_view()
-                                }
-                            }
-                        }}
+#[component]
+fn AlertToast(toast: Toast, queue: AlertQueue) -> impl IntoView {
+    let Toast { id, alert, leaving } = toast;
+    let (color, icon, button) = match alert.typ {
+        AlertType::Success | AlertType::None => (
+            "bg-teal-50 border border-teal-200 text-sm text-teal-800 rounded-lg p-4 shadow-lg dark:bg-teal-800/10 dark:border-teal-900 dark:text-teal-500",
+            view! {
+                <IconCheckCircle
+                    attr:class="flex-shrink-0 size-4 text-blue-600 mt-1"
+                    attr:stroke="teal"
+                />
+            }
+            .into_view(),
+            "inline-flex bg-teal-50 rounded-lg p-1.5 text-teal-500 hover:bg-teal-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-teal-50 focus:ring-teal-600 dark:bg-transparent dark:hover:bg-teal-800/50 dark:text-teal-600",
+        ),
+        AlertType::Error => (
+            "bg-red-50 border border-red-200 text-sm text-red-800 rounded-lg p-4 shadow-lg dark:bg-red-800/10 dark:border-red-900 dark:text-red-500",
+            view! {
+                <IconExclamationCircle attr:class="flex-shrink-0 size-4 mt-0.5" attr:stroke="red"/>
+            }
+            .into_view(),
+            "inline-flex bg-red-50 rounded-lg p-1.5 text-red-500 hover:bg-red-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-red-50 focus:ring-red-600 dark:bg-transparent dark:hover:bg-red-800/50 dark:text-red-600",
+        ),
+        AlertType::Warning => (
+            "bg-yellow-50 border border-yellow-200 text-sm text-yellow-800 rounded-lg p-4 shadow-lg dark:bg-yellow-800/10 dark:border-yellow-900 dark:text-yellow-500",
+            view! {
+                <IconExclamationTriangle
+                    attr:class="flex-shrink-0 size-4 mt-0.5"
+                    attr:stroke="#854d0e"
+                />
+            }
+            .into_view(),
+            "inline-flex bg-yellow-50 rounded-lg p-1.5 text-yellow-500 hover:bg-yellow-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-yellow-50 focus:ring-yellow-600 dark:bg-transparent dark:hover:bg-yellow-800/50 dark:text-yellow-600",
+        ),
+    };
 
-                    </div>
-                    <div class="ms-4">
-                        <h3 class="text-sm font-semibold">
-                            {move || { alert.get().message.clone() }}
-                        </h3>
-                        <div class="mt-1 text-sm">{move || { alert.get().details }}</div>
-                    </div>
+    view! {
+        <div
+            class=move || {
+                format!(
+                    "{color} motion-safe:transition-all duration-200 {}",
+                    if leaving.get() {
+                        "opacity-0 translate-x-4"
+                    } else {
+                        "motion-safe:animate-toast-in"
+                    },
+                )
+            }
 
-                    <div class="ps-3 ms-auto">
-                        <div class="-mx-1.5 -my-1.5">
-                            <button
-                                type="button"
-                                class=move || {
-                                    match alert.get().typ {
-                                        AlertType::Success => {
-                                            "inline-flex bg-teal-50 rounded-lg p-1.5 text-teal-500 hover:bg-teal-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-teal-50 focus:ring-teal-600 dark:bg-transparent dark:hover:bg-teal-800/50 dark:text-teal-600"
-                                        }
-                                        AlertType::Error => {
-                                            "inline-flex bg-red-50 rounded-lg p-1.5 text-red-500 hover:bg-red-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-red-50 focus:ring-red-600 dark:bg-transparent dark:hover:bg-red-800/50 dark:text-red-600"
-                                        }
-                                        AlertType::Warning => {
-                                            "inline-flex bg-yellow-50 rounded-lg p-1.5 text-yellow-500 hover:bg-yellow-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-yellow-50 focus:ring-yellow-600 dark:bg-transparent dark:hover:bg-yellow-800/50 dark:text-yellow-600"
-                                        }
-                                        AlertType::None => "hidden",
-                                    }
-                                }
+            // Errors interrupt the screen reader, other messages wait their turn
+            role=if matches!(alert.typ, AlertType::Error) { "alert" } else { "status" }
+            aria-atomic="true"
+            on:keydown=move |ev| {
+                if ev.key() == "Escape" {
+                    ev.stop_propagation();
+                    queue.dismiss(id);
+                }
+            }
+        >
+            <div class="flex">
+                <div class="flex-shrink-0" aria-hidden="true">
+                    {icon}
+                </div>
+                <div class="ms-4">
+                    <h3 class="text-sm font-semibold">{alert.message}</h3>
+                    <div class="mt-1 text-sm">{alert.details}</div>
+                </div>
 
-                                on:click=move |_| {
-                                    alert
-                                        .update(|alert| {
-                                            alert.close();
-                                        });
-                                }
-                            >
+                <div class="ps-3 ms-auto">
+                    <div class="-mx-1.5 -my-1.5">
+                        <button type="button" class=button on:click=move |_| queue.dismiss(id)>
 
-                                <span class="sr-only">Dismiss</span>
-                                <IconXMark/>
+                            <span class="sr-only">Dismiss</span>
+                            <IconXMark attr:aria-hidden="true"/>
 
-                            </button>
-                        </div>
+                        </button>
                     </div>
                 </div>
             </div>
@@ -320,6 +376,8 @@ impl From<http::Error> for Alert {
             http::Error::Forbidden | http::Error::TotpRequired => Alert::error("Forbidden")
                 .with_details("You are not authorized to perform this action."),
             http::Error::Unauthorized => Alert::error("Unauthorized"),
+            // Nobody is waiting for the response anymore
+            http::Error::Aborted => Alert::disabled(),
         }
     }
 }
@@ -389,3 +447,25 @@ impl From<ReloadSettings> for Alert {
         }
     }
 }
+
+pub fn error_reason(err: http::Error) -> String {
+    match err {
+        http::Error::Server(ManagementApiError::FieldAlreadyExists { field, value }) => {
+            format!("Another record exists with value {value:?} in field {field:?}")
+        }
+        http::Error::Server(ManagementApiError::FieldMissing { field }) => {
+            format!("Field {field} is missing")
+        }
+        http::Error::Server(ManagementApiError::NotFound { item }) => {
+            format!("{item} was not found")
+        }
+        http::Error::Server(ManagementApiError::Unsupported { details }) => details,
+        http::Error::Server(ManagementApiError::Other {
+            details,
+            reason: Some(reason),
+        }) => format!("{details}: {reason}"),
+        http::Error::Server(ManagementApiError::Other { details, .. }) => details,
+        http::Error::Network(details) => format!("Network error: {details}"),
+        err => Alert::from(err).message,
+    }
+}
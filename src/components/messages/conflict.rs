@@ -0,0 +1,113 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use leptos::*;
+
+use crate::{
+    components::messages::modal::{Modal, ModalStack},
+    pages::config::SettingChange,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    Overwrite,
+    Reload,
+    Cancel,
+}
+
+// In each change, old_value is the loaded value and new_value the current one
+pub async fn resolve_conflict(
+    modals: ModalStack,
+    subject: impl Into<String>,
+    changes: Vec<SettingChange>,
+) -> ConflictResolution {
+    let changes = Arc::new(changes);
+    let reload = Rc::new(Cell::new(false));
+    let reload_ = reload.clone();
+
+    let overwrite = modals
+        .confirm(
+            Modal::with_title("Changed by someone else")
+                .with_message(format!(
+                    "{} changed on the server after you opened this form. Overwrite those changes with yours, or reload to start over from the latest version.",
+                    subject.into()
+                ))
+                .with_button("Overwrite")
+                .dangerous()
+                .with_content(move || {
+                    let reload = reload_.clone();
+                    view! {
+                        <ConflictingChanges changes=changes.clone()/>
+                        <button
+                            type="button"
+                            class="mt-3 text-sm text-blue-600 decoration-2 hover:underline font-medium dark:text-blue-500"
+                            on:click=move |_| {
+                                reload.set(true);
+                                modals.close_top();
+                            }
+                        >
+
+                            "Reload the latest version"
+                        </button>
+                    }
+                }),
+        )
+        .await;
+
+    if overwrite {
+        ConflictResolution::Overwrite
+    } else if reload.get() {
+        ConflictResolution::Reload
+    } else {
+        ConflictResolution::Cancel
+    }
+}
+
+#[component]
+fn ConflictingChanges(changes: Arc<Vec<SettingChange>>) -> impl IntoView {
+    view! {
+        <div class="mt-4 overflow-x-auto border border-gray-200 rounded-lg dark:border-gray-700">
+            <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
+                <thead class="bg-gray-50 dark:bg-slate-800">
+                    <tr>
+                        <th class="px-3 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                            Field
+                        </th>
+                        <th class="px-3 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                            When you opened it
+                        </th>
+                        <th class="px-3 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                            On the server now
+                        </th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-200 dark:divide-gray-700">
+                    {changes
+                        .iter()
+                        .cloned()
+                        .map(|change| {
+                            view! {
+                                <tr>
+                                    <td class="px-3 py-2 align-top font-mono text-xs text-gray-800 dark:text-gray-200">
+                                        {change.key}
+                                    </td>
+                                    <td class="px-3 py-2 align-top font-mono text-xs break-all text-gray-500">
+                                        {change.old_value.unwrap_or_else(|| "(not set)".to_string())}
+                                    </td>
+                                    <td class="px-3 py-2 align-top font-mono text-xs break-all bg-yellow-50 text-yellow-800 dark:bg-yellow-800/10 dark:text-yellow-500">
+                                        {change.new_value.unwrap_or_else(|| "(removed)".to_string())}
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{panic::PanicHookInfo, time::Duration};
+
+use leptos::*;
+use leptos_router::use_location;
+
+use crate::{
+    components::{clipboard::write_clipboard, icon::IconExclamationTriangle},
+    core::i18n::{t, t_for},
+    VERSION_NAME,
+};
+
+const COPIED_TIMEOUT: Duration = Duration::from_secs(2);
+const PANIC_ID: &str = "webadmin-panic";
+
+#[component]
+pub fn PageErrorBoundary(children: Children) -> impl IntoView {
+    view! {
+        <ErrorBoundary fallback=|errors| {
+            view! { <ErrorFallback errors/> }
+        }>{children()}</ErrorBoundary>
+    }
+}
+
+#[component]
+fn ErrorFallback(errors: RwSignal<Errors>) -> impl IntoView {
+    let pathname = use_location().pathname;
+    let copied = create_rw_signal(false);
+
+    // Leaving the page clears the errors so that the next page is rendered
+    create_effect(move |prev: Option<String>| {
+        let pathname = pathname.get();
+        if prev.is_some_and(|prev| prev != pathname) {
+            errors.set(Errors::default());
+        }
+        pathname
+    });
+
+    let details = move || {
+        errors.with(|errors| {
+            errors
+                .iter()
+                .map(|(_, err)| err.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    };
+    let report = move || {
+        format!(
+            "{VERSION_NAME}\nPage: {}\n\n{}",
+            pathname.get_untracked(),
+            details()
+        )
+    };
+
+    view! {
+        <div
+            class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto"
+            role="alert"
+        >
+            <div class="bg-white rounded-xl shadow p-4 sm:p-7 dark:bg-slate-900">
+                <div class="flex gap-x-4">
+                    <IconExclamationTriangle attr:class="flex-shrink-0 size-6 text-red-600 dark:text-red-500"/>
+                    <div class="grow">
+                        <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
+                            {move || t("error.title")}
+                        </h2>
+                        <p class="mt-1 text-sm text-gray-600 dark:text-gray-400">
+                            {move || t("error.description")}
+                        </p>
+                        <details class="mt-4 text-sm">
+                            <summary class="cursor-pointer font-medium text-gray-800 dark:text-gray-200">
+                                {move || t("error.details")}
+                            </summary>
+                            <pre class="mt-2 p-3 overflow-auto whitespace-pre-wrap break-all rounded-lg bg-gray-100 text-xs text-gray-800 dark:bg-gray-800 dark:text-gray-300">
+                                {details}
+                            </pre>
+                        </details>
+                        <div class="mt-5 flex gap-x-2">
+                            <button
+                                type="button"
+                                class="py-2 px-3 inline-flex items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent bg-blue-600 text-white hover:bg-blue-700 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:focus-visible:ring-gray-600"
+                                on:click=move |_| {
+                                    let _ = window().location().reload();
+                                }
+                            >
+                                {move || t("error.reload")}
+                            </button>
+                            <button
+                                type="button"
+                                class="py-2 px-3 inline-flex items-center gap-x-2 text-sm font-medium rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800 dark:focus-visible:ring-gray-600"
+                                on:click=move |_| {
+                                    let report = report();
+                                    spawn_local(async move {
+                                        if write_clipboard(&report).await {
+                                            copied.set(true);
+                                            set_timeout(move || copied.set(false), COPIED_TIMEOUT);
+                                        }
+                                    });
+                                }
+                            >
+
+                                {move || {
+                                    if copied.get() { t("error.copied") } else { t("error.copy") }
+                                }}
+
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// The app is unusable after a panic, so the notice is built without the reactive runtime
+pub fn init_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        render_panic(info);
+    }));
+}
+
+fn render_panic(info: &PanicHookInfo) {
+    let document = document();
+    let (Some(body), Ok(notice)) = (document.body(), document.create_element("div")) else {
+        return;
+    };
+    if document.get_element_by_id(PANIC_ID).is_some() {
+        return;
+    }
+    notice.set_id(PANIC_ID);
+    let _ = notice.set_attribute("role", "alert");
+    let _ = notice.set_attribute(
+        "class",
+        "fixed inset-0 z-[100] flex items-center justify-center p-4 bg-gray-900/50",
+    );
+    // Handlers are inline scripts, calling back into the module is unsafe after a panic
+    notice.set_inner_html(concat!(
+        "<div class=\"max-w-lg w-full bg-white rounded-xl shadow p-6 dark:bg-slate-900\">",
+        "<h2 class=\"text-lg font-semibold text-gray-800 dark:text-gray-200\"></h2>",
+        "<p class=\"mt-1 text-sm text-gray-600 dark:text-gray-400\"></p>",
+        "<details class=\"mt-4 text-sm\"><summary class=\"cursor-pointer font-medium ",
+        "text-gray-800 dark:text-gray-200\"></summary><pre class=\"mt-2 p-3 overflow-auto ",
+        "whitespace-pre-wrap break-all rounded-lg bg-gray-100 text-xs text-gray-800 ",
+        "dark:bg-gray-800 dark:text-gray-300\"></pre></details>",
+        "<div class=\"mt-5 flex gap-x-2\">",
+        "<button type=\"button\" class=\"py-2 px-3 text-sm font-semibold rounded-lg ",
+        "bg-blue-600 text-white hover:bg-blue-700\" onclick=\"location.reload()\"></button>",
+        "<button type=\"button\" class=\"py-2 px-3 text-sm font-medium rounded-lg border ",
+        "border-gray-200 bg-white text-gray-800 hover:bg-gray-50\" onclick=\"",
+        "navigator.clipboard&&navigator.clipboard.writeText(",
+        "this.closest('[role=alert]').querySelector('pre').textContent)\"></button>",
+        "</div></div>"
+    ));
+
+    // The reactive runtime may be borrowed while panicking, so the language is
+    // read from the document rather than from the context
+    let language = document
+        .document_element()
+        .and_then(|root| root.get_attribute("lang"))
+        .unwrap_or_default();
+    let t = |key| t_for(&language, key);
+    let details = format!(
+        "{VERSION_NAME}\nPage: {}\n\n{info}",
+        window().location().pathname().unwrap_or_default()
+    );
+    let texts = [
+        ("h2", t("error.title")),
+        ("p", t("error.description")),
+        ("summary", t("error.details")),
+        ("pre", details),
+        ("button[onclick^=location]", t("error.reload")),
+        ("button[onclick^=navigator]", t("error.copy")),
+    ];
+    for (selector, text) in texts {
+        if let Ok(Some(element)) = notice.query_selector(selector) {
+            element.set_text_content(Some(&text));
+        }
+    }
+    let _ = body.append_child(&notice);
+}
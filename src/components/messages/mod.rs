@@ -5,4 +5,6 @@
  */
 
 pub mod alert;
+pub mod conflict;
+pub mod error;
 pub mod modal;
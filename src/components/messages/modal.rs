@@ -6,9 +6,21 @@
 
 use leptos::{html::Div, *};
 use leptos_use::on_click_outside;
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::Promise,
+    wasm_bindgen::{JsCast, JsValue},
+    HtmlElement,
+};
 
-use crate::components::icon::IconXMark;
+use crate::{
+    components::icon::IconXMark,
+    core::{
+        i18n::t,
+        shortcuts::{register_shortcut, Shortcut, FOCUSABLE},
+    },
+};
 
 pub trait ModalCb: Fn() + 'static {}
 
@@ -19,140 +31,396 @@ pub struct Modal {
     pub message: String,
     pub button_text: String,
     pub danger: bool,
+    pub confirm_text: Option<String>,
+    pub content: Option<ViewFn>,
     pub on_confirm: Arc<dyn Fn()>,
+    pub on_cancel: Option<Arc<dyn Fn()>>,
+}
+
+#[derive(Clone, Copy)]
+pub struct ModalStack {
+    entries: RwSignal<Vec<ModalEntry>>,
+    next_id: StoredValue<u64>,
+}
+
+#[derive(Clone)]
+struct ModalEntry {
+    id: u64,
+    modal: Modal,
+    previous_focus: Option<HtmlElement>,
 }
 
 pub fn init_modals() {
-    provide_context(create_rw_signal(Modal::default()));
+    provide_context(ModalStack {
+        entries: create_rw_signal(Vec::new()),
+        next_id: store_value(0),
+    });
+}
+
+pub fn use_modals() -> ModalStack {
+    let stack = expect_context::<ModalStack>();
+    for entry in stack.entries.try_update(std::mem::take).unwrap_or_default() {
+        if let Some(on_cancel) = entry.modal.on_cancel {
+            on_cancel();
+        }
+    }
+    stack
+}
+
+pub fn confirm(
+    title: impl Into<String>,
+    body: impl Into<String>,
+    confirm_label: impl Into<String>,
+) -> impl Future<Output = bool> {
+    expect_context::<ModalStack>().confirm(
+        Modal::with_title(title)
+            .with_message(body)
+            .with_button(confirm_label),
+    )
+}
+
+pub fn confirm_dangerous(
+    title: impl Into<String>,
+    body: impl Into<String>,
+    confirm_label: impl Into<String>,
+) -> impl Future<Output = bool> {
+    expect_context::<ModalStack>().confirm(
+        Modal::with_title(title)
+            .with_message(body)
+            .with_button(confirm_label)
+            .dangerous(),
+    )
 }
 
-pub fn use_modals() -> RwSignal<Modal> {
-    let signal = expect_context::<RwSignal<Modal>>();
-    signal.set(Modal::default());
-    signal
+impl ModalStack {
+    pub fn set(&self, modal: Modal) {
+        if !modal.is_open {
+            return;
+        }
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        let previous_focus = document()
+            .active_element()
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+
+        self.entries.update(|entries| {
+            entries.push(ModalEntry {
+                id,
+                modal,
+                previous_focus,
+            })
+        });
+    }
+
+    // Any callbacks already set on the modal are replaced
+    pub fn confirm(&self, modal: Modal) -> impl Future<Output = bool> {
+        let mut resolve = None;
+        let promise = Promise::new(&mut |resolve_fn, _| resolve = Some(resolve_fn));
+        let resolve = resolve.expect("Promise executor runs synchronously");
+        let resolve_cancel = resolve.clone();
+
+        let on_confirm = move || {
+            let _ = resolve.call1(&JsValue::NULL, &JsValue::TRUE);
+        };
+        let modal = if modal.danger {
+            modal.with_dangerous_callback(on_confirm)
+        } else {
+            modal.with_callback(on_confirm)
+        };
+        self.set(modal.with_cancel_callback(move || {
+            let _ = resolve_cancel.call1(&JsValue::NULL, &JsValue::FALSE);
+        }));
+
+        async move {
+            JsFuture::from(promise)
+                .await
+                .ok()
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false)
+        }
+    }
+
+    pub fn close(&self, id: u64) {
+        if let Some(on_cancel) = self.remove(id).and_then(|modal| modal.on_cancel) {
+            on_cancel();
+        }
+    }
+
+    pub fn close_top(&self) {
+        if let Some(id) = self.top() {
+            self.close(id);
+        }
+    }
+
+    fn remove(&self, id: u64) -> Option<Modal> {
+        let mut removed = None;
+        self.entries.update(|entries| {
+            if let Some(pos) = entries.iter().position(|entry| entry.id == id) {
+                removed = Some((entries.remove(pos), pos == entries.len()));
+            }
+        });
+        let (entry, was_top) = removed?;
+        if was_top {
+            if let Some(element) = &entry.previous_focus {
+                let _ = element.focus();
+            }
+        }
+        Some(entry.modal)
+    }
+
+    fn top(&self) -> Option<u64> {
+        self.entries
+            .with_untracked(|entries| entries.last().map(|entry| entry.id))
+    }
 }
 
 #[component]
 pub fn Modal() -> impl IntoView {
-    let modal = expect_context::<RwSignal<Modal>>();
-    let (processing, set_processing) = create_signal(false);
+    let stack = expect_context::<ModalStack>();
 
-    // Dismiss modal when "Escape" (or 'q') key is pressed
-    let dismiss_modal_with_keyboard = window_event_listener(ev::keydown, move |ev| {
-        if ev.key() == "Escape" || ev.key() == "q" || ev.key() == "Q" {
-            modal.update(|modal| {
-                modal.is_open = false;
-            });
+    // Escape (or 'q') dismisses the topmost modal, Tab cycles within it
+    let handle_keyboard = window_event_listener(ev::keydown, move |ev| {
+        let Some(id) = stack.top() else {
+            return;
+        };
+        let is_typing = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+            .is_some();
+        match ev.key().as_str() {
+            "Escape" => stack.close(id),
+            "q" | "Q" if !is_typing => stack.close(id),
+            "Tab" => {
+                let Some(dialog) = document().get_element_by_id(&dialog_id(id)) else {
+                    return;
+                };
+                let Ok(focusable) = dialog.query_selector_all(FOCUSABLE) else {
+                    return;
+                };
+                let (Some(first), Some(last)) = (
+                    focusable.item(0),
+                    focusable.item(focusable.length().saturating_sub(1)),
+                ) else {
+                    return;
+                };
+                let active = document().active_element().map(web_sys::Node::from);
+                let inside = active
+                    .as_ref()
+                    .is_some_and(|active| dialog.contains(Some(active)));
+                let target = if ev.shift_key() {
+                    (!inside || active.as_ref() == Some(&first)).then_some(last)
+                } else {
+                    (!inside || active.as_ref() == Some(&last)).then_some(first)
+                };
+                if let Some(element) = target.and_then(|node| node.dyn_into::<HtmlElement>().ok()) {
+                    ev.prevent_default();
+                    let _ = element.focus();
+                }
+            }
+            _ => {}
         }
     });
-    on_cleanup(move || dismiss_modal_with_keyboard.remove());
+    on_cleanup(move || handle_keyboard.remove());
+    register_shortcut(Shortcut::new("Escape", "Close the open dialog"));
+
+    view! {
+        <Show when=move || stack.entries.with(|entries| !entries.is_empty()) fallback=|| ()>
+            <Portal mount=document().get_element_by_id("portal_root").unwrap()>
+                <For
+                    each=move || stack.entries.get().into_iter().enumerate()
+                    key=|(_, entry)| entry.id
+                    children=move |(depth, entry)| {
+                        view! { <ModalDialog id=entry.id modal=entry.modal depth stack/> }
+                    }
+                />
+
+            </Portal>
+        </Show>
+    }
+}
 
-    // Click outside modal to dismiss
+#[component]
+fn ModalDialog(id: u64, modal: Modal, depth: usize, stack: ModalStack) -> impl IntoView {
+    let (processing, set_processing) = create_signal(false);
+    let typed_text = create_rw_signal(String::new());
+    let confirm_text = modal.confirm_text.clone();
+    let confirmed = create_memo(move |_| match &confirm_text {
+        Some(text) => typed_text.with(|typed| typed.trim() == text),
+        None => true,
+    });
+    let is_top = move || {
+        stack
+            .entries
+            .with(|entries| entries.last().map(|entry| entry.id))
+            == Some(id)
+    };
+
+    // Click outside the topmost modal to dismiss it
     let modal_target: NodeRef<Div> = create_node_ref::<Div>();
     on_cleanup(on_click_outside(modal_target, move |_| {
-        modal.update(|modal| {
-            modal.is_open = false;
-        });
+        if stack.top() == Some(id) {
+            stack.close(id);
+        }
     }));
 
+    // Move focus into the dialog once it is rendered
+    modal_target.on_load(move |target| {
+        request_animation_frame(move || {
+            if let Some(element) = target
+                .query_selector(FOCUSABLE)
+                .ok()
+                .flatten()
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+            {
+                let _ = element.focus();
+            }
+        });
+    });
+
+    let on_confirm = modal.on_confirm.clone();
+    let z_index = format!("z-index: {}", 50 + depth * 10);
+
     view! {
-        <Show when=move || modal.get().is_open fallback=|| ()>
-            <Portal mount=document().get_element_by_id("portal_root").unwrap()>
-                <div class="transition duration fixed inset-0 z-50 bg-gray-900 bg-opacity-50 dark:bg-opacity-80 hs-overlay-backdrop">
-
-                    <div
-                        id="hs-vertically-centered-modal"
-                        class="open hs-overlay size-full fixed top-0 start-0 z-[80] overflow-x-hidden overflow-y-auto"
-                    >
-
-                        <div
-                            class="hs-overlay-open:mt-7 hs-overlay-open:opacity-100 hs-overlay-open:duration-500 mt-0 opacity-0 ease-out transition-all sm:max-w-lg sm:w-full m-3 sm:mx-auto min-h-[calc(100%-3.5rem)] flex items-center"
-                            node_ref=modal_target
-                        >
-                            <div class="w-full flex flex-col bg-white border shadow-sm rounded-xl dark:bg-gray-800 dark:border-gray-700 dark:shadow-slate-700/[.7]">
-                                <div class="flex justify-between items-center py-3 px-4 border-b dark:border-gray-700">
-                                    <h3 class="font-bold text-gray-800 dark:text-white">
-                                        {move || { modal.get().title }}
-                                    </h3>
-                                    <button
-                                        type="button"
-                                        class="flex justify-center items-center size-7 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                                        on:click=move |_| {
-                                            modal
-                                                .update(|modal| {
-                                                    modal.is_open = false;
-                                                });
-                                        }
-                                    >
-
-                                        <span class="sr-only">Close</span>
-                                        <IconXMark/>
-
-                                    </button>
-                                </div>
-                                <div class="p-4 overflow-y-auto">
-                                    <p class="text-gray-800 dark:text-gray-400">
-                                        {move || { modal.get().message }}
-                                    </p>
-                                </div>
-                                <div class="flex justify-end items-center gap-x-2 py-3 px-4 border-t dark:border-gray-700">
-                                    <button
-                                        type="button"
-                                        class="py-2 px-3 inline-flex items-center gap-x-2 text-sm font-medium rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                                        on:click=move |_| {
-                                            modal
-                                                .update(|modal| {
-                                                    modal.is_open = false;
-                                                });
-                                        }
-                                    >
-
-                                        Close
-                                    </button>
-                                    <button
-                                        type="button"
-                                        class=move || {
-                                            if modal.get().danger {
-                                                "py-3 px-4 inline-flex items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent bg-red-500 text-white hover:bg-red-600 disabled:opacity-50 disabled:pointer-events-none dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
-                                            } else {
-                                                "py-3 px-4 inline-flex items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50 disabled:pointer-events-none dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+        <div
+            class=move || {
+                if depth == 0 {
+                    "motion-safe:transition duration fixed inset-0 bg-gray-900 bg-opacity-50 dark:bg-opacity-80 hs-overlay-backdrop"
+                } else {
+                    "motion-safe:transition duration fixed inset-0 bg-gray-900 bg-opacity-30 dark:bg-opacity-50 hs-overlay-backdrop"
+                }
+            }
+
+            style=z_index
+            aria-hidden=move || (!is_top()).then_some("true")
+        >
+
+            <div
+                id=dialog_id(id)
+                class="open hs-overlay size-full fixed top-0 start-0 overflow-x-hidden overflow-y-auto"
+                role="dialog"
+                aria-modal="true"
+                aria-labelledby=format!("{}-title", dialog_id(id))
+                aria-describedby=(!modal.message.is_empty())
+                    .then(|| format!("{}-message", dialog_id(id)))
+            >
+
+                <div
+                    class="hs-overlay-open:mt-7 hs-overlay-open:opacity-100 hs-overlay-open:duration-500 mt-0 opacity-0 ease-out motion-safe:transition-all sm:max-w-lg sm:w-full m-3 sm:mx-auto min-h-[calc(100%-3.5rem)] flex items-center"
+                    node_ref=modal_target
+                >
+                    <div class="w-full flex flex-col bg-white border shadow-sm rounded-xl dark:bg-gray-800 dark:border-gray-700 dark:shadow-slate-700/[.7]">
+                        <div class="flex justify-between items-center py-3 px-4 border-b dark:border-gray-700">
+                            <h3
+                                id=format!("{}-title", dialog_id(id))
+                                class="font-bold text-gray-800 dark:text-white"
+                            >
+                                {modal.title}
+                            </h3>
+                            <button
+                                type="button"
+                                class="flex justify-center items-center size-7 text-sm font-semibold rounded-full border border-transparent text-gray-800 hover:bg-gray-100 disabled:opacity-50 disabled:pointer-events-none dark:text-white dark:hover:bg-gray-700 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 focus-visible:ring-offset-2 dark:focus-visible:ring-gray-600 dark:focus-visible:ring-offset-gray-800"
+                                on:click=move |_| stack.close(id)
+                            >
+
+                                <span class="sr-only">{t("button.close")}</span>
+                                <IconXMark attr:aria-hidden="true"/>
+
+                            </button>
+                        </div>
+                        <div class="p-4 overflow-y-auto">
+                            {(!modal.message.is_empty())
+                                .then(|| {
+                                    view! {
+                                        <p
+                                            id=format!("{}-message", dialog_id(id))
+                                            class="text-gray-800 dark:text-gray-400"
+                                        >
+                                            {modal.message}
+                                        </p>
+                                    }
+                                })}
+                            {modal.content.map(|content| content.run())}
+                            {modal
+                                .confirm_text
+                                .map(|text| {
+                                    view! {
+                                        <label
+                                            for=format!("{}-confirm", dialog_id(id))
+                                            class="block mt-4 mb-2 text-sm text-gray-800 dark:text-gray-400"
+                                        >
+                                            "Type "
+                                            <span class="font-semibold">{text.clone()}</span>
+                                            " to confirm."
+                                        </label>
+                                        <input
+                                            id=format!("{}-confirm", dialog_id(id))
+                                            type="text"
+                                            class="py-2 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-red-500 focus:ring-red-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                                            autocomplete="off"
+                                            placeholder=text
+                                            prop:value=typed_text
+                                            on:input=move |ev| {
+                                                typed_text.set(event_target_value(&ev));
                                             }
-                                        }
-
-                                        on:click=move |_| {
-                                            modal
-                                                .update(|modal| {
-                                                    set_processing.set(true);
-                                                    (modal.on_confirm)();
-                                                    set_processing.set(false);
-                                                    modal.is_open = false;
-                                                });
-                                        }
-
-                                        disabled=move || processing.get()
-                                    >
-
-                                        {move || { modal.get().button_text }}
-                                    </button>
-                                </div>
-                            </div>
+                                        />
+                                    }
+                                })}
+
+                        </div>
+                        <div class="flex justify-end items-center gap-x-2 py-3 px-4 border-t dark:border-gray-700">
+                            <button
+                                type="button"
+                                class="py-2 px-3 inline-flex items-center gap-x-2 text-sm font-medium rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 focus-visible:ring-offset-2 dark:focus-visible:ring-gray-600 dark:focus-visible:ring-offset-gray-800"
+                                on:click=move |_| stack.close(id)
+                            >
+
+                                {t("button.close")}
+                            </button>
+                            <button
+                                type="button"
+                                class:hidden=modal.button_text.is_empty()
+                                class=if modal.danger {
+                                    "py-3 px-4 inline-flex items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent bg-red-500 text-white hover:bg-red-600 disabled:opacity-50 disabled:pointer-events-none focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 focus-visible:ring-offset-2 dark:focus-visible:ring-gray-600 dark:focus-visible:ring-offset-gray-800"
+                                } else {
+                                    "py-3 px-4 inline-flex items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50 disabled:pointer-events-none focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 focus-visible:ring-offset-2 dark:focus-visible:ring-gray-600 dark:focus-visible:ring-offset-gray-800"
+                                }
+
+                                on:click=move |_| {
+                                    set_processing.set(true);
+                                    on_confirm();
+                                    set_processing.set(false);
+                                    stack.remove(id);
+                                }
+
+                                disabled=move || processing.get() || !confirmed.get()
+                            >
+
+                                {modal.button_text}
+                            </button>
                         </div>
                     </div>
                 </div>
-            </Portal>
-        </Show>
+            </div>
+        </div>
     }
 }
 
+fn dialog_id(id: u64) -> String {
+    format!("modal-{id}")
+}
+
 impl Modal {
     pub fn with_title(title: impl Into<String>) -> Self {
         Self {
             is_open: true,
             title: title.into(),
             message: String::new(),
-            button_text: "Confirm".to_string(),
+            button_text: t("button.confirm"),
             danger: false,
+            confirm_text: None,
+            content: None,
             on_confirm: Arc::new(|| {}),
+            on_cancel: None,
         }
     }
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
@@ -165,6 +433,31 @@ impl Modal {
         self
     }
 
+    pub fn with_confirmation_text(mut self, text: impl Into<String>) -> Self {
+        self.confirm_text = Some(text.into());
+        self
+    }
+
+    pub fn with_content(mut self, content: impl Into<ViewFn>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    pub fn without_button(mut self) -> Self {
+        self.button_text.clear();
+        self
+    }
+
+    pub fn with_cancel_callback(mut self, on_cancel: impl Fn() + 'static) -> Self {
+        self.on_cancel = Some(Arc::new(on_cancel));
+        self
+    }
+
+    pub fn dangerous(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+
     pub fn with_dangerous_callback(mut self, on_confirm: impl Fn() + 'static) -> Self {
         self.danger = true;
         self.on_confirm = Arc::new(on_confirm);
@@ -184,9 +477,12 @@ impl Default for Modal {
             is_open: false,
             title: String::new(),
             message: String::new(),
-            button_text: "Confirm".to_string(),
+            button_text: t("button.confirm"),
             danger: false,
+            confirm_text: None,
+            content: None,
             on_confirm: Arc::new(|| {}),
+            on_cancel: None,
         }
     }
 }
@@ -6,6 +6,8 @@
 
 pub mod badge;
 pub mod card;
+pub mod chart;
+pub mod clipboard;
 pub mod form;
 pub mod icon;
 pub mod layout;
@@ -13,6 +15,7 @@ pub mod list;
 pub mod messages;
 pub mod report;
 pub mod skeleton;
+pub mod timestamp;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Color {
@@ -9,7 +9,7 @@ use leptos::*;
 #[component]
 pub fn Skeleton() -> impl IntoView {
     view! {
-        <div class="flex animate-pulse">
+        <div class="flex motion-safe:animate-pulse">
             <div class="ms-4 mt-2 w-full">
                 <h3 class="h-4 bg-gray-200 rounded-full dark:bg-gray-700" style="width: 40%;"></h3>
 
@@ -23,3 +23,26 @@ pub fn Skeleton() -> impl IntoView {
         </div>
     }
 }
+
+#[component]
+pub fn ListSkeleton(#[prop(default = 5)] rows: usize) -> impl IntoView {
+    view! {
+        <ul class="motion-safe:animate-pulse divide-y divide-gray-200 dark:divide-gray-700">
+            {(0..rows)
+                .map(|_| {
+                    view! {
+                        <li class="flex items-center gap-x-4 px-6 py-3">
+                            <span class="size-4 flex-shrink-0 rounded bg-gray-200 dark:bg-gray-700"></span>
+                            <span class="size-[38px] flex-shrink-0 rounded-full bg-gray-200 dark:bg-gray-700"></span>
+                            <div class="grow space-y-2">
+                                <div class="h-3 w-1/3 rounded-full bg-gray-200 dark:bg-gray-700"></div>
+                                <div class="h-3 w-1/2 rounded-full bg-gray-200 dark:bg-gray-700"></div>
+                            </div>
+                            <span class="hidden sm:block h-3 w-24 rounded-full bg-gray-200 dark:bg-gray-700"></span>
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{DateTime, Utc};
+use leptos::*;
+
+use crate::core::datetime::{
+    format_datetime, format_relative, format_relative_short, use_datetime, DateTimeStyle,
+};
+
+#[component]
+pub fn Timestamp(value: DateTime<Utc>) -> impl IntoView {
+    let prefs = use_datetime();
+    let absolute = move || format_datetime(&value, DateTimeStyle::DateTime);
+    let relative = move || format_relative(&value, &prefs.now_for(&value));
+
+    view! {
+        <time
+            datetime=value.to_rfc3339()
+            title=move || if prefs.relative.get() { absolute() } else { relative() }
+        >
+            {move || if prefs.relative.get() { relative() } else { absolute() }}
+        </time>
+    }
+}
+
+#[component]
+pub fn RelativeTime(value: DateTime<Utc>) -> impl IntoView {
+    let prefs = use_datetime();
+
+    view! {
+        <time
+            datetime=value.to_rfc3339()
+            title=move || format_datetime(&value, DateTimeStyle::DateTime)
+        >
+            {move || format_relative_short(&value, &prefs.now_for(&value))}
+        </time>
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{cell::RefCell, future::Future};
+
+use ahash::AHashMap;
+use gloo_net::http::Method;
+use leptos::{on_cleanup, store_value, RwSignal, SignalGetUntracked, StoredValue};
+use serde::{de::DeserializeOwned, Serialize};
+use web_sys::{AbortController, AbortSignal};
+
+use super::{
+    connectivity::{use_connectivity, Connectivity},
+    http::{decode, Error, HttpRequest, IntoUrlBuilder, Result},
+    oauth::{end_session, refresh_authorization, use_authorization},
+    url::UrlBuilder,
+    AccessToken,
+};
+
+// Retries once after refreshing the token on 401, ending the session if that fails
+#[derive(Clone, Copy)]
+pub struct ApiClient {
+    auth: RwSignal<AccessToken>,
+    scope: Scope,
+    connectivity: Option<Connectivity>,
+}
+
+#[derive(Clone, Copy)]
+enum Scope {
+    Unbound,
+    Component(StoredValue<AbortSignal>),
+    Latest(StoredValue<Option<AbortController>>),
+}
+
+pub struct ApiRequest {
+    auth: RwSignal<AccessToken>,
+    connectivity: Option<Connectivity>,
+    abort_signal: Option<AbortSignal>,
+    method: Method,
+    url: UrlBuilder,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    conditional: bool,
+}
+
+const MAX_CACHED_RESPONSES: usize = 32;
+
+struct CachedResponse {
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+thread_local! {
+    static RESPONSE_CACHE: RefCell<Vec<CachedResponse>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn clear_response_cache() {
+    RESPONSE_CACHE.with_borrow_mut(Vec::clear);
+}
+
+// Requests are aborted when the calling component is unmounted
+pub fn use_api() -> ApiClient {
+    let Ok(abort_controller) = AbortController::new() else {
+        return ApiClient::new(use_authorization());
+    };
+    let abort_signal = store_value(abort_controller.signal());
+    on_cleanup(move || abort_controller.abort());
+    ApiClient {
+        auth: use_authorization(),
+        scope: Scope::Component(abort_signal),
+        connectivity: use_connectivity(),
+    }
+}
+
+// Sending a request also aborts the previous one
+pub fn use_latest_api() -> ApiClient {
+    let latest = store_value(None::<AbortController>);
+    on_cleanup(move || {
+        if let Some(abort_controller) = latest.get_value() {
+            abort_controller.abort();
+        }
+    });
+    ApiClient {
+        auth: use_authorization(),
+        scope: Scope::Latest(latest),
+        connectivity: use_connectivity(),
+    }
+}
+
+impl ApiClient {
+    pub fn new(auth: RwSignal<AccessToken>) -> Self {
+        Self {
+            auth,
+            scope: Scope::Unbound,
+            connectivity: None,
+        }
+    }
+
+    pub fn abort_latest(&self) {
+        if let Scope::Latest(latest) = self.scope {
+            if let Some(Some(abort_controller)) = latest.try_update_value(Option::take) {
+                abort_controller.abort();
+            }
+        }
+    }
+
+    pub fn request(&self, method: Method, url: impl IntoUrlBuilder) -> ApiRequest {
+        ApiRequest {
+            auth: self.auth,
+            connectivity: self.connectivity,
+            abort_signal: self.scope.abort_signal(),
+            method,
+            url: url.into_url_builder(),
+            headers: Vec::new(),
+            body: None,
+            conditional: false,
+        }
+    }
+
+    pub fn get(&self, url: impl IntoUrlBuilder) -> ApiRequest {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl IntoUrlBuilder) -> ApiRequest {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl IntoUrlBuilder) -> ApiRequest {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn delete(&self, url: impl IntoUrlBuilder) -> ApiRequest {
+        self.request(Method::DELETE, url)
+    }
+
+    pub fn patch(&self, url: impl IntoUrlBuilder) -> ApiRequest {
+        self.request(Method::PATCH, url)
+    }
+}
+
+impl ApiRequest {
+    pub fn with_parameter(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.url = self.url.with_parameter(key, value);
+        self
+    }
+
+    pub fn with_parameters(mut self, params: AHashMap<String, String>) -> Self {
+        self.url = self.url.with_parameters(params);
+        self
+    }
+
+    pub fn with_optional_parameter(
+        mut self,
+        key: &'static str,
+        value: Option<impl Into<String>>,
+    ) -> Self {
+        self.url = self.url.with_optional_parameter(key, value);
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_body<B: Serialize>(self, body: B) -> Result<Self> {
+        let body = serde_json::to_string(&body)?;
+        Ok(self
+            .with_raw_body(body)
+            .with_header("Content-Type", "application/json"))
+    }
+
+    pub fn with_raw_body(mut self, body: String) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    // Servers that send no ETag or Last-Modified header are queried as usual
+    pub fn conditional(mut self) -> Self {
+        self.conditional = true;
+        self
+    }
+
+    pub async fn send<T>(self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        if self.conditional && self.method == Method::GET {
+            self.send_conditional().await
+        } else {
+            self.send_with(HttpRequest::send).await
+        }
+    }
+
+    async fn send_conditional<T>(mut self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        // Responses depend on the server and the account they were sent as
+        let auth = self.auth.get_untracked();
+        let key = format!(
+            "{}\n{}\n{}",
+            auth.base_url,
+            auth.username,
+            self.url.clone().finish()
+        );
+        let validators = RESPONSE_CACHE.with_borrow(|cache| {
+            cache
+                .iter()
+                .find(|cached| cached.key == key)
+                .map(|cached| (cached.etag.clone(), cached.last_modified.clone()))
+        });
+        if let Some((etag, last_modified)) = validators {
+            if let Some(etag) = etag {
+                self.headers.push(("If-None-Match".to_string(), etag));
+            }
+            if let Some(last_modified) = last_modified {
+                self.headers
+                    .push(("If-Modified-Since".to_string(), last_modified));
+            }
+        }
+
+        let response = self.send_with(HttpRequest::send_response).await?;
+        if response.status() == 304 {
+            if let Some(body) = RESPONSE_CACHE.with_borrow_mut(|cache| {
+                let pos = cache.iter().position(|cached| cached.key == key)?;
+                // Most recently used last
+                let cached = cache.remove(pos);
+                let body = decode(&cached.body);
+                cache.push(cached);
+                Some(body)
+            }) {
+                return body;
+            }
+            // Evicted in the meantime, ask again without validators
+            self.headers
+                .retain(|(name, _)| name != "If-None-Match" && name != "If-Modified-Since");
+            return self.send_with(HttpRequest::send).await;
+        }
+
+        let etag = response.headers().get("etag");
+        let last_modified = response.headers().get("last-modified");
+        let body = response.binary().await?;
+        let result = decode(&body);
+        RESPONSE_CACHE.with_borrow_mut(|cache| {
+            cache.retain(|cached| cached.key != key);
+            if result.is_ok() && (etag.is_some() || last_modified.is_some()) {
+                if cache.len() >= MAX_CACHED_RESPONSES {
+                    cache.remove(0);
+                }
+                cache.push(CachedResponse {
+                    key,
+                    etag,
+                    last_modified,
+                    body,
+                });
+            }
+        });
+        result
+    }
+
+    pub async fn try_send<T>(self) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.send_with(HttpRequest::try_send).await
+    }
+
+    pub async fn send_raw(self) -> Result<Vec<u8>> {
+        self.send_with(HttpRequest::send_raw).await
+    }
+
+    pub async fn send_response(self) -> Result<gloo_net::http::Response> {
+        self.send_with(HttpRequest::send_response).await
+    }
+
+    async fn send_with<T, F>(&self, send: impl Fn(HttpRequest) -> F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let auth = self.auth.get_untracked();
+        let result = match send(self.build(&auth)).await {
+            Err(Error::Unauthorized) => {
+                // Another request may have refreshed the token in the meantime
+                let current = self.auth.get_untracked();
+                if current.access_token != auth.access_token
+                    || refresh_authorization(self.auth).await
+                {
+                    // Replayed only once, a second 401 is returned as is
+                    send(self.build(&self.auth.get_untracked())).await
+                } else {
+                    end_session(self.auth);
+                    Err(Error::Unauthorized)
+                }
+            }
+            result => result,
+        };
+        if let Some(connectivity) = self.connectivity {
+            connectivity.record(&result);
+        }
+        result
+    }
+
+    fn build(&self, auth: &AccessToken) -> HttpRequest {
+        let mut request =
+            HttpRequest::new(self.method.clone(), self.url.clone()).with_authorization(auth);
+        for (name, value) in &self.headers {
+            request = request.with_header(name, value);
+        }
+        if let Some(abort_signal) = &self.abort_signal {
+            request = request.with_abort_signal(abort_signal.clone());
+        }
+        if let Some(body) = &self.body {
+            request = request.with_raw_body(body.clone());
+        }
+        request
+    }
+}
+
+impl Scope {
+    fn abort_signal(&self) -> Option<AbortSignal> {
+        // Stored values are disposed together with their component, any request
+        // sent after that is aborted right away
+        match self {
+            Scope::Unbound => None,
+            Scope::Component(abort_signal) => {
+                Some(abort_signal.try_get_value().unwrap_or_else(aborted_signal))
+            }
+            Scope::Latest(latest) => {
+                let abort_controller = AbortController::new().ok()?;
+                let abort_signal = abort_controller.signal();
+                match latest.try_update_value(|latest| latest.replace(abort_controller)) {
+                    Some(previous) => {
+                        if let Some(previous) = previous {
+                            previous.abort();
+                        }
+                        Some(abort_signal)
+                    }
+                    None => Some(aborted_signal()),
+                }
+            }
+        }
+    }
+}
+
+fn aborted_signal() -> AbortSignal {
+    let abort_controller = AbortController::new().expect("AbortController is supported");
+    abort_controller.abort();
+    abort_controller.signal()
+}
@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Duration;
+
+use leptos::*;
+use leptos_use::use_event_listener;
+
+use super::{
+    http::{Error, HttpRequest, Result},
+    oauth::use_authorization,
+    AccessToken,
+};
+
+const FAILURE_THRESHOLD: u32 = 2;
+
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    Online,
+    Offline,
+    Unreachable,
+}
+
+#[derive(Clone, Copy)]
+pub struct Connectivity {
+    failures: RwSignal<u32>,
+    pub state: Memo<ConnectionState>,
+}
+
+pub fn init_connectivity() {
+    let online = create_rw_signal(window().navigator().on_line());
+    let failures = create_rw_signal(0);
+    let state = create_memo(move |_| {
+        if !online.get() {
+            ConnectionState::Offline
+        } else if failures.get() >= FAILURE_THRESHOLD {
+            ConnectionState::Unreachable
+        } else {
+            ConnectionState::Online
+        }
+    });
+    let connectivity = Connectivity { failures, state };
+
+    let auth = use_authorization();
+    let _ = use_event_listener(window(), ev::online, move |_| {
+        online.set(true);
+        connectivity.probe(auth.get_untracked());
+    });
+    let _ = use_event_listener(window(), ev::offline, move |_| online.set(false));
+
+    // Keep probing until the server answers
+    create_effect(move |_| {
+        if state.get() == ConnectionState::Unreachable {
+            match set_interval_with_handle(
+                move || connectivity.probe(auth.get_untracked()),
+                PROBE_INTERVAL,
+            ) {
+                Ok(handle) => on_cleanup(move || handle.clear()),
+                Err(err) => log::warn!("Failed to start connectivity probe: {err:?}"),
+            }
+        }
+    });
+
+    provide_context(connectivity);
+}
+
+pub fn use_connectivity() -> Option<Connectivity> {
+    use_context::<Connectivity>()
+}
+
+impl Connectivity {
+    fn record_success(&self) {
+        if self.failures.get_untracked() != 0 {
+            self.failures.set(0);
+        }
+    }
+
+    fn record_failure(&self) {
+        self.failures.update(|failures| *failures += 1);
+    }
+
+    pub fn record<T>(&self, result: &Result<T>) {
+        match result {
+            Err(Error::Network(_)) => self.record_failure(),
+            Err(Error::Aborted) => {}
+            _ => self.record_success(),
+        }
+    }
+
+    fn probe(self, auth: AccessToken) {
+        spawn_local(async move {
+            let result = HttpRequest::get("/healthz/live")
+                .with_base_url(&auth)
+                .send_response()
+                .await;
+            if let Err(Error::Network(err)) = &result {
+                log::debug!("Server is still unreachable: {err}");
+            }
+            self.record(&result);
+        });
+    }
+}
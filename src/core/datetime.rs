@@ -0,0 +1,230 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{cell::RefCell, time::Duration};
+
+use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
+use leptos::*;
+use web_sys::{
+    js_sys::{Array, Date, Function, Intl, Object, Reflect},
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+use super::i18n::current_language;
+
+const TIMEZONE_KEY: &str = "webadmin_timezone";
+const RELATIVE_KEY: &str = "webadmin_relative_dates";
+
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+// Older timestamps are not refreshed, their relative text changes too slowly
+const RECENT_SECS: i64 = 3600;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeStyle {
+    DateTime,
+    Date,
+    Time,
+}
+
+#[derive(Clone, Copy)]
+pub struct DateTimePreferences {
+    pub timezone: RwSignal<Option<String>>,
+    pub relative: RwSignal<bool>,
+    now: RwSignal<DateTime<Utc>>,
+}
+
+type FormatterCache = Option<((&'static str, Option<String>), Vec<Intl::DateTimeFormat>)>;
+
+thread_local! {
+    static FORMATTERS: RefCell<FormatterCache> = const { RefCell::new(None) };
+}
+
+pub fn init_datetime() {
+    let prefs = DateTimePreferences {
+        timezone: create_rw_signal(stored_timezone()),
+        relative: create_rw_signal(LocalStorage::get(RELATIVE_KEY).unwrap_or(false)),
+        now: create_rw_signal(Utc::now()),
+    };
+    create_effect(move |_| match prefs.timezone.get() {
+        Some(timezone) => {
+            let _ = LocalStorage::set(TIMEZONE_KEY, timezone);
+        }
+        None => LocalStorage::delete(TIMEZONE_KEY),
+    });
+    create_effect(move |_| {
+        let _ = LocalStorage::set(RELATIVE_KEY, prefs.relative.get());
+    });
+    if let Err(err) = set_interval_with_handle(move || prefs.now.set(Utc::now()), TICK_INTERVAL) {
+        log::warn!("Failed to start the relative time ticker: {err:?}");
+    }
+    provide_context(prefs);
+}
+
+pub fn use_datetime() -> DateTimePreferences {
+    expect_context::<DateTimePreferences>()
+}
+
+impl DateTimePreferences {
+    pub fn relative_text(&self, timestamp: Option<DateTime<Utc>>) -> Signal<String> {
+        let prefs = *self;
+        Signal::derive(move || {
+            timestamp
+                .map(|timestamp| format_relative(&timestamp, &prefs.now_for(&timestamp)))
+                .unwrap_or_else(|| "N/A".to_string())
+        })
+    }
+
+    // Only tracked for recent timestamps so older ones do not re-render on every tick
+    pub fn now_for(&self, timestamp: &DateTime<Utc>) -> DateTime<Utc> {
+        let now = self.now.get_untracked();
+        if (now - *timestamp).num_seconds().abs() < RECENT_SECS {
+            self.now.get()
+        } else {
+            now
+        }
+    }
+}
+
+pub fn format_datetime(timestamp: &DateTime<Utc>, style: DateTimeStyle) -> String {
+    let language = current_language();
+    let timezone = use_context::<DateTimePreferences>()
+        .map(|prefs| prefs.timezone.get())
+        .unwrap_or_else(stored_timezone);
+    let date = Date::new(&JsValue::from_f64(timestamp.timestamp_millis() as f64));
+
+    FORMATTERS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let key = (language, timezone);
+        if cache.as_ref().is_none_or(|(cached, _)| cached != &key) {
+            let formatters = [
+                DateTimeStyle::DateTime,
+                DateTimeStyle::Date,
+                DateTimeStyle::Time,
+            ]
+            .into_iter()
+            .map(|style| formatter(language, key.1.as_deref(), style))
+            .collect();
+            *cache = Some((key, formatters));
+        }
+        let (_, formatters) = cache.as_ref().unwrap();
+        formatters[style as usize]
+            .format()
+            .call1(&JsValue::UNDEFINED, &date)
+            .ok()
+            .and_then(|value| value.as_string())
+            .unwrap_or_else(|| timestamp.to_rfc3339())
+    })
+}
+
+pub fn format_relative(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    relative_format(timestamp, now, "long")
+}
+
+pub fn format_relative_short(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    relative_format(timestamp, now, "narrow")
+}
+
+fn relative_format(timestamp: &DateTime<Utc>, now: &DateTime<Utc>, style: &str) -> String {
+    let seconds = (*timestamp - *now).num_seconds();
+    let (value, unit) = match seconds.abs() {
+        0..=59 => (seconds, "second"),
+        60..=3599 => (seconds / 60, "minute"),
+        3600..=86399 => (seconds / 3600, "hour"),
+        86400..=2591999 => (seconds / 86400, "day"),
+        2592000..=31535999 => (seconds / 2592000, "month"),
+        _ => (seconds / 31536000, "year"),
+    };
+    let options = Object::new();
+    let _ = Reflect::set(&options, &"numeric".into(), &"auto".into());
+    let _ = Reflect::set(&options, &"style".into(), &style.into());
+    Intl::RelativeTimeFormat::new(&locales(current_language()), &options)
+        .format(value as f64, unit)
+        .into()
+}
+
+pub fn browser_timezone() -> String {
+    let options = Intl::DateTimeFormat::new(&Array::new(), &Object::new()).resolved_options();
+    Reflect::get(&options, &"timeZone".into())
+        .ok()
+        .and_then(|timezone| timezone.as_string())
+        .unwrap_or_else(|| "UTC".to_string())
+}
+
+// Falls back to the browser's own timezone without Intl.supportedValuesOf
+pub fn timezones() -> Vec<String> {
+    let intl = Reflect::get(&window(), &"Intl".into()).ok();
+    let timezones = intl
+        .as_ref()
+        .and_then(|intl| {
+            let supported_values = Reflect::get(intl, &"supportedValuesOf".into()).ok()?;
+            supported_values
+                .dyn_ref::<Function>()?
+                .call1(intl, &"timeZone".into())
+                .ok()
+        })
+        .and_then(|values| values.dyn_into::<Array>().ok())
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if timezones.is_empty() {
+        vec![browser_timezone()]
+    } else {
+        timezones
+    }
+}
+
+fn formatter(language: &str, timezone: Option<&str>, style: DateTimeStyle) -> Intl::DateTimeFormat {
+    let options = Object::new();
+    let mut fields = Vec::new();
+    if matches!(style, DateTimeStyle::DateTime | DateTimeStyle::Date) {
+        fields.extend([
+            ("weekday", "short"),
+            ("year", "numeric"),
+            ("month", "short"),
+            ("day", "2-digit"),
+        ]);
+    }
+    if matches!(style, DateTimeStyle::DateTime | DateTimeStyle::Time) {
+        fields.extend([
+            ("hour", "2-digit"),
+            ("minute", "2-digit"),
+            ("second", "2-digit"),
+        ]);
+    }
+    if let Some(timezone) = timezone {
+        fields.push(("timeZone", timezone));
+    }
+    for (field, value) in fields {
+        let _ = Reflect::set(&options, &field.into(), &value.into());
+    }
+    Intl::DateTimeFormat::new(&locales(language), &options)
+}
+
+// Prefer the browser locale when it matches, to keep regional conventions such as the hour cycle
+fn locales(language: &str) -> Array {
+    let locales = Array::new();
+    if let Some(locale) = window()
+        .navigator()
+        .language()
+        .filter(|locale| locale.split('-').next() == Some(language))
+    {
+        locales.push(&locale.into());
+    }
+    locales.push(&language.into());
+    locales
+}
+
+fn stored_timezone() -> Option<String> {
+    LocalStorage::get::<String>(TIMEZONE_KEY)
+        .ok()
+        .filter(|timezone| timezones().contains(timezone))
+}
@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{rc::Rc, time::Duration};
+
+use leptos::{leptos_dom::helpers::TimeoutHandle, *};
+
+pub const SEARCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub struct Debounced<T: 'static> {
+    delay: Duration,
+    timeout: StoredValue<Option<TimeoutHandle>>,
+    callback: StoredValue<Rc<dyn Fn(T)>>,
+}
+
+pub fn use_debounce<T: 'static>(delay: Duration, callback: impl Fn(T) + 'static) -> Debounced<T> {
+    let debounced = Debounced {
+        delay,
+        timeout: store_value(None),
+        callback: store_value(Rc::new(callback) as Rc<dyn Fn(T)>),
+    };
+    on_cleanup(move || debounced.cancel());
+    debounced
+}
+
+impl<T: 'static> Debounced<T> {
+    pub fn call(&self, value: T) {
+        self.cancel();
+        let callback = self.callback;
+        match set_timeout_with_handle(
+            move || {
+                if let Some(callback) = callback.try_get_value() {
+                    callback(value);
+                }
+            },
+            self.delay,
+        ) {
+            Ok(handle) => {
+                self.timeout.try_set_value(Some(handle));
+            }
+            Err(err) => log::warn!("Failed to schedule debounced call: {err:?}"),
+        }
+    }
+
+    pub fn cancel(&self) {
+        if let Some(Some(handle)) = self.timeout.try_update_value(Option::take) {
+            handle.clear();
+        }
+    }
+}
+
+impl<T: 'static> Clone for Debounced<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Debounced<T> {}
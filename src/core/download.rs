@@ -0,0 +1,63 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{borrow::Cow, time::Duration};
+
+use leptos::{document, set_timeout, wasm_bindgen::JsValue};
+use web_sys::{
+    js_sys::{Array, JsString},
+    wasm_bindgen::JsCast,
+    Blob, BlobPropertyBag, HtmlAnchorElement, Url,
+};
+
+pub fn download_file(file_name: &str, mime_type: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = Array::of1(&JsString::from(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let anchor = document()
+        .create_element("a")?
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // Some browsers cancel the download if the URL is revoked right away
+    set_timeout(
+        move || {
+            let _ = Url::revoke_object_url(&url);
+        },
+        Duration::from_secs(1),
+    );
+    Ok(())
+}
+
+pub fn csv_row<T: AsRef<str>>(fields: impl IntoIterator<Item = T>) -> String {
+    let mut row = String::new();
+    for (pos, field) in fields.into_iter().enumerate() {
+        let field = field.as_ref();
+        if pos > 0 {
+            row.push(',');
+        }
+        // Keep spreadsheets from evaluating fields as formulas
+        let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+            Cow::Owned(format!("'{field}"))
+        } else {
+            Cow::Borrowed(field)
+        };
+        if field.contains([',', '"', '\n', '\r']) {
+            row.push('"');
+            row.push_str(&field.replace('"', "\"\""));
+            row.push('"');
+        } else {
+            row.push_str(&field);
+        }
+    }
+    row.push_str("\r\n");
+    row
+}
@@ -14,12 +14,13 @@ use std::time::Duration;
 use ahash::AHashMap;
 use leptos::RwSignal;
 
+use crate::components::form::input::Size;
 use crate::pages::config::{Settings, SettingsValues};
 
 use super::expr::parser::ExpressionParser;
 use super::expr::tokenizer::Tokenizer;
 use super::expr::{Constant, ParseValue, Token};
-use super::schema::{NumberType, SchemaType, SelectType, Type};
+use super::schema::{Constraint, NumberType, SchemaType, SelectType, Type};
 
 use super::schema::{InputCheck, Schema, Transformer, Validator};
 
@@ -188,6 +189,16 @@ impl FormData {
         self.errors.remove(id);
     }
 
+    pub fn array_move(&mut self, id: &str, from: usize, to: usize) {
+        if let Some(FormValue::Array(values)) = self.values.get_mut(id) {
+            if from < values.len() && to < values.len() {
+                let value = values.remove(from);
+                values.insert(to, value);
+            }
+        }
+        self.errors.remove(id);
+    }
+
     pub fn array_push(&mut self, id: &str, value: impl Into<String>, unique: bool) {
         let v = self
             .values
@@ -481,11 +492,32 @@ impl FormData {
                     | Type::Rate
                     | Type::Cron
                     | Type::Select {
-                        typ: SelectType::Single,
+                        typ: SelectType::Single | SelectType::Radio,
                         ..
                     } => {
                         match check.check_value(self.value::<String>(field.id).unwrap_or_default())
                         {
+                            Ok(value)
+                                if matches!(field.typ_, Type::Duration)
+                                    && !value.is_empty()
+                                    && value != "false"
+                                    && Duration::parse_value(&value).is_none() =>
+                            {
+                                self.new_error(
+                                    field.id,
+                                    "This field must be a positive number followed by a unit",
+                                );
+                            }
+                            Ok(value)
+                                if matches!(field.typ_, Type::Size)
+                                    && !value.is_empty()
+                                    && value.parse::<Size>().is_err() =>
+                            {
+                                self.new_error(
+                                    field.id,
+                                    "This field must be a size in bytes, KB, MB or GB",
+                                );
+                            }
                             Ok(value) => {
                                 if !value.is_empty() {
                                     self.values.insert(field.id.into(), value.into());
@@ -504,14 +536,15 @@ impl FormData {
                         ..
                     } => {
                         let mut total_values = 0;
-                        let mut has_errors = false;
+                        let mut first_error = None;
 
-                        for (idx, result) in self
+                        for result in self
                             .array_value(field.id)
                             .map(|v| check.check_value(v.to_string()))
-                            .enumerate()
                             .collect::<Vec<_>>()
                         {
+                            // Empty entries are removed, so later ones shift back
+                            let idx = total_values;
                             match result {
                                 Ok(value) => {
                                     if !value.is_empty() {
@@ -522,19 +555,22 @@ impl FormData {
                                     }
                                 }
                                 Err(err) => {
-                                    self.errors.insert(
-                                        field.id.to_string(),
-                                        FormError {
-                                            id: FormErrorType::Array(idx),
-                                            error: err.to_string(),
-                                        },
-                                    );
-                                    has_errors = true;
+                                    first_error.get_or_insert((idx, err));
+                                    total_values += 1;
                                 }
                             }
                         }
 
-                        if !has_errors {
+                        // Updating the entries clears the errors of the field
+                        if let Some((idx, err)) = first_error {
+                            self.errors.insert(
+                                field.id.to_string(),
+                                FormError {
+                                    id: FormErrorType::Array(idx),
+                                    error: err.to_string(),
+                                },
+                            );
+                        } else {
                             for validator in &check.validators {
                                 match validator {
                                     Validator::Required => {
@@ -656,9 +692,92 @@ impl FormData {
             }
         }
 
+        if self.errors.is_empty() {
+            self.validate_constraints();
+        }
+
         self.errors.is_empty()
     }
 
+    fn validate_constraints(&mut self) {
+        let schema = self.schema.clone();
+        for constraint in &schema.constraints {
+            match *constraint {
+                Constraint::LessThan { field, than } => {
+                    if let (Some(value), Some(than_value)) =
+                        (self.comparable_value(field), self.comparable_value(than))
+                    {
+                        if value >= than_value {
+                            let label = self.field_label(than);
+                            self.new_error(field, format!("This value must be lower than {label}"));
+                        }
+                    }
+                }
+                Constraint::RequiredIf { field, other } => {
+                    if self.is_set(other) && !self.is_set(field) {
+                        let label = self.field_label(other);
+                        self.new_error(
+                            field,
+                            format!("This field is required when {label} is set"),
+                        );
+                    }
+                }
+                Constraint::MutuallyExclusive(fields) => {
+                    let set_fields = fields
+                        .iter()
+                        .filter(|field| self.is_set(field))
+                        .collect::<Vec<_>>();
+                    if let [first, rest @ ..] = set_fields.as_slice() {
+                        for field in rest {
+                            let label = self.field_label(first);
+                            self.new_error(
+                                **field,
+                                format!("This field cannot be set together with {label}"),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_set(&self, id: &str) -> bool {
+        self.schema
+            .fields
+            .get(id)
+            .is_some_and(|field| field.display(self))
+            && !self.value_is_empty(id)
+    }
+
+    fn comparable_value(&self, id: &str) -> Option<f64> {
+        let field = self.schema.fields.get(id)?;
+        if !self.is_set(id) {
+            return None;
+        }
+        let value = self.value::<String>(id)?;
+        match field.typ_ {
+            Type::Duration => Duration::parse_value(&value).map(|d| d.as_millis() as f64),
+            Type::Size => value.parse::<Size>().ok().map(|size| size.0 as f64),
+            _ => value.trim().parse::<f64>().ok(),
+        }
+    }
+
+    fn field_label(&self, id: &str) -> String {
+        self.schema
+            .fields
+            .get(id)
+            .map(|field| {
+                if !field.label_form.is_empty() {
+                    field.label_form
+                } else {
+                    field.label_column
+                }
+            })
+            .filter(|label| !label.is_empty())
+            .map(|label| format!("{label:?}"))
+            .unwrap_or_else(|| id.to_string())
+    }
+
     pub fn from_settings(schema: Arc<Schema>, settings: Option<Settings>) -> Self {
         let mut data = FormData::from(schema);
         let schema = data.schema.clone();
@@ -670,7 +789,7 @@ impl FormData {
                     | Type::Secret
                     | Type::Text
                     | Type::Select {
-                        typ: SelectType::Single,
+                        typ: SelectType::Single | SelectType::Radio,
                         ..
                     }
                     | Type::Boolean
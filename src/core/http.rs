@@ -8,6 +8,7 @@ use ahash::AHashMap;
 use base64::{engine::general_purpose::STANDARD, Engine};
 use gloo_net::http::{Headers, Method, RequestBuilder};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use web_sys::AbortSignal;
 
 use super::{url::UrlBuilder, AccessToken};
 
@@ -16,6 +17,7 @@ pub struct HttpRequest {
     url: UrlBuilder,
     headers: Headers,
     body: Option<String>,
+    abort_signal: Option<AbortSignal>,
 }
 
 #[derive(Deserialize)]
@@ -58,6 +60,7 @@ pub enum Error {
     Network(String),
     Serializer { error: String, response: String },
     Server(ManagementApiError),
+    Aborted,
 }
 
 pub trait IntoUrlBuilder {
@@ -73,6 +76,7 @@ impl<'x> HttpRequest {
             url: url.into_url_builder(),
             headers: Headers::new(),
             body: None,
+            abort_signal: None,
         }
     }
 
@@ -171,19 +175,16 @@ impl<'x> HttpRequest {
         self
     }
 
+    pub fn with_abort_signal(mut self, signal: AbortSignal) -> Self {
+        self.abort_signal = Some(signal);
+        self
+    }
+
     pub async fn send<T>(self) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        let response = self.send_raw().await?;
-        match serde_json::from_slice::<Response<T>>(response.as_slice()) {
-            Ok(Response::Data { data }) => Ok(data),
-            Ok(Response::Error(error)) => Err(Error::Server(error)),
-            Err(err) => Err(Error::Serializer {
-                error: err.to_string(),
-                response: String::from_utf8_lossy(&response).to_string(),
-            }),
-        }
+        decode(&self.send_raw().await?)
     }
 
     pub async fn try_send<T>(self) -> Result<Option<T>>
@@ -198,15 +199,25 @@ impl<'x> HttpRequest {
     }
 
     pub async fn send_raw(self) -> Result<Vec<u8>> {
-        let abort_controller = web_sys::AbortController::new().ok();
-        let abort_signal = abort_controller.as_ref().map(|a| a.signal());
+        self.send_response()
+            .await?
+            .binary()
+            .await
+            .map_err(Into::into)
+    }
 
-        // abort in-flight requests if, e.g., we've navigated away from this page
-        leptos::on_cleanup(move || {
-            if let Some(abort_controller) = abort_controller {
-                abort_controller.abort()
-            }
+    pub async fn send_response(self) -> Result<gloo_net::http::Response> {
+        let abort_signal = self.abort_signal.or_else(|| {
+            let abort_controller = web_sys::AbortController::new().ok()?;
+            let abort_signal = abort_controller.signal();
+
+            // abort in-flight requests if, e.g., we've navigated away from this page
+            leptos::on_cleanup(move || abort_controller.abort());
+            Some(abort_signal)
         });
+        if abort_signal.as_ref().is_some_and(|signal| signal.aborted()) {
+            return Err(Error::Aborted);
+        }
 
         let builder = RequestBuilder::new(&self.url.finish())
             .method(self.method)
@@ -221,19 +232,46 @@ impl<'x> HttpRequest {
         let response = req.send().await?;
 
         match response.status() {
-            200..=299 => response.binary().await.map_err(Into::into),
+            // Only returned to requests sent with validators, see `ApiRequest::conditional`
+            200..=299 | 304 => Ok(response),
             401 => Err(Error::Unauthorized),
             402 => Err(Error::TotpRequired),
             403 => Err(Error::Forbidden),
             404 => Err(Error::NotFound),
-            code => Err(Error::Server(ManagementApiError::Other {
-                details: format!("Invalid response code {code}"),
-                reason: response.status_text().into(),
-            })),
+            code => {
+                // Prefer the error envelope when the server sent one
+                let fallback = ManagementApiError::Other {
+                    details: format!("Invalid response code {code}"),
+                    reason: response.status_text().into(),
+                };
+                Err(Error::Server(
+                    response
+                        .json::<ManagementApiError>()
+                        .await
+                        .unwrap_or(fallback),
+                ))
+            }
         }
     }
 }
 
+pub fn decode<T: DeserializeOwned>(response: &[u8]) -> Result<T> {
+    match serde_json::from_slice::<Response<T>>(response) {
+        Ok(Response::Data { data }) => Ok(data),
+        Ok(Response::Error(error)) => Err(Error::Server(error)),
+        Err(err) => Err(Error::Serializer {
+            error: err.to_string(),
+            response: String::from_utf8_lossy(response).to_string(),
+        }),
+    }
+}
+
+impl IntoUrlBuilder for UrlBuilder {
+    fn into_url_builder(self) -> UrlBuilder {
+        self
+    }
+}
+
 impl IntoUrlBuilder for String {
     fn into_url_builder(self) -> UrlBuilder {
         UrlBuilder::new(self)
@@ -266,7 +304,10 @@ impl IntoUrlBuilder for (&str, &String) {
 
 impl From<gloo_net::Error> for Error {
     fn from(err: gloo_net::Error) -> Self {
-        Error::Network(format!("HTTP request failed: {err}"))
+        match err {
+            gloo_net::Error::JsError(err) if err.name == "AbortError" => Error::Aborted,
+            err => Error::Network(format!("HTTP request failed: {err}")),
+        }
     }
 }
 
@@ -278,3 +319,43 @@ impl From<serde_json::Error> for Error {
         }
     }
 }
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Unauthorized => write!(f, "Unauthorized"),
+            Error::Forbidden => write!(f, "Forbidden"),
+            Error::NotFound => write!(f, "Not found"),
+            Error::TotpRequired => write!(f, "TOTP code required"),
+            Error::Network(details) => write!(f, "Network error: {details}"),
+            Error::Serializer { error, .. } => write!(f, "Failed to deserialize response: {error}"),
+            Error::Server(error) => error.fmt(f),
+            Error::Aborted => write!(f, "Request cancelled"),
+        }
+    }
+}
+
+impl std::fmt::Display for ManagementApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManagementApiError::FieldAlreadyExists { field, value } => {
+                write!(
+                    f,
+                    "Another record exists with value {value:?} in field {field:?}"
+                )
+            }
+            ManagementApiError::FieldMissing { field } => write!(f, "Field {field} is missing"),
+            ManagementApiError::NotFound { item } => write!(f, "{item} was not found"),
+            ManagementApiError::Unsupported { details } => write!(f, "{details}"),
+            ManagementApiError::AssertFailed => {
+                write!(f, "Another record with the same ID already exists")
+            }
+            ManagementApiError::Other { details, reason } => match reason {
+                Some(reason) => write!(f, "{details}: {reason}"),
+                None => write!(f, "{details}"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,128 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::OnceLock;
+
+use ahash::AHashMap;
+use gloo_storage::{LocalStorage, Storage};
+use leptos::*;
+
+const LANGUAGE_KEY: &str = "webadmin_language";
+const DEFAULT_LANGUAGE: &str = "en";
+
+pub struct Language {
+    pub code: &'static str,
+    pub name: &'static str,
+    catalog: &'static str,
+}
+
+pub static LANGUAGES: &[Language] = &[
+    Language {
+        code: "en",
+        name: "English",
+        catalog: include_str!("en.json"),
+    },
+    Language {
+        code: "de",
+        name: "Deutsch",
+        catalog: include_str!("de.json"),
+    },
+    Language {
+        code: "es",
+        name: "Español",
+        catalog: include_str!("es.json"),
+    },
+    Language {
+        code: "fr",
+        name: "Français",
+        catalog: include_str!("fr.json"),
+    },
+];
+
+type Catalogs = AHashMap<&'static str, AHashMap<String, String>>;
+
+#[derive(Clone, Copy)]
+pub struct I18n {
+    pub language: RwSignal<&'static str>,
+}
+
+pub fn init_i18n() {
+    let i18n = I18n {
+        language: create_rw_signal(stored_language()),
+    };
+    create_effect(move |_| {
+        let language = i18n.language.get();
+        let _ = LocalStorage::set(LANGUAGE_KEY, language);
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("lang", language);
+        }
+    });
+    provide_context(i18n);
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
+
+// Falls back to English, then to the key. Handlers without an owner use the saved language
+pub fn t(key: &str) -> String {
+    t_for(current_language(), key)
+}
+
+pub fn t_for(language: &str, key: &str) -> String {
+    let catalogs = catalogs();
+    [language, DEFAULT_LANGUAGE]
+        .into_iter()
+        .find_map(|language| catalogs.get(language)?.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+pub fn current_language() -> &'static str {
+    use_context::<I18n>()
+        .map(|i18n| i18n.language.get())
+        .unwrap_or_else(stored_language)
+}
+
+pub fn t_signal(key: &'static str) -> Signal<String> {
+    Signal::derive(move || t(key))
+}
+
+fn catalogs() -> &'static Catalogs {
+    static CATALOGS: OnceLock<Catalogs> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        LANGUAGES
+            .iter()
+            .map(|language| {
+                let messages = serde_json::from_str(language.catalog).unwrap_or_else(|err| {
+                    log::error!("Failed to parse {} message catalog: {err}", language.code);
+                    AHashMap::new()
+                });
+                (language.code, messages)
+            })
+            .collect()
+    })
+}
+
+fn stored_language() -> &'static str {
+    LocalStorage::get::<String>(LANGUAGE_KEY)
+        .ok()
+        .and_then(|code| find_language(&code))
+        .or_else(browser_language)
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+fn find_language(code: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|language| language.code.eq_ignore_ascii_case(code))
+        .map(|language| language.code)
+}
+
+fn browser_language() -> Option<&'static str> {
+    let language = window().navigator().language()?;
+    find_language(language.split('-').next().unwrap_or_default())
+}
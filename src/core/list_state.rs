@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use gloo_storage::{SessionStorage, Storage};
+use leptos::*;
+use leptos_router::{use_query_map, ParamsMap};
+
+use super::url::UrlBuilder;
+
+#[derive(Clone, Copy)]
+pub struct ListState {
+    query: Memo<ParamsMap>,
+    pub page: Memo<u32>,
+}
+
+pub fn use_list_state() -> ListState {
+    let query = use_query_map();
+    let page = create_memo(move |_| {
+        query
+            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
+            .filter(|&page| page > 0)
+            .unwrap_or(1)
+    });
+    ListState { query, page }
+}
+
+impl ListState {
+    pub fn value(&self, key: &'static str) -> Memo<Option<String>> {
+        let query = self.query;
+        create_memo(move |_| {
+            query.with(|q| {
+                q.get(key)
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+            })
+        })
+    }
+
+    pub fn flag(&self, key: &'static str) -> Memo<bool> {
+        let query = self.query;
+        create_memo(move |_| query.with(|q| q.get(key).is_some_and(|value| value == "true")))
+    }
+
+    // Falls back to the last size picked in this session
+    pub fn page_size(&self, storage_key: &'static str, sizes: &'static [u32]) -> Memo<u32> {
+        let query = self.query;
+        create_memo(move |_| {
+            query
+                .with(|q| q.get("limit").and_then(|limit| limit.parse::<u32>().ok()))
+                .or_else(|| SessionStorage::get::<u32>(storage_key).ok())
+                .filter(|limit| sizes.contains(limit))
+                .unwrap_or(sizes[0])
+        })
+    }
+
+    // Changing anything but the page starts over from the first one
+    pub fn url(
+        &self,
+        path: impl Into<String>,
+        key: &'static str,
+        value: Option<String>,
+    ) -> UrlBuilder {
+        let mut url = UrlBuilder::new(path);
+        self.query.with(|q| {
+            for (param, current) in &q.0 {
+                if param != "page" && param != key && !current.is_empty() {
+                    url.params.insert(param.clone().into(), current.clone());
+                }
+            }
+        });
+        url.with_optional_parameter(key, value.filter(|value| !value.trim().is_empty()))
+    }
+}
@@ -9,11 +9,24 @@ use std::sync::Arc;
 use ahash::AHashSet;
 use serde::{Deserialize, Serialize};
 
+use self::sso::IdentityProvider;
+
+pub mod api;
+pub mod connectivity;
+pub mod datetime;
+pub mod debounce;
+pub mod download;
 pub mod expr;
 pub mod form;
 pub mod http;
+pub mod i18n;
+pub mod list_state;
 pub mod oauth;
 pub mod schema;
+pub mod shortcuts;
+pub mod sso;
+pub mod theme;
+pub mod upload;
 pub mod url;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -25,6 +38,8 @@ pub struct AccessToken {
     pub is_valid: bool,
     pub is_enterprise: bool,
     pub permissions: Permissions,
+    #[serde(default)]
+    pub identity_provider: Option<Arc<IdentityProvider>>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -142,9 +157,19 @@ impl AccessToken {
         self.is_enterprise
     }
 
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions.has_access(permission)
+    }
+
     pub fn default_url(&self) -> &'static str {
         self.permissions.default_url(self.is_enterprise)
     }
+
+    pub fn is_viewer(&self) -> bool {
+        self.is_logged_in()
+            && self.permissions.has_admin_access()
+            && !self.permissions.has_write_access()
+    }
 }
 
 impl Permissions {
@@ -164,6 +189,10 @@ impl Permissions {
         self.0.iter().any(Permission::is_admin_permission)
     }
 
+    pub fn has_write_access(&self) -> bool {
+        self.0.iter().any(Permission::is_write_permission)
+    }
+
     pub fn has_access(&self, permission: Permission) -> bool {
         self.0.contains(&permission)
     }
@@ -304,4 +333,55 @@ impl Permission {
                 | Permission::MetricsLive
         )
     }
+    pub fn is_write_permission(&self) -> bool {
+        matches!(
+            self,
+            Permission::Impersonate
+                | Permission::DeleteSystemFolders
+                | Permission::MessageQueueUpdate
+                | Permission::MessageQueueDelete
+                | Permission::OutgoingReportDelete
+                | Permission::IncomingReportDelete
+                | Permission::SettingsUpdate
+                | Permission::SettingsDelete
+                | Permission::SettingsReload
+                | Permission::IndividualUpdate
+                | Permission::IndividualDelete
+                | Permission::IndividualCreate
+                | Permission::GroupUpdate
+                | Permission::GroupDelete
+                | Permission::GroupCreate
+                | Permission::DomainCreate
+                | Permission::DomainUpdate
+                | Permission::DomainDelete
+                | Permission::TenantCreate
+                | Permission::TenantUpdate
+                | Permission::TenantDelete
+                | Permission::MailingListCreate
+                | Permission::MailingListUpdate
+                | Permission::MailingListDelete
+                | Permission::RoleCreate
+                | Permission::RoleUpdate
+                | Permission::RoleDelete
+                | Permission::PrincipalCreate
+                | Permission::PrincipalUpdate
+                | Permission::PrincipalDelete
+                | Permission::PurgeBlobStore
+                | Permission::PurgeDataStore
+                | Permission::PurgeLookupStore
+                | Permission::PurgeAccount
+                | Permission::FtsReindex
+                | Permission::Undelete
+                | Permission::DkimSignatureCreate
+                | Permission::UpdateSpamFilter
+                | Permission::UpdateWebadmin
+                | Permission::Restart
+                | Permission::ApiKeyCreate
+                | Permission::ApiKeyUpdate
+                | Permission::ApiKeyDelete
+                | Permission::OauthClientCreate
+                | Permission::OauthClientUpdate
+                | Permission::OauthClientDelete
+        )
+    }
 }
@@ -4,17 +4,25 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::time::Duration;
+use std::{cell::RefCell, time::Duration};
 
 use ahash::AHashSet;
-use leptos::{expect_context, RwSignal};
+use gloo_storage::{SessionStorage, Storage};
+use leptos::{
+    expect_context, set_timeout, window, RwSignal, Signal, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate,
+};
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::js_sys::Promise;
 
-use crate::components::messages::alert::Alert;
+use crate::{components::messages::alert::Alert, STATE_STORAGE_KEY};
 
 use super::{
+    api::clear_response_cache,
     http::{self, HttpRequest},
+    sso::sso_refresh_token,
     AccessToken, Permission,
 };
 
@@ -101,6 +109,7 @@ pub struct AuthenticationResponse {
 }
 
 const REDIRECT_URI: &str = "stalwart://auth";
+const LOGIN_REDIRECT_KEY: &str = "webadmin_login_redirect";
 
 pub async fn oauth_authenticate(
     base_url: &str,
@@ -188,6 +197,21 @@ pub async fn oauth_user_authentication(
     }
 }
 
+pub async fn oauth_session_permissions(
+    auth_token: &AccessToken,
+) -> http::Result<OAuthCodeResponse> {
+    HttpRequest::post("/api/oauth")
+        .with_authorization(auth_token)
+        .with_body(OAuthCodeRequest::Code {
+            client_id: "webadmin".to_string(),
+            redirect_uri: REDIRECT_URI.to_string().into(),
+            nonce: None,
+        })?
+        .send::<OAuthCodeResponse>()
+        .await
+        .map(OAuthCodeResponse::legacy_admin)
+}
+
 pub async fn oauth_device_authentication(
     base_url: &str,
     username: &str,
@@ -238,10 +262,106 @@ pub async fn oauth_refresh_token(base_url: &str, refresh_token: &str) -> Option<
     }
 }
 
+thread_local! {
+    static REFRESHING: RefCell<Option<Promise>> = const { RefCell::new(None) };
+}
+
+// Concurrent callers share a single refresh, as refresh tokens may only be usable once
+pub async fn refresh_authorization(auth_token: RwSignal<AccessToken>) -> bool {
+    let refresh = REFRESHING.with(|refreshing| {
+        refreshing
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                future_to_promise(async move {
+                    let refreshed = exchange_refresh_token(auth_token).await;
+                    REFRESHING.with(|refreshing| refreshing.borrow_mut().take());
+                    Ok(refreshed.into())
+                })
+            })
+            .clone()
+    });
+    JsFuture::from(refresh)
+        .await
+        .ok()
+        .and_then(|refreshed| refreshed.as_bool())
+        .unwrap_or(false)
+}
+
+pub fn end_session(auth_token: RwSignal<AccessToken>) {
+    clear_response_cache();
+    SessionStorage::delete(STATE_STORAGE_KEY);
+    auth_token.set(AccessToken::default());
+}
+
+pub fn remember_login_redirect() {
+    let location = window().location();
+    let (Ok(path), Ok(search)) = (location.pathname(), location.search()) else {
+        return;
+    };
+    if path != "/" && !path.starts_with("/login") {
+        if let Err(err) = SessionStorage::set(LOGIN_REDIRECT_KEY, format!("{path}{search}")) {
+            log::warn!("Failed to save login redirect: {err}");
+        }
+    }
+}
+
+pub fn take_login_redirect() -> Option<String> {
+    let path = SessionStorage::get::<String>(LOGIN_REDIRECT_KEY).ok()?;
+    SessionStorage::delete(LOGIN_REDIRECT_KEY);
+    // Only paths within this application
+    Some(path).filter(|path| path.starts_with('/') && !path.starts_with("//"))
+}
+
+async fn exchange_refresh_token(auth_token: RwSignal<AccessToken>) -> bool {
+    let current = auth_token.get_untracked();
+    if current.refresh_token.is_empty() {
+        return false;
+    }
+    let grant = match &current.identity_provider {
+        Some(provider) => sso_refresh_token(provider, &current.refresh_token).await,
+        None => oauth_refresh_token(&current.base_url, &current.refresh_token).await,
+    };
+    let Some(grant) = grant else {
+        return false;
+    };
+
+    let refresh_token = grant.refresh_token.unwrap_or_default();
+    auth_token.update(|auth_token| {
+        auth_token.access_token = grant.access_token.into();
+        auth_token.refresh_token = refresh_token.clone().into();
+        auth_token.is_valid = true;
+
+        if let Err(err) = SessionStorage::set(STATE_STORAGE_KEY, auth_token.clone()) {
+            log::error!(
+                "Failed to save authorization token to session storage: {}",
+                err
+            );
+        }
+    });
+    // Set timer to refresh token
+    if grant.expires_in > 0 && !refresh_token.is_empty() {
+        log::debug!("Next OAuth token refresh in {} seconds.", grant.expires_in);
+        set_timeout(
+            move || {
+                auth_token.update(|auth_token| {
+                    auth_token.is_valid = false;
+                });
+            },
+            Duration::from_secs(grant.expires_in),
+        );
+    }
+    true
+}
+
 pub fn use_authorization() -> RwSignal<AccessToken> {
     expect_context::<RwSignal<AccessToken>>()
 }
 
+pub fn use_is_viewer() -> Signal<bool> {
+    let auth = use_authorization();
+    Signal::derive(move || auth.get().is_viewer())
+}
+
 impl OAuthCodeResponse {
     pub fn legacy_admin(mut self) -> Self {
         if self.is_admin && self.permissions.is_empty() {
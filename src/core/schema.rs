@@ -43,6 +43,7 @@ pub enum Type<S, F> {
 pub enum SelectType {
     #[default]
     Single,
+    Radio,
     Many,
     ManyWithSearch,
 }
@@ -53,11 +54,14 @@ pub struct Field {
     pub label_form: &'static str,
     pub label_column: &'static str,
     pub help: Option<&'static str>,
+    pub description: Option<&'static str>,
     pub checks: Value<InputCheck>,
     pub typ_: Type<Arc<Schema>, Arc<Field>>,
     pub default: Value<FormValue>,
     pub placeholder: Value<&'static str>,
     pub display: Vec<Eval>,
+    pub enable: Vec<Eval>,
+    pub option_help: &'static [(&'static str, &'static str)],
     pub readonly: bool,
     pub enterprise: bool,
 }
@@ -70,10 +74,24 @@ pub struct Schema {
     pub fields: AHashMap<&'static str, Arc<Field>>,
     pub typ: SchemaType,
     pub reload_prefix: Option<&'static str>,
+    pub constraints: Vec<Constraint>,
     pub list: List,
     pub form: Form,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    LessThan {
+        field: &'static str,
+        than: &'static str,
+    },
+    RequiredIf {
+        field: &'static str,
+        other: &'static str,
+    },
+    MutuallyExclusive(&'static [&'static str]),
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub enum SchemaType {
     Record {
@@ -313,6 +331,10 @@ impl Field {
         self.display.is_empty() || self.display.iter().any(|eval| eval.eval(settings))
     }
 
+    pub fn is_enabled(&self, settings: &FormData) -> bool {
+        self.enable.is_empty() || self.enable.iter().any(|eval| eval.eval(settings))
+    }
+
     pub fn placeholder(&self, settings: &FormData) -> Option<&str> {
         self.placeholder.eval(settings).copied()
     }
@@ -358,6 +380,16 @@ impl<T> Value<T> {
     }
 }
 
+impl Constraint {
+    pub fn fields(&self) -> Vec<&'static str> {
+        match self {
+            Constraint::LessThan { field, than } => vec![field, than],
+            Constraint::RequiredIf { field, other } => vec![field, other],
+            Constraint::MutuallyExclusive(fields) => fields.to_vec(),
+        }
+    }
+}
+
 impl Eval {
     pub fn eval(&self, settings: &FormData) -> bool {
         let value = settings.get(self.field.id);
@@ -415,6 +447,28 @@ impl Builder<Schemas, ()> {
 }
 
 impl Builder<Schemas, Schema> {
+    pub fn less_than(self, field: &'static str, than: &'static str) -> Self {
+        self.constraint(Constraint::LessThan { field, than })
+    }
+
+    pub fn required_if(self, field: &'static str, other: &'static str) -> Self {
+        self.constraint(Constraint::RequiredIf { field, other })
+    }
+
+    pub fn mutually_exclusive(self, fields: &'static [&'static str]) -> Self {
+        self.constraint(Constraint::MutuallyExclusive(fields))
+    }
+
+    fn constraint(mut self, constraint: Constraint) -> Self {
+        for id in constraint.fields() {
+            if !self.item.fields.contains_key(id) {
+                panic!("Field {id:?} not found in schema {:?}.", self.item.id);
+            }
+        }
+        self.item.constraints.push(constraint);
+        self
+    }
+
     pub fn new_field(self, id: &'static str) -> Builder<(Schemas, Schema), Field> {
         Builder {
             parent: (self.parent, self.item),
@@ -615,11 +669,21 @@ impl Builder<(Schemas, Schema), Field> {
         self
     }
 
+    pub fn description(mut self, description: &'static str) -> Self {
+        self.item.description = Some(description);
+        self
+    }
+
     pub fn readonly(mut self) -> Self {
         self.item.readonly = true;
         self
     }
 
+    pub fn option_help(mut self, help: &'static [(&'static str, &'static str)]) -> Self {
+        self.item.option_help = help;
+        self
+    }
+
     pub fn enterprise_feature(mut self) -> Self {
         self.item.enterprise = true;
         self
@@ -781,6 +845,39 @@ impl Builder<(Schemas, Schema), Field> {
         self.display_if(field, values, Condition::MatchNone)
     }
 
+    pub fn enable_if(
+        mut self,
+        field: &'static str,
+        values: impl IntoIterator<Item = &'static str>,
+        condition: Condition,
+    ) -> Self {
+        let values = values.into_iter().collect::<Vec<_>>();
+        if !values.is_empty() {
+            self.item.enable.push(Eval {
+                field: self.field(field),
+                values,
+                condition,
+            });
+        }
+        self
+    }
+
+    pub fn enable_if_eq(
+        self,
+        field: &'static str,
+        values: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        self.enable_if(field, values, Condition::MatchAny)
+    }
+
+    pub fn enable_if_ne(
+        self,
+        field: &'static str,
+        values: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        self.enable_if(field, values, Condition::MatchNone)
+    }
+
     pub fn build(mut self) -> Builder<Schemas, Schema> {
         self.parent
             .1
@@ -797,6 +894,7 @@ impl Builder<(Schemas, Schema), Field> {
             id,
             typ_: self.item.typ_.clone(),
             display: self.item.display.clone(),
+            enable: self.item.enable.clone(),
             checks: self.item.checks.clone(),
             ..Default::default()
         };
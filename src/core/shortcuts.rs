@@ -0,0 +1,244 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use web_sys::{wasm_bindgen::JsCast, Element, HtmlElement, KeyboardEvent};
+
+pub const FOCUSABLE: &str = "button:not([disabled]), input:not([disabled]), select:not([disabled]), textarea:not([disabled]), a[href], [tabindex]:not([tabindex='-1'])";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShortcutContext {
+    Global,
+    Forms,
+    Lists,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    pub key: &'static str,
+    pub ctrl: bool,
+    pub description: &'static str,
+    pub context: ShortcutContext,
+}
+
+#[derive(Clone, Copy)]
+pub struct ShortcutRegistry {
+    shortcuts: RwSignal<Vec<(u64, Shortcut)>>,
+    next_id: StoredValue<u64>,
+}
+
+pub fn init_shortcuts() {
+    provide_context(ShortcutRegistry {
+        shortcuts: create_rw_signal(Vec::new()),
+        next_id: store_value(0),
+    });
+}
+
+pub fn register_shortcut(shortcut: Shortcut) {
+    let Some(registry) = use_context::<ShortcutRegistry>() else {
+        return;
+    };
+    let id = registry.next_id.get_value();
+    registry.next_id.set_value(id + 1);
+    registry
+        .shortcuts
+        .update(|shortcuts| shortcuts.push((id, shortcut)));
+    on_cleanup(move || {
+        registry
+            .shortcuts
+            .update(|shortcuts| shortcuts.retain(|(entry_id, _)| *entry_id != id));
+    });
+}
+
+pub fn use_shortcut(shortcut: Shortcut, handler: impl Fn(&KeyboardEvent) + 'static) {
+    register_shortcut(shortcut);
+    let listener = window_event_listener(ev::keydown, move |ev| {
+        if shortcut.matches(&ev) && (shortcut.ctrl || !is_editing(&ev)) {
+            ev.prevent_default();
+            handler(&ev);
+        }
+    });
+    on_cleanup(move || listener.remove());
+}
+
+// Blur the focused field first so its pending change event reaches the form data
+pub fn use_save_shortcut(on_save: impl Fn() + 'static) {
+    use_shortcut(
+        Shortcut::new("s", "Save changes")
+            .with_ctrl()
+            .in_context(ShortcutContext::Forms),
+        move |_| {
+            if let Some(element) = document()
+                .active_element()
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+            {
+                let _ = element.blur();
+            }
+            on_save();
+        },
+    );
+}
+
+// Returns false when `ev` is not an arrow, Home or End key
+pub fn move_focus(container: &Element, selector: &str, ev: &KeyboardEvent) -> bool {
+    let key = ev.key();
+    if next_focus(&key, None, 1).is_none() {
+        return false;
+    }
+    let Ok(nodes) = container.query_selector_all(selector) else {
+        return false;
+    };
+    let items = (0..nodes.length())
+        .filter_map(|idx| nodes.item(idx)?.dyn_into::<HtmlElement>().ok())
+        .filter(|item| item.offset_parent().is_some())
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return false;
+    }
+    let active = document().active_element();
+    let current = items
+        .iter()
+        .position(|item| active.as_ref() == Some(item.unchecked_ref::<Element>()));
+    let Some(next) = next_focus(&key, current, items.len()) else {
+        return false;
+    };
+    ev.prevent_default();
+    let _ = items[next].focus();
+    true
+}
+
+// Index to focus among `len` items, wrapping down and stopping at the top
+fn next_focus(key: &str, current: Option<usize>, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match (key, current) {
+        ("Home", _) | ("ArrowDown", None) => Some(0),
+        ("ArrowDown", Some(idx)) => Some((idx + 1) % len),
+        ("ArrowUp", Some(idx)) if idx > 0 => Some(idx - 1),
+        ("ArrowUp" | "End", _) => Some(len - 1),
+        _ => None,
+    }
+}
+
+impl ShortcutRegistry {
+    pub fn grouped(&self) -> Vec<(ShortcutContext, Vec<Shortcut>)> {
+        let mut groups: Vec<(ShortcutContext, Vec<Shortcut>)> = Vec::new();
+        self.shortcuts.with(|shortcuts| {
+            for (_, shortcut) in shortcuts {
+                match groups
+                    .iter_mut()
+                    .find(|(context, _)| *context == shortcut.context)
+                {
+                    Some((_, group)) => {
+                        if !group.contains(shortcut) {
+                            group.push(*shortcut);
+                        }
+                    }
+                    None => groups.push((shortcut.context, vec![*shortcut])),
+                }
+            }
+        });
+        groups.sort_by_key(|(context, _)| *context);
+        groups
+    }
+}
+
+impl Shortcut {
+    pub const fn new(key: &'static str, description: &'static str) -> Self {
+        Shortcut {
+            key,
+            ctrl: false,
+            description,
+            context: ShortcutContext::Global,
+        }
+    }
+
+    pub const fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub const fn in_context(mut self, context: ShortcutContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn matches(&self, ev: &KeyboardEvent) -> bool {
+        ev.key().eq_ignore_ascii_case(self.key)
+            && (ev.ctrl_key() || ev.meta_key()) == self.ctrl
+            && !ev.alt_key()
+    }
+
+    pub fn label(&self) -> String {
+        let key = if self.key.len() == 1 {
+            self.key.to_uppercase()
+        } else {
+            self.key.to_string()
+        };
+        if self.ctrl {
+            let modifier = if is_mac() { "⌘" } else { "Ctrl+" };
+            format!("{modifier}{key}")
+        } else {
+            key
+        }
+    }
+}
+
+impl ShortcutContext {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ShortcutContext::Global => "Global",
+            ShortcutContext::Forms => "Forms",
+            ShortcutContext::Lists => "Lists",
+        }
+    }
+}
+
+fn is_editing(ev: &KeyboardEvent) -> bool {
+    ev.target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|element| {
+            element.is_content_editable()
+                || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        })
+}
+
+fn is_mac() -> bool {
+    window()
+        .navigator()
+        .platform()
+        .is_ok_and(|platform| platform.starts_with("Mac"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_focus;
+
+    #[test]
+    fn arrows_move_between_items() {
+        assert_eq!(next_focus("ArrowDown", None, 3), Some(0));
+        assert_eq!(next_focus("ArrowDown", Some(0), 3), Some(1));
+        assert_eq!(next_focus("ArrowDown", Some(2), 3), Some(0));
+        assert_eq!(next_focus("ArrowUp", Some(2), 3), Some(1));
+        assert_eq!(next_focus("ArrowUp", Some(0), 3), Some(2));
+        assert_eq!(next_focus("ArrowUp", None, 3), Some(2));
+    }
+
+    #[test]
+    fn home_and_end_jump_to_the_edges() {
+        assert_eq!(next_focus("Home", Some(2), 3), Some(0));
+        assert_eq!(next_focus("End", Some(0), 3), Some(2));
+        assert_eq!(next_focus("End", None, 1), Some(0));
+    }
+
+    #[test]
+    fn other_keys_are_ignored() {
+        assert_eq!(next_focus("Enter", Some(0), 3), None);
+        assert_eq!(next_focus("ArrowRight", None, 3), None);
+        assert_eq!(next_focus("ArrowDown", None, 0), None);
+    }
+}
@@ -0,0 +1,340 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{sync::Arc, time::Duration};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
+use leptos::{set_timeout, window, RwSignal, SignalSet, SignalUpdate};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{components::messages::alert::Alert, STATE_STORAGE_KEY};
+
+use super::{
+    http::HttpRequest,
+    oauth::{oauth_session_permissions, OAuthGrant, OAuthResponse},
+    url::UrlBuilder,
+    AccessToken, Permissions,
+};
+
+pub const SSO_CALLBACK_PATH: &str = "/authorize/sso";
+
+const SSO_REQUEST_KEY: &str = "webadmin_sso_request";
+const SSO_SETTINGS_KEY: &str = "webadmin_sso_settings";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityProvider {
+    pub token_endpoint: String,
+    pub client_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SsoSettings {
+    pub issuer: String,
+    pub client_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingSsoLogin {
+    base_url: String,
+    issuer: String,
+    provider: IdentityProvider,
+    redirect_uri: String,
+    state: String,
+    nonce: String,
+    code_verifier: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TokenResponse {
+    Granted {
+        #[serde(flatten)]
+        grant: OAuthGrant,
+        #[serde(default)]
+        id_token: Option<String>,
+    },
+    Error {
+        error: String,
+        #[serde(default)]
+        error_description: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    exp: i64,
+    #[serde(default)]
+    nonce: Option<String>,
+    #[serde(default)]
+    preferred_username: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct SsoCallback {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+impl SsoSettings {
+    pub fn load() -> Self {
+        LocalStorage::get(SSO_SETTINGS_KEY).unwrap_or_default()
+    }
+}
+
+pub async fn start_sso_login(base_url: &str, settings: SsoSettings) -> Result<(), Alert> {
+    let issuer = settings.issuer.trim_end_matches('/');
+    let metadata = HttpRequest::get(format!("{issuer}/.well-known/openid-configuration"))
+        .send_raw()
+        .await
+        .and_then(|response| {
+            serde_json::from_slice::<ProviderMetadata>(&response).map_err(Into::into)
+        })
+        .map_err(|err| {
+            Alert::error("Identity provider not found").with_details(format!(
+                "Failed to fetch the OpenID configuration of {issuer}: {err}"
+            ))
+        })?;
+    let origin = window()
+        .location()
+        .origin()
+        .map_err(|_| Alert::error("Failed to determine the address of this page"))?;
+
+    let pending = PendingSsoLogin {
+        base_url: base_url.to_string(),
+        issuer: metadata.issuer,
+        provider: IdentityProvider {
+            token_endpoint: metadata.token_endpoint,
+            client_id: settings.client_id.clone(),
+        },
+        redirect_uri: format!("{origin}{SSO_CALLBACK_PATH}"),
+        state: random_string(32),
+        nonce: random_string(32),
+        code_verifier: random_string(64),
+    };
+    let url = UrlBuilder::new(metadata.authorization_endpoint)
+        .with_parameter("response_type", "code")
+        .with_parameter("client_id", pending.provider.client_id.clone())
+        .with_parameter("redirect_uri", pending.redirect_uri.clone())
+        .with_parameter("scope", "openid profile email")
+        .with_parameter("state", pending.state.clone())
+        .with_parameter("nonce", pending.nonce.clone())
+        .with_parameter(
+            "code_challenge",
+            URL_SAFE_NO_PAD.encode(Sha256::digest(pending.code_verifier.as_bytes())),
+        )
+        .with_parameter("code_challenge_method", "S256")
+        .finish();
+
+    if let Err(err) = SessionStorage::set(SSO_REQUEST_KEY, &pending) {
+        return Err(
+            Alert::error("Failed to save the sign-in request").with_details(err.to_string())
+        );
+    }
+    if let Err(err) = LocalStorage::set(SSO_SETTINGS_KEY, &settings) {
+        log::warn!("Failed to save identity provider settings: {err}");
+    }
+    window().location().set_href(&url).map_err(|err| {
+        Alert::error("Failed to open the identity provider").with_details(format!("{err:?}"))
+    })
+}
+
+pub async fn complete_sso_login(
+    auth_token: RwSignal<AccessToken>,
+    callback: SsoCallback,
+) -> Result<&'static str, Alert> {
+    let pending = SessionStorage::get::<PendingSsoLogin>(SSO_REQUEST_KEY).map_err(|_| {
+        Alert::error("No sign-in in progress").with_details("Start again from the login page.")
+    })?;
+    SessionStorage::delete(SSO_REQUEST_KEY);
+
+    if let Some(error) = callback.error {
+        return Err(Alert::error("Sign-in refused by the identity provider")
+            .with_details(callback.error_description.unwrap_or(error)));
+    }
+    // Reject responses to a request that was not sent from this tab
+    if callback.state.as_deref() != Some(pending.state.as_str()) {
+        return Err(Alert::error("Invalid sign-in response")
+            .with_details("The state returned by the identity provider does not match."));
+    }
+    let Some(code) = callback.code else {
+        return Err(Alert::error("Invalid sign-in response")
+            .with_details("The identity provider did not return an authorization code."));
+    };
+
+    let (grant, id_token) = match HttpRequest::post(pending.provider.token_endpoint.as_str())
+        .with_header("Content-Type", "application/x-www-form-urlencoded")
+        .with_raw_body(
+            serde_urlencoded::to_string([
+                ("grant_type", "authorization_code"),
+                ("code", &code),
+                ("redirect_uri", &pending.redirect_uri),
+                ("client_id", &pending.provider.client_id),
+                ("code_verifier", &pending.code_verifier),
+            ])
+            .unwrap(),
+        )
+        .send_raw()
+        .await
+        .and_then(|response| {
+            serde_json::from_slice::<TokenResponse>(response.as_slice()).map_err(Into::into)
+        }) {
+        Ok(TokenResponse::Granted { grant, id_token }) => (grant, id_token),
+        Ok(TokenResponse::Error {
+            error,
+            error_description,
+        }) => {
+            return Err(Alert::error("OAuth failure").with_details(
+                error_description
+                    .unwrap_or_else(|| format!("Identity provider returned error code {error}")),
+            ));
+        }
+        Err(err) => return Err(Alert::from(err)),
+    };
+
+    // The signature is not verified, the ID token only names the user and ties
+    // the response to this sign-in. Requests are authorized by the access token.
+    let claims = id_token
+        .as_deref()
+        .and_then(decode_id_token)
+        .filter(|claims| {
+            claims.nonce.as_deref() == Some(pending.nonce.as_str())
+                && claims.iss == pending.issuer
+                && claims.aud.contains(&pending.provider.client_id)
+                && claims.exp > Utc::now().timestamp()
+        })
+        .ok_or_else(|| {
+            Alert::error("Invalid sign-in response").with_details(
+                "The ID token returned by the identity provider is missing or does not match.",
+            )
+        })?;
+
+    let mut session = AccessToken {
+        base_url: pending.base_url.into(),
+        access_token: grant.access_token.into(),
+        refresh_token: grant.refresh_token.unwrap_or_default().into(),
+        username: claims
+            .preferred_username
+            .or(claims.email)
+            .unwrap_or(claims.sub)
+            .into(),
+        is_valid: true,
+        identity_provider: Some(Arc::new(pending.provider)),
+        ..Default::default()
+    };
+    let account = oauth_session_permissions(&session)
+        .await
+        .map_err(Alert::from)?;
+    session.permissions = Permissions::new(account.permissions);
+    session.is_enterprise = account.is_enterprise;
+
+    let default_url = session.default_url();
+    if default_url.is_empty() {
+        return Err(Alert::error(
+            "You are not authorized to access this service.",
+        ));
+    }
+
+    if let Err(err) = SessionStorage::set(STATE_STORAGE_KEY, &session) {
+        log::error!("Failed to save state to session storage: {}", err);
+    }
+    let refresh_token = session.refresh_token.clone();
+    auth_token.set(session);
+
+    // Set timer to refresh token
+    if grant.expires_in > 0 && !refresh_token.is_empty() {
+        log::debug!("Next OAuth token refresh in {} seconds.", grant.expires_in);
+        set_timeout(
+            move || {
+                auth_token.update(|auth_token| {
+                    auth_token.is_valid = false;
+                });
+            },
+            Duration::from_secs(grant.expires_in),
+        );
+    }
+
+    Ok(default_url)
+}
+
+pub async fn sso_refresh_token(
+    provider: &IdentityProvider,
+    refresh_token: &str,
+) -> Option<OAuthGrant> {
+    log::debug!("Refreshing OAuth token with the identity provider");
+
+    match HttpRequest::post(provider.token_endpoint.as_str())
+        .with_header("Content-Type", "application/x-www-form-urlencoded")
+        .with_raw_body(
+            serde_urlencoded::to_string([
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", &provider.client_id),
+            ])
+            .unwrap(),
+        )
+        .send_raw()
+        .await
+        .and_then(|response| {
+            serde_json::from_slice::<OAuthResponse>(response.as_slice()).map_err(Into::into)
+        }) {
+        Ok(OAuthResponse::Granted(grant)) => Some(grant),
+        Ok(OAuthResponse::Error { error }) => {
+            log::error!("OAuth failure: Identity provider returned error code {error:?}");
+            None
+        }
+        Err(err) => {
+            log::error!("OAuth failure: {err:?}");
+            None
+        }
+    }
+}
+
+impl Audience {
+    fn contains(&self, client_id: &str) -> bool {
+        match self {
+            Audience::One(aud) => aud == client_id,
+            Audience::Many(aud) => aud.iter().any(|aud| aud == client_id),
+        }
+    }
+}
+
+fn decode_id_token(id_token: &str) -> Option<IdTokenClaims> {
+    let payload = id_token.split('.').nth(1)?;
+    serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()
+}
+
+fn random_string(len: usize) -> String {
+    thread_rng()
+        .sample_iter(Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
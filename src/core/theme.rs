@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use gloo_storage::{LocalStorage, Storage};
+use leptos::*;
+use leptos_use::{use_preferred_contrast, use_preferred_dark, PreferredContrast};
+use serde::{Deserialize, Serialize};
+
+const THEME_KEY: &str = "webadmin_theme";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+pub static THEMES: &[Theme] = &[
+    Theme::System,
+    Theme::Light,
+    Theme::Dark,
+    Theme::HighContrast,
+];
+
+#[derive(Clone, Copy)]
+pub struct ThemePreference {
+    pub theme: RwSignal<Theme>,
+}
+
+pub fn init_theme() {
+    let preference = ThemePreference {
+        theme: create_rw_signal(LocalStorage::get(THEME_KEY).unwrap_or_default()),
+    };
+    let prefers_dark = use_preferred_dark();
+    let prefers_contrast = use_preferred_contrast();
+
+    create_effect(move |_| {
+        let theme = preference.theme.get();
+        let _ = LocalStorage::set(THEME_KEY, theme);
+
+        let (dark, high_contrast) = match theme {
+            Theme::System => (
+                prefers_dark.get(),
+                prefers_contrast.get() == PreferredContrast::More,
+            ),
+            Theme::Light => (false, false),
+            Theme::Dark => (true, false),
+            Theme::HighContrast => (false, true),
+        };
+        if let Some(root) = document().document_element() {
+            let classes = root.class_list();
+            let _ = classes.toggle_with_force("dark", dark);
+            let _ = classes.toggle_with_force("high-contrast", high_contrast);
+        }
+    });
+    provide_context(preference);
+}
+
+pub fn use_theme() -> ThemePreference {
+    expect_context::<ThemePreference>()
+}
+
+impl Theme {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn parse(id: &str) -> Option<Self> {
+        THEMES.iter().copied().find(|theme| theme.id() == id)
+    }
+}
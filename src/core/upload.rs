@@ -0,0 +1,72 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::File;
+
+pub async fn read_file(file: File) -> Result<String, JsValue> {
+    JsFuture::from(file.text())
+        .await?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("File contents are not valid text"))
+}
+
+// Each record comes with the line number it starts on
+pub fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => {
+                in_quotes = true;
+            }
+            ',' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+            }
+            '\r' | '\n' if !in_quotes => {
+                if ch == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|field| !field.is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                } else {
+                    row.clear();
+                }
+                line += 1;
+                row_line = line;
+            }
+            _ => {
+                if ch == '\n' || (ch == '\r' && chars.peek() != Some(&'\n')) {
+                    line += 1;
+                }
+                field.push(ch);
+            }
+        }
+    }
+
+    row.push(field);
+    if row.iter().any(|field| !field.is_empty()) {
+        rows.push((row_line, row));
+    }
+
+    rows
+}
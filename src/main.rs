@@ -6,7 +6,7 @@
 
 #![allow(unstable_name_collisions)]
 use core::{schema::Schemas, AccessToken, Permission, Permissions};
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use components::{
     icon::{
@@ -23,11 +23,15 @@ use leptos_meta::*;
 use leptos_router::*;
 use pages::{
     account::{
+        activity::AccountActivity,
         app_password::{AppPasswordCreate, AppPasswords},
         mfa::ManageMfa,
     },
-    config::edit::DEFAULT_SETTINGS_URL,
-    directory::{dns::DnsDisplay, edit::PrincipalEdit, list::PrincipalList},
+    config::{edit::DEFAULT_SETTINGS_URL, RecentSettings},
+    directory::{
+        dns::DnsDisplay, edit::PrincipalEdit, import::PrincipalImport, list::PrincipalList,
+        setup::SetupWizard,
+    },
     manage::spam::{SpamTest, SpamTrain},
 };
 
@@ -36,15 +40,35 @@ pub static VERSION_NAME: &str = concat!("Stalwart Management UI v", env!("CARGO_
 use crate::{
     components::{
         layout::{Layout, LayoutBuilder},
-        messages::{alert::init_alerts, modal::init_modals},
+        messages::{alert::init_alerts, error::init_panic_hook, modal::init_modals},
+    },
+    core::{
+        connectivity::init_connectivity,
+        datetime::init_datetime,
+        i18n::init_i18n,
+        oauth::{refresh_authorization, remember_login_redirect},
+        shortcuts::init_shortcuts,
+        theme::init_theme,
     },
-    core::oauth::oauth_refresh_token,
     pages::{
-        account::{crypto::ManageCrypto, password::ChangePassword},
+        account::{crypto::ManageCrypto, password::ChangePassword, preferences::Preferences},
         authorize::Authorize,
-        config::{edit::SettingsEdit, list::SettingsList, search::SettingsSearch},
+        config::{
+            edit::SettingsEdit, list::SettingsList, replace::SettingsReplace,
+            search::SettingsSearch,
+        },
+        forbidden::Forbidden,
         login::Login,
-        manage::{logs::Logs, maintenance::Maintenance},
+        manage::{
+            dashboard::{
+                delivery::DeliveryDashboard, network::NetworkDashboard, overview::Overview,
+                performance::PerformanceDashboard, security::SecurityDashboard,
+            },
+            history::{DeliveryHistory, ReceivedHistory, TraceDisplay},
+            logs::Logs,
+            maintenance::Maintenance,
+            tracing::LiveTracing,
+        },
         notfound::NotFound,
         queue::{
             messages::{list::QueueList, manage::QueueManage},
@@ -63,7 +87,7 @@ pub const STATE_LOGIN_NAME_KEY: &str = "webadmin_login_name";
 
 fn main() {
     _ = console_log::init_with_level(log::Level::Debug);
-    console_error_panic_hook::set_once();
+    init_panic_hook();
     leptos::mount_to_body(|| view! { <App/> })
 }
 
@@ -83,6 +107,11 @@ pub fn App() -> impl IntoView {
     provide_context(build_schemas());
     init_alerts();
     init_modals();
+    init_shortcuts();
+    init_i18n();
+    init_datetime();
+    init_theme();
+    init_connectivity();
 
     // Create a resource to refresh the OAuth token
     let _refresh_token_resource = create_resource(
@@ -92,43 +121,7 @@ pub fn App() -> impl IntoView {
 
             async move {
                 if !changed_auth_token.is_valid && !changed_auth_token.refresh_token.is_empty() {
-                    if let Some(grant) = oauth_refresh_token(
-                        &changed_auth_token.base_url,
-                        &changed_auth_token.refresh_token,
-                    )
-                    .await
-                    {
-                        let refresh_token = grant.refresh_token.unwrap_or_default();
-                        auth_token.update(|auth_token| {
-                            auth_token.access_token = grant.access_token.into();
-                            auth_token.refresh_token = refresh_token.clone().into();
-                            auth_token.is_valid = true;
-
-                            if let Err(err) =
-                                SessionStorage::set(STATE_STORAGE_KEY, auth_token.clone())
-                            {
-                                log::error!(
-                                    "Failed to save authorization token to session storage: {}",
-                                    err
-                                );
-                            }
-                        });
-                        // Set timer to refresh token
-                        if grant.expires_in > 0 && !refresh_token.is_empty() {
-                            log::debug!(
-                                "Next OAuth token refresh in {} seconds.",
-                                grant.expires_in
-                            );
-                            set_timeout(
-                                move || {
-                                    auth_token.update(|auth_token| {
-                                        auth_token.is_valid = false;
-                                    });
-                                },
-                                Duration::from_secs(grant.expires_in),
-                            );
-                        }
-                    }
+                    refresh_authorization(auth_token).await;
                 }
             }
         },
@@ -143,25 +136,38 @@ pub fn App() -> impl IntoView {
         }
     });
 
+    // Only a page opened without a session is returned to after signing in,
+    // not the one that was open when the session ended
+    let had_session = store_value(false);
+    let is_logged_in = move || {
+        let is_logged_in = permissions.get().is_some();
+        if is_logged_in {
+            had_session.set_value(true);
+        } else if !had_session.get_value() {
+            remember_login_redirect();
+        }
+        is_logged_in
+    };
+
     view! {
         <Router>
             <Routes>
                 <ProtectedRoute
                     path="/manage"
                     view=move || {
-                        let menu_items = LayoutBuilder::manage(
-                            &permissions.get().unwrap_or_default(),
-                        );
+                        let menu_items = Signal::derive(move || {
+                            LayoutBuilder::manage(&permissions.get().unwrap_or_default())
+                        });
                         view! { <Layout menu_items=menu_items permissions=permissions/> }
                     }
 
                     redirect_path="/login"
-                    condition=move || permissions.get().is_some()
+                    condition=is_logged_in
                 >
                     <ProtectedRoute
                         path="/directory/:object"
                         view=PrincipalList
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -188,7 +194,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/directory/:object/:id?/edit"
                         view=PrincipalEdit
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -212,10 +218,75 @@ pub fn App() -> impl IntoView {
                         }
                     />
 
+                    <ProtectedRoute
+                        path="/directory/:object/:id?/view"
+                        view=PrincipalEdit
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_any(
+                                        &[
+                                            Permission::IndividualList,
+                                            Permission::GroupList,
+                                            Permission::RoleList,
+                                            Permission::TenantList,
+                                            Permission::DomainList,
+                                            Permission::MailingListList,
+                                            Permission::OauthClientList,
+                                            Permission::ApiKeyList,
+                                        ],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/directory/accounts/:id/activity"
+                        view=AccountActivity
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::IndividualList, Permission::TracingList],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/directory/accounts/import"
+                        view=PrincipalImport
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| { p.has_access(Permission::IndividualCreate) })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/setup"
+                        view=SetupWizard
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::DomainCreate, Permission::IndividualCreate],
+                                    )
+                                })
+                        }
+                    />
+
                     <ProtectedRoute
                         path="/dns/:id/view"
                         view=DnsDisplay
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -230,10 +301,85 @@ pub fn App() -> impl IntoView {
                         }
                     />
 
+                    <ProtectedRoute
+                        path="/dashboard/overview"
+                        view=Overview
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::MetricsList, Permission::MetricsLive],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/dashboard/network"
+                        view=NetworkDashboard
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::MetricsList, Permission::MetricsLive],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/dashboard/security"
+                        view=SecurityDashboard
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::MetricsList, Permission::MetricsLive],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/dashboard/delivery"
+                        view=DeliveryDashboard
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::MetricsList, Permission::MetricsLive],
+                                    )
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/dashboard/performance"
+                        view=PerformanceDashboard
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access_all(
+                                        &[Permission::MetricsList, Permission::MetricsLive],
+                                    )
+                                })
+                        }
+                    />
+
                     <ProtectedRoute
                         path="/queue/messages"
                         view=QueueList
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -244,7 +390,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/queue/message/:id"
                         view=QueueManage
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -255,7 +401,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/queue/reports"
                         view=ReportList
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -266,7 +412,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/queue/report/:id"
                         view=ReportDisplay
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -277,7 +423,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/reports/:object"
                         view=IncomingReportList
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -288,7 +434,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/reports/:object/:id"
                         view=IncomingReportDisplay
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -299,7 +445,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/logs"
                         view=Logs
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -307,10 +453,54 @@ pub fn App() -> impl IntoView {
                         }
                     />
 
+                    <ProtectedRoute
+                        path="/tracing/received"
+                        view=ReceivedHistory
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| { p.has_access(Permission::TracingList) })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/tracing/delivery"
+                        view=DeliveryHistory
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| { p.has_access(Permission::TracingList) })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/tracing/span/:id"
+                        view=TraceDisplay
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| { p.has_access(Permission::TracingGet) })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/tracing/live"
+                        view=LiveTracing
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| { p.has_access(Permission::TracingLive) })
+                        }
+                    />
+
                     <ProtectedRoute
                         path="/spam/train"
                         view=SpamTrain
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -321,7 +511,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/spam/test"
                         view=SpamTest
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -332,7 +522,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/maintenance"
                         view=Maintenance
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -345,6 +535,9 @@ pub fn App() -> impl IntoView {
                                                 Permission::Restart,
                                                 Permission::UpdateSpamFilter,
                                                 Permission::UpdateWebadmin,
+                                                Permission::FtsReindex,
+                                                Permission::PurgeBlobStore,
+                                                Permission::PurgeAccount,
                                             ],
                                         )
                                     },
@@ -356,17 +549,21 @@ pub fn App() -> impl IntoView {
                 <ProtectedRoute
                     path="/settings"
                     view=move || {
-                        let menu_items = LayoutBuilder::settings(auth_token.get().default_url());
+                        let recent = RecentSettings::load();
+                        provide_context(recent);
+                        let menu_items = Signal::derive(move || {
+                            LayoutBuilder::settings(auth_token.get().default_url(), &recent.get())
+                        });
                         view! { <Layout menu_items=menu_items permissions=permissions/> }
                     }
 
                     redirect_path="/login"
-                    condition=move || permissions.get().is_some()
+                    condition=is_logged_in
                 >
                     <ProtectedRoute
                         path="/:object"
                         view=SettingsList
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -377,18 +574,33 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/:object/:id?/edit"
                         view=SettingsEdit
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access(Permission::SettingsUpdate)
+                                        || (p.has_access(Permission::SettingsList)
+                                            && auth_token.get().is_viewer())
+                                })
+                        }
+                    />
+
+                    <ProtectedRoute
+                        path="/:object/:id?/view"
+                        view=SettingsEdit
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
-                                .map_or(false, |p| { p.has_access(Permission::SettingsUpdate) })
+                                .is_some_and(|p| { p.has_access(Permission::SettingsList) })
                         }
                     />
 
                     <ProtectedRoute
                         path="/search"
                         view=SettingsSearch
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -396,23 +608,37 @@ pub fn App() -> impl IntoView {
                         }
                     />
 
+                    <ProtectedRoute
+                        path="/replace"
+                        view=SettingsReplace
+                        redirect_path="/forbidden"
+                        condition=move || {
+                            permissions
+                                .get()
+                                .is_some_and(|p| {
+                                    p.has_access(Permission::SettingsList)
+                                        && p.has_access(Permission::SettingsUpdate)
+                                })
+                        }
+                    />
+
                 </ProtectedRoute>
                 <ProtectedRoute
                     path="/account"
                     view=move || {
-                        let menu_items = LayoutBuilder::account(
-                            &permissions.get().unwrap_or_default(),
-                        );
+                        let menu_items = Signal::derive(move || {
+                            LayoutBuilder::account(&permissions.get().unwrap_or_default())
+                        });
                         view! { <Layout menu_items=menu_items permissions=permissions/> }
                     }
 
                     redirect_path="/login"
-                    condition=move || permissions.get().is_some()
+                    condition=is_logged_in
                 >
                     <ProtectedRoute
                         path="/crypto"
                         view=ManageCrypto
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -423,7 +649,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/password"
                         view=ChangePassword
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -434,7 +660,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/mfa"
                         view=ManageMfa
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -445,7 +671,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/app-passwords"
                         view=AppPasswords
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -456,7 +682,7 @@ pub fn App() -> impl IntoView {
                     <ProtectedRoute
                         path="/app-passwords/edit"
                         view=AppPasswordCreate
-                        redirect_path="/login"
+                        redirect_path="/forbidden"
                         condition=move || {
                             permissions
                                 .get()
@@ -464,11 +690,14 @@ pub fn App() -> impl IntoView {
                         }
                     />
 
+                    <Route path="/preferences" view=Preferences/>
+
                 </ProtectedRoute>
 
                 <Route path="/" view=Login/>
                 <Route path="/login" view=Login/>
                 <Route path="/authorize/:type?" view=Authorize/>
+                <Route path="/forbidden" view=Forbidden/>
                 <Route path="/*any" view=NotFound/>
             </Routes>
         </Router>
@@ -479,48 +708,48 @@ pub fn App() -> impl IntoView {
 impl LayoutBuilder {
     pub fn manage(permissions: &Permissions) -> Vec<MenuItem> {
         LayoutBuilder::new("/manage")
-            .create("Dashboard")
+            .create("menu.dashboard")
             .icon(view! { <IconChartBarSquare/> })
-            .create("Overview")
+            .create("menu.overview")
             .route("/dashboard/overview")
             .insert(true)
-            .create("Network")
+            .create("menu.network")
             .route("/dashboard/network")
             .insert(true)
-            .create("Security")
+            .create("menu.security")
             .route("/dashboard/security")
             .insert(true)
-            .create("Delivery")
+            .create("menu.delivery")
             .route("/dashboard/delivery")
             .insert(true)
-            .create("Performance")
+            .create("menu.performance")
             .route("/dashboard/performance")
             .insert(true)
             .insert(permissions.has_access_all(&[Permission::MetricsList, Permission::MetricsLive]))
-            .create("Directory")
+            .create("menu.directory")
             .icon(view! { <IconUserGroup/> })
-            .create("Accounts")
+            .create("menu.accounts")
             .route("/directory/accounts")
             .insert(permissions.has_access(Permission::IndividualList))
-            .create("Groups")
+            .create("menu.groups")
             .route("/directory/groups")
             .insert(permissions.has_access(Permission::GroupList))
-            .create("Lists")
+            .create("menu.lists")
             .route("/directory/lists")
             .insert(permissions.has_access(Permission::MailingListList))
-            .create("Domains")
+            .create("menu.domains")
             .route("/directory/domains")
             .insert(permissions.has_access(Permission::DomainList))
-            .create("Roles")
+            .create("menu.roles")
             .route("/directory/roles")
             .insert(permissions.has_access(Permission::RoleList))
-            .create("Tenants")
+            .create("menu.tenants")
             .route("/directory/tenants")
             .insert(permissions.has_access(Permission::TenantList))
-            .create("API Keys")
+            .create("menu.api-keys")
             .route("/directory/api-keys")
             .insert(permissions.has_access(Permission::ApiKeyList))
-            .create("OAuth Clients")
+            .create("menu.oauth-clients")
             .route("/directory/oauth-clients")
             .insert(permissions.has_access(Permission::OauthClientList))
             .insert(permissions.has_access_any(&[
@@ -533,12 +762,12 @@ impl LayoutBuilder {
                 Permission::OauthClientList,
                 Permission::ApiKeyList,
             ]))
-            .create("Queues")
+            .create("menu.queues")
             .icon(view! { <IconQueueList/> })
-            .create("Messages")
+            .create("menu.messages")
             .route("/queue/messages")
             .insert(permissions.has_access(Permission::MessageQueueList))
-            .create("Reports")
+            .create("menu.reports")
             .route("/queue/reports")
             .insert(permissions.has_access(Permission::OutgoingReportList))
             .insert(
@@ -547,50 +776,50 @@ impl LayoutBuilder {
                     Permission::OutgoingReportList,
                 ]),
             )
-            .create("Reports")
+            .create("menu.reports")
             .icon(view! { <IconDocumentChartBar/> })
-            .create("DMARC Aggregate")
+            .create("menu.dmarc-aggregate")
             .route("/reports/dmarc")
             .insert(true)
-            .create("TLS Aggregate")
+            .create("menu.tls-aggregate")
             .route("/reports/tls")
             .insert(true)
-            .create("Failures")
+            .create("menu.failures")
             .route("/reports/arf")
             .insert(true)
             .insert(permissions.has_access(Permission::IncomingReportList))
-            .create("History")
+            .create("menu.history")
             .icon(view! { <IconClock/> })
-            .create("Received Messages")
+            .create("menu.received-messages")
             .route("/tracing/received")
             .insert(true)
-            .create("Delivery Attempts")
+            .create("menu.delivery-attempts")
             .route("/tracing/delivery")
             .insert(true)
             .insert(permissions.has_access(Permission::TracingList))
-            .create("Telemetry")
+            .create("menu.telemetry")
             .icon(view! { <IconSignal/> })
-            .create("Logs")
+            .create("menu.logs")
             .route("/logs")
             .insert(permissions.has_access(Permission::LogsView))
-            .create("Live tracing")
+            .create("menu.live-tracing")
             .route("/tracing/live")
             .insert(permissions.has_access(Permission::TracingLive))
             .insert(permissions.has_access_any(&[Permission::LogsView, Permission::TracingLive]))
-            .create("Antispam")
+            .create("menu.antispam")
             .icon(view! { <IconShieldCheck/> })
-            .create("Train")
+            .create("menu.train")
             .route("/spam/train")
             .insert(true)
-            .create("Test")
+            .create("menu.test")
             .route("/spam/test")
             .insert(true)
             .insert(permissions.has_access(Permission::SieveRun))
-            .create("Settings")
+            .create("menu.settings")
             .icon(view! { <IconAdjustmentsHorizontal/> })
             .raw_route(DEFAULT_SETTINGS_URL)
             .insert(permissions.has_access(Permission::SettingsList))
-            .create("Maintenance")
+            .create("menu.maintenance")
             .icon(view! { <IconWrench/> })
             .route("/maintenance")
             .insert(permissions.has_access_any(&[
@@ -598,28 +827,35 @@ impl LayoutBuilder {
                 Permission::Restart,
                 Permission::UpdateSpamFilter,
                 Permission::UpdateWebadmin,
+                Permission::FtsReindex,
+                Permission::PurgeBlobStore,
+                Permission::PurgeAccount,
             ]))
             .menu_items
     }
 
     pub fn account(permissions: &Permissions) -> Vec<MenuItem> {
         LayoutBuilder::new("/account")
-            .create("Encryption-at-rest")
+            .create("menu.encryption-at-rest")
             .icon(view! { <IconLockClosed/> })
             .route("/crypto")
             .insert(permissions.has_access(Permission::ManageEncryption))
-            .create("Change Password")
+            .create("menu.change-password")
             .icon(view! { <IconKey/> })
             .route("/password")
             .insert(permissions.has_access(Permission::ManagePasswords))
-            .create("Two-factor Auth")
+            .create("menu.two-factor-auth")
             .icon(view! { <IconShieldCheck/> })
             .route("/mfa")
             .insert(permissions.has_access(Permission::ManagePasswords))
-            .create("App Passwords")
+            .create("menu.app-passwords")
             .icon(view! { <IconSquare2x2/> })
             .route("/app-passwords")
             .insert(permissions.has_access(Permission::ManagePasswords))
+            .create("menu.preferences")
+            .icon(view! { <IconAdjustmentsHorizontal/> })
+            .route("/preferences")
+            .insert(true)
             .menu_items
     }
 }
@@ -628,6 +864,7 @@ pub fn build_schemas() -> Arc<Schemas> {
     Schemas::builder()
         .build_login()
         .build_principals()
+        .build_setup()
         .build_store()
         .build_directory()
         .build_authentication()
@@ -649,6 +886,7 @@ pub fn build_schemas() -> Arc<Schemas> {
         .build_authorize()
         .build_mfa()
         .build_app_passwords()
+        .build_security_dashboard()
         .build()
         .into()
 }
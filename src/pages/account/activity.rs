@@ -0,0 +1,225 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{SecondsFormat, TimeDelta, Utc};
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::{
+        list::{
+            pagination::Pagination,
+            toolbar::{FilterInput, ToolbarButton},
+            Filters, Footer, ListSection, ListTable, Toolbar, ZeroResults,
+        },
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+        timestamp::Timestamp,
+        Color,
+    },
+    core::{api::use_api, http, i18n::t_signal, url::UrlBuilder},
+    pages::{
+        manage::tracing::{SpanEvent, CONNECTION_SPAN},
+        queue::messages::list::{from_local_input, query_value, to_local_input},
+        List,
+    },
+};
+
+const PAGE_SIZE: u32 = 25;
+const DEFAULT_RANGE_DAYS: i64 = 30;
+
+#[component]
+pub fn AccountActivity() -> impl IntoView {
+    let query = use_query_map();
+    let params = use_params_map();
+    let account =
+        create_memo(move |_| params.with(|params| params.get("id").cloned().unwrap_or_default()));
+    let page = create_memo(move |_| {
+        query
+            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
+            .filter(|&page| page > 0)
+            .unwrap_or(1)
+    });
+    let default_after = (Utc::now() - TimeDelta::days(DEFAULT_RANGE_DAYS))
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+    let after = query_value(query, "after");
+    let after = create_memo(move |_| after.get().unwrap_or_else(|| default_after.clone()));
+    let before = query_value(query, "before");
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        let mut url = UrlBuilder::new(format!(
+            "/manage/directory/accounts/{}/activity",
+            account.get()
+        ));
+        for (param, current) in [("after", Some(after.get())), ("before", before.get())] {
+            url = url.with_optional_parameter(
+                param,
+                if param == key { value.clone() } else { current }.filter(|v| !v.is_empty()),
+            );
+        }
+        url
+    };
+
+    let api = use_api();
+    let alert = use_alerts();
+
+    let events = create_resource(
+        move || (account.get(), page.get(), after.get(), before.get()),
+        move |(account, page, after, before)| async move {
+            // The server keeps no audit log, SMTP sessions mentioning the
+            // account are the closest record of its activity
+            api.get("/api/telemetry/traces")
+                .with_parameter("type", CONNECTION_SPAN)
+                .with_parameter("filter", account)
+                .with_parameter("page", page.to_string())
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_parameter("values", "1")
+                .with_parameter("after", after)
+                .with_optional_parameter("before", before)
+                .send::<List<SpanEvent>>()
+                .await
+        },
+    );
+
+    let total_results = create_rw_signal(None::<u32>);
+
+    view! {
+        <ListSection>
+            <ListTable
+                title=Signal::derive(move || format!("Activity of {}", account.get()))
+                subtitle="SMTP sessions that mention the account, such as sign-ins and messages sent"
+            >
+                <Toolbar slot>
+                    <ToolbarButton
+                        text=t_signal("button.refresh")
+                        color=Color::Gray
+                        on_click=move |_| {
+                            events.refetch();
+                        }
+                    />
+
+                </Toolbar>
+
+                <Filters slot>
+                    <FilterInput
+                        label="From"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || to_local_input(&after.get()))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("after", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Until"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || before.get().and_then(|dt| to_local_input(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("before", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=Skeleton>
+                    {move || match events.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => {
+                            total_results.set(Some(0));
+                            alert.set(Alert::from(err));
+                            Some(view! { <Skeleton/> }.into_view())
+                        }
+                        Some(Ok(events)) if !events.items.is_empty() => {
+                            total_results.set(Some(events.total as u32));
+                            Some(
+                                view! {
+                                    <ol class="px-6 py-4">
+                                        <For
+                                            each=move || events.items.clone()
+                                            key=|event| event.id.clone()
+                                            let:event
+                                        >
+                                            <ActivityItem event/>
+                                        </For>
+                                    </ol>
+                                }
+                                    .into_view(),
+                            )
+                        }
+                        Some(Ok(_)) => {
+                            total_results.set(Some(0));
+                            Some(
+                                view! {
+                                    <ZeroResults
+                                        title="No activity"
+                                        subtitle="No sessions mentioned this account in the selected period."
+                                    />
+                                }
+                                    .into_view(),
+                            )
+                        }
+                    }}
+
+                </Transition>
+
+                <Footer slot>
+
+                    <Pagination
+                        current_page=page
+                        total_results=total_results.read_only()
+                        page_size=PAGE_SIZE
+                        on_page_change=move |page: u32| {
+                            use_navigate()(
+                                &filter_url("page", None)
+                                    .with_parameter("page", page.to_string())
+                                    .finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Footer>
+            </ListTable>
+        </ListSection>
+    }
+}
+
+#[component]
+fn ActivityItem(event: SpanEvent) -> impl IntoView {
+    let source = [event.value("remoteIp"), event.value("listenerId")]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let href = format!("/manage/tracing/span/{}", event.id);
+
+    view! {
+        <li class="relative flex gap-x-4 pb-6 last:pb-0 group">
+            <div class="absolute top-5 bottom-0 start-[7px] w-px bg-gray-200 group-last:hidden dark:bg-gray-700"></div>
+            <div class="relative mt-1.5 size-4 flex-shrink-0 rounded-full border-2 border-white bg-gray-300 dark:border-slate-900 dark:bg-gray-600"></div>
+            <div class="grow">
+                <div class="flex flex-wrap items-center gap-x-3 gap-y-1">
+                    <A href=href class="text-sm text-blue-600 hover:underline dark:text-blue-500">
+                        {event.text}
+                    </A>
+                    <span class="text-xs text-gray-500">
+                        <Timestamp value=event.created_at/>
+                    </span>
+                </div>
+                <p class="mt-0.5 text-xs text-gray-500 truncate max-w-xl">{source}</p>
+            </div>
+        </li>
+    }
+}
@@ -4,10 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::{collections::HashSet, sync::Arc};
+use std::{cmp::Ordering, collections::HashSet, sync::Arc};
 
-use chrono::{DateTime, Utc};
-use chrono_humanize::HumanTime;
+use chrono::{DateTime, TimeDelta, Utc};
 use leptos::*;
 use leptos_router::{use_navigate, use_query_map};
 use pwhash::sha512_crypt;
@@ -16,17 +15,19 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
+        clipboard::CopyButton,
         form::{
-            button::Button, input::InputText, Form, FormButtonBar, FormElement, FormItem,
-            FormSection,
+            button::Button,
+            input::{InputPassword, InputText},
+            Form, FormButtonBar, FormElement, FormItem, FormSection,
         },
         icon::{IconAdd, IconTrash},
         list::{
-            header::ColumnList,
+            header::{ColumnList, ColumnSort, SortOrder},
             pagination::Pagination,
             row::SelectItem,
-            toolbar::{SearchBox, ToolbarButton},
-            Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
+            toolbar::{FilterSelect, SearchBox, ToolbarButton},
+            Filters, Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
         },
         messages::{
             alert::{use_alerts, Alert},
@@ -36,14 +37,15 @@ use crate::{
         Color,
     },
     core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
+        api::use_api,
+        http,
+        i18n::t_signal,
         schema::{Builder, Schemas, Transformer, Type, Validator},
         url::UrlBuilder,
     },
     pages::{
-        account::{AccountAuthRequest, AccountAuthResponse},
-        maybe_plural, List,
+        account::{AccountAuthRequest, AccountAuthResponse, AppPasswordUsage},
+        maybe_plural, FormatDateTime, List,
     },
 };
 
@@ -54,9 +56,13 @@ struct AppPassword {
     id: String,
     name: String,
     created: Option<DateTime<Utc>>,
+    usage: Option<AppPasswordUsage>,
 }
 
 const PAGE_SIZE: u32 = 10;
+const UNUSED_DAYS: i64 = 90;
+
+const USAGE_FILTERS: [(&str, &str); 2] = [("", "All passwords"), ("unused", "Unused in 90 days")];
 
 #[component]
 pub fn AppPasswords() -> impl IntoView {
@@ -79,82 +85,99 @@ pub fn AppPasswords() -> impl IntoView {
             })
         })
     });
-
-    let auth = use_authorization();
+    let unused =
+        create_memo(move |_| query.with(|q| q.get("usage").is_some_and(|s| s == "unused")));
+    let sort =
+        create_memo(move |_| query.with(|q| q.get("sort").and_then(|sort| SortOrder::parse(sort))));
+    let list_url = move || {
+        UrlBuilder::new("/account/app-passwords")
+            .with_optional_parameter("filter", filter.get())
+            .with_optional_parameter("usage", unused.get().then_some("unused"))
+            .with_optional_parameter("sort", sort.get().map(|sort| sort.to_param()))
+    };
+
+    let api = use_api();
     let alert = use_alerts();
     let modal = use_modals();
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
     provide_context(selected);
+    // Servers that do not track app password usage send none at all
+    let has_usage = create_rw_signal(false);
 
     let passwords = create_resource(
-        move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
-
-            async move {
-                let response = HttpRequest::get("/api/account/auth")
-                    .with_authorization(&auth)
-                    .send::<AccountAuthResponse>()
-                    .await?;
-                let mut items = Vec::with_capacity(response.app_passwords.len());
-                let mut offset = PAGE_SIZE * page.saturating_sub(1);
-                let total = response.app_passwords.len() as u64;
-
-                for id in response.app_passwords {
-                    let mut app_password = AppPassword {
-                        id,
-                        name: String::new(),
-                        created: None,
-                    };
-
-                    if let Some((name, created)) = STANDARD
-                        .decode(&app_password.id)
-                        .ok()
-                        .and_then(|id| String::from_utf8(id).ok())
-                        .and_then(|id| {
-                            id.rsplit_once('$').map(|(name, created)| {
-                                (
-                                    name.to_string(),
-                                    DateTime::parse_from_rfc3339(created)
-                                        .ok()
-                                        .map(|dt| dt.with_timezone(&Utc)),
-                                )
-                            })
+        move || (page.get(), filter.get(), unused.get(), sort.get()),
+        move |(page, filter, unused, sort)| async move {
+            let response = api
+                .get("/api/account/auth")
+                .send::<AccountAuthResponse>()
+                .await?;
+            let mut usage = response.app_password_usage;
+            has_usage.try_set(!usage.is_empty());
+            let unused = unused && !usage.is_empty();
+            let mut items = Vec::with_capacity(response.app_passwords.len());
+            let unused_since = Utc::now() - TimeDelta::days(UNUSED_DAYS);
+
+            for id in response.app_passwords {
+                let mut app_password = AppPassword {
+                    usage: usage.remove(&id),
+                    id,
+                    name: String::new(),
+                    created: None,
+                };
+
+                if let Some((name, created)) = STANDARD
+                    .decode(&app_password.id)
+                    .ok()
+                    .and_then(|id| String::from_utf8(id).ok())
+                    .and_then(|id| {
+                        id.rsplit_once('$').map(|(name, created)| {
+                            (
+                                name.to_string(),
+                                DateTime::parse_from_rfc3339(created)
+                                    .ok()
+                                    .map(|dt| dt.with_timezone(&Utc)),
+                            )
                         })
-                    {
-                        app_password.name = name;
-                        app_password.created = created;
-                    } else {
-                        app_password.name.clone_from(&app_password.id);
-                    }
+                    })
+                {
+                    app_password.name = name;
+                    app_password.created = created;
+                } else {
+                    app_password.name.clone_from(&app_password.id);
+                }
 
-                    if filter
-                        .as_ref()
-                        .map_or(true, |filter| app_password.name.contains(filter))
-                    {
-                        if offset == 0 {
-                            items.push(app_password);
-                            if items.len() as u32 >= PAGE_SIZE {
-                                break;
-                            }
-                        } else {
-                            offset -= 1;
-                        }
-                    }
+                if filter
+                    .as_ref()
+                    .map_or(true, |filter| app_password.name.contains(filter))
+                    && (!unused
+                        || app_password
+                            .last_activity()
+                            .is_none_or(|last_activity| last_activity < unused_since))
+                {
+                    items.push(app_password);
                 }
+            }
 
-                Ok(Arc::new(List { items, total }))
+            if let Some(sort) = sort {
+                items.sort_by(|a, b| a.compare(b, &sort));
             }
+            let total = items.len() as u64;
+            let items = items
+                .into_iter()
+                .skip((PAGE_SIZE * page.saturating_sub(1)) as usize)
+                .take(PAGE_SIZE as usize)
+                .collect();
+
+            Ok(Arc::new(List { items, total }))
         },
     );
 
     let delete_action = create_action(move |items: &Arc<HashSet<String>>| {
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
-            if let Err(err) = HttpRequest::post("/api/account/auth")
-                .with_authorization(&auth)
+            if let Err(err) = api
+                .post("/api/account/auth")
                 .with_body(
                     items
                         .iter()
@@ -188,9 +211,7 @@ pub fn AppPasswords() -> impl IntoView {
                         value=filter
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new("/account/app-passwords")
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &list_url().with_parameter("filter", value).finish(),
                                 Default::default(),
                             );
                         }
@@ -245,6 +266,33 @@ pub fn AppPasswords() -> impl IntoView {
 
                 </Toolbar>
 
+                <Filters slot>
+                    <Show when=move || has_usage.get()>
+                        <FilterSelect
+                            label="Usage"
+                            value=Signal::derive(move || {
+                                if unused.get() { "unused" } else { "" }.to_string()
+                            })
+
+                            options=USAGE_FILTERS.to_vec()
+                            on_change=move |value: String| {
+                                let mut url = list_url();
+                                url.params.remove("usage");
+                                use_navigate()(
+                                    &url
+                                        .with_optional_parameter(
+                                            "usage",
+                                            Some(value).filter(|v| !v.is_empty()),
+                                        )
+                                        .finish(),
+                                    Default::default(),
+                                );
+                            }
+                        />
+                    </Show>
+
+                </Filters>
+
                 <Transition fallback=Skeleton>
                     {move || match passwords.get() {
                         None => None,
@@ -260,10 +308,40 @@ pub fn AppPasswords() -> impl IntoView {
                         Some(Ok(passwords)) if !passwords.items.is_empty() => {
                             total_results.set(Some(passwords.total as u32));
                             let passwords_ = passwords.clone();
+                            let show_usage = has_usage.get_untracked();
                             Some(
                                 view! {
                                     <ColumnList
-                                        headers=vec!["Name".to_string(), "Created".to_string()]
+                                        headers=if show_usage {
+                                            vec![
+                                                "Name".to_string(),
+                                                "Last used".to_string(),
+                                                "Created".to_string(),
+                                            ]
+                                        } else {
+                                            vec!["Name".to_string(), "Created".to_string()]
+                                        }
+
+                                        sort=ColumnSort {
+                                            columns: if show_usage {
+                                                vec![
+                                                    ("Name", "name"),
+                                                    ("Last used", "last-used"),
+                                                    ("Created", "created"),
+                                                ]
+                                            } else {
+                                                vec![("Name", "name"), ("Created", "created")]
+                                            },
+                                            current: sort.into(),
+                                            on_sort: Callback::new(move |order: SortOrder| {
+                                                use_navigate()(
+                                                    &list_url()
+                                                        .with_parameter("sort", order.to_param())
+                                                        .finish(),
+                                                    Default::default(),
+                                                );
+                                            }),
+                                        }
 
                                         select_all=Callback::new(move |_| {
                                             passwords_
@@ -279,7 +357,7 @@ pub fn AppPasswords() -> impl IntoView {
                                             key=|password| password.name.clone()
                                             let:password
                                         >
-                                            <PasswordItem password/>
+                                            <PasswordItem password show_usage/>
                                         </For>
 
                                     </ColumnList>
@@ -319,10 +397,7 @@ pub fn AppPasswords() -> impl IntoView {
                         page_size=PAGE_SIZE
                         on_page_change=move |page: u32| {
                             use_navigate()(
-                                &UrlBuilder::new("/account/app-passwords")
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &list_url().with_parameter("page", page.to_string()).finish(),
                                 Default::default(),
                             );
                         }
@@ -335,7 +410,7 @@ pub fn AppPasswords() -> impl IntoView {
 }
 
 #[component]
-fn PasswordItem(password: AppPassword) -> impl IntoView {
+fn PasswordItem(password: AppPassword, show_usage: bool) -> impl IntoView {
     let password_id = password.id.clone();
 
     view! {
@@ -356,10 +431,41 @@ fn PasswordItem(password: AppPassword) -> impl IntoView {
                 </div>
             </ListItem>
 
+            {show_usage
+                .then(|| {
+                    let last_used = match &password.usage {
+                        Some(AppPasswordUsage { last_used: Some(last_used), .. }) => {
+                            last_used.format_relative()
+                        }
+                        Some(_) => "Never".to_string(),
+                        None => "Unknown".to_string(),
+                    };
+                    let usage = password.usage.unwrap_or_default();
+                    view! {
+                        <ListItem subclass="px-6 py-1.5">
+                            <div class="flex flex-col">
+                                <span class="text-sm text-gray-800 dark:text-gray-200">
+                                    {last_used}
+                                </span>
+                                <span
+                                    class="text-xs text-gray-500 truncate max-w-xs"
+                                    title=usage.user_agent.clone()
+                                >
+                                    {[usage.remote_ip, usage.user_agent]
+                                        .into_iter()
+                                        .flatten()
+                                        .collect::<Vec<_>>()
+                                        .join(" · ")}
+                                </span>
+                            </div>
+                        </ListItem>
+                    }
+                })}
+
             <ListItem subclass="px-6 py-1.5">
                 {password
                     .created
-                    .map(|created| HumanTime::from(created).to_string())
+                    .map(|created| created.format_relative())
                     .unwrap_or_default()}
             </ListItem>
 
@@ -369,7 +475,7 @@ fn PasswordItem(password: AppPassword) -> impl IntoView {
 
 #[component]
 pub fn AppPasswordCreate() -> impl IntoView {
-    let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
 
     let (pending, set_pending) = create_signal(false);
@@ -390,15 +496,14 @@ pub fn AppPasswordCreate() -> impl IntoView {
     let data = data.into_signal();
 
     let save_changes = create_action(move |(name, password): &(String, String)| {
-        let auth = auth.get();
         let name = name.clone();
         let password = password.clone();
 
         async move {
             set_pending.set(true);
 
-            let result = HttpRequest::post("/api/account/auth")
-                .with_authorization(&auth)
+            let result = api
+                .post("/api/account/auth")
                 .with_body(vec![AccountAuthRequest::AddAppPassword {
                     name: STANDARD.encode(format!("{}${}", name, Utc::now().to_rfc3339())),
                     password: sha512_crypt::hash(password).unwrap(),
@@ -428,14 +533,28 @@ pub fn AppPasswordCreate() -> impl IntoView {
                     <InputText element=FormElement::new("name", data)/>
                 </FormItem>
                 <FormItem label="Password">
-                    <InputText element=FormElement::new("password", data)/>
+                    <div class="flex items-center gap-x-2">
+                        <div class="grow">
+                            <InputPassword
+                                element=FormElement::new("password", data)
+                                revealed=true
+                                attr:id="app-password"
+                            />
+                        </div>
+                        <CopyButton
+                            value=Signal::derive(move || {
+                                data.get().value::<String>("password").unwrap_or_default()
+                            })
+                            target="app-password"
+                        />
+                    </div>
                 </FormItem>
 
             </FormSection>
 
             <FormButtonBar>
                 <Button
-                    text="Cancel"
+                    text=t_signal("button.cancel")
                     color=Color::Gray
                     on_click=move |_| {
                         use_navigate()("/account/app-passwords", Default::default());
@@ -443,7 +562,7 @@ pub fn AppPasswordCreate() -> impl IntoView {
                 />
 
                 <Button
-                    text="Create"
+                    text=t_signal("button.create")
                     color=Color::Blue
                     on_click=Callback::new(move |_| {
                         data.update(|data| {
@@ -479,3 +598,29 @@ impl Builder<Schemas, ()> {
             .build()
     }
 }
+
+impl AppPassword {
+    fn last_activity(&self) -> Option<DateTime<Utc>> {
+        self.last_used().or(self.created)
+    }
+
+    fn last_used(&self) -> Option<DateTime<Utc>> {
+        self.usage.as_ref().and_then(|usage| usage.last_used)
+    }
+
+    fn compare(&self, other: &AppPassword, order: &SortOrder) -> Ordering {
+        let result = match order.column.as_str() {
+            "name" => self.name.cmp(&other.name),
+            "last-used" => self.last_used().cmp(&other.last_used()),
+            "created" => self.created.cmp(&other.created),
+            _ => Ordering::Equal,
+        };
+
+        if order.ascending {
+            result
+        } else {
+            result.reverse()
+        }
+        .then_with(|| self.id.cmp(&other.id))
+    }
+}
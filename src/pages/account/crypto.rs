@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
+        clipboard::CopyButton,
         form::{
             button::Button,
             input::{InputPassword, InputText, TextArea},
@@ -23,8 +24,10 @@ use crate::{
         Color,
     },
     core::{
+        api::use_api,
         form::FormData,
         http::{self, Error, HttpRequest},
+        i18n::t_signal,
         oauth::use_authorization,
         schema::{Builder, Schemas, SelectType, Source, Transformer, Type, Validator},
     },
@@ -60,19 +63,15 @@ pub enum EncryptionMethod {
 #[component]
 pub fn ManageCrypto() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
     let show_totp = create_rw_signal(false);
     let fetch_crypto = create_resource(
         move || (),
-        move |_| {
-            let auth = auth.get_untracked();
-
-            async move {
-                HttpRequest::get("/api/account/crypto")
-                    .with_authorization(&auth)
-                    .send::<EncryptionType>()
-                    .await
-            }
+        move |_| async move {
+            api.get("/api/account/crypto")
+                .send::<EncryptionType>()
+                .await
         },
     );
 
@@ -147,10 +146,7 @@ pub fn ManageCrypto() -> impl IntoView {
                         use_navigate()("/login", Default::default());
                         Some(view! { <div></div> }.into_view())
                     }
-                    Some(Err(err)) => {
-                        alert.set(Alert::from(err));
-                        Some(view! { <div></div> }.into_view())
-                    }
+                    Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
                     Some(Ok(crypto)) => {
                         data.update(|data| {
                             data.from_encryption_params(&crypto);
@@ -192,7 +188,18 @@ pub fn ManageCrypto() -> impl IntoView {
                                             tooltip="The armored OpenPGP certificate or S/MIME certificate in PEM format."
                                             hide=has_no_crypto
                                         >
-                                            <TextArea element=FormElement::new("certs", data)/>
+                                            <TextArea
+                                                element=FormElement::new("certs", data)
+                                                attr:id="crypto-certs"
+                                            />
+                                            <div class="mt-1 flex justify-end">
+                                                <CopyButton
+                                                    value=Signal::derive(move || {
+                                                        data.get().value::<String>("certs").unwrap_or_default()
+                                                    })
+                                                    target="crypto-certs"
+                                                />
+                                            </div>
                                         </FormItem>
                                     </Show>
 
@@ -208,7 +215,7 @@ pub fn ManageCrypto() -> impl IntoView {
             <FormButtonBar>
 
                 <Button
-                    text="Save changes"
+                    text=t_signal("button.save-changes")
                     color=Color::Blue
                     on_click=Callback::new(move |_| {
                         data.update(|data| {
@@ -13,6 +13,7 @@ use web_time::SystemTime;
 
 use crate::{
     components::{
+        clipboard::CopyButton,
         form::{
             button::Button,
             input::{InputPassword, InputText},
@@ -23,18 +24,23 @@ use crate::{
         Color,
     },
     core::{
+        api::use_api,
+        download::download_file,
         http::{self, Error, HttpRequest},
+        i18n::t_signal,
         oauth::use_authorization,
         schema::{Builder, Schemas, Type, Validator},
     },
-    pages::account::{AccountAuthRequest, AccountAuthResponse},
+    pages::account::{AccountAuthRequest, AccountAuthResponse, AccountAuthResult},
 };
 
 #[component]
 pub fn ManageMfa() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
     let (pending, set_pending) = create_signal(false);
+    let recovery_codes = create_rw_signal(None::<Arc<Vec<String>>>);
 
     let data = expect_context::<Arc<Schemas>>()
         .build_form("mfa")
@@ -42,15 +48,10 @@ pub fn ManageMfa() -> impl IntoView {
 
     let fetch_auth = create_resource(
         move || (),
-        move |_| {
-            let auth = auth.get_untracked();
-
-            async move {
-                HttpRequest::get("/api/account/auth")
-                    .with_authorization(&auth)
-                    .send::<AccountAuthResponse>()
-                    .await
-            }
+        move |_| async move {
+            api.get("/api/account/auth")
+                .send::<AccountAuthResponse>()
+                .await
         },
     );
 
@@ -73,14 +74,21 @@ pub fn ManageMfa() -> impl IntoView {
                     .with_base_url(&auth)
                     .with_body(vec![request])
                     .unwrap()
-                    .send::<()>()
+                    .send::<Option<AccountAuthResult>>()
                     .await;
                 set_pending.set(false);
 
                 alert.set(match result {
-                    Ok(_) => Alert::success("2FA Settings Updated")
-                        .with_details("Your 2FA settings has been updated successfully")
-                        .without_timeout(),
+                    Ok(result) => {
+                        let codes = result.unwrap_or_default().recovery_codes;
+                        if !codes.is_empty() {
+                            recovery_codes.set(Some(Arc::new(codes)));
+                        }
+                        fetch_auth.refetch();
+                        Alert::success("2FA Settings Updated")
+                            .with_details("Your 2FA settings has been updated successfully")
+                            .without_timeout()
+                    }
                     Err(Error::Unauthorized) => Alert::warning("Incorrect password")
                         .with_details("The password you entered is incorrect"),
                     Err(err) => Alert::from(err),
@@ -94,163 +102,281 @@ pub fn ManageMfa() -> impl IntoView {
             title="Two-factor Authentication"
             subtitle="Manage your two-factor authentication settings"
         >
-            <Transition fallback=Skeleton set_pending>
+            {move || {
+                recovery_codes
+                    .get()
+                    .map(|codes| {
+                        view! {
+                            <RecoveryCodes
+                                codes
+                                on_dismiss=move |_| recovery_codes.set(None)
+                            />
+                        }
+                    })
+            }}
 
-                {move || match fetch_auth.get() {
-                    None => None,
-                    Some(Err(http::Error::Unauthorized)) => {
-                        use_navigate()("/login", Default::default());
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Err(err)) => {
-                        alert.set(Alert::from(err));
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Ok(response)) => {
-                        if !response.otp_auth {
-                            let totp = TOTP::new(
-                                    Algorithm::SHA1,
-                                    6,
-                                    1,
-                                    30,
-                                    Secret::default().to_bytes().unwrap(),
-                                    Some("Stalwart Mail".to_string()),
-                                    auth.get_untracked().username.to_string(),
-                                )
-                                .unwrap();
-                            let url = totp.get_url();
-                            let qr_code = qrcodegen_image::draw_base64(
-                                    &format!(
-                                        "{url}&image=https%3A%2F%2Fstalw.art%2Fimg%2Ffavicon-32x32.png",
-                                    ),
+            <div class:hidden=move || recovery_codes.with(Option::is_some)>
+                <Transition fallback=Skeleton set_pending>
+
+                    {move || match fetch_auth.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
+                        Some(Ok(response)) => {
+                            if !response.otp_auth {
+                                let totp = TOTP::new(
+                                        Algorithm::SHA1,
+                                        6,
+                                        1,
+                                        30,
+                                        Secret::default().to_bytes().unwrap(),
+                                        Some("Stalwart Mail".to_string()),
+                                        auth.get_untracked().username.to_string(),
+                                    )
+                                    .unwrap();
+                                let url = totp.get_url();
+                                let qr_code = qrcodegen_image::draw_base64(
+                                        &format!(
+                                            "{url}&image=https%3A%2F%2Fstalw.art%2Fimg%2Ffavicon-32x32.png",
+                                        ),
+                                    )
+                                    .unwrap();
+                                let secret = totp.get_secret_base32();
+                                let totp = Arc::new(totp);
+                                Some(
+                                    view! {
+                                        <div class="flex flex-col items-center pb-[30px]">
+                                            <img
+                                                src=format!("data:image/png;base64,{qr_code}")
+                                                alt="QR Code"
+                                                class="w-64 h-auto"
+                                            />
+                                            <div class="flex items-center gap-x-1">
+                                                <p class="text-xs font-mono" id="totp-secret">
+                                                    {secret.clone()}
+                                                </p>
+                                                <CopyButton value=secret target="totp-secret"/>
+                                            </div>
+                                        </div>
+
+                                        <FormSection>
+                                            <FormItem
+                                                label="Password"
+                                                tooltip="Enter your current password to enable 2FA."
+                                            >
+                                                <InputPassword element=FormElement::new("password", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                label="OTP Code"
+                                                tooltip="Enter a code generated by your authenticator app to enable 2FA."
+                                            >
+                                                <InputText element=FormElement::new("otp-code", data)/>
+                                            </FormItem>
+                                        </FormSection>
+
+                                        <FormButtonBar>
+
+                                            <Button
+                                                text="Enable 2FA"
+                                                color=Color::Blue
+                                                on_click=Callback::new(move |_| {
+                                                    let totp = totp.clone();
+                                                    data.update(|data| {
+                                                        if data.validate_form() {
+                                                            let password = data.value::<String>("password").unwrap();
+                                                            let otp_code = data.value::<String>("otp-code").unwrap();
+                                                            if totp
+                                                                .check(
+                                                                    otp_code.as_str(),
+                                                                    SystemTime::now()
+                                                                        .duration_since(SystemTime::UNIX_EPOCH)
+                                                                        .map_or(0, |t| t.as_secs()),
+                                                                )
+                                                            {
+                                                                update_otp
+                                                                    .dispatch((
+                                                                        password,
+                                                                        None,
+                                                                        AccountAuthRequest::EnableOtpAuth {
+                                                                            url: url.clone(),
+                                                                        },
+                                                                    ));
+                                                            } else {
+                                                                alert
+                                                                    .set(
+                                                                        Alert::warning("Invalid OTP code")
+                                                                            .with_details("The OTP code you entered is invalid"),
+                                                                    );
+                                                            }
+                                                        }
+                                                    });
+                                                })
+
+                                                disabled=pending
+                                            />
+                                        </FormButtonBar>
+                                    }
+                                        .into_view(),
                                 )
-                                .unwrap();
-                            let secret = totp.get_secret_base32();
-                            let totp = Arc::new(totp);
-                            Some(
-                                view! {
-                                    <div class="flex flex-col items-center pb-[30px]">
-                                        <img
-                                            src=format!("data:image/png;base64,{qr_code}")
-                                            alt="QR Code"
-                                            class="w-64 h-auto"
-                                        />
-                                        <p class="text-xs">{secret}</p>
-                                    </div>
-
-                                    <FormSection>
-                                        <FormItem
-                                            label="Password"
-                                            tooltip="Enter your current password to enable 2FA."
-                                        >
-                                            <InputPassword element=FormElement::new("password", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            label="OTP Code"
-                                            tooltip="Enter a code generated by your authenticator app to enable 2FA."
-                                        >
-                                            <InputText element=FormElement::new("otp-code", data)/>
-                                        </FormItem>
-                                    </FormSection>
-
-                                    <FormButtonBar>
-
-                                        <Button
-                                            text="Enable 2FA"
-                                            color=Color::Blue
-                                            on_click=Callback::new(move |_| {
-                                                let totp = totp.clone();
-                                                data.update(|data| {
-                                                    if data.validate_form() {
-                                                        let password = data.value::<String>("password").unwrap();
-                                                        let otp_code = data.value::<String>("otp-code").unwrap();
-                                                        if totp
-                                                            .check(
-                                                                otp_code.as_str(),
-                                                                SystemTime::now()
-                                                                    .duration_since(SystemTime::UNIX_EPOCH)
-                                                                    .map_or(0, |t| t.as_secs()),
-                                                            )
-                                                        {
+                            } else {
+                                Some(
+                                    view! {
+                                        <FormSection>
+                                            <FormItem
+                                                label="Password"
+                                                tooltip="Enter your current password to disable 2FA."
+                                            >
+                                                <InputPassword element=FormElement::new("password", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                label="OTP Code"
+                                                tooltip="Enter a code generated by your authenticator app to disable 2FA."
+                                            >
+                                                <InputText element=FormElement::new("otp-code", data)/>
+                                            </FormItem>
+                                        </FormSection>
+
+                                        <FormButtonBar>
+
+                                            <Button
+                                                text="Regenerate recovery codes"
+                                                color=Color::Gray
+                                                on_click=Callback::new(move |_| {
+                                                    data.update(|data| {
+                                                        if data.validate_form() {
                                                             update_otp
                                                                 .dispatch((
-                                                                    password,
-                                                                    None,
-                                                                    AccountAuthRequest::EnableOtpAuth {
-                                                                        url: url.clone(),
+                                                                    data.value::<String>("password").unwrap(),
+                                                                    data.value::<String>("otp-code"),
+                                                                    AccountAuthRequest::RegenerateRecoveryCodes,
+                                                                ));
+                                                        }
+                                                    });
+                                                })
+
+                                                disabled=pending
+                                            />
+
+                                            <Button
+                                                text="Disable 2FA"
+                                                color=Color::Red
+                                                on_click=Callback::new(move |_| {
+                                                    data.update(|data| {
+                                                        if data.validate_form() {
+                                                            update_otp
+                                                                .dispatch((
+                                                                    data.value::<String>("password").unwrap(),
+                                                                    data.value::<String>("otp-code"),
+                                                                    AccountAuthRequest::DisableOtpAuth {
+                                                                        url: None,
                                                                     },
                                                                 ));
-                                                        } else {
-                                                            alert
-                                                                .set(
-                                                                    Alert::warning("Invalid OTP code")
-                                                                        .with_details("The OTP code you entered is invalid"),
-                                                                );
                                                         }
-                                                    }
-                                                });
-                                            })
-
-                                            disabled=pending
-                                        />
-                                    </FormButtonBar>
-                                }
-                                    .into_view(),
-                            )
-                        } else {
-                            Some(
-                                view! {
-                                    <FormSection>
-                                        <FormItem
-                                            label="Password"
-                                            tooltip="Enter your current password to disable 2FA."
-                                        >
-                                            <InputPassword element=FormElement::new("password", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            label="OTP Code"
-                                            tooltip="Enter a code generated by your authenticator app to disable 2FA."
-                                        >
-                                            <InputText element=FormElement::new("otp-code", data)/>
-                                        </FormItem>
-                                    </FormSection>
-
-                                    <FormButtonBar>
-
-                                        <Button
-                                            text="Disable 2FA"
-                                            color=Color::Red
-                                            on_click=Callback::new(move |_| {
-                                                data.update(|data| {
-                                                    if data.validate_form() {
-                                                        update_otp
-                                                            .dispatch((
-                                                                data.value::<String>("password").unwrap(),
-                                                                data.value::<String>("otp-code"),
-                                                                AccountAuthRequest::DisableOtpAuth {
-                                                                    url: None,
-                                                                },
-                                                            ));
-                                                    }
-                                                });
-                                            })
-
-                                            disabled=pending
-                                        />
-                                    </FormButtonBar>
-                                }
-                                    .into_view(),
-                            )
+                                                    });
+                                                })
+
+                                                disabled=pending
+                                            />
+                                        </FormButtonBar>
+                                    }
+                                        .into_view(),
+                                )
+                            }
                         }
-                    }
-                }}
+                    }}
 
-            </Transition>
+                </Transition>
+            </div>
 
         </Form>
     }
 }
 
+// The server only sends recovery codes right after generating them
+#[component]
+fn RecoveryCodes(codes: Arc<Vec<String>>, #[prop(into)] on_dismiss: Callback<()>) -> impl IntoView {
+    let revealed = create_rw_signal(false);
+    let saved = create_rw_signal(false);
+    let contents = codes.join("\n");
+    let contents_download = contents.clone();
+
+    view! {
+        <div class="mb-8 p-4 bg-yellow-50 border border-yellow-200 rounded-xl dark:bg-yellow-800/10 dark:border-yellow-900">
+            <h3 class="font-semibold text-gray-800 dark:text-white">Recovery codes</h3>
+            <p class="mt-1 text-sm text-gray-600 dark:text-gray-400">
+                Each code can be used once to sign in if you lose access to your authenticator app.
+                Store them somewhere safe, they will not be shown again.
+            </p>
+
+            <div
+                id="recovery-codes"
+                class="mt-4 grid grid-cols-2 gap-2 font-mono text-sm text-gray-800 dark:text-gray-200 select-all"
+                class:blur-sm=move || !revealed.get()
+                aria-hidden=move || (!revealed.get()).then_some("true")
+            >
+                {codes
+                    .iter()
+                    .map(|code| view! { <span>{code.clone()}</span> })
+                    .collect_view()}
+            </div>
+
+            <div class="mt-4 flex flex-wrap items-center gap-2">
+                <Show
+                    when=move || revealed.get()
+                    fallback=move || {
+                        view! {
+                            <Button
+                                text="Reveal codes"
+                                color=Color::Gray
+                                on_click=move |_| revealed.set(true)
+                            />
+                        }
+                    }
+                >
+
+                    <CopyButton value=contents.clone() target="recovery-codes"/>
+                </Show>
+                <Button
+                    text=t_signal("button.download")
+                    color=Color::Gray
+                    on_click=move |_| {
+                        if let Err(err) = download_file(
+                            "recovery-codes.txt",
+                            "text/plain",
+                            &format!("{contents_download}\n"),
+                        ) {
+                            log::warn!("Failed to download recovery codes: {err:?}");
+                        }
+                    }
+                />
+
+            </div>
+
+            <label class="mt-4 flex items-center gap-x-2 text-sm text-gray-800 dark:text-gray-200">
+                <input
+                    type="checkbox"
+                    class="shrink-0 border-gray-300 rounded text-blue-600 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-600 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                    prop:checked=saved
+                    on:change=move |ev| saved.set(event_target_checked(&ev))
+                />
+                "I've saved these codes"
+            </label>
+
+            <div class="mt-4 flex justify-end">
+                <Button
+                    text=t_signal("button.done")
+                    color=Color::Blue
+                    on_click=move |_| on_dismiss.call(())
+                    disabled=Signal::derive(move || !saved.get())
+                />
+            </div>
+        </div>
+    }
+}
+
 impl Builder<Schemas, ()> {
     pub fn build_mfa(self) -> Self {
         self.new_schema("mfa")
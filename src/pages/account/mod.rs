@@ -4,12 +4,16 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod activity;
 pub mod app_password;
 pub mod crypto;
 pub mod mfa;
 pub mod password;
+pub mod preferences;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
@@ -20,6 +24,7 @@ pub enum AccountAuthRequest {
     DisableOtpAuth { url: Option<String> },
     AddAppPassword { name: String, password: String },
     RemoveAppPassword { name: String },
+    RegenerateRecoveryCodes,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -28,4 +33,69 @@ pub struct AccountAuthResponse {
     pub otp_auth: bool,
     #[serde(rename = "appPasswords")]
     pub app_passwords: Vec<String>,
+    #[serde(rename = "appPasswordUsage", default)]
+    pub app_password_usage: AHashMap<String, AppPasswordUsage>,
+    #[serde(rename = "passwordPolicy", default)]
+    pub password_policy: PasswordPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_number: bool,
+    pub require_symbol: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct AppPasswordUsage {
+    #[serde(rename = "lastUsed", default)]
+    pub last_used: Option<DateTime<Utc>>,
+    #[serde(rename = "remoteIp", default)]
+    pub remote_ip: Option<String>,
+    #[serde(rename = "userAgent", default)]
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AccountAuthResult {
+    #[serde(rename = "recoveryCodes", default)]
+    pub recovery_codes: Vec<String>,
+}
+
+impl PasswordPolicy {
+    pub fn check(&self, password: &str) -> Vec<(String, bool)> {
+        let mut result = Vec::new();
+        if self.min_length > 0 {
+            result.push((
+                format!("At least {} characters", self.min_length),
+                password.chars().count() >= self.min_length,
+            ));
+        }
+        for (required, description, matches) in [
+            (
+                self.require_uppercase,
+                "An uppercase letter",
+                char::is_uppercase as fn(char) -> bool,
+            ),
+            (
+                self.require_lowercase,
+                "A lowercase letter",
+                char::is_lowercase,
+            ),
+            (self.require_number, "A number", |ch: char| {
+                ch.is_ascii_digit()
+            }),
+            (self.require_symbol, "A symbol", |ch: char| {
+                !ch.is_alphanumeric() && !ch.is_whitespace()
+            }),
+        ] {
+            if required {
+                result.push((description.to_string(), password.chars().any(matches)));
+            }
+        }
+        result
+    }
 }
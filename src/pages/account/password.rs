@@ -4,9 +4,10 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use leptos::*;
+use leptos_router::use_navigate;
 use pwhash::sha512_crypt;
 
 use crate::{
@@ -16,20 +17,23 @@ use crate::{
             input::{InputPassword, InputText},
             Form, FormButtonBar, FormElement, FormItem, FormSection,
         },
+        icon::{IconCheck, IconXMark},
         messages::alert::{use_alerts, Alert},
         Color,
     },
     core::{
+        api::use_api,
         http::{Error, HttpRequest},
         oauth::use_authorization,
         schema::{Builder, Schemas, Transformer, Type, Validator},
     },
-    pages::account::AccountAuthRequest,
+    pages::account::{AccountAuthRequest, AccountAuthResponse, PasswordPolicy},
 };
 
 #[component]
 pub fn ChangePassword() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
     let (pending, set_pending) = create_signal(false);
 
@@ -37,6 +41,30 @@ pub fn ChangePassword() -> impl IntoView {
         .build_form("change-pass")
         .into_signal();
     let show_totp = create_rw_signal(false);
+    let new_password = create_rw_signal(String::new());
+
+    let policy = create_resource(
+        || (),
+        move |_| async move {
+            match api
+                .get("/api/account/auth")
+                .send::<AccountAuthResponse>()
+                .await
+            {
+                Ok(response) => response.password_policy,
+                Err(Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                    PasswordPolicy::default()
+                }
+                Err(_) => PasswordPolicy::default(),
+            }
+        },
+    );
+    let requirements = create_memo(move |_| {
+        let policy = policy.get().unwrap_or_default();
+        new_password.with(|password| policy.check(password))
+    });
+    let policy_met = create_memo(move |_| requirements.get().iter().all(|(_, met)| *met));
 
     let change_password = create_action(move |(old_password, new_password): &(String, String)| {
         let old_password = old_password.clone();
@@ -90,7 +118,33 @@ pub fn ChangePassword() -> impl IntoView {
                         <InputPassword element=FormElement::new("old-password", data)/>
                     </FormItem>
                     <FormItem label="New Password">
-                        <InputPassword element=FormElement::new("new-password", data)/>
+                        <InputPassword
+                            element=FormElement::new("new-password", data)
+                            on_input=move |value| new_password.set(value)
+                        />
+                        <StrengthMeter password=new_password/>
+                        <ul class="mt-3 space-y-1">
+                            <For
+                                each=move || requirements.get()
+                                key=|requirement| requirement.clone()
+                                let:requirement
+                            >
+                                <li
+                                    class="flex items-center gap-x-2 text-sm"
+                                    class=("text-teal-600", requirement.1)
+                                    class=("dark:text-teal-500", requirement.1)
+                                    class=("text-gray-500", !requirement.1)
+                                    class=("dark:text-gray-400", !requirement.1)
+                                >
+                                    {if requirement.1 {
+                                        view! { <IconCheck attr:class="flex-shrink-0 size-4"/> }
+                                    } else {
+                                        view! { <IconXMark attr:class="flex-shrink-0 size-4"/> }
+                                    }}
+                                    <span>{requirement.0}</span>
+                                </li>
+                            </For>
+                        </ul>
                     </FormItem>
                 </Show>
 
@@ -109,7 +163,18 @@ pub fn ChangePassword() -> impl IntoView {
                     color=Color::Blue
                     on_click=Callback::new(move |_| {
                         data.update(|data| {
-                            if data.validate_form() {
+                            let unmet = requirements
+                                .get()
+                                .into_iter()
+                                .filter(|(_, met)| !met)
+                                .map(|(requirement, _)| requirement.to_lowercase())
+                                .collect::<Vec<_>>();
+                            if !unmet.is_empty() {
+                                data.new_error(
+                                    "new-password",
+                                    format!("Password must contain {}", unmet.join(", ")),
+                                );
+                            } else if data.validate_form() {
                                 change_password
                                     .dispatch((
                                         match (
@@ -128,7 +193,9 @@ pub fn ChangePassword() -> impl IntoView {
                         });
                     })
 
-                    disabled=pending
+                    disabled=Signal::derive(move || {
+                        pending.get() || (!show_totp.get() && !policy_met.get())
+                    })
                 />
             </FormButtonBar>
 
@@ -150,3 +217,76 @@ impl Builder<Schemas, ()> {
             .build()
     }
 }
+
+const STRENGTH_LEVELS: [(&str, &str); 5] = [
+    ("Very weak", "bg-red-500"),
+    ("Weak", "bg-orange-500"),
+    ("Fair", "bg-yellow-400"),
+    ("Strong", "bg-teal-500"),
+    ("Very strong", "bg-teal-600"),
+];
+
+#[component]
+fn StrengthMeter(#[prop(into)] password: Signal<String>) -> impl IntoView {
+    let level = create_memo(move |_| {
+        password.with(|password| {
+            (!password.is_empty()).then(|| match estimate_entropy(password) {
+                bits if bits < 28.0 => 0,
+                bits if bits < 36.0 => 1,
+                bits if bits < 60.0 => 2,
+                bits if bits < 80.0 => 3,
+                _ => 4,
+            })
+        })
+    });
+
+    view! {
+        <div class="mt-2" class:hidden=move || level.get().is_none()>
+            <div class="flex gap-x-1">
+                {(0..STRENGTH_LEVELS.len())
+                    .map(|idx| {
+                        view! {
+                            <div class=move || {
+                                let color = match level.get() {
+                                    Some(level) if idx <= level => STRENGTH_LEVELS[level].1,
+                                    _ => "bg-gray-200 dark:bg-gray-700",
+                                };
+                                format!("h-1.5 flex-auto rounded-full {color}")
+                            }></div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <p class="mt-1 text-xs text-gray-500 dark:text-gray-400" aria-live="polite">
+                {move || level.get().map(|level| STRENGTH_LEVELS[level].0)}
+            </p>
+        </div>
+    }
+}
+
+// Repeated characters only count for half as they add little guessing work
+fn estimate_entropy(password: &str) -> f64 {
+    let mut pool = 0;
+    if password.chars().any(|ch| ch.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|ch| ch.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|ch| ch.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password
+        .chars()
+        .any(|ch| ch.is_ascii_punctuation() || ch == ' ')
+    {
+        pool += 33;
+    }
+    if !password.is_ascii() {
+        pool += 100;
+    }
+
+    let length = password.chars().count() as f64;
+    let unique = password.chars().collect::<HashSet<_>>().len() as f64;
+    (unique + (length - unique) / 2.0) * f64::from(pool.max(1)).log2()
+}
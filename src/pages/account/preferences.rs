@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{TimeDelta, Utc};
+use leptos::*;
+
+use crate::{
+    components::form::{Form, FormItem, FormSection},
+    core::{
+        datetime::{browser_timezone, timezones, use_datetime},
+        i18n::t,
+        theme::{use_theme, Theme, THEMES},
+    },
+    pages::{
+        directory::dns::{dns_resolver, set_dns_resolver},
+        FormatDateTime,
+    },
+};
+
+const SELECT_CLASS: &str = concat!(
+    "py-2 px-3 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 ",
+    "focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 ",
+    "dark:focus:ring-gray-600"
+);
+
+const INPUT_CLASS: &str = concat!(
+    "py-2 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 ",
+    "focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 ",
+    "dark:focus:ring-gray-600"
+);
+
+#[component]
+pub fn Preferences() -> impl IntoView {
+    let prefs = use_datetime();
+    let theme = use_theme().theme;
+    let timezones = timezones();
+    let browser_timezone = browser_timezone();
+    let sample = Utc::now() - TimeDelta::minutes(5);
+
+    view! {
+        <Form
+            title=Signal::derive(move || t("preferences.title"))
+            subtitle=Signal::derive(move || t("preferences.subtitle"))
+        >
+            <FormSection>
+                <FormItem label=Signal::derive(move || t("preferences.theme"))>
+                    <select
+                        class=SELECT_CLASS
+                        on:change=move |ev| {
+                            if let Some(value) = Theme::parse(&event_target_value(&ev)) {
+                                theme.set(value);
+                            }
+                        }
+                    >
+
+                        {THEMES
+                            .iter()
+                            .map(|value| {
+                                let value = *value;
+                                view! {
+                                    <option value=value.id() selected=move || theme.get() == value>
+                                        {move || t(&format!("preferences.theme.{}", value.id()))}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </FormItem>
+                <FormItem label=Signal::derive(move || t("preferences.timezone"))>
+                    <select
+                        class=SELECT_CLASS
+                        on:change=move |ev| {
+                            let timezone = event_target_value(&ev);
+                            prefs.timezone.set((!timezone.is_empty()).then_some(timezone));
+                        }
+                    >
+
+                        <option value="" selected=move || prefs.timezone.get().is_none()>
+                            {move || {
+                                format!("{} ({browser_timezone})", t("preferences.browser-timezone"))
+                            }}
+                        </option>
+                        {timezones
+                            .into_iter()
+                            .map(|timezone| {
+                                let timezone_ = timezone.clone();
+                                view! {
+                                    <option
+                                        value=timezone.clone()
+                                        selected=move || {
+                                            prefs.timezone.get().as_deref() == Some(timezone_.as_str())
+                                        }
+                                    >
+                                        {timezone}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </FormItem>
+                <FormItem label=Signal::derive(move || t("preferences.date-display"))>
+                    <select
+                        class=SELECT_CLASS
+                        on:change=move |ev| {
+                            prefs.relative.set(event_target_value(&ev) == "relative");
+                        }
+                    >
+
+                        <option value="absolute" selected=move || !prefs.relative.get()>
+                            {move || sample.format_date_time()}
+                        </option>
+                        <option value="relative" selected=move || prefs.relative.get()>
+                            {move || sample.format_relative()}
+                        </option>
+                    </select>
+                </FormItem>
+                <FormItem
+                    label=Signal::derive(move || t("preferences.dns-resolver"))
+                    tooltip="DNS-over-HTTPS endpoint used to check domain records, e.g. https://cloudflare-dns.com/dns-query. Lookups are sent from this browser; leave empty to skip them."
+                    is_optional=true
+                >
+                    <input
+                        type="url"
+                        class=INPUT_CLASS
+                        placeholder="https://"
+                        prop:value=dns_resolver().unwrap_or_default()
+                        on:change=move |ev| set_dns_resolver(&event_target_value(&ev))
+                    />
+                </FormItem>
+            </FormSection>
+        </Form>
+    }
+}
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use leptos::*;
 use leptos_meta::*;
-use leptos_router::{use_params_map, use_query_map};
+use leptos_router::{use_navigate, use_params_map, use_query_map};
 
 use crate::{
     components::{
@@ -20,11 +20,13 @@ use crate::{
     },
     core::{
         oauth::{
-            oauth_device_authentication, oauth_user_authentication, AuthenticationResult,
-            OAuthCodeRequest,
+            oauth_device_authentication, oauth_user_authentication, use_authorization,
+            AuthenticationResult, OAuthCodeRequest,
         },
         schema::{Builder, Schemas, Transformer, Type, Validator},
+        sso::{complete_sso_login, SsoCallback},
     },
+    pages::directory::setup::landing_page,
 };
 
 const BASE_URL: &str = "";
@@ -34,6 +36,9 @@ pub fn Authorize() -> impl IntoView {
     let alert = use_alerts();
     let query = use_query_map();
     let params = use_params_map();
+    if params.get_untracked().get("type").is_some_and(|t| t == "sso") {
+        return view! { <SsoSignIn/> }.into_view();
+    }
     let is_device_auth =
         create_memo(move |_| params.get().get("type").map_or(true, |t| t != "code"));
     let redirect_uri = create_memo(move |_| query.get().get("redirect_uri").cloned());
@@ -257,6 +262,69 @@ pub fn Authorize() -> impl IntoView {
             </div>
         </main>
     }
+    .into_view()
+}
+
+#[component]
+fn SsoSignIn() -> impl IntoView {
+    let auth = use_authorization();
+    let alert = use_alerts();
+    let query = use_query_map().get_untracked();
+    let failed = create_rw_signal(false);
+    let callback = SsoCallback {
+        code: query.get("code").cloned(),
+        state: query.get("state").cloned(),
+        error: query.get("error").cloned(),
+        error_description: query.get("error_description").cloned(),
+    };
+
+    spawn_local(async move {
+        match complete_sso_login(auth, callback).await {
+            Ok(default_url) => {
+                let redirect = landing_page(auth, default_url).await;
+                use_navigate()(&redirect, Default::default());
+            }
+            Err(err) => {
+                alert.set(err.without_timeout());
+                failed.set(true);
+            }
+        }
+    });
+
+    view! {
+        <Body class="dark:bg-slate-900 bg-gray-100 flex h-full items-center py-16"/>
+        <main class="w-full max-w-md mx-auto p-6">
+            <div class="mt-7 bg-white border border-gray-200 rounded-xl shadow-sm dark:bg-gray-800 dark:border-gray-700">
+                <div class="p-4 sm:p-7">
+                    <div class="text-center p-6">
+                        <img src="/logo.svg"/>
+                    </div>
+
+                    <div class="mt-5">
+                        <Alerts/>
+                        <Show
+                            when=move || failed.get()
+                            fallback=|| {
+                                view! {
+                                    <p class="text-center text-sm text-gray-600 dark:text-gray-400">
+                                        Signing you in...
+                                    </p>
+                                }
+                            }
+                        >
+
+                            <a
+                                class="w-full py-3 px-4 inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                                href="/login"
+                            >
+                                Back to sign in
+                            </a>
+                        </Show>
+                    </div>
+                </div>
+            </div>
+        </main>
+    }
 }
 
 impl Builder<Schemas, ()> {
@@ -281,5 +349,15 @@ impl Builder<Schemas, ()> {
             .input_check([Transformer::Trim], [])
             .build()
             .build()
+            .new_schema("sso")
+            .new_field("issuer")
+            .typ(Type::Input)
+            .input_check([Transformer::Trim], [Validator::Required, Validator::IsUrl])
+            .build()
+            .new_field("client-id")
+            .typ(Type::Input)
+            .input_check([Transformer::Trim], [Validator::Required])
+            .build()
+            .build()
     }
 }
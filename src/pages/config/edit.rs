@@ -20,33 +20,41 @@ use crate::{
                 InputDuration, InputPassword, InputRate, InputSize, InputSwitch, InputText,
                 TextArea,
             },
-            select::{CheckboxGroup, Select, SelectCron},
+            select::{CheckboxGroup, RadioGroup, Select, SelectCron},
             stacked_badge::StackedBadge,
             stacked_input::StackedInput,
-            Form, FormButtonBar, FormElement, FormItem, FormSection,
+            unsaved::use_unsaved_changes,
+            use_read_only_form, Form, FormButtonBar, FormElement, FormItem, FormSection,
         },
         icon::IconRefresh,
         messages::{
             alert::{use_alerts, Alert},
+            conflict::{resolve_conflict, ConflictResolution},
             modal::{use_modals, Modal},
         },
         skeleton::Skeleton,
         Color,
     },
     core::{
+        api::{use_api, ApiClient},
         form::{ExternalSources, FormData},
-        http::{self, HttpRequest},
+        http,
+        i18n::t_signal,
         oauth::use_authorization,
         schema::SelectType,
+        shortcuts::use_save_shortcut,
     },
     pages::{
-        config::{ReloadSettings, Schema, SchemaType, Schemas, Settings, Type, UpdateSettings},
+        config::{
+            use_recent_settings, ReloadSettings, Schema, SchemaType, Schemas, SettingChange,
+            Settings, Type, UpdateSettings,
+        },
         List,
     },
 };
 
 #[derive(Clone, Serialize, Deserialize, Default)]
-struct FetchSettings {
+pub struct FetchSettings {
     pub items: Settings,
     pub total: u64,
 }
@@ -63,14 +71,22 @@ enum FetchResult {
     NotFound,
 }
 
+#[derive(Clone)]
+struct ReviewChanges {
+    changes: Arc<Vec<SettingChange>>,
+    reload: bool,
+}
+
 pub const DEFAULT_SETTINGS_URL: &str = "/settings/network/edit";
 
 #[component]
 pub fn SettingsEdit() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
     let params = use_params_map();
     let modal = use_modals();
+    let read_only = use_read_only_form();
 
     let schemas = expect_context::<Arc<Schemas>>();
     let current_schema = create_memo(move |_| {
@@ -89,7 +105,6 @@ pub fn SettingsEdit() -> impl IntoView {
     let fetch_settings = create_resource(
         move || params.get().get("id").cloned().unwrap_or_default(),
         move |name| {
-            let auth = auth.get_untracked();
             let current_schema = current_schema.get();
             let is_create = name.is_empty();
 
@@ -100,8 +115,8 @@ pub fn SettingsEdit() -> impl IntoView {
                     let schema = schema.unwrap_or_else(|| current_schema.clone());
                     let source_key = format!("{}_{}", schema.id, field.id);
                     if !external_sources.contains_key(&source_key) {
-                        let items = HttpRequest::get("/api/settings/group")
-                            .with_authorization(&auth)
+                        let items = api
+                            .get("/api/settings/group")
                             .with_parameter("prefix", schema.unwrap_prefix())
                             .with_parameter(
                                 "suffix",
@@ -129,106 +144,83 @@ pub fn SettingsEdit() -> impl IntoView {
                 }
 
                 // Fetch settings
-                match current_schema.typ {
-                    SchemaType::Record { prefix, .. } => {
-                        if !is_create {
-                            HttpRequest::get("/api/settings/list")
-                                .with_authorization(&auth)
-                                .with_parameter("prefix", format!("{prefix}.{name}"))
-                                .send::<FetchSettings>()
-                                .await
-                                .map(|mut list| {
-                                    if !list.items.is_empty() {
-                                        list.items.insert("_id".to_string(), name.to_string());
-                                        FetchResult::Update {
-                                            settings: list.items,
-                                            external_sources,
-                                        }
-                                    } else {
-                                        FetchResult::NotFound
-                                    }
-                                })
-                        } else {
-                            Ok(FetchResult::Create { external_sources })
+                Ok(
+                    match fetch_stored_settings(api, &current_schema, &name).await? {
+                        Some(settings) => FetchResult::Update {
+                            settings,
+                            external_sources,
+                        },
+                        None if is_create || matches!(current_schema.typ, SchemaType::List) => {
+                            FetchResult::Create { external_sources }
                         }
-                    }
-                    SchemaType::Entry { prefix } => {
-                        if !is_create {
-                            HttpRequest::get("/api/settings/keys")
-                                .with_authorization(&auth)
-                                .with_parameter("keys", format!("{prefix}.{name}"))
-                                .send::<AHashMap<String, Option<String>>>()
-                                .await
-                                .map(|list| {
-                                    if let Some(value) = list.into_values().next().flatten() {
-                                        let mut settings = Settings::new();
-                                        settings.insert("_id".to_string(), name.to_string());
-                                        settings.insert("_value".to_string(), value);
-                                        FetchResult::Update {
-                                            settings,
-                                            external_sources,
-                                        }
-                                    } else {
-                                        FetchResult::NotFound
-                                    }
-                                })
-                        } else {
-                            Ok(FetchResult::Create { external_sources })
-                        }
-                    }
-                    SchemaType::List => {
-                        let mut keys = Vec::new();
-                        let mut prefixes = Vec::new();
-
-                        for field in current_schema.fields.values() {
-                            if field.is_multivalue() {
-                                prefixes.push(field.id);
-                                keys.push(field.id);
-                            } else {
-                                keys.push(field.id);
-                            }
-                        }
-
-                        HttpRequest::get("/api/settings/keys")
-                            .with_authorization(&auth)
-                            .with_parameter("keys", keys.join(","))
-                            .with_parameter("prefixes", prefixes.join(","))
-                            .send::<Settings>()
-                            .await
-                            .map(|mut list| {
-                                let mut settings = Settings::new();
-                                for (name, value) in list.drain() {
-                                    settings.insert(name, value);
-                                }
-
-                                if !settings.is_empty() {
-                                    FetchResult::Update {
-                                        settings,
-                                        external_sources,
-                                    }
-                                } else {
-                                    FetchResult::Create { external_sources }
-                                }
-                            })
-                    }
-                }
+                        None => FetchResult::NotFound,
+                    },
+                )
             }
         },
     );
     let (pending, set_pending) = create_signal(false);
     let data = FormData::default().into_signal();
+    let unsaved = use_unsaved_changes(data);
+    let review = create_rw_signal(None::<ReviewChanges>);
+    let raw_mode = create_rw_signal(false);
+    let raw_text = create_rw_signal(String::new());
+    let loaded_settings = store_value(None::<Settings>);
 
     let save_changes = create_action(
         move |(changes, reload): &(Arc<Vec<UpdateSettings>>, bool)| {
             let changes = changes.clone();
             let reload = *reload;
-            let auth = auth.get();
             let schema = current_schema.get();
+            let name = params
+                .get_untracked()
+                .get("id")
+                .cloned()
+                .unwrap_or_default();
+            let loaded = loaded_settings.get_value();
 
             async move {
                 set_pending.set(true);
-                match HttpRequest::post("/api/settings")
-                    .with_authorization(&auth)
+
+                // Check that nobody else modified these settings in the meantime
+                if let Some(loaded) = loaded {
+                    let conflicts = match fetch_stored_settings(api, &schema, &name).await {
+                        Ok(latest) => data.with_untracked(|data| {
+                            data.conflicts(&loaded, &latest.unwrap_or_default())
+                        }),
+                        Err(http::Error::Unauthorized) => {
+                            use_navigate()("/login", Default::default());
+                            return;
+                        }
+                        Err(err) => {
+                            set_pending.set(false);
+                            alert.set(Alert::from(err));
+                            return;
+                        }
+                    };
+                    if !conflicts.is_empty() {
+                        let subject = if !name.is_empty() {
+                            format!("'{name}'")
+                        } else {
+                            "These settings".to_string()
+                        };
+                        match resolve_conflict(modal, subject, conflicts).await {
+                            ConflictResolution::Overwrite => {}
+                            ConflictResolution::Reload => {
+                                set_pending.set(false);
+                                fetch_settings.refetch();
+                                return;
+                            }
+                            ConflictResolution::Cancel => {
+                                set_pending.set(false);
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                match api
+                    .post("/api/settings")
                     .with_body(changes)
                     .unwrap()
                     .send::<Option<String>>()
@@ -236,20 +228,32 @@ pub fn SettingsEdit() -> impl IntoView {
                     .map(|_| ())
                 {
                     Ok(_) => {
+                        // What was just saved is the version later saves are checked against
+                        if let Ok(latest) = fetch_stored_settings(api, &schema, &name).await {
+                            loaded_settings.try_set_value(latest);
+                        }
                         if reload {
-                            match HttpRequest::get(format!(
-                                "/api/reload/{}",
-                                schema.reload_prefix.unwrap_or_default()
-                            ))
-                            .with_authorization(&auth)
-                            .send::<ReloadSettings>()
-                            .await
+                            match api
+                                .get(format!(
+                                    "/api/reload/{}",
+                                    schema.reload_prefix.unwrap_or_default()
+                                ))
+                                .send::<ReloadSettings>()
+                                .await
                             {
-                                Ok(result) => {
+                                Ok(mut result) => {
                                     set_pending.set(false);
-                                    if result.errors.is_empty() {
+                                    let has_errors = !result.errors.is_empty();
+                                    if let Some(field) = data
+                                        .try_update(|data| data.apply_reload_errors(&mut result))
+                                        .flatten()
+                                    {
+                                        scroll_to_field(field);
+                                    }
+                                    if !has_errors {
+                                        unsaved.mark_clean();
                                         match schema.list_path() {
-                                            Some(url) => use_navigate()(&url, Default::default()),
+                                            Some(url) => unsaved.navigate(&url),
                                             None => {
                                                 modal.set(
                                                     Modal::with_title("Settings reloaded")
@@ -263,7 +267,9 @@ pub fn SettingsEdit() -> impl IntoView {
                                                 );
                                             }
                                         }
-                                    } else {
+                                    } else if !result.errors.is_empty()
+                                        || !result.warnings.is_empty()
+                                    {
                                         alert.set(Alert::from(result));
                                     }
                                 }
@@ -277,8 +283,9 @@ pub fn SettingsEdit() -> impl IntoView {
                             }
                         } else {
                             set_pending.set(false);
+                            unsaved.mark_clean();
                             match schema.list_path() {
-                                Some(url) => use_navigate()(&url, Default::default()),
+                                Some(url) => unsaved.navigate(&url),
                                 None => {
                                     modal.set(
                                         Modal::with_title("Settings saved")
@@ -300,7 +307,13 @@ pub fn SettingsEdit() -> impl IntoView {
                                 use_navigate()("/login", Default::default());
                             }
                             err => {
-                                alert.set(Alert::from(err));
+                                match data
+                                    .try_update(|data| data.apply_server_error(&err))
+                                    .flatten()
+                                {
+                                    Some(field) => scroll_to_field(field),
+                                    None => alert.set(Alert::from(err)),
+                                }
                             }
                         }
                     }
@@ -309,293 +322,610 @@ pub fn SettingsEdit() -> impl IntoView {
         },
     );
 
+    // Copies the raw listing back into the form, returning false if it was rejected
+    let apply_raw = move || {
+        if !raw_mode.get_untracked() {
+            return true;
+        }
+        match data
+            .try_update(|data| raw_text.with_untracked(|raw| data.apply_raw(raw)))
+            .unwrap_or(Ok(()))
+        {
+            Ok(_) => true,
+            Err(err) => {
+                alert.set(Alert::error(err));
+                false
+            }
+        }
+    };
+    let set_raw_mode = move |enable: bool| {
+        if enable == raw_mode.get_untracked() {
+            return;
+        }
+        if enable {
+            let has_id = data.with_untracked(|data| {
+                matches!(data.schema.typ, SchemaType::List)
+                    || data.value_as_str("_id").is_some_and(|id| !id.is_empty())
+            });
+            if has_id {
+                raw_text.set(data.with_untracked(|data| data.to_raw()));
+                raw_mode.set(true);
+            } else {
+                alert.set(Alert::warning(
+                    "Enter an identifier before switching to the raw view.",
+                ));
+            }
+        } else if apply_raw() {
+            raw_mode.set(false);
+        }
+    };
+
+    let review_changes = move |reload: bool| {
+        if !apply_raw() {
+            return;
+        }
+        data.update(|data| {
+            if data.validate_form() {
+                let changes = unsaved
+                    .baseline()
+                    .map(|baseline| data.changes_since(&baseline))
+                    .unwrap_or_default();
+                if changes.is_empty() {
+                    save_changes.dispatch((Arc::new(data.build_update()), reload));
+                } else {
+                    review.set(Some(ReviewChanges {
+                        changes: Arc::new(changes),
+                        reload,
+                    }));
+                    window().scroll_to_with_x_and_y(0.0, 0.0);
+                }
+            }
+        });
+    };
+
+    let apply_review = move || {
+        if let Some(ReviewChanges { reload, .. }) = review.get() {
+            review.set(None);
+            save_changes.dispatch((
+                Arc::new(data.with_untracked(|data| data.build_update())),
+                reload,
+            ));
+        }
+    };
+
+    use_save_shortcut(move || {
+        if pending.get_untracked() || read_only.get_untracked() {
+            return;
+        }
+        if review.with_untracked(Option::is_some) {
+            apply_review();
+        } else {
+            review_changes(false);
+        }
+    });
+
     view! {
         <Form
             title=Signal::derive(move || current_schema.get().form.title.to_string())
             subtitle=Signal::derive(move || current_schema.get().form.subtitle.to_string())
         >
 
-            <Transition fallback=Skeleton set_pending>
+            <div
+                class="mb-4 flex justify-end"
+                class:hidden=move || review.with(Option::is_some) || read_only.get()
+            >
+                <div class="inline-flex rounded-lg shadow-sm">
+                    <button
+                        type="button"
+                        class="py-2 px-3 inline-flex items-center gap-x-2 -ms-px first:rounded-s-lg first:ms-0 last:rounded-e-lg text-sm font-medium focus:z-10 border border-gray-200 shadow-sm dark:border-gray-700"
+                        class=(
+                            ["bg-blue-600", "text-white", "border-blue-600"],
+                            move || !raw_mode.get(),
+                        )
 
-                {move || match fetch_settings.get() {
-                    None => None,
-                    Some(Err(http::Error::Unauthorized)) => {
-                        use_navigate()("/login", Default::default());
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Err(http::Error::NotFound) | Ok(FetchResult::NotFound)) => {
-                        let url = format!("/settings/{}", current_schema.get().id);
-                        use_navigate()(&url, Default::default());
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Err(err)) => {
-                        alert.set(Alert::from(err));
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Ok(result)) => {
-                        let (is_create, settings, external_sources) = match result {
-                            FetchResult::Update { settings, external_sources } => {
-                                (false, Some(settings), external_sources)
-                            }
-                            FetchResult::Create { external_sources } => {
-                                (true, None, external_sources)
+                        class=(
+                            ["bg-white", "text-gray-800", "hover:bg-gray-50", "dark:bg-slate-900", "dark:text-white"],
+                            move || raw_mode.get(),
+                        )
+
+                        on:click=move |_| set_raw_mode(false)
+                    >
+                        Form
+                    </button>
+                    <button
+                        type="button"
+                        class="py-2 px-3 inline-flex items-center gap-x-2 -ms-px first:rounded-s-lg first:ms-0 last:rounded-e-lg text-sm font-medium focus:z-10 border border-gray-200 shadow-sm dark:border-gray-700"
+                        class=(
+                            ["bg-blue-600", "text-white", "border-blue-600"],
+                            move || raw_mode.get(),
+                        )
+
+                        class=(
+                            ["bg-white", "text-gray-800", "hover:bg-gray-50", "dark:bg-slate-900", "dark:text-white"],
+                            move || !raw_mode.get(),
+                        )
+
+                        on:click=move |_| set_raw_mode(true)
+                    >
+                        Raw
+                    </button>
+                </div>
+            </div>
+
+            <div class:hidden=move || !raw_mode.get() || review.with(Option::is_some)>
+                <textarea
+                    class="py-3 px-4 block w-full font-mono border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                    rows="20"
+                    spellcheck="false"
+                    prop:value=move || raw_text.get()
+                    on:input=move |ev| raw_text.set(event_target_value(&ev))
+                ></textarea>
+                <p class="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                    "One setting per line as "
+                    <code>"key = \"value\""</code>
+                    ". Removing a line removes the setting."
+                </p>
+            </div>
+
+            {move || {
+                review
+                    .get()
+                    .map(|review| {
+                        view! { <ChangesReview changes=review.changes/> }
+                    })
+            }}
+
+            <fieldset
+                class:hidden=move || raw_mode.get() || review.with(Option::is_some)
+                disabled=move || read_only.get()
+            >
+                <Transition fallback=Skeleton set_pending>
+
+                    {move || match fetch_settings.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(http::Error::NotFound) | Ok(FetchResult::NotFound)) => {
+                            let url = format!("/settings/{}", current_schema.get().id);
+                            use_navigate()(&url, Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
+                        Some(Ok(result)) => {
+                            let (is_create, settings, external_sources) = match result {
+                                FetchResult::Update { settings, external_sources } => {
+                                    (false, Some(settings), external_sources)
+                                }
+                                FetchResult::Create { external_sources } => {
+                                    (true, None, external_sources)
+                                }
+                                FetchResult::NotFound => unreachable!(),
+                            };
+                            let schema = current_schema.get();
+                            if let (false, Some(recent)) = (is_create, use_recent_settings()) {
+                                let id = params
+                                    .with_untracked(|p| p.get("id").cloned())
+                                    .filter(|id| !id.is_empty());
+                                let (title, url) = match &id {
+                                    Some(id) => (
+                                        format!("{}: {id}", schema.list.title),
+                                        format!("/settings/{}/{id}/edit", schema.id),
+                                    ),
+                                    None => (
+                                        schema.form.title.to_string(),
+                                        format!("/settings/{}/edit", schema.id),
+                                    ),
+                                };
+                                recent.record(title, url);
                             }
-                            FetchResult::NotFound => unreachable!(),
-                        };
-                        let schema = current_schema.get();
-                        let sections = schema.form.sections.iter().cloned();
-                        let is_enterprise = auth.get().is_enterprise();
-                        data.set(
-                            FormData::from_settings(schema.clone(), settings)
-                                .with_external_sources(external_sources),
-                        );
-                        Some(
-                            sections
-                                .map(|section| {
-                                    let title = section.title.map(|s| s.to_string());
-                                    let section_ = section.clone();
-                                    let hide_section = create_memo(move |_| {
-                                        !section_.display(&data.get())
-                                    });
-                                    let components = section
-                                        .fields
-                                        .iter()
-                                        .cloned()
-                                        .map(|field| {
-                                            let is_disabled = (field.readonly && !is_create)
-                                                || (!is_enterprise && field.enterprise);
-                                            let field_label = field.label_form;
-                                            let help = field.help;
-                                            let field_ = field.clone();
-                                            let hide_label = create_memo(move |_| {
-                                                !field_.display(&data.get())
-                                            });
-                                            let field_ = field.clone();
-                                            let is_optional = create_memo(move |_| {
-                                                !field_.is_required(&data.get())
-                                            });
-                                            let is_switch = matches!(field.typ_, Type::Boolean);
-                                            let component = match field.typ_ {
-                                                Type::Input => {
-                                                    view! {
-                                                        <InputText
-                                                            element=FormElement::new(field.id, data)
-                                                            placeholder=create_memo(move |_| {
-                                                                field
-                                                                    .placeholder(&data.get())
-                                                                    .unwrap_or_default()
-                                                                    .to_string()
-                                                            })
-
-                                                            disabled=is_disabled
-                                                        />
+                            let sections = schema.form.sections.iter().cloned();
+                            let is_enterprise = auth.get().is_enterprise();
+                            loaded_settings.set_value(settings.clone());
+                            data.set(
+                                FormData::from_settings(schema.clone(), settings)
+                                    .with_external_sources(external_sources),
+                            );
+                            unsaved.mark_clean();
+                            review.set(None);
+                            raw_mode.set(false);
+                            Some(
+                                sections
+                                    .map(|section| {
+                                        let title = section.title.map(|s| s.to_string());
+                                        let section_ = section.clone();
+                                        let hide_section = create_memo(move |_| {
+                                            !section_.display(&data.get())
+                                        });
+                                        let components = section
+                                            .fields
+                                            .iter()
+                                            .cloned()
+                                            .map(|field| {
+                                                let field_ = field.clone();
+                                                let is_disabled = Signal::derive(move || {
+                                                    (field_.readonly && !is_create)
+                                                        || (!is_enterprise && field_.enterprise)
+                                                        || !field_.is_enabled(&data.get())
+                                                });
+                                                let field_label = field.label_form;
+                                                let help = field.help;
+                                                let description = field.description;
+                                                let field_id = field.id;
+                                                let field_ = field.clone();
+                                                let hide_label = create_memo(move |_| {
+                                                    !field_.display(&data.get())
+                                                });
+                                                let field_ = field.clone();
+                                                let is_optional = create_memo(move |_| {
+                                                    !field_.is_required(&data.get())
+                                                });
+                                                let is_switch = matches!(field.typ_, Type::Boolean);
+                                                let component = match field.typ_ {
+                                                    Type::Input => {
+                                                        view! {
+                                                            <InputText
+                                                                element=FormElement::new(field.id, data)
+                                                                placeholder=create_memo(move |_| {
+                                                                    field
+                                                                        .placeholder(&data.get())
+                                                                        .unwrap_or_default()
+                                                                        .to_string()
+                                                                })
+
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Array => {
-                                                    view! {
-                                                        <StackedInput
-                                                            add_button_text="Add".to_string()
-                                                            element=FormElement::new(field.id, data)
-                                                            placeholder=create_memo(move |_| {
-                                                                field
-                                                                    .placeholder(&data.get())
-                                                                    .unwrap_or_default()
-                                                                    .to_string()
-                                                            })
-                                                        />
+                                                    Type::Array => {
+                                                        view! {
+                                                            <StackedInput
+                                                                add_button_text="Add".to_string()
+                                                                element=FormElement::new(field.id, data)
+                                                                placeholder=create_memo(move |_| {
+                                                                    field
+                                                                        .placeholder(&data.get())
+                                                                        .unwrap_or_default()
+                                                                        .to_string()
+                                                                })
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Secret => {
-                                                    view! {
-                                                        <InputPassword
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Secret => {
+                                                        view! {
+                                                            <InputPassword
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Select { typ: SelectType::Single, .. } => {
-                                                    view! {
-                                                        <Select
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Select { typ: SelectType::Single, .. } => {
+                                                        view! {
+                                                            <Select
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Select { typ: SelectType::Many, .. } => {
-                                                    view! {
-                                                        <CheckboxGroup
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Select { typ: SelectType::Radio, .. } => {
+                                                        view! {
+                                                            <RadioGroup
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Select { typ: SelectType::ManyWithSearch, .. } => {
-                                                    view! {
-                                                        <StackedBadge
-                                                            element=FormElement::new(field.id, data)
-                                                            add_button_text="Add Item"
-                                                            color=Color::Green
-                                                        />
+                                                    Type::Select { typ: SelectType::Many, .. } => {
+                                                        view! {
+                                                            <CheckboxGroup
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Size => {
-                                                    view! {
-                                                        <InputSize
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Select { typ: SelectType::ManyWithSearch, .. } => {
+                                                        view! {
+                                                            <StackedBadge
+                                                                element=FormElement::new(field.id, data)
+                                                                add_button_text="Add Item"
+                                                                color=Color::Green
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Boolean => {
-                                                    view! {
-                                                        <InputSwitch
-                                                            label=field_label
-                                                            tooltip=help.unwrap_or_default()
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Size => {
+                                                        view! {
+                                                            <InputSize
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Duration => {
-                                                    view! {
-                                                        <InputDuration
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Boolean => {
+                                                        view! {
+                                                            <InputSwitch
+                                                                label=field_label
+                                                                tooltip=help.unwrap_or_default()
+                                                                description=description
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Rate => {
-                                                    view! {
-                                                        <InputRate
-                                                            element=FormElement::new(field.id, data)
-                                                            disabled=is_disabled
-                                                        />
+                                                    Type::Duration => {
+                                                        view! {
+                                                            <InputDuration
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Expression => {
-                                                    view! {
-                                                        <InputExpression element=FormElement::new(field.id, data)/>
+                                                    Type::Rate => {
+                                                        view! {
+                                                            <InputRate
+                                                                element=FormElement::new(field.id, data)
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Cron => {
+                                                    Type::Expression => {
+                                                        view! {
+                                                            <InputExpression element=FormElement::new(field.id, data)/>
+                                                        }
+                                                            .into_view()
+                                                    }
+                                                    Type::Cron => {
+                                                        view! {
+                                                            <SelectCron element=FormElement::new(field.id, data)/>
+                                                        }
+                                                            .into_view()
+                                                    }
+                                                    Type::Text => {
+                                                        view! {
+                                                            <TextArea
+                                                                element=FormElement::new(field.id, data)
+                                                                placeholder=create_memo(move |_| {
+                                                                    field
+                                                                        .placeholder(&data.get())
+                                                                        .unwrap_or_default()
+                                                                        .to_string()
+                                                                })
+
+                                                                disabled=is_disabled
+                                                            />
+                                                        }
+                                                            .into_view()
+                                                    }
+                                                };
+                                                let component = view! {
+                                                    <div data-field=field_id>{component}</div>
+                                                };
+                                                if !is_switch {
                                                     view! {
-                                                        <SelectCron element=FormElement::new(field.id, data)/>
+                                                        <FormItem
+                                                            label=field_label
+                                                            hide=hide_label
+                                                            is_optional=is_optional
+                                                            tooltip=help.unwrap_or_default()
+                                                            description=description
+                                                        >
+                                                            {component}
+                                                        </FormItem>
                                                     }
-                                                        .into_view()
-                                                }
-                                                Type::Text => {
+                                                } else {
                                                     view! {
-                                                        <TextArea
-                                                            element=FormElement::new(field.id, data)
-                                                            placeholder=create_memo(move |_| {
-                                                                field
-                                                                    .placeholder(&data.get())
-                                                                    .unwrap_or_default()
-                                                                    .to_string()
-                                                            })
-
-                                                            disabled=is_disabled
-                                                        />
+                                                        <FormItem label="" hide=hide_label is_optional=is_optional>
+                                                            {component}
+                                                        </FormItem>
                                                     }
-                                                        .into_view()
-                                                }
-                                            };
-                                            if !is_switch {
-                                                view! {
-                                                    <FormItem
-                                                        label=field_label
-                                                        hide=hide_label
-                                                        is_optional=is_optional
-                                                        tooltip=help.unwrap_or_default()
-                                                    >
-                                                        {component}
-                                                    </FormItem>
-                                                }
-                                            } else {
-                                                view! {
-                                                    <FormItem label="" hide=hide_label is_optional=is_optional>
-                                                        {component}
-                                                    </FormItem>
                                                 }
-                                            }
-                                        })
-                                        .collect_view();
-                                    view! {
-                                        <FormSection
-                                            title=title.unwrap_or_default()
-                                            hide=hide_section
-                                        >
-                                            {components}
-                                        </FormSection>
-                                    }
-                                        .into_view()
-                                })
-                                .collect_view(),
-                        )
-                    }
-                }}
+                                            })
+                                            .collect_view();
+                                        view! {
+                                            <FormSection
+                                                title=title.unwrap_or_default()
+                                                hide=hide_section
+                                            >
+                                                {components}
+                                            </FormSection>
+                                        }
+                                            .into_view()
+                                    })
+                                    .collect_view(),
+                            )
+                        }
+                    }}
 
-            </Transition>
+                </Transition>
+            </fieldset>
 
             <FormButtonBar>
-                <Button
-                    text="Cancel"
-                    color=Color::Gray
-                    on_click=move |_| {
-                        use_navigate()(
-                            &current_schema.get().list_path_or_default(),
-                            Default::default(),
-                        );
-                    }
-                />
-
-                <Button
-                    text="Save & Reload"
-                    color=Color::Gray
-                    on_click=Callback::new(move |_| {
-                        data.update(|data| {
-                            if data.validate_form() {
-                                save_changes.dispatch((Arc::new(data.build_update()), true));
-                            }
-                        });
-                    })
+                <Show when=move || review.with(Option::is_some)>
+                    <Button
+                        text="Back to editing"
+                        color=Color::Gray
+                        on_click=move |_| {
+                            review.set(None);
+                        }
+                    />
+
+                    <Button
+                        text=t_signal("button.apply-changes")
+                        color=Color::Blue
+                        on_click=Callback::new(move |_| apply_review())
+
+                        disabled=pending
+                    />
+                </Show>
+                <Show when=move || review.with(Option::is_none)>
+                    <Button
+                        text=t_signal("button.cancel")
+                        color=Color::Gray
+                        on_click=move |_| {
+                            unsaved.navigate(&current_schema.get().list_path_or_default());
+                        }
+                    />
 
-                    disabled=pending
-                >
+                    <Show when=move || !read_only.get()>
+                        <Button
+                            text="Save & Reload"
+                            color=Color::Gray
+                            on_click=Callback::new(move |_| review_changes(true))
 
-                    <IconRefresh/>
-                </Button>
+                            disabled=pending
+                        >
 
-                <Button
-                    text="Save changes"
-                    color=Color::Blue
-                    on_click=Callback::new(move |_| {
-                        data.update(|data| {
-                            if data.validate_form() {
-                                save_changes.dispatch((Arc::new(data.build_update()), false));
-                            }
-                        });
-                    })
+                            <IconRefresh/>
+                        </Button>
+
+                        <Button
+                            text=t_signal("button.save-changes")
+                            color=Color::Blue
+                            on_click=Callback::new(move |_| review_changes(false))
 
-                    disabled=pending
-                />
+                            disabled=pending
+                        />
+                    </Show>
+                </Show>
             </FormButtonBar>
 
         </Form>
     }
 }
 
+#[component]
+pub(super) fn ChangesReview(changes: Arc<Vec<SettingChange>>) -> impl IntoView {
+    let total = changes.len();
+
+    view! {
+        <FormSection title="Review changes".to_string()>
+            <p class="text-sm text-gray-600 dark:text-gray-400">
+                {format!(
+                    "The following {} will be written to the server. Confirm to apply them.",
+                    if total == 1 { "setting".to_string() } else { format!("{total} settings") },
+                )}
+
+            </p>
+            <div class="overflow-x-auto border border-gray-200 rounded-lg dark:border-gray-700">
+                <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
+                    <thead class="bg-gray-50 dark:bg-slate-800">
+                        <tr>
+                            <th class="px-4 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                                Setting
+                            </th>
+                            <th class="px-4 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                                Current value
+                            </th>
+                            <th class="px-4 py-2 text-start text-xs font-semibold uppercase text-gray-800 dark:text-gray-200">
+                                New value
+                            </th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-200 dark:divide-gray-700">
+                        {changes
+                            .iter()
+                            .cloned()
+                            .map(|change| {
+                                view! {
+                                    <tr>
+                                        <td class="px-4 py-2 align-top font-mono text-sm text-gray-800 dark:text-gray-200">
+                                            {change.key}
+                                        </td>
+                                        <td class="px-4 py-2 align-top font-mono text-sm break-all bg-red-50 text-red-800 dark:bg-red-800/10 dark:text-red-500">
+                                            {change.old_value.unwrap_or_else(|| "(not set)".to_string())}
+                                        </td>
+                                        <td class="px-4 py-2 align-top font-mono text-sm break-all bg-teal-50 text-teal-800 dark:bg-teal-800/10 dark:text-teal-500">
+                                            {change.new_value.unwrap_or_else(|| "(removed)".to_string())}
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        </FormSection>
+    }
+}
+
+async fn fetch_stored_settings(
+    api: ApiClient,
+    schema: &Schema,
+    name: &str,
+) -> http::Result<Option<Settings>> {
+    match &schema.typ {
+        SchemaType::Record { prefix, .. } => {
+            if name.is_empty() {
+                return Ok(None);
+            }
+            api.get("/api/settings/list")
+                .with_parameter("prefix", format!("{prefix}.{name}"))
+                .send::<FetchSettings>()
+                .await
+                .map(|mut list| {
+                    (!list.items.is_empty()).then(|| {
+                        list.items.insert("_id".to_string(), name.to_string());
+                        list.items
+                    })
+                })
+        }
+        SchemaType::Entry { prefix } => {
+            if name.is_empty() {
+                return Ok(None);
+            }
+            api.get("/api/settings/keys")
+                .with_parameter("keys", format!("{prefix}.{name}"))
+                .send::<AHashMap<String, Option<String>>>()
+                .await
+                .map(|list| {
+                    list.into_values().next().flatten().map(|value| {
+                        let mut settings = Settings::new();
+                        settings.insert("_id".to_string(), name.to_string());
+                        settings.insert("_value".to_string(), value);
+                        settings
+                    })
+                })
+        }
+        SchemaType::List => {
+            let mut keys = Vec::new();
+            let mut prefixes = Vec::new();
+
+            for field in schema.fields.values() {
+                if field.is_multivalue() {
+                    prefixes.push(field.id);
+                    keys.push(field.id);
+                } else {
+                    keys.push(field.id);
+                }
+            }
+
+            api.get("/api/settings/keys")
+                .with_parameter("keys", keys.join(","))
+                .with_parameter("prefixes", prefixes.join(","))
+                .send::<Settings>()
+                .await
+                .map(|settings| (!settings.is_empty()).then_some(settings))
+        }
+    }
+}
+
+fn scroll_to_field(id: &str) {
+    if let Ok(Some(element)) = document().query_selector(&format!("[data-field=\"{id}\"]")) {
+        element.scroll_into_view();
+    }
+}
+
 impl Schema {
     fn list_path(&self) -> Option<String> {
         if !matches!(self.typ, SchemaType::List) {
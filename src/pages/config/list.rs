@@ -8,10 +8,12 @@ use std::{collections::HashSet, sync::Arc};
 
 use leptos::*;
 use leptos_router::*;
+use web_sys::HtmlInputElement;
 
 use crate::{
     components::{
-        icon::{IconAdd, IconRefresh, IconTrash},
+        form::button::Button,
+        icon::{IconAdd, IconArrowDownTray, IconArrowUpTray, IconRefresh, IconTrash},
         list::{
             header::ColumnList,
             pagination::Pagination,
@@ -27,12 +29,20 @@ use crate::{
         Color,
     },
     core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
+        api::{use_api, use_latest_api},
+        download::download_file,
+        http,
+        i18n::t_signal,
+        oauth::use_is_viewer,
+        schema::Type,
+        upload::read_file,
         url::UrlBuilder,
     },
     pages::{
-        config::{ReloadSettings, SchemaType, Schemas, SettingsValues},
+        config::{
+            edit::{ChangesReview, FetchSettings},
+            ReloadSettings, SchemaType, Schemas, SettingChange, SettingsBundle, SettingsValues,
+        },
         maybe_plural, List,
     },
 };
@@ -41,6 +51,7 @@ use super::{Schema, Settings, UpdateSettings};
 
 #[component]
 pub fn SettingsList() -> impl IntoView {
+    let is_viewer = use_is_viewer();
     let schemas = expect_context::<Arc<Schemas>>();
     let query = use_query_map();
     let page = create_memo(move |_| {
@@ -77,7 +88,8 @@ pub fn SettingsList() -> impl IntoView {
         }
     });
 
-    let auth = use_authorization();
+    let api = use_api();
+    let latest = use_latest_api();
     let alert = use_alerts();
     let modal = use_modals();
     provide_context(selected);
@@ -85,12 +97,11 @@ pub fn SettingsList() -> impl IntoView {
     let settings = create_resource(
         move || (page.get(), filter.get()),
         move |(page, filter)| {
-            let auth = auth.get_untracked();
             let schema = current_schema.get();
 
             async move {
-                HttpRequest::get("/api/settings/group")
-                    .with_authorization(&auth)
+                latest
+                    .get("/api/settings/group")
                     .with_parameter("page", page.to_string())
                     .with_parameter("limit", schema.list.page_size.to_string())
                     .with_parameter("prefix", schema.unwrap_prefix())
@@ -104,16 +115,15 @@ pub fn SettingsList() -> impl IntoView {
 
     let reload_config_action = create_action(move |()| {
         let schema = current_schema.get();
-        let auth = auth.get();
 
         async move {
-            match HttpRequest::get(format!(
-                "/api/reload/{}",
-                schema.reload_prefix.unwrap_or_default()
-            ))
-            .with_authorization(&auth)
-            .send::<ReloadSettings>()
-            .await
+            match api
+                .get(format!(
+                    "/api/reload/{}",
+                    schema.reload_prefix.unwrap_or_default()
+                ))
+                .send::<ReloadSettings>()
+                .await
             {
                 Ok(result) => {
                     alert.set(Alert::from(result));
@@ -130,7 +140,6 @@ pub fn SettingsList() -> impl IntoView {
 
     let delete_action = create_action(move |items: &Arc<HashSet<String>>| {
         let items = items.clone();
-        let auth = auth.get();
         let schema = current_schema.get();
 
         async move {
@@ -153,8 +162,8 @@ pub fn SettingsList() -> impl IntoView {
                 }
             }
 
-            match HttpRequest::post("/api/settings")
-                .with_authorization(&auth)
+            match api
+                .post("/api/settings")
                 .with_body(updates)
                 .unwrap()
                 .send::<()>()
@@ -174,6 +183,191 @@ pub fn SettingsList() -> impl IntoView {
         }
     });
 
+    let include_secrets = create_rw_signal(false);
+    let import_review = create_rw_signal(None::<ImportReview>);
+
+    let export_action = create_action(move |include_secrets: &bool| {
+        let include_secrets = *include_secrets;
+        let schema = current_schema.get();
+
+        async move {
+            let prefix = schema.unwrap_prefix();
+            match api
+                .get("/api/settings/list")
+                .with_parameter("prefix", prefix)
+                .send::<FetchSettings>()
+                .await
+            {
+                Ok(list) => {
+                    let bundle = SettingsBundle {
+                        schema: schema.id.to_string(),
+                        settings: list
+                            .items
+                            .into_iter()
+                            .map(|(key, value)| (format!("{prefix}.{key}"), value))
+                            .filter(|(key, _)| include_secrets || !schema.is_secret(key))
+                            .collect(),
+                    };
+                    if let Err(err) = download_file(
+                        &format!("{}-settings.json", schema.id),
+                        "application/json",
+                        &serde_json::to_string_pretty(&bundle).unwrap_or_default(),
+                    ) {
+                        log::warn!("Failed to download settings: {err:?}");
+                    }
+                }
+                Err(http::Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let review_import_action = create_action(move |bundle: &Arc<SettingsBundle>| {
+        let bundle = bundle.clone();
+        let schema = current_schema.get();
+
+        async move {
+            if bundle.schema != schema.id {
+                alert.set(Alert::error(format!(
+                    "This file contains {} settings and cannot be imported into {}.",
+                    bundle.schema, schema.name_plural
+                )));
+                return;
+            }
+            let unknown_keys = bundle
+                .settings
+                .keys()
+                .filter(|key| schema.field_for_setting(key).is_none())
+                .map(|key| key.as_str())
+                .collect::<Vec<_>>();
+            if !unknown_keys.is_empty() {
+                alert.set(
+                    Alert::error("The file contains unknown setting keys")
+                        .with_details(unknown_keys.join(", ")),
+                );
+                return;
+            }
+
+            let prefix = schema.unwrap_prefix();
+            let current = match api
+                .get("/api/settings/list")
+                .with_parameter("prefix", prefix)
+                .send::<FetchSettings>()
+                .await
+            {
+                Ok(list) => list.items,
+                Err(http::Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                    return;
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                    return;
+                }
+            };
+
+            let mut changes = Vec::new();
+            let mut values = Vec::new();
+            for (key, value) in &bundle.settings {
+                let old_value = key
+                    .strip_prefix(prefix)
+                    .and_then(|key| key.strip_prefix('.'))
+                    .and_then(|key| current.get(key));
+                if old_value != Some(value) {
+                    let mask = |value: &String| {
+                        if schema.is_secret(key) {
+                            "••••••••".to_string()
+                        } else {
+                            value.clone()
+                        }
+                    };
+                    changes.push(SettingChange {
+                        key: key.clone(),
+                        old_value: old_value.map(mask),
+                        new_value: Some(mask(value)),
+                    });
+                    values.push((key.clone(), value.clone()));
+                }
+            }
+
+            if changes.is_empty() {
+                alert.set(Alert::success(
+                    "All imported settings match the current configuration.",
+                ));
+            } else {
+                import_review.set(Some(ImportReview {
+                    changes: Arc::new(changes),
+                    values: Arc::new(values),
+                }));
+            }
+        }
+    });
+
+    let apply_import_action = create_action(move |values: &Arc<Vec<(String, String)>>| {
+        let values = values.clone();
+
+        async move {
+            match api
+                .post("/api/settings")
+                .with_body(vec![UpdateSettings::Insert {
+                    prefix: None,
+                    values: values.as_ref().clone(),
+                    assert_empty: false,
+                }])
+                .unwrap()
+                .send::<()>()
+                .await
+            {
+                Ok(_) => {
+                    import_review.set(None);
+                    settings.refetch();
+                    alert.set(Alert::success(format!(
+                        "Imported {}.",
+                        maybe_plural(values.len(), "setting", "settings")
+                    )));
+                }
+                Err(http::Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let import_input = create_node_ref::<html::Input>();
+    let on_import_file = move |ev: ev::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+
+        spawn_local(async move {
+            match read_file(file).await {
+                Ok(contents) => match serde_json::from_str::<SettingsBundle>(&contents) {
+                    Ok(bundle) => {
+                        review_import_action.dispatch(Arc::new(bundle));
+                    }
+                    Err(err) => {
+                        alert.set(Alert::error(format!("Invalid settings file: {err}")));
+                    }
+                },
+                Err(err) => {
+                    alert.set(Alert::error(format!(
+                        "Failed to read file: {}",
+                        err.as_string().unwrap_or_default()
+                    )));
+                }
+            }
+        });
+    };
+
     let total_results = create_rw_signal(None::<u32>);
     view! {
         <ListSection>
@@ -184,6 +378,7 @@ pub fn SettingsList() -> impl IntoView {
                 <Toolbar slot>
                     <SearchBox
                         value=filter
+                        latest
                         on_search=move |value| {
                             use_navigate()(
                                 &UrlBuilder::new("/settings")
@@ -196,6 +391,7 @@ pub fn SettingsList() -> impl IntoView {
                     />
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Delete ({ns})") } else { "Delete".to_string() }
@@ -236,7 +432,52 @@ pub fn SettingsList() -> impl IntoView {
                         <IconTrash/>
                     </ToolbarButton>
 
+                    <label class="inline-flex items-center gap-x-2 text-sm text-gray-600 dark:text-gray-400">
+                        <input
+                            type="checkbox"
+                            class="shrink-0 border-gray-300 rounded text-blue-600 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-600 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                            prop:checked=include_secrets
+                            on:change=move |ev| include_secrets.set(event_target_checked(&ev))
+                        />
+                        "Include secrets"
+                    </label>
+
+                    <ToolbarButton
+                        text=t_signal("button.export")
+
+                        color=Color::Gray
+                        on_click=Callback::new(move |_| {
+                            export_action.dispatch(include_secrets.get());
+                        })
+                    >
+
+                        <IconArrowDownTray attr:class="flex-shrink-0 size-4"/>
+                    </ToolbarButton>
+
+                    <input
+                        type="file"
+                        accept="application/json,.json"
+                        class="hidden"
+                        node_ref=import_input
+                        on:change=on_import_file
+                    />
                     <ToolbarButton
+                        hide=is_viewer
+                        text=t_signal("button.import")
+
+                        color=Color::Gray
+                        on_click=Callback::new(move |_| {
+                            if let Some(input) = import_input.get() {
+                                input.click();
+                            }
+                        })
+                    >
+
+                        <IconArrowUpTray attr:class="flex-shrink-0 size-4"/>
+                    </ToolbarButton>
+
+                    <ToolbarButton
+                        hide=is_viewer
                         text="Reload config"
 
                         color=Color::Gray
@@ -249,6 +490,7 @@ pub fn SettingsList() -> impl IntoView {
                     </ToolbarButton>
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             format!("Create {}", current_schema.get().name_singular)
                         })
@@ -267,6 +509,38 @@ pub fn SettingsList() -> impl IntoView {
 
                 </Toolbar>
 
+                {move || {
+                    import_review
+                        .get()
+                        .map(|review| {
+                            let values = review.values.clone();
+                            view! {
+                                <div class="px-6 py-4">
+                                    <ChangesReview changes=review.changes/>
+                                    <div class="mt-5 flex justify-end gap-x-2">
+                                        <Button
+                                            text=t_signal("button.cancel")
+                                            color=Color::Gray
+                                            on_click=move |_| {
+                                                import_review.set(None);
+                                            }
+                                        />
+
+                                        <Button
+                                            text=t_signal("button.apply-changes")
+                                            color=Color::Blue
+                                            on_click=Callback::new(move |_| {
+                                                apply_import_action.dispatch(values.clone());
+                                            })
+
+                                            disabled=apply_import_action.pending()
+                                        />
+                                    </div>
+                                </div>
+                            }
+                        })
+                }}
+
                 <Transition fallback=Skeleton>
                     {move || match settings.get() {
                         None => None,
@@ -378,6 +652,19 @@ pub fn SettingsList() -> impl IntoView {
     }
 }
 
+#[derive(Clone)]
+struct ImportReview {
+    changes: Arc<Vec<SettingChange>>,
+    values: Arc<Vec<(String, String)>>,
+}
+
+impl Schema {
+    fn is_secret(&self, key: &str) -> bool {
+        self.field_for_setting(key)
+            .is_some_and(|field| matches!(field.typ_, Type::Secret))
+    }
+}
+
 #[component]
 fn SettingsItem(settings: Settings, schema: Arc<Schema>) -> impl IntoView {
     let columns = schema
@@ -6,32 +6,40 @@
 
 pub mod edit;
 pub mod list;
+pub mod replace;
 pub mod schema;
 pub mod search;
 
-use std::{collections::BTreeMap, str::FromStr};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
 use crate::{
     components::{
         form::input::{Duration, Rate},
         icon::{
-            IconCircleStack, IconCodeBracket, IconHandRaised, IconInbox, IconInboxArrowDown,
-            IconInboxStack, IconKey, IconServer, IconServerStack, IconShieldCheck, IconSignal,
+            IconCircleStack, IconClock, IconCodeBracket, IconHandRaised, IconInbox,
+            IconInboxArrowDown, IconInboxStack, IconKey, IconServer, IconServerStack,
+            IconShieldCheck, IconSignal,
         },
         layout::{LayoutBuilder, MenuItem},
     },
     core::{
+        api::ApiClient,
         form::{FormData, FormValue},
+        http::{self, ManagementApiError},
         schema::*,
     },
 };
 use ahash::AHashMap;
+use gloo_storage::{LocalStorage, Storage};
 use humansize::{format_size, DECIMAL};
-use leptos::view;
+use leptos::{create_rw_signal, use_context, view, RwSignal, SignalGet, SignalUpdate};
 use serde::{Deserialize, Serialize};
 
 pub type Settings = AHashMap<String, String>;
 
+const RECENT_SETTINGS_KEY: &str = "webadmin_recent_settings";
+const MAX_RECENT_SETTINGS: usize = 8;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum UpdateSettings {
@@ -48,6 +56,19 @@ pub enum UpdateSettings {
     },
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub schema: String,
+    pub settings: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingChange {
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
 pub struct ReloadSettings {
     pub warnings: BTreeMap<String, ConfigWarning>,
@@ -73,6 +94,126 @@ pub enum ConfigError {
 }
 
 impl FormData {
+    pub fn field_for_key(&self, key: &str) -> Option<&'static str> {
+        let key = match &self.schema.typ {
+            SchemaType::Record { prefix, .. } => key
+                .strip_prefix(&format!("{prefix}.{}.", self.value_as_str("_id")?))
+                .unwrap_or(key),
+            SchemaType::Entry { .. } => return Some("_value"),
+            SchemaType::List => key,
+        };
+
+        self.schema.field_for_relative_key(key)
+    }
+
+    pub fn to_raw(&self) -> String {
+        let mut raw = String::new();
+        for (key, value) in self.settings_values() {
+            raw.push_str(&key);
+            raw.push_str(" = ");
+            raw.push_str(&serde_json::to_string(&value).unwrap_or_default());
+            raw.push('\n');
+        }
+        raw
+    }
+
+    // Rejects keys that do not belong to this object's schema
+    pub fn apply_raw(&mut self, raw: &str) -> Result<(), String> {
+        let id = self.value_as_str("_id").unwrap_or_default().to_string();
+        let mut settings = Settings::new();
+
+        for (line_num, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line_num = line_num + 1;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line {line_num}: expected \"key = value\"."))?;
+            let (key, value) = (key.trim(), value.trim());
+            let value = if value.starts_with('"') {
+                serde_json::from_str::<String>(value)
+                    .map_err(|_| format!("Line {line_num}: invalid quoted value for {key:?}."))?
+            } else {
+                value.to_string()
+            };
+
+            let relative_key = match &self.schema.typ {
+                SchemaType::Record { prefix, .. } => key
+                    .strip_prefix(&format!("{prefix}.{id}."))
+                    .ok_or_else(|| {
+                    format!("Line {line_num}: key {key:?} does not belong to \"{prefix}.{id}\".")
+                })?,
+                SchemaType::Entry { prefix } => {
+                    if key == format!("{prefix}.{id}") {
+                        "_value"
+                    } else {
+                        return Err(format!(
+                            "Line {line_num}: expected the key \"{prefix}.{id}\", found {key:?}."
+                        ));
+                    }
+                }
+                SchemaType::List => key,
+            };
+            if relative_key == "_id"
+                || (relative_key.starts_with('_') && relative_key != "_value")
+                || self.schema.field_for_relative_key(relative_key).is_none()
+            {
+                return Err(format!("Line {line_num}: unknown setting key {key:?}."));
+            }
+            if settings.insert(relative_key.to_string(), value).is_some() {
+                return Err(format!("Line {line_num}: duplicate setting key {key:?}."));
+            }
+        }
+
+        if !id.is_empty() {
+            settings.insert("_id".to_string(), id);
+        }
+        let mut data = FormData::from_settings(self.schema.clone(), Some(settings));
+        data.is_update = self.is_update;
+        self.values = data.values;
+        self.errors.clear();
+        Ok(())
+    }
+
+    pub fn apply_server_error(&mut self, err: &http::Error) -> Option<&'static str> {
+        let (key, message) = match err {
+            http::Error::Server(ManagementApiError::FieldAlreadyExists { field, value }) => (
+                field,
+                format!("Another record already uses the value {value:?}"),
+            ),
+            http::Error::Server(ManagementApiError::FieldMissing { field }) => {
+                (field, "This field is required".to_string())
+            }
+            _ => return None,
+        };
+        let id = self.field_for_key(key)?;
+        self.new_error(id, message);
+        Some(id)
+    }
+
+    pub fn apply_reload_errors(&mut self, result: &mut ReloadSettings) -> Option<&'static str> {
+        let mut first_field = None;
+        result.errors.retain(|key, error| {
+            if let Some(id) = self.field_for_key(key) {
+                self.new_error(
+                    id,
+                    match error {
+                        ConfigError::Parse { error }
+                        | ConfigError::Build { error }
+                        | ConfigError::Macro { error } => error.clone(),
+                    },
+                );
+                first_field.get_or_insert(id);
+                false
+            } else {
+                true
+            }
+        });
+        first_field
+    }
+
     pub fn build_update(&self) -> Vec<UpdateSettings> {
         let mut updates = Vec::new();
         let mut insert_prefix = None;
@@ -105,6 +246,10 @@ impl FormData {
                 if self.is_update {
                     let mut delete_keys = Vec::new();
                     for field in self.schema.fields.values() {
+                        // Hidden fields are left as stored
+                        if !field.display(self) {
+                            continue;
+                        }
                         if field.is_multivalue() {
                             updates.push(UpdateSettings::Clear {
                                 prefix: format!("{}.", field.id),
@@ -122,9 +267,133 @@ impl FormData {
             }
         }
 
+        let key_values = self.key_values();
+        if !key_values.is_empty() {
+            updates.push(UpdateSettings::Insert {
+                prefix: insert_prefix,
+                values: key_values,
+                assert_empty,
+            });
+        }
+
+        updates
+    }
+
+    // Full setting keys, so they match what is stored on the server
+    pub fn changes_since(&self, baseline: &AHashMap<String, FormValue>) -> Vec<SettingChange> {
+        let old_values = if self.is_update {
+            let mut data = self.clone();
+            data.values = baseline.clone();
+            data.settings_values()
+        } else {
+            BTreeMap::new()
+        };
+        let mut new_values = self.settings_values();
+
+        let mut changes = old_values
+            .into_iter()
+            .filter_map(|(key, old_value)| match new_values.remove(&key) {
+                Some(new_value) if new_value == old_value => None,
+                new_value => Some(SettingChange {
+                    key,
+                    old_value: Some(old_value),
+                    new_value,
+                }),
+            })
+            .collect::<Vec<_>>();
+        changes.extend(
+            new_values
+                .into_iter()
+                .map(|(key, new_value)| SettingChange {
+                    key,
+                    old_value: None,
+                    new_value: Some(new_value),
+                }),
+        );
+        changes.sort_by(|a, b| a.key.cmp(&b.key));
+        self.mask_secrets(&mut changes);
+
+        changes
+    }
+
+    // `latest` is what is stored on the server now
+    pub fn conflicts(&self, loaded: &Settings, latest: &Settings) -> Vec<SettingChange> {
+        let display_key = |key: &str| match &self.schema.typ {
+            SchemaType::Entry { prefix } if key == "_value" => {
+                format!("{prefix}.{}", self.value_as_str("_id").unwrap_or_default())
+            }
+            _ => key.to_string(),
+        };
+        let mut changes = loaded
+            .keys()
+            .chain(latest.keys().filter(|key| !loaded.contains_key(*key)))
+            .filter(|key| *key != "_id" && loaded.get(*key) != latest.get(*key))
+            .map(|key| SettingChange {
+                key: display_key(key),
+                old_value: loaded.get(key).cloned(),
+                new_value: latest.get(key).cloned(),
+            })
+            .collect::<Vec<_>>();
+        changes.sort_by(|a, b| a.key.cmp(&b.key));
+        self.mask_secrets(&mut changes);
+
+        changes
+    }
+
+    fn mask_secrets(&self, changes: &mut [SettingChange]) {
+        for change in changes {
+            if self
+                .field_for_key(&change.key)
+                .and_then(|id| self.schema.fields.get(id))
+                .is_some_and(|field| matches!(field.typ_, Type::Secret))
+            {
+                for value in [&mut change.old_value, &mut change.new_value]
+                    .into_iter()
+                    .flatten()
+                {
+                    *value = "••••••••".to_string();
+                }
+            }
+        }
+    }
+
+    fn settings_values(&self) -> BTreeMap<String, String> {
+        match &self.schema.typ {
+            SchemaType::Record { prefix, .. } => {
+                let prefix = format!("{prefix}.{}", self.value_as_str("_id").unwrap_or_default());
+                self.key_values()
+                    .into_iter()
+                    .map(|(key, value)| (format!("{prefix}.{key}"), value))
+                    .collect()
+            }
+            SchemaType::Entry { prefix } => self
+                .value_as_str("_value")
+                .filter(|value| !value.is_empty())
+                .map(|value| {
+                    (
+                        format!("{prefix}.{}", self.value_as_str("_id").unwrap_or_default()),
+                        value.to_string(),
+                    )
+                })
+                .into_iter()
+                .collect(),
+            SchemaType::List => self.key_values().into_iter().collect(),
+        }
+    }
+
+    fn key_values(&self) -> Vec<(String, String)> {
         let mut key_values = Vec::new();
         for (key, value) in &self.values {
-            if key.starts_with('_') {
+            // Fields hidden by the value of another field are not saved, except
+            // in records which are cleared and rewritten as a whole on update
+            if key.starts_with('_')
+                || (matches!(self.schema.typ, SchemaType::List)
+                    && self
+                        .schema
+                        .fields
+                        .get(key.as_str())
+                        .is_some_and(|field| !field.display(self)))
+            {
                 continue;
             }
 
@@ -172,18 +441,88 @@ impl FormData {
             }
         }
 
-        if !key_values.is_empty() {
-            updates.push(UpdateSettings::Insert {
-                prefix: insert_prefix,
-                values: key_values,
-                assert_empty,
-            });
-        }
+        key_values
+    }
+}
 
-        updates
+impl Schema {
+    fn field_for_relative_key(&self, key: &str) -> Option<&'static str> {
+        self.fields
+            .keys()
+            .filter(|id| {
+                key == **id
+                    || key
+                        .strip_prefix(**id)
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|id| id.len())
+            .copied()
+    }
+
+    pub fn field_for_setting(&self, key: &str) -> Option<&Arc<Field>> {
+        let key = key
+            .strip_prefix(self.unwrap_prefix())?
+            .strip_prefix('.')
+            .filter(|key| !key.is_empty())?;
+        let id = match self.typ {
+            SchemaType::Record { .. } => self.field_for_relative_key(key.split_once('.')?.1)?,
+            SchemaType::Entry { .. } => "_value",
+            SchemaType::List => return None,
+        };
+        self.fields
+            .get(id)
+            .filter(|_| !id.starts_with('_') || id == "_value")
     }
 }
 
+impl Schemas {
+    pub fn is_secret_setting(&self, key: &str) -> bool {
+        self.schemas.values().any(|schema| {
+            match schema.typ {
+                // Keys of lists are the field ids, or items of an array field
+                SchemaType::List => schema.fields.get(key).or_else(|| {
+                    key.rsplit_once('.')
+                        .and_then(|(id, _)| schema.fields.get(id))
+                }),
+                _ => schema.field_for_setting(key),
+            }
+            .is_some_and(|field| matches!(field.typ_, Type::Secret))
+        })
+    }
+}
+
+pub fn diff_settings(before: &Settings, after: &Settings) -> Vec<SettingChange> {
+    let mut changes = before
+        .iter()
+        .filter(|(key, value)| after.get(*key) != Some(*value))
+        .map(|(key, value)| SettingChange {
+            key: key.clone(),
+            old_value: Some(value.clone()),
+            new_value: after.get(key).cloned(),
+        })
+        .chain(
+            after
+                .iter()
+                .filter(|(key, _)| !before.contains_key(*key))
+                .map(|(key, value)| SettingChange {
+                    key: key.clone(),
+                    old_value: None,
+                    new_value: Some(value.clone()),
+                }),
+        )
+        .collect::<Vec<_>>();
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+pub async fn fetch_all_settings(api: ApiClient) -> http::Result<Settings> {
+    api.get("/api/settings/list")
+        .with_parameter("prefix", "")
+        .send::<edit::FetchSettings>()
+        .await
+        .map(|list| list.items)
+}
+
 pub trait SettingsValues {
     fn array_values(&self, prefix: &str) -> Vec<(&str, &str)>;
     fn format(&self, field: &Field) -> String;
@@ -214,7 +553,7 @@ impl SettingsValues for Settings {
         match &field.typ_ {
             Type::Select {
                 source: Source::Static(items),
-                typ: SelectType::Single,
+                typ: SelectType::Single | SelectType::Radio,
             } => {
                 let value = self
                     .get(field.id)
@@ -263,313 +602,366 @@ impl SettingsValues for Settings {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct RecentSettings(RwSignal<Vec<RecentSetting>>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentSetting {
+    pub title: String,
+    pub url: String,
+}
+
+impl RecentSettings {
+    pub fn load() -> Self {
+        Self(create_rw_signal(
+            LocalStorage::get(RECENT_SETTINGS_KEY).unwrap_or_default(),
+        ))
+    }
+
+    pub fn get(&self) -> Vec<RecentSetting> {
+        self.0.get()
+    }
+
+    pub fn record(&self, title: String, url: String) {
+        self.0.update(|recent| {
+            recent.retain(|item| item.url != url);
+            recent.insert(0, RecentSetting { title, url });
+            recent.truncate(MAX_RECENT_SETTINGS);
+            if let Err(err) = LocalStorage::set(RECENT_SETTINGS_KEY, &*recent) {
+                log::warn!("Failed to save recent settings: {err}");
+            }
+        });
+    }
+}
+
+pub fn use_recent_settings() -> Option<RecentSettings> {
+    use_context::<RecentSettings>()
+}
+
 impl LayoutBuilder {
-    pub fn settings(manage_url: &'static str) -> Vec<MenuItem> {
-        LayoutBuilder::new("/settings")
+    pub fn settings(manage_url: &'static str, recent: &[RecentSetting]) -> Vec<MenuItem> {
+        let mut builder = LayoutBuilder::new("/settings");
+        if !recent.is_empty() {
+            builder = builder
+                .create("menu.recent-settings")
+                .icon(view! { <IconClock/> });
+            for item in recent {
+                builder = builder
+                    .create_named(item.url.clone(), item.title.clone())
+                    .raw_route(item.url.clone())
+                    .insert(true);
+            }
+            builder = builder.insert(true);
+        }
+
+        builder
             // Server
-            .create("Server")
+            .create("menu.server")
             .icon(view! { <IconServerStack/> })
             // Network
-            .create("Network")
+            .create("menu.network")
             .route("/network/edit")
             .insert(true)
             // HTTP
-            .create("HTTP")
+            .create("menu.http")
             .route("/http/edit")
             .insert(true)
             // System
-            .create("System")
+            .create("menu.system")
             .route("/system/edit")
             .insert(true)
             // Listener
-            .create("Listeners")
+            .create("menu.listeners")
             .route("/listener")
             .insert(true)
             // TLS
-            .create("TLS")
-            .create("ACME Providers")
+            .create("menu.tls")
+            .create("menu.acme-providers")
             .route("/acme")
             .insert(true)
-            .create("Certificates")
+            .create("menu.certificates")
             .route("/certificate")
             .insert(true)
-            .create("Defaults")
+            .create("menu.defaults")
             .route("/tls/edit")
             .insert(true)
             .insert(true)
             // System
-            .create("Cluster")
+            .create("menu.cluster")
             .route("/cluster/edit")
             .insert(true)
             // Cache
-            .create("Cache")
+            .create("menu.cache")
             .route("/cache/edit")
             .insert(true)
             // Contact form
-            .create("Form submission")
+            .create("menu.form-submission")
             .route("/form/edit")
             .insert(true)
             // Enterprise
-            .create("AI Models")
+            .create("menu.ai-models")
             .route("/ai-models")
             .insert(true)
             // Enterprise
-            .create("Enterprise")
+            .create("menu.enterprise")
             .route("/enterprise/edit")
             .insert(true)
             .insert(true)
             // Storage
-            .create("Storage")
+            .create("menu.storage")
             .icon(view! { <IconCircleStack/> })
-            .create("Settings")
+            .create("menu.settings")
             .route("/storage/edit")
             .insert(true)
-            .create("Stores")
+            .create("menu.stores")
             .route("/store")
             .insert(true)
             .insert(true)
             // Authentication
-            .create("Authentication")
+            .create("menu.authentication")
             .icon(view! { <IconKey/> })
-            .create("Settings")
+            .create("menu.settings")
             .route("/authentication/edit")
             .insert(true)
-            .create("Directories")
+            .create("menu.directories")
             .route("/directory")
             .insert(true)
-            .create("OAuth")
+            .create("menu.oauth")
             .route("/oauth/edit")
             .insert(true)
-            .create("OpenID Connect")
+            .create("menu.openid-connect")
             .route("/openid/edit")
             .insert(true)
+            .create("menu.single-sign-on")
+            .route("/single-sign-on/edit")
+            .insert(true)
             .insert(true)
             // SMTP
-            .create("SMTP")
+            .create("menu.smtp")
             .icon(view! { <IconInboxArrowDown/> })
-            .create("Inbound")
-            .create("Connect stage")
+            .create("menu.inbound")
+            .create("menu.connect-stage")
             .route("/smtp-in-connect/edit")
             .insert(true)
-            .create("EHLO stage")
+            .create("menu.ehlo-stage")
             .route("/smtp-in-ehlo/edit")
             .insert(true)
-            .create("AUTH stage")
+            .create("menu.auth-stage")
             .route("/smtp-in-auth/edit")
             .insert(true)
-            .create("MAIL stage")
+            .create("menu.mail-stage")
             .route("/smtp-in-mail/edit")
             .insert(true)
-            .create("RCPT stage")
+            .create("menu.rcpt-stage")
             .route("/smtp-in-rcpt/edit")
             .insert(true)
-            .create("DATA stage")
+            .create("menu.data-stage")
             .route("/smtp-in-data/edit")
             .insert(true)
-            .create("Extensions")
+            .create("menu.extensions")
             .route("/smtp-in-extensions/edit")
             .insert(true)
-            .create("Session Limits")
+            .create("menu.session-limits")
             .route("/smtp-in-limits/edit")
             .insert(true)
-            .create("MTA-STS")
+            .create("menu.mta-sts")
             .route("/smtp-in-mta-sts/edit")
             .insert(true)
-            .create("Throttles")
+            .create("menu.throttles")
             .route("/smtp-in-throttle")
             .insert(true)
-            .create("Milters")
+            .create("menu.milters")
             .route("/milter")
             .insert(true)
-            .create("MTA Hooks")
+            .create("menu.mta-hooks")
             .route("/mta-hooks")
             .insert(true)
-            .create("Pipes")
+            .create("menu.pipes")
             .route("/pipe")
             .insert(true)
             .insert(true)
-            .create("Outbound")
-            .create("Queue")
+            .create("menu.outbound")
+            .create("menu.queue")
             .route("/smtp-out-queue/edit")
             .insert(true)
-            .create("Routing")
+            .create("menu.routing")
             .route("/smtp-out-routing/edit")
             .insert(true)
-            .create("TLS")
+            .create("menu.tls")
             .route("/smtp-out-tls/edit")
             .insert(true)
-            .create("Limits")
+            .create("menu.limits")
             .route("/smtp-out-limits/edit")
             .insert(true)
-            .create("DNS Resolver")
+            .create("menu.dns-resolver")
             .route("/smtp-out-resolver/edit")
             .insert(true)
-            .create("Remote Hosts")
+            .create("menu.remote-hosts")
             .route("/smtp-out-remote")
             .insert(true)
-            .create("Throttles")
+            .create("menu.throttles")
             .route("/smtp-out-throttle")
             .insert(true)
-            .create("Quotas")
+            .create("menu.quotas")
             .route("/smtp-out-quota")
             .insert(true)
             .insert(true)
-            .create("DKIM")
-            .create("Settings")
+            .create("menu.dkim")
+            .create("menu.settings")
             .route("/dkim/edit")
             .insert(true)
-            .create("Signatures")
+            .create("menu.signatures")
             .route("/signature")
             .insert(true)
             .insert(true)
-            .create("ARC")
+            .create("menu.arc")
             .route("/arc/edit")
             .insert(true)
-            .create("SPF")
+            .create("menu.spf")
             .route("/spf/edit")
             .insert(true)
-            .create("DMARC")
+            .create("menu.dmarc")
             .route("/dmarc/edit")
             .insert(true)
-            .create("Reporting")
+            .create("menu.reporting")
             .route("/report/edit")
             .insert(true)
             .insert(true)
             // JMAP
-            .create("JMAP")
+            .create("menu.jmap")
             .icon(view! { <IconInboxStack/> })
-            .create("Session")
+            .create("menu.session")
             .route("/jmap-session/edit")
             .insert(true)
-            .create("Push Notifications")
+            .create("menu.push-notifications")
             .route("/jmap-push/edit")
             .insert(true)
-            .create("Web Sockets")
+            .create("menu.web-sockets")
             .route("/jmap-web-sockets/edit")
             .insert(true)
-            .create("Protocol Limits")
+            .create("menu.protocol-limits")
             .route("/jmap-limits/edit")
             .insert(true)
-            .create("Rate Limits")
+            .create("menu.rate-limits")
             .route("/jmap-rate-limit/edit")
             .insert(true)
             .insert(true)
             // IMAP
-            .create("IMAP & POP3")
+            .create("menu.imap-pop3")
             .icon(view! { <IconInbox/> })
-            .create("Authentication")
+            .create("menu.authentication")
             .route("/imap-auth/edit")
             .insert(true)
-            .create("Folders")
+            .create("menu.folders")
             .route("/imap-folders/edit")
             .insert(true)
-            .create("Protocol Limits")
+            .create("menu.protocol-limits")
             .route("/imap-limits/edit")
             .insert(true)
-            .create("Rate Limits")
+            .create("menu.rate-limits")
             .route("/imap-rate-limit/edit")
             .insert(true)
             .insert(true)
             // Security
-            .create("Security")
+            .create("menu.security")
             .icon(view! { <IconHandRaised/> })
             // Threat Shield
-            .create("Automatic Ban")
+            .create("menu.automatic-ban")
             .route("/auto-ban/edit")
             .insert(true)
             // Blocked IPs
-            .create("Blocked IPs")
+            .create("menu.blocked-ips")
             .route("/blocked-ip")
             .insert(true)
             // Blocked IPs
-            .create("Allowed IPs")
+            .create("menu.allowed-ips")
             .route("/allowed-ip")
             .insert(true)
             .insert(true)
             // Telemetry
-            .create("Telemetry")
+            .create("menu.telemetry")
             .icon(view! { <IconSignal/> })
-            .create("Logging & Tracing")
+            .create("menu.logging-tracing")
             .route("/tracing")
             .insert(true)
-            .create("Metrics")
+            .create("menu.metrics")
             .route("/metrics/edit")
             .insert(true)
-            .create("Alerts")
+            .create("menu.alerts")
             .route("/alerts")
             .insert(true)
-            .create("Webhooks")
+            .create("menu.webhooks")
             .route("/web-hooks")
             .insert(true)
-            .create("Custom levels")
+            .create("menu.custom-levels")
             .route("/custom-levels")
             .insert(true)
-            .create("History")
+            .create("menu.history")
             .route("/telemetry-history/edit")
             .insert(true)
             .insert(true)
             // SPAM Filter
-            .create("Antispam")
+            .create("menu.antispam")
             .icon(view! { <IconShieldCheck/> })
-            .create("Settings")
+            .create("menu.settings")
             .route("/spam-settings/edit")
             .insert(true)
-            .create("Bayes classifier")
+            .create("menu.bayes-classifier")
             .route("/spam-bayes/edit")
             .insert(true)
-            .create("LLM classifier")
+            .create("menu.llm-classifier")
             .route("/spam-llm/edit")
             .insert(true)
-            .create("Scores")
+            .create("menu.scores")
             .route("/spam-scores")
             .insert(true)
-            .create("Domains")
-            .create("Free domains")
+            .create("menu.domains")
+            .create("menu.free-domains")
             .route("/spam-free")
             .insert(true)
-            .create("Disposable domains")
+            .create("menu.disposable-domains")
             .route("/spam-disposable")
             .insert(true)
-            .create("URL Redirectors")
+            .create("menu.url-redirectors")
             .route("/spam-redirect")
             .insert(true)
-            .create("Trusted domains")
+            .create("menu.trusted-domains")
             .route("/spam-allow")
             .insert(true)
-            .create("Blocked domains")
+            .create("menu.blocked-domains")
             .route("/spam-block")
             .insert(true)
-            .create("DMARC domains")
+            .create("menu.dmarc-domains")
             .route("/spam-dmarc")
             .insert(true)
-            .create("SPF/DKIM domains")
+            .create("menu.spf-dkim-domains")
             .route("/spam-spdk")
             .insert(true)
             .insert(true)
-            .create("Spam traps")
+            .create("menu.spam-traps")
             .route("/spam-trap")
             .insert(true)
-            .create("MIME Types")
+            .create("menu.mime-types")
             .route("/spam-mime")
             .insert(true)
             .insert(true)
             // Sieve Scripting
-            .create("Scripting")
+            .create("menu.scripting")
             .icon(view! { <IconCodeBracket/> })
-            .create("Settings")
+            .create("menu.settings")
             .route("/sieve-settings/edit")
             .insert(true)
-            .create("Limits")
+            .create("menu.limits")
             .route("/sieve-limits/edit")
             .insert(true)
-            .create("System Scripts")
+            .create("menu.system-scripts")
             .route("/trusted-script")
             .insert(true)
-            .create("User Scripts")
+            .create("menu.user-scripts")
             .route("/untrusted-script")
             .insert(true)
             .insert(true)
-            .create("Management")
+            .create("menu.management")
             .icon(view! { <IconServer/> })
             .raw_route(manage_url)
             .insert(true)
@@ -0,0 +1,275 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::Arc;
+
+use leptos::*;
+use leptos_router::{use_navigate, use_query_map};
+use regex::Regex;
+
+use crate::{
+    components::{
+        form::{button::Button, Form, FormButtonBar, FormItem, FormSection},
+        list::table::{Table, TableRow},
+        messages::alert::{error_reason, use_alerts, Alert},
+        Color,
+    },
+    core::{api::use_api, http, i18n::t_signal, schema::Schemas},
+    pages::maybe_plural,
+};
+
+use super::{edit::ChangesReview, edit::FetchSettings, SettingChange, UpdateSettings};
+
+#[derive(Debug, Clone)]
+struct ReplaceError {
+    key: String,
+    reason: String,
+}
+
+#[component]
+pub fn SettingsReplace() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let query = use_query_map();
+    let schemas = expect_context::<Arc<Schemas>>();
+
+    let search =
+        create_rw_signal(query.with_untracked(|q| q.get("query").cloned().unwrap_or_default()));
+    let replace = create_rw_signal(String::new());
+    let use_regex = create_rw_signal(false);
+    let changes = create_rw_signal(None::<Arc<Vec<SettingChange>>>);
+    let errors = create_rw_signal(Vec::<ReplaceError>::new());
+
+    let preview_action = create_action(
+        move |(search, replace, use_regex): &(String, String, bool)| {
+            let (search, replace, use_regex) = (search.clone(), replace.clone(), *use_regex);
+            let schemas = schemas.clone();
+
+            async move {
+                let pattern = if use_regex {
+                    match Regex::new(&search) {
+                        Ok(regex) => Some(regex),
+                        Err(err) => {
+                            alert.set(
+                                Alert::error("Invalid regular expression")
+                                    .with_details(err.to_string()),
+                            );
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                match api
+                    .get("/api/settings/list")
+                    .with_parameter("prefix", "")
+                    .send::<FetchSettings>()
+                    .await
+                {
+                    Ok(list) => {
+                        let mut results = list
+                            .items
+                            .into_iter()
+                            // Secrets are neither shown nor rewritten
+                            .filter(|(key, _)| !schemas.is_secret_setting(key))
+                            .filter_map(|(key, value)| {
+                                let new_value = match &pattern {
+                                    Some(regex) if regex.is_match(&value) => {
+                                        regex.replace_all(&value, replace.as_str()).into_owned()
+                                    }
+                                    None if value.contains(&search) => {
+                                        value.replace(&search, &replace)
+                                    }
+                                    _ => return None,
+                                };
+                                (new_value != value).then_some(SettingChange {
+                                    key,
+                                    old_value: Some(value),
+                                    new_value: Some(new_value),
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        results.sort_by(|a, b| a.key.cmp(&b.key));
+                        errors.set(vec![]);
+                        if results.is_empty() {
+                            changes.set(None);
+                            alert.set(Alert::warning("No setting values match the search."));
+                        } else {
+                            changes.set(Some(Arc::new(results)));
+                        }
+                    }
+                    Err(http::Error::Unauthorized) => {
+                        use_navigate()("/login", Default::default());
+                    }
+                    Err(err) => {
+                        alert.set(Alert::from(err));
+                    }
+                }
+            }
+        },
+    );
+
+    let apply_action = create_action(move |changes_: &Arc<Vec<SettingChange>>| {
+        let changes_ = changes_.clone();
+
+        async move {
+            let mut failed = Vec::new();
+            for change in changes_.iter() {
+                let Some(new_value) = &change.new_value else {
+                    continue;
+                };
+                if let Err(err) = api
+                    .post("/api/settings")
+                    .with_body(vec![UpdateSettings::Insert {
+                        prefix: None,
+                        values: vec![(change.key.clone(), new_value.clone())],
+                        assert_empty: false,
+                    }])
+                    .unwrap()
+                    .send::<()>()
+                    .await
+                {
+                    if matches!(err, http::Error::Unauthorized) {
+                        use_navigate()("/login", Default::default());
+                        return;
+                    }
+                    failed.push(ReplaceError {
+                        key: change.key.clone(),
+                        reason: error_reason(err),
+                    });
+                }
+            }
+
+            let updated = changes_.len() - failed.len();
+            changes.set(None);
+            if failed.is_empty() {
+                alert.set(Alert::success(format!(
+                    "Updated {}.",
+                    maybe_plural(updated, "setting", "settings")
+                )));
+            } else {
+                alert.set(Alert::warning(format!(
+                    "Updated {}, {} failed.",
+                    maybe_plural(updated, "setting", "settings"),
+                    failed.len()
+                )));
+            }
+            errors.set(failed);
+        }
+    });
+    let is_applying = apply_action.pending();
+
+    view! {
+        <Form
+            title="Search and replace"
+            subtitle="Find a value across all settings and replace it in bulk, secrets are left out"
+        >
+
+            <FormSection>
+                <FormItem label="Search for">
+                    <input
+                        type="text"
+                        class="py-3 px-4 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        prop:value=search
+                        on:input=move |ev| {
+                            search.set(event_target_value(&ev));
+                            changes.set(None);
+                        }
+                    />
+                </FormItem>
+                <FormItem label="Replace with" is_optional=true>
+                    <input
+                        type="text"
+                        class="py-3 px-4 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        prop:value=replace
+                        on:input=move |ev| {
+                            replace.set(event_target_value(&ev));
+                            changes.set(None);
+                        }
+                    />
+                </FormItem>
+                <FormItem
+                    label="Regular expression"
+                    tooltip="Treat the search text as a regular expression. Use $1, $2 to refer to captured groups."
+                >
+                    <label class="inline-flex items-center gap-x-2 text-sm text-gray-600 dark:text-gray-400">
+                        <input
+                            type="checkbox"
+                            class="shrink-0 border-gray-300 rounded text-blue-600 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-600 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                            prop:checked=use_regex
+                            on:change=move |ev| {
+                                use_regex.set(event_target_checked(&ev));
+                                changes.set(None);
+                            }
+                        />
+                        "Match using a regular expression"
+                    </label>
+                </FormItem>
+            </FormSection>
+
+            {move || changes.get().map(|changes| view! { <ChangesReview changes/> })}
+
+            <Show when=move || errors.with(|errors| !errors.is_empty())>
+                <div class="mt-5">
+                    <h2 class="pb-4 text-lg font-semibold text-gray-800 dark:text-gray-200">
+                        Failed updates
+                    </h2>
+                    <Table headers=vec!["Setting".to_string(), "Reason".to_string()]>
+                        {move || {
+                            errors
+                                .get()
+                                .into_iter()
+                                .map(|error| {
+                                    view! {
+                                        <TableRow>
+                                            <span class="font-mono">{error.key}</span>
+                                            <span>{error.reason}</span>
+                                        </TableRow>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+
+                    </Table>
+                </div>
+            </Show>
+
+            <FormButtonBar>
+                <Button
+                    text="Preview changes"
+                    color=Color::Gray
+                    on_click=Callback::new(move |_| {
+                        let search = search.get();
+                        if search.is_empty() {
+                            alert.set(Alert::warning("Enter the text to search for."));
+                        } else {
+                            preview_action.dispatch((search, replace.get(), use_regex.get()));
+                        }
+                    })
+
+                    disabled=Signal::derive(move || {
+                        preview_action.pending().get() || is_applying.get()
+                    })
+                />
+
+                <Button
+                    text=t_signal("button.apply-changes")
+                    color=Color::Blue
+                    on_click=Callback::new(move |_| {
+                        if let Some(changes) = changes.get() {
+                            apply_action.dispatch(changes);
+                        }
+                    })
+
+                    disabled=Signal::derive(move || {
+                        changes.with(Option::is_none) || is_applying.get()
+                    })
+                />
+            </FormButtonBar>
+        </Form>
+    }
+}
@@ -39,6 +39,11 @@ impl Builder<Schemas, ()> {
                 "A rescue admin user can access the server in case the ",
                 "directory becomes unavailable"
             ))
+            .description(concat!(
+                "The fallback administrator is defined in the local configuration ",
+                "file rather than in the directory, so it keeps working when the ",
+                "directory is misconfigured or its backend is down"
+            ))
             .typ(Type::Input)
             .input_check([Transformer::Trim], [])
             .build()
@@ -59,6 +64,12 @@ impl Builder<Schemas, ()> {
                 "using 'user-login%master-user' as the login name. ",
                 "Leave blank to disable"
             ))
+            .description(concat!(
+                "For example, the master user 'master' logs in to the account of ",
+                "'jane' with the login name 'jane%master' and its own password. ",
+                "Use it for migrations and support only, as it bypasses the ",
+                "credentials of every account"
+            ))
             .typ(Type::Input)
             .input_check([Transformer::Trim], [])
             .build()
@@ -70,6 +81,55 @@ impl Builder<Schemas, ()> {
             .typ(Type::Secret)
             .input_check([Transformer::Trim], [])
             .build()
+            // Password policy
+            .new_field("authentication.password.min-length")
+            .label("Minimum length")
+            .help("Minimum number of characters required when users change their password")
+            .typ(Type::Input)
+            .default("8")
+            .input_check(
+                [Transformer::Trim],
+                [
+                    Validator::Required,
+                    Validator::MinValue(1.into()),
+                    Validator::MaxValue(128.into()),
+                ],
+            )
+            .build()
+            .new_field("authentication.password.require-uppercase")
+            .label("Require uppercase")
+            .help("Whether passwords must contain at least one uppercase letter")
+            .typ(Type::Boolean)
+            .default("false")
+            .build()
+            .new_field("authentication.password.require-lowercase")
+            .label("Require lowercase")
+            .help("Whether passwords must contain at least one lowercase letter")
+            .typ(Type::Boolean)
+            .default("false")
+            .build()
+            .new_field("authentication.password.require-number")
+            .label("Require number")
+            .help("Whether passwords must contain at least one digit")
+            .typ(Type::Boolean)
+            .default("false")
+            .build()
+            .new_field("authentication.password.require-symbol")
+            .label("Require symbol")
+            .help("Whether passwords must contain at least one symbol")
+            .typ(Type::Boolean)
+            .default("false")
+            .build()
+            .required_if(
+                "authentication.fallback-admin.secret",
+                "authentication.fallback-admin.user",
+            )
+            .required_if(
+                "authentication.fallback-admin.user",
+                "authentication.fallback-admin.secret",
+            )
+            .required_if("authentication.master.secret", "authentication.master.user")
+            .required_if("authentication.master.user", "authentication.master.secret")
             .new_form_section()
             .title("Authentication")
             .fields(["storage.directory"])
@@ -86,6 +146,16 @@ impl Builder<Schemas, ()> {
             .fields(["authentication.master.user", "authentication.master.secret"])
             .build()
             .new_form_section()
+            .title("Password Policy")
+            .fields([
+                "authentication.password.min-length",
+                "authentication.password.require-uppercase",
+                "authentication.password.require-lowercase",
+                "authentication.password.require-number",
+                "authentication.password.require-symbol",
+            ])
+            .build()
+            .new_form_section()
             .title("Security")
             .fields(["authentication.rate-limit"])
             .build()
@@ -206,5 +276,28 @@ impl Builder<Schemas, ()> {
             .fields(["oauth.oidc.signature-algorithm", "oauth.oidc.signature-key"])
             .build()
             .build()
+            // Single sign-on
+            .new_schema("single-sign-on")
+            .new_field("webadmin.sso.issuer")
+            .label("Identity provider")
+            .help(concat!(
+                "Issuer URL of the OpenID Connect provider administrators sign in with. ",
+                "The login page is opened with it filled in from ",
+                "/login?sso-issuer=<issuer>&sso-client-id=<client id>"
+            ))
+            .typ(Type::Input)
+            .input_check([Transformer::Trim], [Validator::IsUrl])
+            .build()
+            .new_field("webadmin.sso.client-id")
+            .label("Client ID")
+            .help("Client ID registered for the web admin with the identity provider")
+            .typ(Type::Input)
+            .input_check([Transformer::Trim], [])
+            .build()
+            .new_form_section()
+            .title("Single sign-on")
+            .fields(["webadmin.sso.issuer", "webadmin.sso.client-id"])
+            .build()
+            .build()
     }
 }
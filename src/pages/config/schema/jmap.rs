@@ -136,6 +136,7 @@ impl Builder<Schemas, ()> {
             .typ(Type::Duration)
             .input_check([], [Validator::Required])
             .build()
+            .less_than("jmap.email.max-attachment-size", "jmap.email.max-size")
             .new_form_section()
             .title("Request Limits")
             .fields([
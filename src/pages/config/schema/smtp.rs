@@ -669,6 +669,7 @@ impl Builder<Schemas, ()> {
             ))
             .typ(Type::Input)
             .input_check([Transformer::Trim], [Validator::MinValue(1.into())])
+            .enable_if_eq("enable", ["true"])
             .build()
             .new_field("rate")
             .label("Rate limit")
@@ -677,6 +678,7 @@ impl Builder<Schemas, ()> {
                 "that the rate limiter will allow"
             ))
             .typ(Type::Rate)
+            .enable_if_eq("enable", ["true"])
             .build()
             .new_form_section()
             .title("Throttle")
@@ -743,6 +745,7 @@ impl Builder<Schemas, ()> {
             ))
             .typ(Type::Input)
             .input_check([Transformer::Trim], [Validator::MinValue(1.into())])
+            .enable_if_eq("enable", ["true"])
             .build()
             .new_field("size")
             .label("Max Size")
@@ -751,6 +754,7 @@ impl Builder<Schemas, ()> {
                 "this quota will allow"
             ))
             .typ(Type::Size)
+            .enable_if_eq("enable", ["true"])
             .build()
             .new_form_section()
             .title("Quota")
@@ -1713,6 +1717,8 @@ impl Builder<Schemas, ()> {
             .label("Timeout")
             .help("Maximum time to wait for the command to complete")
             .default("30s")
+            .typ(Type::Duration)
+            .input_check([], [Validator::Required])
             .build()
             .new_form_section()
             .title("Pipe settings")
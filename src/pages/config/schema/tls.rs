@@ -84,10 +84,29 @@ impl Builder<Schemas, ()> {
                     ("dns-01", "DNS-01"),
                     ("http-01", "HTTP-01"),
                 ]),
-                typ: SelectType::Single,
+                typ: SelectType::Radio,
             })
+            .option_help(&[
+                (
+                    "tls-alpn-01",
+                    "Answered on the TLS port, port 443 must be reachable",
+                ),
+                (
+                    "dns-01",
+                    "Answered with a TXT record, required for wildcard domains",
+                ),
+                (
+                    "http-01",
+                    "Answered on the HTTP port, port 80 must be reachable",
+                ),
+            ])
             .label("Challenge type")
             .help("The ACME challenge type used to validate domain ownership")
+            .description(concat!(
+                "TLS-ALPN-01 works with the default listeners. HTTP-01 requires ",
+                "an HTTP listener on port 80, and DNS-01 requires a DNS provider ",
+                "that Stalwart can update"
+            ))
             .input_check([], [Validator::Required])
             .default("tls-alpn-01")
             .build()
@@ -7,11 +7,12 @@
 use std::sync::Arc;
 
 use leptos::*;
-use leptos_router::use_query_map;
+use leptos_router::{use_navigate, use_query_map};
 
 use crate::{
     components::{list::ZeroResults, report::ReportView},
-    pages::config::Schemas,
+    core::{oauth::use_authorization, url::UrlBuilder, Permission},
+    pages::config::{use_recent_settings, Schemas},
 };
 
 use super::{Field, Form, Section};
@@ -20,6 +21,18 @@ use super::{Field, Form, Section};
 pub fn SettingsSearch() -> impl IntoView {
     let query = use_query_map();
     let schemas = expect_context::<Arc<Schemas>>();
+    let auth = use_authorization();
+    let can_replace = auth
+        .get_untracked()
+        .permissions()
+        .has_access_all(&[Permission::SettingsList, Permission::SettingsUpdate]);
+    let replace_url = move || {
+        UrlBuilder::new("/settings/replace")
+            .with_optional_parameter("query", query.with(|q| q.get("query").cloned()))
+            .finish()
+    };
+
+    let recent_settings = use_recent_settings();
 
     let results = create_memo(move |_| {
         let params = query.with(|q| {
@@ -88,8 +101,50 @@ pub fn SettingsSearch() -> impl IntoView {
                 })
                 .collect_view();
             if has_results {
+                // Only shown on the landing page, not when searching
+                let recent = recent_settings
+                    .filter(|_| {
+                        query.with(|q| q.get("query").filter(|q| !q.trim().is_empty()).is_none())
+                    })
+                    .map(|recent| recent.get())
+                    .unwrap_or_default();
+                let recent = (!recent.is_empty())
+                    .then(|| {
+                        view! {
+                            <h2 class="mb-3 text-sm font-semibold text-gray-800 dark:text-gray-200">
+                                Recently edited
+                            </h2>
+                            <div class="mb-8 flex flex-wrap gap-2">
+                                {recent
+                                    .iter()
+                                    .map(|item| {
+                                        view! {
+                                            <a
+                                                class="py-1.5 px-3 inline-flex items-center text-sm font-medium rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                                                href=item.url.clone()
+                                            >
+                                                {item.title.clone()}
+                                            </a>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    });
                 view! {
                     <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+                        {recent}
+                        <Show when=move || can_replace>
+                            <p class="mb-6 text-sm text-gray-600 dark:text-gray-400">
+                                "Looking for a value rather than a setting? "
+                                <a
+                                    class="text-blue-600 decoration-2 hover:underline font-medium"
+                                    href=replace_url
+                                >
+                                    "Search and replace setting values"
+                                </a>
+                            </p>
+                        </Show>
                         <div class="grid sm:grid-cols-2 md:grid-cols-3 xl:grid-cols-4 gap-3 sm:gap-6">
                             {results}
                         </div>
@@ -98,15 +153,32 @@ pub fn SettingsSearch() -> impl IntoView {
                 }
                     .into_view()
             } else {
-                view! {
-                    <ReportView>
-                        <ZeroResults
-                            title="No results"
-                            subtitle="No search settings were found with the selected criteria."
-                        />
-                    </ReportView>
+                if can_replace {
+                    view! {
+                        <ReportView>
+                            <ZeroResults
+                                title="No results"
+                                subtitle="No search settings were found with the selected criteria."
+                                button_text="Search setting values"
+                                button_action=Callback::new(move |_| {
+                                    use_navigate()(&replace_url(), Default::default());
+                                })
+                            />
+
+                        </ReportView>
+                    }
+                        .into_view()
+                } else {
+                    view! {
+                        <ReportView>
+                            <ZeroResults
+                                title="No results"
+                                subtitle="No search settings were found with the selected criteria."
+                            />
+                        </ReportView>
+                    }
+                        .into_view()
                 }
-                    .into_view()
             }
         }}
     }
@@ -0,0 +1,354 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use leptos_router::use_navigate;
+use serde::Deserialize;
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast},
+    Event, EventSource, MessageEvent,
+};
+
+use crate::{
+    components::{
+        form::button::Button,
+        icon::{IconCheckCircle, IconExclamationCircle},
+        Color,
+    },
+    core::{api::use_api, http, oauth::use_authorization, AccessToken},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct DeliveryStage {
+    #[serde(rename = "type")]
+    typ: String,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    hostname: Option<String>,
+    #[serde(default)]
+    domain: Option<String>,
+    #[serde(default)]
+    remote_ip: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StageMessage {
+    Batch(Vec<DeliveryStage>),
+    Single(DeliveryStage),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TestState {
+    Idle,
+    Running,
+    Completed,
+    Failed(String),
+}
+
+struct LiveSource {
+    source: EventSource,
+    on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(Event)>,
+}
+
+impl LiveSource {
+    fn close(self) {
+        let _ = self
+            .source
+            .remove_event_listener_with_callback("event", self.on_message.as_ref().unchecked_ref());
+        self.source.set_onmessage(None);
+        self.source.set_onerror(None);
+        self.source.close();
+    }
+}
+
+// Runs the server's delivery troubleshooter, which looks up the mail exchangers
+// of the target and talks SMTP to them up to RCPT TO without sending a message.
+#[component]
+pub fn TestDelivery(#[prop(into)] recipient: String) -> impl IntoView {
+    let api = use_api();
+    let auth = use_authorization();
+    let recipient = create_rw_signal(recipient);
+    let stages = create_rw_signal(Vec::<DeliveryStage>::new());
+    let state = create_rw_signal(TestState::Idle);
+    let source = store_value(None::<LiveSource>);
+
+    let stop = move || {
+        if let Some(live) = source.try_update_value(Option::take).flatten() {
+            live.close();
+        }
+    };
+    on_cleanup(stop);
+
+    let start = move |target: String| {
+        stop();
+        stages.set(Vec::new());
+        state.set(TestState::Running);
+
+        spawn_local(async move {
+            // Browsers cannot send headers with an EventSource, so a
+            // short-lived token is requested and passed in the URL instead.
+            let token = match api.get("/api/troubleshoot/token").send::<String>().await {
+                Ok(token) => token,
+                Err(http::Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                    return;
+                }
+                Err(http::Error::Aborted) => return,
+                Err(err) => {
+                    state.try_set(TestState::Failed(err.to_string()));
+                    return;
+                }
+            };
+
+            let url = troubleshoot_url(&auth.get_untracked(), &target, &token);
+            let event_source = match EventSource::new(&url) {
+                Ok(event_source) => event_source,
+                Err(err) => {
+                    state.try_set(TestState::Failed(format!("{err:?}")));
+                    return;
+                }
+            };
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |ev: MessageEvent| {
+                let Some(text) = ev.data().as_string() else {
+                    return;
+                };
+                let batch = match serde_json::from_str::<StageMessage>(&text) {
+                    Ok(StageMessage::Batch(batch)) => batch,
+                    Ok(StageMessage::Single(stage)) => vec![stage],
+                    Err(err) => {
+                        log::debug!("Ignoring malformed delivery stage: {err}");
+                        return;
+                    }
+                };
+                let is_completed = batch.iter().any(|stage| stage.typ == "completed");
+                stages.update(|stages| stages.extend(batch));
+                if is_completed {
+                    state.set(TestState::Completed);
+                    stop();
+                }
+            });
+            // The stream ends once the test is over, which browsers report as
+            // an error and would reconnect from, running the test again
+            let on_error = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+                if state.get_untracked() == TestState::Running {
+                    state.set(TestState::Failed(
+                        "The connection to the server was lost.".to_string(),
+                    ));
+                }
+                stop();
+            });
+            let _ = event_source
+                .add_event_listener_with_callback("event", on_message.as_ref().unchecked_ref());
+            event_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            event_source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+            // The component may be gone by now, in which case nothing else would close it
+            let live = LiveSource {
+                source: event_source,
+                on_message,
+                _on_error: on_error,
+            };
+            if let Some(Some(live)) = source.try_set_value(Some(live)) {
+                live.close();
+            }
+        });
+    };
+
+    let running = Signal::derive(move || state.get() == TestState::Running);
+
+    view! {
+        <div class="space-y-3">
+            <div class="flex gap-x-2">
+                <input
+                    type="text"
+                    class="py-2 px-3 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                    placeholder="user@example.org"
+                    prop:value=recipient
+                    disabled=running
+                    on:input=move |ev| recipient.set(event_target_value(&ev))
+                />
+                <Button
+                    text="Test delivery"
+                    color=Color::Blue
+                    on_click=move |_| {
+                        start(recipient.get_untracked().trim().to_string());
+                    }
+
+                    disabled=Signal::derive(move || {
+                        running.get() || recipient.with(|recipient| recipient.trim().is_empty())
+                    })
+                />
+
+            </div>
+
+            <div aria-live="polite">
+                {move || match state.get() {
+                    TestState::Idle => None,
+                    TestState::Running => {
+                        Some(
+                            view! { <p class="text-sm text-gray-500">Testing delivery...</p> }
+                                .into_view(),
+                        )
+                    }
+                    TestState::Completed => {
+                        Some(stages.with(|stages| delivery_outcome(stages)).into_view())
+                    }
+                    TestState::Failed(error) => {
+                        Some(
+                            view! {
+                                <DeliveryOutcome
+                                    is_success=false
+                                    summary="The delivery test could not be run."
+                                    details=Some(error)
+                                />
+                            }
+                                .into_view(),
+                        )
+                    }
+                }}
+
+            </div>
+
+            <ul class="space-y-1 text-xs font-mono">
+                {move || {
+                    stages
+                        .get()
+                        .into_iter()
+                        .filter(|stage| stage.typ != "completed")
+                        .map(|stage| view! { <StageItem stage/> })
+                        .collect_view()
+                }}
+
+            </ul>
+        </div>
+    }
+}
+
+#[component]
+fn StageItem(stage: DeliveryStage) -> impl IntoView {
+    let is_error = stage.typ.ends_with("Error");
+    let details = stage
+        .reason
+        .or(stage.hostname)
+        .or(stage.remote_ip)
+        .or(stage.domain);
+
+    view! {
+        <li
+            class=("text-gray-500", !is_error)
+            class=("text-red-600", is_error)
+            class=("dark:text-red-500", is_error)
+        >
+            {stage_label(&stage.typ)}
+            {details.map(|details| format!(": {details}"))}
+        </li>
+    }
+}
+
+#[component]
+fn DeliveryOutcome(
+    is_success: bool,
+    summary: &'static str,
+    details: Option<String>,
+) -> impl IntoView {
+    view! {
+        <div class="text-sm">
+            <span
+                class="inline-flex items-center gap-x-1.5"
+                class=("text-teal-600", is_success)
+                class=("dark:text-teal-500", is_success)
+                class=("text-red-600", !is_success)
+                class=("dark:text-red-500", !is_success)
+            >
+                {if is_success {
+                    view! { <IconCheckCircle attr:class="flex-shrink-0 size-4"/> }
+                } else {
+                    view! { <IconExclamationCircle attr:class="flex-shrink-0 size-4"/> }
+                }}
+                {summary}
+            </span>
+            {details
+                .map(|details| {
+                    view! { <p class="mt-1 text-xs text-gray-500 break-words">{details}</p> }
+                })}
+
+        </div>
+    }
+}
+
+fn delivery_outcome(stages: &[DeliveryStage]) -> impl IntoView {
+    let mut host = None;
+    let mut last_error = None;
+    for stage in stages {
+        match stage.typ.as_str() {
+            "deliveryAttemptStart" => host = stage.hostname.clone(),
+            "rcptToSuccess" => {
+                return view! {
+                    <DeliveryOutcome
+                        is_success=true
+                        summary="The recipient was accepted."
+                        details=host.map(|host| format!("Accepted by {host}"))
+                    />
+                };
+            }
+            typ if typ.ends_with("Error") => last_error = stage.reason.clone(),
+            _ => {}
+        }
+    }
+
+    view! {
+        <DeliveryOutcome
+            is_success=false
+            summary="No mail exchanger accepted the recipient."
+            details=last_error
+        />
+    }
+}
+
+fn stage_label(typ: &str) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for ch in typ.chars() {
+        if ch.is_ascii_uppercase() && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(ch.to_ascii_lowercase());
+    }
+    words.push(word);
+
+    let mut label = words
+        .into_iter()
+        .map(|word| match word.as_str() {
+            "mx" | "mta" | "sts" | "tls" | "rpt" | "tlsa" | "ip" | "ehlo" | "dane" | "rcpt" => {
+                word.to_uppercase()
+            }
+            _ => word,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(first) = label.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    label
+}
+
+fn troubleshoot_url(auth: &AccessToken, target: &str, token: &str) -> String {
+    let base_url = if auth.base_url.is_empty() {
+        window().location().origin().unwrap_or_default()
+    } else {
+        auth.base_url.to_string()
+    };
+
+    format!(
+        "{}/api/troubleshoot/delivery/{}?token={}",
+        base_url.trim_end_matches('/'),
+        form_urlencoded::byte_serialize(target.as_bytes()).collect::<String>(),
+        form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>()
+    )
+}
@@ -3,6 +3,7 @@
  *
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
+use gloo_storage::{LocalStorage, Storage};
 use leptos::*;
 use leptos_router::{use_navigate, use_params_map};
 use serde::{Deserialize, Serialize};
@@ -11,27 +12,54 @@ use std::ops::Add;
 use crate::{
     components::{
         card::{Card, CardItem},
+        clipboard::CopyButton,
         form::button::Button,
-        icon::{IconEnvelope, IconShieldCheck, IconUserGroup},
+        icon::{
+            IconCheckCircle, IconEnvelope, IconExclamationCircle, IconShieldCheck, IconUserGroup,
+        },
         list::table::{Table, TableRow},
-        messages::alert::{use_alerts, Alert, Alerts},
         report::ReportView,
         skeleton::Skeleton,
         Color,
     },
     core::{
+        api::use_api,
         http::{self, HttpRequest},
-        oauth::use_authorization,
+        i18n::t_signal,
+    },
+    pages::{
+        directory::{delivery::TestDelivery, Principal},
+        List,
     },
-    pages::{directory::Principal, List},
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct DnsRecord {
+const DNS_RESOLVER_KEY: &str = "webadmin_dns_resolver";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(super) struct DnsRecord {
     #[serde(rename = "type")]
-    typ: String,
-    name: String,
-    content: String,
+    pub(super) typ: String,
+    pub(super) name: String,
+    pub(super) content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum DnsCheck {
+    Published,
+    Mismatch(Vec<String>),
+    Missing,
+    Failed(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct DnsResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DnsAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DnsAnswer {
+    data: String,
 }
 
 fn format_zonefile(records: &[DnsRecord], domain: &str) -> String {
@@ -80,38 +108,60 @@ fn format_zonefile(records: &[DnsRecord], domain: &str) -> String {
 
 #[component]
 pub fn DnsDisplay() -> impl IntoView {
-    let auth = use_authorization();
-    let alert = use_alerts();
+    let api = use_api();
 
     let params = use_params_map();
     let domain_details = create_resource(
         move || params.get().get("id").cloned().unwrap_or_default(),
-        move |name| {
-            let auth = auth.get_untracked();
+        move |name| async move {
+            let result = api
+                .get(("/api/dns/records", &name))
+                .send::<Vec<DnsRecord>>()
+                .await?;
+            let user_count = api
+                .get("/api/principal")
+                .with_parameter("filter", &name)
+                .with_parameter("fields", "name")
+                .with_parameter("count", "1")
+                .with_parameter("types", "individual")
+                .send::<List<Principal>>()
+                .await
+                .map(|r| r.total)
+                .unwrap_or_default();
 
+            Ok((result, user_count))
+        },
+    );
+
+    // Records are only looked up when the user opted into a DoH resolver
+    let resolver = dns_resolver();
+    let has_resolver = resolver.is_some();
+    let recheck = create_rw_signal(0u32);
+    let dns_checks = create_resource(
+        move || {
+            (
+                domain_details
+                    .get()
+                    .and_then(|result| result.ok())
+                    .map(|(records, _)| records),
+                recheck.get(),
+            )
+        },
+        move |(records, _)| {
+            let resolver = resolver.clone();
             async move {
-                let result = HttpRequest::get(("/api/dns/records", &name))
-                    .with_authorization(&auth)
-                    .send::<Vec<DnsRecord>>()
-                    .await?;
-                let user_count = HttpRequest::get("/api/principal")
-                    .with_authorization(&auth)
-                    .with_parameter("filter", &name)
-                    .with_parameter("fields", "name")
-                    .with_parameter("count", "1")
-                    .with_parameter("types", "individual")
-                    .send::<List<Principal>>()
-                    .await
-                    .map(|r| r.total)
-                    .unwrap_or_default();
-
-                Ok((result, user_count))
+                let mut checks = Vec::new();
+                if let Some(resolver) = resolver {
+                    for record in records.unwrap_or_default() {
+                        checks.push(check_record(&resolver, &record).await);
+                    }
+                }
+                checks
             }
         },
     );
 
     view! {
-        <Alerts/>
         <Transition fallback=Skeleton>
 
             {move || match domain_details.get() {
@@ -124,10 +174,7 @@ pub fn DnsDisplay() -> impl IntoView {
                     use_navigate()("/manage/directory/domain", Default::default());
                     Some(view! { <div></div> }.into_view())
                 }
-                Some(Err(err)) => {
-                    alert.set(Alert::from(err));
-                    Some(view! { <div></div> }.into_view())
-                }
+                Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
                 Some(Ok((records, user_count))) => {
                     let signature_count = records
                         .iter()
@@ -165,24 +212,66 @@ pub fn DnsDisplay() -> impl IntoView {
                             <ReportView>
 
                                 <div class="gap-2 sm:gap-4 py-8 first:pt-0 last:pb-0 border-t first:border-transparent border-gray-200 dark:border-gray-700 dark:first:border-transparent">
-                                    <div class="sm:col-span-12 pb-4">
+                                    <div class="sm:col-span-12 pb-4 flex justify-between items-center">
                                         <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
                                             DNS Records
                                         </h2>
+                                        <Show when=move || has_resolver>
+                                            <Button
+                                                text="Re-check"
+                                                color=Color::Gray
+                                                on_click=move |_| {
+                                                    recheck.update(|n| *n += 1);
+                                                }
+
+                                                disabled=dns_checks.loading()
+                                            />
+                                        </Show>
                                     </div>
-                                    <Table headers=vec![
-                                        "Type".to_string(),
-                                        "Name".to_string(),
-                                        "Contents".to_string(),
-                                    ]>
+                                    <Show when=move || !has_resolver>
+                                        <p class="pb-4 text-sm text-gray-500">
+                                            Set a DNS-over-HTTPS resolver in Preferences to check whether these records are published.
+                                        </p>
+                                    </Show>
+                                    <Table headers=if has_resolver {
+                                        vec![
+                                            "Type".to_string(),
+                                            "Name".to_string(),
+                                            "Contents".to_string(),
+                                            "Status".to_string(),
+                                        ]
+                                    } else {
+                                        vec![
+                                            "Type".to_string(),
+                                            "Name".to_string(),
+                                            "Contents".to_string(),
+                                        ]
+                                    }>
                                         {records
                                             .into_iter()
-                                            .map(|record| {
+                                            .enumerate()
+                                            .map(|(idx, record)| {
+                                                let check = Signal::derive(move || {
+                                                    if !dns_checks.loading().get() {
+                                                        dns_checks
+                                                            .get()
+                                                            .and_then(|checks| checks.get(idx).cloned())
+                                                    } else {
+                                                        None
+                                                    }
+                                                });
                                                 view! {
                                                     <TableRow>
                                                         <span>{record.typ}</span>
                                                         <span>{record.name}</span>
-                                                        <span>{record.content}</span>
+                                                        <div class="flex items-center gap-x-2">
+                                                            <span class="truncate max-w-md">
+                                                                {record.content.clone()}
+                                                            </span>
+                                                            <CopyButton value=record.content/>
+                                                        </div>
+                                                        {has_resolver
+                                                            .then(|| view! { <DnsCheckStatus check/> })}
 
                                                     </TableRow>
                                                 }
@@ -204,10 +293,25 @@ pub fn DnsDisplay() -> impl IntoView {
 
                                 </div>
 
+                                <div class="gap-2 sm:gap-4 py-8 first:pt-0 last:pb-0 border-t first:border-transparent border-gray-200 dark:border-gray-700 dark:first:border-transparent">
+                                    <div class="sm:col-span-12 pb-4">
+                                        <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
+                                            Test delivery
+                                        </h2>
+                                        <p class="text-sm text-gray-500">
+                                            Checks that the mail exchangers of this domain accept mail for an address, without sending a message.
+                                        </p>
+                                    </div>
+                                    <TestDelivery recipient=format!(
+                                        "postmaster@{}",
+                                        params.get().get("id").map(|d| d.as_str()).unwrap_or_default(),
+                                    )/>
+                                </div>
+
                                 <div class="flex justify-end">
 
                                     <Button
-                                        text="Close"
+                                        text=t_signal("button.close")
                                         color=Color::Blue
                                         on_click=move |_| {
                                             use_navigate()(
@@ -228,3 +332,129 @@ pub fn DnsDisplay() -> impl IntoView {
         </Transition>
     }
 }
+
+#[component]
+fn DnsCheckStatus(check: Signal<Option<DnsCheck>>) -> impl IntoView {
+    move || match check.get() {
+        None => view! { <span class="text-gray-500">Checking...</span> }.into_view(),
+        Some(DnsCheck::Published) => view! {
+            <span class="inline-flex items-center gap-x-1.5 text-teal-600 dark:text-teal-500">
+                <IconCheckCircle attr:class="flex-shrink-0 size-4"/>
+                Published
+            </span>
+        }
+        .into_view(),
+        Some(DnsCheck::Missing) => view! {
+            <span class="inline-flex items-center gap-x-1.5 text-red-600 dark:text-red-500">
+                <IconExclamationCircle attr:class="flex-shrink-0 size-4"/>
+                Not found
+            </span>
+        }
+        .into_view(),
+        Some(DnsCheck::Mismatch(observed)) => view! {
+            <div>
+                <span class="inline-flex items-center gap-x-1.5 text-red-600 dark:text-red-500">
+                    <IconExclamationCircle attr:class="flex-shrink-0 size-4"/>
+                    Mismatch
+                </span>
+                {observed
+                    .into_iter()
+                    .map(|value| {
+                        view! {
+                            <span class="block truncate max-w-xs text-xs text-gray-500">
+                                {value}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        }
+        .into_view(),
+        Some(DnsCheck::Failed(reason)) => view! {
+            <span class="inline-flex items-center gap-x-1.5 text-yellow-600 dark:text-yellow-500">
+                <IconExclamationCircle attr:class="flex-shrink-0 size-4"/>
+                {reason}
+            </span>
+        }
+        .into_view(),
+    }
+}
+
+pub fn dns_resolver() -> Option<String> {
+    LocalStorage::get::<String>(DNS_RESOLVER_KEY)
+        .ok()
+        .filter(|url| !url.is_empty())
+}
+
+pub fn set_dns_resolver(url: &str) {
+    let url = url.trim();
+    if url.is_empty() {
+        LocalStorage::delete(DNS_RESOLVER_KEY);
+    } else {
+        let _ = LocalStorage::set(DNS_RESOLVER_KEY, url);
+    }
+}
+
+async fn check_record(resolver: &str, record: &DnsRecord) -> DnsCheck {
+    let response = HttpRequest::get(resolver)
+        .with_parameter("name", record.name.clone())
+        .with_parameter("type", record.typ.clone())
+        .with_header("Accept", "application/dns-json")
+        .send_raw()
+        .await;
+
+    match response.map(|response| serde_json::from_slice::<DnsResponse>(&response)) {
+        Ok(Ok(response)) => {
+            let expected = normalize_dns_value(&record.typ, &record.content);
+            let observed = response
+                .answer
+                .into_iter()
+                .map(|answer| answer.data)
+                .collect::<Vec<_>>();
+
+            if observed.is_empty() {
+                DnsCheck::Missing
+            } else if observed
+                .iter()
+                .any(|value| normalize_dns_value(&record.typ, value) == expected)
+            {
+                DnsCheck::Published
+            } else {
+                DnsCheck::Mismatch(observed)
+            }
+        }
+        Ok(Err(_)) => DnsCheck::Failed("Invalid resolver response".to_string()),
+        Err(_) => DnsCheck::Failed("Lookup failed".to_string()),
+    }
+}
+
+fn normalize_dns_value(typ: &str, value: &str) -> String {
+    let value = value.trim();
+    let value = if typ == "TXT" && value.starts_with('"') {
+        // Join the character-strings of a TXT record
+        let mut result = String::with_capacity(value.len());
+        let mut in_quotes = false;
+        let mut chars = value.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' => in_quotes = !in_quotes,
+                '\\' if in_quotes => {
+                    if let Some(ch) = chars.next() {
+                        result.push(ch);
+                    }
+                }
+                _ if in_quotes => result.push(ch),
+                _ => {}
+            }
+        }
+        result
+    } else {
+        value.to_string()
+    };
+
+    // Host names are case-insensitive, TXT values such as DKIM keys are not
+    match typ {
+        "CNAME" | "MX" | "NS" | "SRV" => value.trim_end_matches('.').to_lowercase(),
+        _ => value,
+    }
+}
@@ -8,6 +8,7 @@ use std::{sync::Arc, vec};
 
 use ahash::{AHashMap, AHashSet};
 use base64::{engine::general_purpose, Engine};
+use gloo_storage::{SessionStorage, Storage};
 use humansize::{format_size, DECIMAL};
 use leptos::*;
 use leptos_router::{use_navigate, use_params_map};
@@ -19,43 +20,55 @@ use crate::{
     components::{
         form::{
             button::Button,
+            dual_list::DualList,
             input::{InputPassword, InputSize, InputText},
             select::Select,
             stacked_badge::StackedBadge,
             stacked_input::StackedInput,
             tab::Tab,
-            Form, FormButtonBar, FormElement, FormItem, FormSection,
+            unsaved::use_unsaved_changes,
+            use_read_only_form, Form, FormButtonBar, FormElement, FormItem, FormSection,
+        },
+        messages::{
+            alert::{use_alerts, Alert},
+            conflict::{resolve_conflict, ConflictResolution},
+            modal::{use_modals, Modal},
         },
-        messages::alert::{use_alerts, Alert},
         skeleton::Skeleton,
         Color,
     },
     core::{
+        api::use_api,
         form::FormData,
-        http::{self, HttpRequest},
+        http,
+        i18n::t_signal,
         oauth::use_authorization,
-        schema::{Builder, Schemas, Transformer, Type, Validator},
+        schema::{Builder, Schemas, SelectType, Source, Transformer, Type, Validator},
+        shortcuts::use_save_shortcut,
         Permission,
     },
     pages::{
-        directory::{Principal, PrincipalType, PrincipalValue, PERMISSIONS},
+        config::Settings,
+        directory::{guard_principal_type, Principal, PrincipalType, PrincipalValue, PERMISSIONS},
         List,
     },
 };
 
-use super::{build_app_password, parse_app_password, SpecialSecrets};
+use super::{build_app_password, delivery::TestDelivery, parse_app_password, SpecialSecrets};
 
 type PrincipalMap = AHashMap<PrincipalType, Vec<(String, String)>>;
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-enum Algorithm {
+const CLONE_STORAGE_KEY: &str = "webadmin_principal_clone";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub(super) enum Algorithm {
     #[default]
     Rsa,
     Ed25519,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct DkimSignature {
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(super) struct DkimSignature {
     id: Option<String>,
     algorithm: Algorithm,
     domain: String,
@@ -65,8 +78,11 @@ struct DkimSignature {
 #[component]
 pub fn PrincipalEdit() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
+    let modal = use_modals();
     let params = use_params_map();
+    let read_only = use_read_only_form();
     let selected_type = create_memo(move |_| {
         match params
             .get()
@@ -90,6 +106,7 @@ pub fn PrincipalEdit() -> impl IntoView {
         .get_untracked()
         .permissions()
         .has_access(Permission::TenantList);
+    guard_principal_type(selected_type);
     let principals: RwSignal<Arc<PrincipalMap>> = create_rw_signal(Arc::new(AHashMap::new()));
 
     let fetch_principal = create_resource(
@@ -127,14 +144,11 @@ pub fn PrincipalEdit() -> impl IntoView {
             };
             let mut fetch_types = String::new();
             for typ in needed_types {
-                let permission = match typ {
-                    PrincipalType::Individual => Permission::IndividualList,
-                    PrincipalType::Group => Permission::GroupList,
-                    PrincipalType::List => Permission::MailingListList,
-                    PrincipalType::Domain => Permission::DomainList,
-                    PrincipalType::Tenant if is_enterprise => Permission::TenantList,
-                    PrincipalType::Role => Permission::RoleList,
-                    _ => continue,
+                let Some(permission) = typ
+                    .list_permission()
+                    .filter(|_| is_enterprise || *typ != PrincipalType::Tenant)
+                else {
+                    continue;
                 };
                 if permissions.has_access(permission) {
                     if !fetch_types.is_empty() {
@@ -147,10 +161,15 @@ pub fn PrincipalEdit() -> impl IntoView {
             async move {
                 // Fetch principal
                 let principal = if !name.is_empty() {
-                    HttpRequest::get(("/api/principal", &name))
-                        .with_authorization(&auth)
+                    api.get(("/api/principal", &name))
                         .send::<Principal>()
                         .await?
+                } else if let Some(principal) = SessionStorage::get::<Principal>(CLONE_STORAGE_KEY)
+                    .ok()
+                    .filter(|principal| principal.typ == Some(selected_type))
+                {
+                    SessionStorage::delete(CLONE_STORAGE_KEY);
+                    principal
                 } else {
                     // Add default roles
                     let mut principal = Principal::default();
@@ -200,8 +219,8 @@ pub fn PrincipalEdit() -> impl IntoView {
                 );
 
                 if !fetch_types.is_empty() {
-                    for principal in HttpRequest::get("/api/principal")
-                        .with_authorization(&auth)
+                    for principal in api
+                        .get("/api/principal")
                         .with_parameter("types", fetch_types)
                         .with_parameter("fields", "name,description")
                         .with_optional_parameter("tenant", principal.tenant.as_str())
@@ -234,74 +253,200 @@ pub fn PrincipalEdit() -> impl IntoView {
     let data = expect_context::<Arc<Schemas>>()
         .build_form("principals")
         .into_signal();
+    let unsaved = use_unsaved_changes(data);
+
+    let save_changes = create_action(
+        move |(changes, signatures): &(Principal, Vec<DkimSignature>)| {
+            let current = current_principal.get();
+            let changes = changes.clone();
+            let signatures = signatures.clone();
+            let auth = auth.get();
+            let selected_type = selected_type.get();
 
-    let save_changes = create_action(move |changes: &Principal| {
-        let current = current_principal.get();
-        let changes = changes.clone();
-        let auth = auth.get();
-        let selected_type = selected_type.get();
-
-        async move {
-            set_pending.set(true);
-            let result = if !current.is_blank() {
-                let name = current.name().unwrap_or_default().to_string();
-                let updates = current.into_updates(changes);
-
-                if !updates.is_empty() {
-                    HttpRequest::patch(("/api/principal", &name))
-                        .with_authorization(&auth)
-                        .with_body(updates)
+            async move {
+                set_pending.set(true);
+                let result = if !current.is_blank() {
+                    let name = current.name().unwrap_or_default().to_string();
+
+                    // Check that nobody else modified the principal in the meantime
+                    match api.get(("/api/principal", &name)).send::<Principal>().await {
+                        Ok(latest) => {
+                            let conflicts = latest.changes_since(&current);
+                            let base = if conflicts.is_empty() {
+                                current
+                            } else {
+                                match resolve_conflict(modal, format!("'{name}'"), conflicts).await
+                                {
+                                    ConflictResolution::Overwrite => latest,
+                                    ConflictResolution::Reload => {
+                                        set_pending.set(false);
+                                        fetch_principal.refetch();
+                                        return;
+                                    }
+                                    ConflictResolution::Cancel => {
+                                        set_pending.set(false);
+                                        return;
+                                    }
+                                }
+                            };
+                            let updates = base.into_updates(changes);
+
+                            if !updates.is_empty() {
+                                api.patch(("/api/principal", &name))
+                                    .with_body(updates)
+                                    .unwrap()
+                                    .send::<()>()
+                                    .await
+                            } else {
+                                Ok(())
+                            }
+                        }
+                        Err(err) => Err(err),
+                    }
+                } else {
+                    let result = api
+                        .post("/api/principal")
+                        .with_body(&changes)
                         .unwrap()
-                        .send::<()>()
+                        .send::<u32>()
                         .await
-                } else {
-                    Ok(())
+                        .map(|_| ());
+
+                    // Create DKIM keys
+                    if result.is_ok() {
+                        for signature in &signatures {
+                            if let Err(err) = api
+                                .post("/api/dkim")
+                                .with_body(signature)
+                                .unwrap()
+                                .send::<()>()
+                                .await
+                            {
+                                alert.set(Alert::from(err));
+                                break;
+                            }
+                        }
+                    }
+
+                    result
+                };
+                set_pending.set(false);
+
+                match result {
+                    Ok(_) => {
+                        unsaved.mark_clean();
+                        let permissions = auth.permissions();
+                        if let Some(signature) = signatures.first().filter(|_| {
+                            permissions.has_access(Permission::DkimSignatureGet)
+                                && permissions.has_access(Permission::DomainGet)
+                        }) {
+                            // Show the DNS records to publish, including the new DKIM keys
+                            unsaved.navigate(&format!("/manage/dns/{}/view", signature.domain));
+                        } else {
+                            unsaved.navigate(&format!(
+                                "/manage/directory/{}",
+                                selected_type.resource_name()
+                            ));
+                        }
+                    }
+                    Err(err) => {
+                        alert.set(Alert::from(err));
+                    }
                 }
-            } else {
-                let result = HttpRequest::post("/api/principal")
-                    .with_authorization(&auth)
-                    .with_body(&changes)
-                    .unwrap()
-                    .send::<u32>()
+            }
+        },
+    );
+
+    let can_create_dkim = create_memo(move |_| {
+        selected_type.get() == PrincipalType::Domain
+            && params.get().get("id").is_none()
+            && auth
+                .get()
+                .permissions()
+                .has_access(Permission::DkimSignatureCreate)
+    });
+
+    let check_dkim = create_action(
+        move |(changes, signatures): &(Principal, Vec<DkimSignature>)| {
+            let changes = changes.clone();
+            let mut signatures = signatures.clone();
+
+            async move {
+                let domain = changes.name().unwrap_or_default().to_string();
+                let existing = match api
+                    .get("/api/settings/group")
+                    .with_parameter("prefix", "signature")
+                    .with_parameter("suffix", "algorithm")
+                    .with_parameter("filter", domain.clone())
+                    .send::<List<Settings>>()
                     .await
-                    .map(|_| ());
-
-                // Create DKIM keys
-                if matches!(changes.typ, Some(PrincipalType::Domain))
-                    && result.is_ok()
-                    && auth
-                        .permissions()
-                        .has_access(Permission::DkimSignatureCreate)
                 {
-                    for algo in [Algorithm::Ed25519, Algorithm::Rsa] {
-                        let _ = HttpRequest::post("/api/dkim")
-                            .with_authorization(&auth)
-                            .with_body(DkimSignature {
-                                algorithm: algo,
-                                domain: changes.name().unwrap_or_default().to_string(),
-                                ..Default::default()
-                            })
-                            .unwrap()
-                            .send::<()>()
-                            .await;
+                    Ok(existing) => existing.items,
+                    Err(err) => {
+                        alert.set(Alert::from(err));
+                        return;
                     }
-                }
+                };
 
-                result
-            };
-            set_pending.set(false);
+                let mut conflicts = Vec::new();
+                for signature in &mut signatures {
+                    if let Some(id) = existing.iter().find_map(|settings| {
+                        (settings.get("domain") == Some(&domain)
+                            && settings.get("selector") == signature.selector.as_ref())
+                        .then(|| settings.get("_id").cloned())
+                        .flatten()
+                    }) {
+                        conflicts.push(signature.selector.clone().unwrap_or_default());
+                        signature.id = Some(id);
+                    }
+                }
 
-            match result {
-                Ok(_) => {
-                    use_navigate()(
-                        &format!("/manage/directory/{}", selected_type.resource_name()),
-                        Default::default(),
+                if conflicts.is_empty() {
+                    save_changes.dispatch((changes, signatures));
+                } else {
+                    modal.set(
+                        Modal::with_title("Overwrite DKIM keys")
+                            .with_message(format!(
+                                concat!(
+                                    "A DKIM signature with selector {} already exists for {}. ",
+                                    "Do you want to replace it with a new key?"
+                                ),
+                                conflicts.join(", "),
+                                domain
+                            ))
+                            .with_button("Overwrite")
+                            .with_dangerous_callback(move || {
+                                save_changes.dispatch((changes.clone(), signatures.clone()));
+                            }),
                     );
                 }
-                Err(err) => {
-                    alert.set(Alert::from(err));
+            }
+        },
+    );
+
+    let save = move || {
+        data.update(|data| {
+            if let Some(changes) = data.to_principal() {
+                let signatures = if can_create_dkim.get_untracked() {
+                    data.dkim_signatures(changes.name().unwrap_or_default())
+                } else {
+                    vec![]
+                };
+                if signatures.iter().any(|s| s.selector.is_some()) {
+                    check_dkim.dispatch((changes, signatures));
+                } else {
+                    save_changes.dispatch((changes, signatures));
                 }
             }
+        });
+    };
+
+    use_save_shortcut(move || {
+        if !pending.get_untracked()
+            && !check_dkim.pending().get_untracked()
+            && !read_only.get_untracked()
+        {
+            save();
         }
     });
 
@@ -353,676 +498,796 @@ pub fn PrincipalEdit() -> impl IntoView {
     view! {
         <Form title=title subtitle="".to_string()>
 
-            <Transition fallback=Skeleton set_pending>
+            <fieldset disabled=move || read_only.get()>
+                <Transition fallback=Skeleton set_pending>
 
-                {move || match fetch_principal.get() {
-                    None => None,
-                    Some(Err(http::Error::Unauthorized)) => {
-                        use_navigate()("/login", Default::default());
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Err(http::Error::NotFound)) => {
-                        let url = format!(
-                            "/manage/directory/{}",
-                            selected_type.get().resource_name(),
-                        );
-                        use_navigate()(&url, Default::default());
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Err(err)) => {
-                        alert.set(Alert::from(err));
-                        Some(view! { <div></div> }.into_view())
-                    }
-                    Some(Ok(principal)) => {
-                        data.update(|data| {
-                            data.from_principal(&principal, selected_type.get());
-                        });
-                        let used_quota = principal.used_quota.as_int().unwrap_or_default();
-                        let total_quota = principal.quota.as_int().unwrap_or_default();
-                        current_principal.set(principal);
-                        let typ = selected_type.get();
-                        Some(
-                            view! {
-                                <Tab tabs=Signal::derive(move || {
-                                    vec![
-                                        Some("Details".to_string()),
-                                        matches!(
-                                            typ,
-                                            PrincipalType::Individual | PrincipalType::ApiKey
-                                        )
-                                            .then_some("Authentication".to_string()),
-                                        matches!(
-                                            typ,
-                                            PrincipalType::Individual | PrincipalType::Tenant
-                                        )
-                                            .then_some("Limits".to_string()),
-                                        (!matches!(
-                                            typ,
-                                            PrincipalType::Tenant
-                                            | PrincipalType::Domain
-                                            | PrincipalType::OauthClient
-                                            | PrincipalType::ApiKey
-                                        ))
-                                            .then_some("Memberships".to_string()),
-                                        matches!(
-                                            typ,
-                                            PrincipalType::Individual
-                                            | PrincipalType::Group
-                                            | PrincipalType::Role
-                                            | PrincipalType::Tenant
-                                            | PrincipalType::ApiKey
-                                        )
-                                            .then_some("Permissions".to_string()),
-                                    ]
-                                })>
-
-                                    <FormSection stacked=true>
-                                        <FormItem
-                                            stacked=true
-                                            label=Signal::derive(move || {
-                                                match selected_type.get() {
-                                                    PrincipalType::Individual => "Login name",
-                                                    PrincipalType::Domain => "Domain name",
-                                                    PrincipalType::ApiKey => "Key Id",
-                                                    PrincipalType::OauthClient => "Client Id",
-                                                    _ => "Name",
-                                                }
-                                                    .to_string()
-                                            })
-                                        >
-
-                                            <InputText
-                                                placeholder=Signal::derive(move || {
+                    {move || match fetch_principal.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(http::Error::NotFound)) => {
+                            let url = format!(
+                                "/manage/directory/{}",
+                                selected_type.get().resource_name(),
+                            );
+                            use_navigate()(&url, Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
+                        Some(Ok(principal)) => {
+                            data.update(|data| {
+                                data.reset();
+                                data.from_principal(&principal, selected_type.get());
+                            });
+                            unsaved.mark_clean();
+                            let used_quota = principal.used_quota.as_int().unwrap_or_default();
+                            let total_quota = principal.quota.as_int().unwrap_or_default();
+                            // Cloned principals have no id and are created rather than updated
+                            current_principal
+                                .set(
+                                    if principal.id.is_some() {
+                                        principal
+                                    } else {
+                                        Principal::default()
+                                    },
+                                );
+                            let typ = selected_type.get();
+                            Some(
+                                view! {
+                                    <Tab tabs=Signal::derive(move || {
+                                        vec![
+                                            Some("Details".to_string()),
+                                            matches!(
+                                                typ,
+                                                PrincipalType::Individual | PrincipalType::ApiKey
+                                            )
+                                                .then_some("Authentication".to_string()),
+                                            matches!(
+                                                typ,
+                                                PrincipalType::Individual | PrincipalType::Tenant
+                                            )
+                                                .then_some("Limits".to_string()),
+                                            (!matches!(
+                                                typ,
+                                                PrincipalType::Tenant
+                                                | PrincipalType::Domain
+                                                | PrincipalType::OauthClient
+                                                | PrincipalType::ApiKey
+                                            ))
+                                                .then_some("Memberships".to_string()),
+                                            matches!(
+                                                typ,
+                                                PrincipalType::Individual
+                                                | PrincipalType::Group
+                                                | PrincipalType::Role
+                                                | PrincipalType::Tenant
+                                                | PrincipalType::ApiKey
+                                            )
+                                                .then_some("Permissions".to_string()),
+                                        ]
+                                    })>
+
+                                        <FormSection stacked=true>
+                                            <FormItem
+                                                stacked=true
+                                                label=Signal::derive(move || {
                                                     match selected_type.get() {
                                                         PrincipalType::Individual => "Login name",
-                                                        PrincipalType::Domain => "example.org",
-                                                        _ => "Short Name",
+                                                        PrincipalType::Domain => "Domain name",
+                                                        PrincipalType::ApiKey => "Key Id",
+                                                        PrincipalType::OauthClient => "Client Id",
+                                                        _ => "Name",
                                                     }
                                                         .to_string()
                                                 })
+                                            >
+
+                                                <InputText
+                                                    placeholder=Signal::derive(move || {
+                                                        match selected_type.get() {
+                                                            PrincipalType::Individual => "Login name",
+                                                            PrincipalType::Domain => "example.org",
+                                                            _ => "Short Name",
+                                                        }
+                                                            .to_string()
+                                                    })
 
-                                                element=FormElement::new("name", data)
-                                            />
-                                        </FormItem>
+                                                    element=FormElement::new("name", data)
+                                                />
+                                            </FormItem>
 
-                                        <FormItem
-                                            stacked=true
-                                            label=Signal::derive(move || {
-                                                match selected_type.get() {
-                                                    PrincipalType::Individual | PrincipalType::OauthClient => {
-                                                        "Name"
-                                                    }
-                                                    _ => "Description",
-                                                }
-                                                    .to_string()
-                                            })
-                                        >
-
-                                            <InputText
-                                                placeholder=Signal::derive(move || {
+                                            <FormItem
+                                                stacked=true
+                                                label=Signal::derive(move || {
                                                     match selected_type.get() {
-                                                        PrincipalType::Individual => "Full Name",
+                                                        PrincipalType::Individual | PrincipalType::OauthClient => {
+                                                            "Name"
+                                                        }
                                                         _ => "Description",
                                                     }
                                                         .to_string()
                                                 })
+                                            >
 
-                                                element=FormElement::new("description", data)
-                                            />
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Tenant"
-
-                                            hide=Signal::derive(move || {
-                                                is_tenant
-                                                    || matches!(
+                                                <InputText
+                                                    placeholder=Signal::derive(move || {
+                                                        match selected_type.get() {
+                                                            PrincipalType::Individual => "Full Name",
+                                                            _ => "Description",
+                                                        }
+                                                            .to_string()
+                                                    })
+
+                                                    element=FormElement::new("description", data)
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Tenant"
+
+                                                hide=Signal::derive(move || {
+                                                    is_tenant
+                                                        || matches!(
+                                                            selected_type.get(),
+                                                            PrincipalType::Tenant | PrincipalType::OauthClient
+                                                        )
+                                                })
+                                            >
+
+                                                <Select
+                                                    element=FormElement::new("tenant", data)
+                                                    add_none=true
+                                                    disabled=!is_enterprise
+                                                    options=create_memo(move |_| {
+                                                        principals
+                                                            .get()
+                                                            .get(&PrincipalType::Tenant)
+                                                            .cloned()
+                                                            .unwrap_or_default()
+                                                    })
+                                                />
+
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label=Signal::derive(move || {
+                                                    match selected_type.get() {
+                                                        PrincipalType::List => "List address",
+                                                        _ => "Email",
+                                                    }
+                                                        .to_string()
+                                                })
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
                                                         selected_type.get(),
-                                                        PrincipalType::Tenant | PrincipalType::OauthClient
+                                                        PrincipalType::Individual
+                                                        | PrincipalType::Group
+                                                        | PrincipalType::List
+                                                        | PrincipalType::OauthClient
                                                     )
-                                            })
-                                        >
-
-                                            <Select
-                                                element=FormElement::new("tenant", data)
-                                                add_none=true
-                                                disabled=!is_enterprise
-                                                options=create_memo(move |_| {
-                                                    principals
-                                                        .get()
-                                                        .get(&PrincipalType::Tenant)
-                                                        .cloned()
-                                                        .unwrap_or_default()
                                                 })
-                                            />
-
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Email"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual
-                                                    | PrincipalType::Group
-                                                    | PrincipalType::List
-                                                    | PrincipalType::OauthClient
-                                                )
-                                            })
-                                        >
-
-                                            <InputText
-                                                placeholder="user@example.org"
-                                                element=FormElement::new("email", data)
-                                            />
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Aliases"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual
-                                                    | PrincipalType::Group
-                                                    | PrincipalType::List
-                                                )
-                                            })
-                                        >
-
-                                            <StackedInput
-                                                element=FormElement::new("aliases", data)
-                                                placeholder="Email"
-                                                add_button_text="Add Email".to_string()
-                                            />
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Logo URL"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Tenant
-                                                    | PrincipalType::Domain
-                                                    | PrincipalType::OauthClient
-                                                )
-                                            })
-                                        >
-
-                                            <InputText
-                                                element=FormElement::new("picture", data)
-                                                disabled=!is_enterprise
-                                            />
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Redirect URIs"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::OauthClient)
-                                            })
-                                        >
-
-                                            <StackedInput
-                                                element=FormElement::new("urls", data)
-                                                placeholder="URI"
-                                                add_button_text="Add URI".to_string()
-                                            />
-                                        </FormItem>
-
-                                    </FormSection>
-
-                                    <FormSection stacked=true>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Password"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Individual)
-                                            })
-                                        >
-
-                                            <InputPassword element=FormElement::new("password", data)/>
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="OTP Auth URL"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Individual)
-                                            })
-                                        >
-
-                                            <InputPassword element=FormElement::new(
-                                                "otpauth_url",
-                                                data,
-                                            )/>
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="App Passwords"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Individual)
-                                            })
-                                        >
-
-                                            <StackedBadge
-                                                color=Color::Gray
-                                                element=FormElement::new("app_passwords", data)
-                                                add_button_text="Add password".to_string()
-                                            />
-
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Key"
-
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::ApiKey)
-                                            })
-                                        >
-
-                                            <span class="block font-semibold font-mono text-gray-800 dark:text-gray-200">
-                                                {move || {
-                                                    let data = data.get();
-                                                    let name = data.value::<String>("name").unwrap_or_default();
-                                                    let secret = data
-                                                        .value::<String>("api_secret")
-                                                        .unwrap_or_default();
-                                                    (!name.is_empty() && !secret.is_empty())
-                                                        .then(|| {
-                                                            format!(
-                                                                "api_{}",
-                                                                general_purpose::STANDARD
-                                                                    .encode(format!("{}:{}", name, secret).as_bytes()),
-                                                            )
-                                                        })
-                                                }}
-
-                                            </span>
-
-                                        </FormItem>
-
-                                    </FormSection>
-
-                                    <FormSection stacked=true>
-                                        <FormItem
-                                            stacked=true
-                                            label="Disk quota"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual | PrincipalType::Tenant
+                                            >
+
+                                                <InputText
+                                                    placeholder="user@example.org"
+                                                    element=FormElement::new("email", data)
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Aliases"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual
+                                                        | PrincipalType::Group
+                                                        | PrincipalType::List
+                                                    )
+                                                })
+                                            >
+
+                                                <StackedInput
+                                                    element=FormElement::new("aliases", data)
+                                                    placeholder="Email"
+                                                    add_button_text="Add Email".to_string()
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Logo URL"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Tenant
+                                                        | PrincipalType::Domain
+                                                        | PrincipalType::OauthClient
+                                                    )
+                                                })
+                                            >
+
+                                                <InputText
+                                                    element=FormElement::new("picture", data)
+                                                    disabled=!is_enterprise
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Redirect URIs"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::OauthClient)
+                                                })
+                                            >
+
+                                                <StackedInput
+                                                    element=FormElement::new("urls", data)
+                                                    placeholder="URI"
+                                                    add_button_text="Add URI".to_string()
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="DKIM keys"
+                                                hide=Signal::derive(move || !can_create_dkim.get())
+                                            >
+
+                                                <Select element=FormElement::new("dkim_algorithm", data)/>
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="DKIM selector"
+                                                tooltip=concat!(
+                                                    "Leave empty to use a server-generated selector. ",
+                                                    "When generating both keys, 'e' and 'r' are appended ",
+                                                    "to tell them apart."
                                                 )
-                                            })
-                                        >
+                                                is_optional=true
+                                                hide=Signal::derive(move || {
+                                                    !can_create_dkim.get()
+                                                        || data
+                                                            .get()
+                                                            .value_as_str("dkim_algorithm")
+                                                            == Some("none")
+                                                })
+                                            >
 
-                                            <div class="relative">
-                                                <InputSize element=FormElement::new("quota", data)/>
-                                                <Show when=move || { used_quota > 0 }>
-                                                    <p class="mt-3">
-                                                        <label class="inline-flex items-center gap-x-1 text-xs text-black-600 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600">
+                                                <InputText
+                                                    placeholder="stalwart"
+                                                    element=FormElement::new("dkim_selector", data)
+                                                />
+                                            </FormItem>
 
-                                                            {if total_quota > 0 {
-                                                                format!(
-                                                                    "{} used ({:.1}%)",
-                                                                    format_size(used_quota, DECIMAL),
-                                                                    (used_quota as f64 / total_quota as f64) * 100.0,
-                                                                )
-                                                            } else {
-                                                                format!("{} used", format_size(used_quota, DECIMAL))
-                                                            }}
-
-                                                        </label>
-                                                    </p>
-                                                </Show>
-                                            </div>
-                                        </FormItem>
-
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of Accounts"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_accounts", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of Domains"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_domains", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of Groups"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_groups", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of Lists"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_lists", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of Roles"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_roles", data)/>
-                                        </FormItem>
-                                        <FormItem
-                                            stacked=true
-                                            label="Maximum number of API Keys"
-                                            hide=Signal::derive(move || {
-                                                !matches!(selected_type.get(), PrincipalType::Tenant)
-                                            })
-                                        >
-
-                                            <InputText element=FormElement::new("max_api_keys", data)/>
-                                        </FormItem>
-
-                                    </FormSection>
-
-                                    <FormSection>
-                                        <FormItem
-                                            label="Members"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Role
-                                                    | PrincipalType::Group
-                                                    | PrincipalType::List
-                                                    | PrincipalType::Tenant
-                                                )
-                                            })
-                                        >
-
-                                            <StackedBadge
-                                                color=Color::Green
-                                                element=FormElement::new("members", data)
-                                                add_button_text="Add member".to_string()
-                                                options=create_memo(move |_| {
-                                                    let principals = principals.get();
-                                                    let mut results = Vec::new();
-                                                    let types = match selected_type.get() {
-                                                        PrincipalType::Group | PrincipalType::List => {
-                                                            &[PrincipalType::Individual, PrincipalType::Group][..]
-                                                        }
-                                                        PrincipalType::Role => {
-                                                            &[PrincipalType::Individual, PrincipalType::Role][..]
-                                                        }
-                                                        _ => &[][..],
-                                                    };
-                                                    for typ in types {
-                                                        if let Some(principals) = principals.get(typ) {
-                                                            for (id, name) in principals {
-                                                                results
-                                                                    .push((id.clone(), format!("{} - {name}", typ.name())));
-                                                            }
-                                                        }
-                                                    }
-                                                    results
+                                        </FormSection>
+
+                                        <FormSection stacked=true>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Password"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Individual)
                                                 })
-                                            />
+                                            >
 
-                                        </FormItem>
+                                                <InputPassword element=FormElement::new("password", data)/>
+                                            </FormItem>
 
-                                        <FormItem
-                                            label="Member of"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual | PrincipalType::Group
-                                                )
-                                            })
-                                        >
-
-                                            <StackedBadge
-                                                color=Color::Blue
-                                                element=FormElement::new("member-of", data)
-                                                add_button_text="Add to group".to_string()
-                                                options=create_memo(move |_| {
-                                                    principals
-                                                        .get()
-                                                        .get(&PrincipalType::Group)
-                                                        .cloned()
-                                                        .unwrap_or_default()
+                                            <FormItem
+                                                stacked=true
+                                                label="OTP Auth URL"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Individual)
+                                                })
+                                            >
+
+                                                <InputPassword element=FormElement::new(
+                                                    "otpauth_url",
+                                                    data,
+                                                )/>
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="App Passwords"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Individual)
                                                 })
-                                            />
+                                            >
 
-                                        </FormItem>
+                                                <StackedBadge
+                                                    color=Color::Gray
+                                                    element=FormElement::new("app_passwords", data)
+                                                    add_button_text="Add password".to_string()
+                                                />
 
-                                        <FormItem
-                                            label="Mailing lists"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual | PrincipalType::Group
-                                                )
-                                            })
-                                        >
-
-                                            <StackedBadge
-                                                color=Color::Blue
-                                                element=FormElement::new("lists", data)
-                                                add_button_text="Add to list".to_string()
-                                                options=create_memo(move |_| {
-                                                    principals
-                                                        .get()
-                                                        .get(&PrincipalType::List)
-                                                        .cloned()
-                                                        .unwrap_or_default()
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Key"
+
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::ApiKey)
                                                 })
-                                            />
-
-                                        </FormItem>
-                                    </FormSection>
-
-                                    <FormSection>
-                                        <FormItem
-                                            label="Roles"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual
-                                                    | PrincipalType::Group
-                                                    | PrincipalType::Tenant
-                                                    | PrincipalType::Role
-                                                    | PrincipalType::ApiKey
-                                                )
-                                            })
-                                        >
-
-                                            <StackedBadge
-                                                color=Color::Blue
-                                                element=FormElement::new("roles", data)
-                                                add_button_text="Assign roles".to_string()
-                                                options=create_memo(move |_| {
-                                                    principals
-                                                        .get()
-                                                        .get(&PrincipalType::Role)
-                                                        .cloned()
-                                                        .unwrap_or_default()
+                                            >
+
+                                                <span class="block font-semibold font-mono text-gray-800 dark:text-gray-200">
+                                                    {move || {
+                                                        let data = data.get();
+                                                        let name = data.value::<String>("name").unwrap_or_default();
+                                                        let secret = data
+                                                            .value::<String>("api_secret")
+                                                            .unwrap_or_default();
+                                                        (!name.is_empty() && !secret.is_empty())
+                                                            .then(|| {
+                                                                format!(
+                                                                    "api_{}",
+                                                                    general_purpose::STANDARD
+                                                                        .encode(format!("{}:{}", name, secret).as_bytes()),
+                                                                )
+                                                            })
+                                                    }}
+
+                                                </span>
+
+                                            </FormItem>
+
+                                        </FormSection>
+
+                                        <FormSection stacked=true>
+                                            <FormItem
+                                                stacked=true
+                                                label="Disk quota"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual | PrincipalType::Tenant
+                                                    )
                                                 })
-                                            />
-
-                                        </FormItem>
-                                        <FormItem
-                                            label="Permissions"
-                                            hide=Signal::derive(move || {
-                                                !matches!(
-                                                    selected_type.get(),
-                                                    PrincipalType::Individual
-                                                    | PrincipalType::Group
-                                                    | PrincipalType::Role
-                                                    | PrincipalType::Tenant
-                                                    | PrincipalType::ApiKey
-                                                )
-                                            })
-                                        >
-
-                                            <div class="grid space-y-2">
-                                                {move || {
-                                                    let form_data = data.get();
-                                                    let enabled_permissions = form_data
-                                                        .array_value("enabled-permissions")
-                                                        .collect::<AHashSet<_>>();
-                                                    let disabled_permissions = form_data
-                                                        .array_value("disabled-permissions")
-                                                        .collect::<AHashSet<_>>();
-                                                    PERMISSIONS
-                                                        .iter()
-                                                        .map(|(id, name)| {
-                                                            view! {
-                                                                <div class="flex flex-col space-y-3 p-3 w-full bg-white border border-gray-200 rounded-lg focus:border-blue-500 focus:ring-blue-500 dark:bg-neutral-900 dark:border-neutral-700">
-                                                                    <div class="flex justify-between items-center">
-                                                                        <span
-                                                                            id="hs-radio-delete-description"
-                                                                            class="block text-sm text-gray-600 dark:text-neutral-500"
-                                                                        >
-                                                                            {name.to_string()}
-                                                                        </span>
-
-                                                                        <div class="flex gap-x-6">
-                                                                            <div class="flex">
-                                                                                <input
-                                                                                    type="radio"
-                                                                                    name=id.to_string()
-                                                                                    class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
-                                                                                    id=format!("{id}-on")
-                                                                                    prop:checked=enabled_permissions.contains(id)
-                                                                                    on:input=move |_| {
-                                                                                        data.update_untracked(|data| {
-                                                                                            data.array_push(
-                                                                                                "enabled-permissions",
-                                                                                                id.to_string(),
-                                                                                                true,
-                                                                                            );
-                                                                                            data.array_delete_item("disabled-permissions", id);
-                                                                                        });
-                                                                                    }
-                                                                                />
-
-                                                                                <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
-                                                                                    On
-                                                                                </label>
-                                                                            </div>
+                                            >
+
+                                                <div class="relative">
+                                                    <InputSize element=FormElement::new("quota", data)/>
+                                                    <Show when=move || { used_quota > 0 }>
+                                                        <p class="mt-3">
+                                                            <label class="inline-flex items-center gap-x-1 text-xs text-black-600 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600">
+
+                                                                {if total_quota > 0 {
+                                                                    format!(
+                                                                        "{} used ({:.1}%)",
+                                                                        format_size(used_quota, DECIMAL),
+                                                                        (used_quota as f64 / total_quota as f64) * 100.0,
+                                                                    )
+                                                                } else {
+                                                                    format!("{} used", format_size(used_quota, DECIMAL))
+                                                                }}
+
+                                                            </label>
+                                                        </p>
+                                                    </Show>
+                                                </div>
+                                            </FormItem>
+
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of Accounts"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
+
+                                                <InputText element=FormElement::new("max_accounts", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of Domains"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
+
+                                                <InputText element=FormElement::new("max_domains", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of Groups"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
+
+                                                <InputText element=FormElement::new("max_groups", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of Lists"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
+
+                                                <InputText element=FormElement::new("max_lists", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of Roles"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
+
+                                                <InputText element=FormElement::new("max_roles", data)/>
+                                            </FormItem>
+                                            <FormItem
+                                                stacked=true
+                                                label="Maximum number of API Keys"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::Tenant)
+                                                })
+                                            >
 
-                                                                            <div class="flex">
-                                                                                <input
-                                                                                    type="radio"
-                                                                                    name=id.to_string()
-                                                                                    class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
-                                                                                    id=format!("{id}-off")
-                                                                                    prop:checked=disabled_permissions.contains(id)
-                                                                                    on:input=move |_| {
-                                                                                        data.update_untracked(|data| {
-                                                                                            data.array_push(
-                                                                                                "disabled-permissions",
-                                                                                                id.to_string(),
-                                                                                                true,
-                                                                                            );
-                                                                                            data.array_delete_item("enabled-permissions", id);
-                                                                                        });
-                                                                                    }
-                                                                                />
-
-                                                                                <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
-                                                                                    Off
-                                                                                </label>
-                                                                            </div>
+                                                <InputText element=FormElement::new("max_api_keys", data)/>
+                                            </FormItem>
+
+                                        </FormSection>
 
-                                                                            <div class="flex">
-                                                                                <input
-                                                                                    type="radio"
-                                                                                    name=id.to_string()
-                                                                                    class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
-                                                                                    id=format!("{id}-inherit")
-                                                                                    prop:checked=!enabled_permissions.contains(id)
-                                                                                        && !disabled_permissions.contains(id)
-                                                                                    on:input=move |_| {
-                                                                                        data.update_untracked(|data| {
-                                                                                            data.array_delete_item("disabled-permissions", id);
-                                                                                            data.array_delete_item("enabled-permissions", id);
-                                                                                        });
-                                                                                    }
-                                                                                />
-
-                                                                                <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
-                                                                                    Default
-                                                                                </label>
+                                        <FormSection>
+                                            <FormItem
+                                                label="Members"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Role
+                                                        | PrincipalType::Group
+                                                        | PrincipalType::List
+                                                        | PrincipalType::Tenant
+                                                    )
+                                                })
+                                            >
+
+                                                <StackedBadge
+                                                    color=Color::Green
+                                                    element=FormElement::new("members", data)
+                                                    add_button_text="Add member".to_string()
+                                                    options=create_memo(move |_| {
+                                                        let principals = principals.get();
+                                                        let mut results = Vec::new();
+                                                        let types = match selected_type.get() {
+                                                            PrincipalType::Group | PrincipalType::List => {
+                                                                &[PrincipalType::Individual, PrincipalType::Group][..]
+                                                            }
+                                                            PrincipalType::Role => {
+                                                                &[PrincipalType::Individual, PrincipalType::Role][..]
+                                                            }
+                                                            _ => &[][..],
+                                                        };
+                                                        for typ in types {
+                                                            if let Some(principals) = principals.get(typ) {
+                                                                for (id, name) in principals {
+                                                                    results
+                                                                        .push((id.clone(), format!("{} - {name}", typ.name())));
+                                                                }
+                                                            }
+                                                        }
+                                                        results
+                                                    })
+                                                />
+
+                                            </FormItem>
+
+                                            <FormItem
+                                                label="External members"
+                                                tooltip="Addresses outside this server that also receive messages sent to the list"
+                                                is_optional=true
+                                                hide=Signal::derive(move || {
+                                                    !matches!(selected_type.get(), PrincipalType::List)
+                                                })
+                                            >
+
+                                                <StackedInput
+                                                    element=FormElement::new("external-members", data)
+                                                    placeholder="Email"
+                                                    add_button_text="Add address".to_string()
+                                                />
+                                            </FormItem>
+
+                                            <FormItem
+                                                label="Member of"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual | PrincipalType::Group
+                                                    )
+                                                })
+                                            >
+
+                                                <DualList
+                                                    element=FormElement::new("member-of", data)
+                                                    available_title="Available groups"
+                                                    selected_title="Current groups"
+                                                    options=create_memo(move |_| {
+                                                        principals
+                                                            .get()
+                                                            .get(&PrincipalType::Group)
+                                                            .cloned()
+                                                            .unwrap_or_default()
+                                                    })
+                                                />
+
+                                            </FormItem>
+
+                                            <FormItem
+                                                label="Mailing lists"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual | PrincipalType::Group
+                                                    )
+                                                })
+                                            >
+
+                                                <StackedBadge
+                                                    color=Color::Blue
+                                                    element=FormElement::new("lists", data)
+                                                    add_button_text="Add to list".to_string()
+                                                    options=create_memo(move |_| {
+                                                        principals
+                                                            .get()
+                                                            .get(&PrincipalType::List)
+                                                            .cloned()
+                                                            .unwrap_or_default()
+                                                    })
+                                                />
+
+                                            </FormItem>
+                                        </FormSection>
+
+                                        <FormSection>
+                                            <FormItem
+                                                label="Roles"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual
+                                                        | PrincipalType::Group
+                                                        | PrincipalType::Tenant
+                                                        | PrincipalType::Role
+                                                        | PrincipalType::ApiKey
+                                                    )
+                                                })
+                                            >
+
+                                                <StackedBadge
+                                                    color=Color::Blue
+                                                    element=FormElement::new("roles", data)
+                                                    add_button_text="Assign roles".to_string()
+                                                    options=create_memo(move |_| {
+                                                        principals
+                                                            .get()
+                                                            .get(&PrincipalType::Role)
+                                                            .cloned()
+                                                            .unwrap_or_default()
+                                                    })
+                                                />
+
+                                            </FormItem>
+                                            <FormItem
+                                                label="Permissions"
+                                                hide=Signal::derive(move || {
+                                                    !matches!(
+                                                        selected_type.get(),
+                                                        PrincipalType::Individual
+                                                        | PrincipalType::Group
+                                                        | PrincipalType::Role
+                                                        | PrincipalType::Tenant
+                                                        | PrincipalType::ApiKey
+                                                    )
+                                                })
+                                            >
+
+                                                <div class="grid space-y-2">
+                                                    {move || {
+                                                        let form_data = data.get();
+                                                        let enabled_permissions = form_data
+                                                            .array_value("enabled-permissions")
+                                                            .collect::<AHashSet<_>>();
+                                                        let disabled_permissions = form_data
+                                                            .array_value("disabled-permissions")
+                                                            .collect::<AHashSet<_>>();
+                                                        PERMISSIONS
+                                                            .iter()
+                                                            .map(|(id, name)| {
+                                                                view! {
+                                                                    <div class="flex flex-col space-y-3 p-3 w-full bg-white border border-gray-200 rounded-lg focus:border-blue-500 focus:ring-blue-500 dark:bg-neutral-900 dark:border-neutral-700">
+                                                                        <div class="flex justify-between items-center">
+                                                                            <span
+                                                                                id="hs-radio-delete-description"
+                                                                                class="block text-sm text-gray-600 dark:text-neutral-500"
+                                                                            >
+                                                                                {name.to_string()}
+                                                                            </span>
+
+                                                                            <div class="flex gap-x-6">
+                                                                                <div class="flex">
+                                                                                    <input
+                                                                                        type="radio"
+                                                                                        name=id.to_string()
+                                                                                        class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                                                                                        id=format!("{id}-on")
+                                                                                        prop:checked=enabled_permissions.contains(id)
+                                                                                        on:input=move |_| {
+                                                                                            data.update_untracked(|data| {
+                                                                                                data.array_push(
+                                                                                                    "enabled-permissions",
+                                                                                                    id.to_string(),
+                                                                                                    true,
+                                                                                                );
+                                                                                                data.array_delete_item("disabled-permissions", id);
+                                                                                            });
+                                                                                        }
+                                                                                    />
+
+                                                                                    <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
+                                                                                        On
+                                                                                    </label>
+                                                                                </div>
+
+                                                                                <div class="flex">
+                                                                                    <input
+                                                                                        type="radio"
+                                                                                        name=id.to_string()
+                                                                                        class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                                                                                        id=format!("{id}-off")
+                                                                                        prop:checked=disabled_permissions.contains(id)
+                                                                                        on:input=move |_| {
+                                                                                            data.update_untracked(|data| {
+                                                                                                data.array_push(
+                                                                                                    "disabled-permissions",
+                                                                                                    id.to_string(),
+                                                                                                    true,
+                                                                                                );
+                                                                                                data.array_delete_item("enabled-permissions", id);
+                                                                                            });
+                                                                                        }
+                                                                                    />
+
+                                                                                    <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
+                                                                                        Off
+                                                                                    </label>
+                                                                                </div>
+
+                                                                                <div class="flex">
+                                                                                    <input
+                                                                                        type="radio"
+                                                                                        name=id.to_string()
+                                                                                        class="shrink-0 mt-0.5 border-gray-200 rounded-full text-blue-600 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-neutral-800 dark:border-neutral-700 dark:checked:bg-blue-500 dark:checked:border-blue-500 dark:focus:ring-offset-gray-800"
+                                                                                        id=format!("{id}-inherit")
+                                                                                        prop:checked=!enabled_permissions.contains(id)
+                                                                                            && !disabled_permissions.contains(id)
+                                                                                        on:input=move |_| {
+                                                                                            data.update_untracked(|data| {
+                                                                                                data.array_delete_item("disabled-permissions", id);
+                                                                                                data.array_delete_item("enabled-permissions", id);
+                                                                                            });
+                                                                                        }
+                                                                                    />
+
+                                                                                    <label class="text-sm text-gray-500 ms-2 dark:text-neutral-400">
+                                                                                        Default
+                                                                                    </label>
+                                                                                </div>
                                                                             </div>
                                                                         </div>
                                                                     </div>
-                                                                </div>
-                                                            }
-                                                        })
-                                                        .collect_view()
-                                                }}
+                                                                }
+                                                            })
+                                                            .collect_view()
+                                                    }}
 
-                                            </div>
-                                        </FormItem>
+                                                </div>
+                                            </FormItem>
 
-                                    </FormSection>
+                                        </FormSection>
 
-                                </Tab>
-                            }
-                                .into_view(),
-                        )
-                    }
-                }}
+                                    </Tab>
+                                }
+                                    .into_view(),
+                            )
+                        }
+                    }}
 
-            </Transition>
+                </Transition>
+            </fieldset>
 
             <FormButtonBar>
                 <Button
-                    text="Cancel"
+                    text=t_signal("button.cancel")
                     color=Color::Gray
                     on_click=move |_| {
-                        use_navigate()(
+                        unsaved.navigate(
                             &format!("/manage/directory/{}", selected_type.get().resource_name()),
-                            Default::default(),
                         );
                     }
                 />
 
-                <Button
-                    text="Save changes"
-                    color=Color::Blue
-                    on_click=Callback::new(move |_| {
-                        data.update(|data| {
-                            if let Some(changes) = data.to_principal() {
-                                save_changes.dispatch(changes);
+                <Show when=move || {
+                    !read_only.get() && selected_type.get() == PrincipalType::Individual
+                        && current_principal.with(|principal| principal.email().is_some())
+                }>
+                    <Button
+                        text="Test delivery"
+                        color=Color::Gray
+                        on_click=move |_| {
+                            let recipient = current_principal
+                                .with_untracked(|principal| {
+                                    principal.email().unwrap_or_default().to_string()
+                                });
+                            modal
+                                .set(
+                                    Modal::with_title("Test delivery")
+                                        .with_message(
+                                            "Checks that the mail exchangers of this account accept mail for it, without sending a message.",
+                                        )
+                                        .without_button()
+                                        .with_content(move || {
+                                            view! { <TestDelivery recipient=recipient.clone()/> }
+                                        }),
+                                );
+                        }
+                    />
+
+                </Show>
+
+                <Show when=move || !read_only.get() && params.get().get("id").is_some()>
+                    <Button
+                        text="Save as new"
+                        color=Color::Gray
+                        on_click=Callback::new(move |_| {
+                            let principal = data.try_update(|data| data.to_principal()).flatten();
+                            if let Some(principal) = principal {
+                                if let Err(err) = SessionStorage::set(
+                                    CLONE_STORAGE_KEY,
+                                    principal.into_clone(),
+                                ) {
+                                    alert.set(Alert::error(format!("Failed to clone: {err}")));
+                                    return;
+                                }
+                                unsaved.navigate(
+                                    &format!(
+                                        "/manage/directory/{}/edit",
+                                        selected_type.get().resource_name(),
+                                    ),
+                                );
+                                alert
+                                    .set(
+                                        Alert::warning(
+                                            "Choose a new unique name before saving the cloned principal.",
+                                        ),
+                                    );
                             }
-                        });
-                    })
+                        })
 
-                    disabled=pending
-                />
+                        disabled=pending
+                    />
+                </Show>
+
+                <Show when=move || !read_only.get()>
+                    <Button
+                        text=t_signal("button.save-changes")
+                        color=Color::Blue
+                        on_click=Callback::new(move |_| save())
+
+                        disabled=Signal::derive(move || pending.get() || check_dkim.pending().get())
+                    />
+                </Show>
             </FormButtonBar>
 
         </Form>
@@ -1083,6 +1348,10 @@ impl FormData {
         for (key, list) in [
             ("member-of", principal.member_of.as_string_list()),
             ("members", principal.members.as_string_list()),
+            (
+                "external-members",
+                principal.external_members.as_string_list(),
+            ),
             ("roles", principal.roles.as_string_list()),
             ("lists", principal.lists.as_string_list()),
             (
@@ -1130,6 +1399,10 @@ impl FormData {
             }
 
             let typ = self.value::<PrincipalType>("type").unwrap();
+            if typ == PrincipalType::List && self.value::<String>("email").is_none() {
+                self.new_error("email", "Mailing lists need an address to receive messages");
+                return None;
+            }
             let mut principal = Principal {
                 typ: Some(typ),
                 quota: self.quota(typ),
@@ -1151,6 +1424,7 @@ impl FormData {
             for (key, list) in [
                 ("member-of", &mut principal.member_of),
                 ("members", &mut principal.members),
+                ("external-members", &mut principal.external_members),
                 ("roles", &mut principal.roles),
                 ("lists", &mut principal.lists),
                 ("enabled-permissions", &mut principal.enabled_permissions),
@@ -1168,6 +1442,35 @@ impl FormData {
         }
     }
 
+    pub(super) fn dkim_signatures(&self, domain: &str) -> Vec<DkimSignature> {
+        let algorithms = match self.value::<String>("dkim_algorithm").as_deref() {
+            Some("ed25519") => &[Algorithm::Ed25519][..],
+            Some("rsa") => &[Algorithm::Rsa][..],
+            Some("none") => &[][..],
+            _ => &[Algorithm::Ed25519, Algorithm::Rsa][..],
+        };
+        let selector = self.value::<String>("dkim_selector");
+
+        algorithms
+            .iter()
+            .map(|algorithm| DkimSignature {
+                id: None,
+                algorithm: *algorithm,
+                domain: domain.to_string(),
+                selector: selector.as_ref().map(|selector| {
+                    if algorithms.len() > 1 {
+                        match algorithm {
+                            Algorithm::Ed25519 => format!("{selector}e"),
+                            Algorithm::Rsa => format!("{selector}r"),
+                        }
+                    } else {
+                        selector.clone()
+                    }
+                }),
+            })
+            .collect()
+    }
+
     pub fn quota(&mut self, typ: PrincipalType) -> PrincipalValue {
         if typ == PrincipalType::Tenant {
             PrincipalValue::IntegerList(
@@ -1195,6 +1498,19 @@ impl FormData {
     }
 }
 
+impl Principal {
+    fn into_clone(self) -> Principal {
+        Principal {
+            id: None,
+            name: PrincipalValue::default(),
+            emails: PrincipalValue::default(),
+            secrets: PrincipalValue::default(),
+            used_quota: PrincipalValue::default(),
+            ..self
+        }
+    }
+}
+
 impl Builder<Schemas, ()> {
     pub fn build_principals(self) -> Self {
         self.new_schema("principals")
@@ -1219,6 +1535,13 @@ impl Builder<Schemas, ()> {
                 [Validator::IsEmail],
             )
             .build()
+            .new_field("external-members")
+            .typ(Type::Array)
+            .input_check(
+                [Transformer::Trim, Transformer::Lowercase],
+                [Validator::IsEmail],
+            )
+            .build()
             .new_field("urls")
             .typ(Type::Array)
             .input_check([Transformer::Trim], [Validator::IsUrl])
@@ -1231,6 +1554,25 @@ impl Builder<Schemas, ()> {
             .typ(Type::Input)
             .input_check([Transformer::Trim], [Validator::IsUrl])
             .build()
+            .new_field("dkim_algorithm")
+            .typ(Type::Select {
+                typ: SelectType::Single,
+                source: Source::Static(&[
+                    ("all", "Generate Ed25519 and RSA keys"),
+                    ("ed25519", "Generate an Ed25519 key"),
+                    ("rsa", "Generate an RSA 2048 key"),
+                    ("none", "Do not generate keys"),
+                ]),
+            })
+            .default("all")
+            .build()
+            .new_field("dkim_selector")
+            .typ(Type::Input)
+            .input_check(
+                [Transformer::Trim, Transformer::Lowercase],
+                [Validator::IsId],
+            )
+            .build()
             .build()
     }
 }
@@ -0,0 +1,373 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::Arc;
+
+use ahash::AHashSet;
+use leptos::*;
+use leptos_router::use_navigate;
+use web_sys::HtmlInputElement;
+
+use crate::{
+    components::{
+        form::{button::Button, Form, FormButtonBar, FormItem, FormSection},
+        icon::IconArrowDownTray,
+        list::table::{Table, TableRow},
+        messages::alert::{error_reason, use_alerts, Alert},
+        Color,
+    },
+    core::{
+        api::use_api,
+        download::{csv_row, download_file},
+        i18n::t_signal,
+        schema::Schemas,
+        upload::{parse_csv, read_file},
+    },
+    pages::{
+        directory::{Principal, PrincipalType, PrincipalValue},
+        maybe_plural,
+    },
+};
+
+const COLUMNS: [&str; 4] = ["name", "email", "quota", "display_name"];
+
+#[derive(Debug, Clone)]
+struct ImportError {
+    line: usize,
+    name: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ImportProgress {
+    completed: usize,
+    failed: usize,
+    total: usize,
+}
+
+#[component]
+pub fn PrincipalImport() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let schemas = expect_context::<Arc<Schemas>>();
+
+    let pending = create_rw_signal(Vec::<(usize, Principal)>::new());
+    let errors = create_rw_signal(Vec::<ImportError>::new());
+    let progress = create_rw_signal(ImportProgress::default());
+
+    let import_action = create_action(move |principals: &Arc<Vec<(usize, Principal)>>| {
+        let principals = principals.clone();
+
+        async move {
+            progress.set(ImportProgress {
+                total: principals.len(),
+                ..Default::default()
+            });
+
+            for (line, principal) in principals.iter() {
+                if let Err(err) = api
+                    .post("/api/principal")
+                    .with_body(principal)
+                    .unwrap()
+                    .send::<u32>()
+                    .await
+                {
+                    errors.update(|errors| {
+                        errors.push(ImportError {
+                            line: *line,
+                            name: principal.name().unwrap_or_default().to_string(),
+                            reason: error_reason(err),
+                        });
+                    });
+                    progress.update(|progress| progress.failed += 1);
+                }
+                progress.update(|progress| progress.completed += 1);
+            }
+
+            let progress = progress.get_untracked();
+            let imported = progress.total - progress.failed;
+            if progress.failed == 0 {
+                alert.set(Alert::success(format!(
+                    "Imported {}.",
+                    maybe_plural(imported, "account", "accounts")
+                )));
+            } else {
+                alert.set(Alert::warning(format!(
+                    "Imported {}, {} failed.",
+                    maybe_plural(imported, "account", "accounts"),
+                    progress.failed
+                )));
+            }
+        }
+    });
+    let is_importing = import_action.pending();
+
+    let on_file = move |ev: ev::Event| {
+        let Some(file) = event_target::<HtmlInputElement>(&ev)
+            .files()
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        let schemas = schemas.clone();
+
+        spawn_local(async move {
+            match read_file(file).await {
+                Ok(contents) => {
+                    let (valid, invalid) = parse_accounts(&contents, &schemas);
+                    pending.set(valid);
+                    errors.set(invalid);
+                    progress.set(ImportProgress::default());
+                }
+                Err(err) => {
+                    alert.set(Alert::error(format!(
+                        "Failed to read file: {}",
+                        err.as_string().unwrap_or_default()
+                    )));
+                }
+            }
+        });
+    };
+
+    view! {
+        <Form title="Import Accounts" subtitle="Create accounts in bulk from a CSV file">
+
+            <FormSection>
+                <FormItem label="CSV file">
+                    <input
+                        type="file"
+                        accept=".csv,text/csv"
+                        class="block w-full border border-gray-200 shadow-sm rounded-lg text-sm focus:z-10 focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 file:bg-gray-50 file:border-0 file:me-4 file:py-2 file:px-4 dark:file:bg-gray-700 dark:file:text-gray-400"
+                        disabled=move || is_importing.get()
+                        on:change=on_file
+                    />
+                    <p class="mt-2 text-sm text-gray-500 dark:text-gray-400">
+                        "The first row must contain the columns "
+                        <code>{COLUMNS.join(", ")}</code>
+                        ". Quotas are given in bytes, leave empty for unlimited."
+                    </p>
+                </FormItem>
+            </FormSection>
+
+            <Show when=move || { progress.get().total > 0 }>
+                <div class="mt-5">
+                    <div class="flex justify-between items-center mb-2 text-sm text-gray-800 dark:text-white">
+                        <span>
+                            {move || {
+                                let progress = progress.get();
+                                format!("Processed {} of {}", progress.completed, progress.total)
+                            }}
+
+                        </span>
+                        <span>
+                            {move || {
+                                let progress = progress.get();
+                                format!("{} failed", progress.failed)
+                            }}
+
+                        </span>
+                    </div>
+                    <div
+                        class="flex w-full h-2 bg-gray-200 rounded-full overflow-hidden dark:bg-gray-700"
+                        role="progressbar"
+                    >
+                        <div
+                            class="flex flex-col justify-center rounded-full overflow-hidden bg-blue-600 transition duration-500"
+                            style=move || {
+                                let progress = progress.get();
+                                format!(
+                                    "width: {}%",
+                                    progress.completed * 100 / std::cmp::max(progress.total, 1),
+                                )
+                            }
+                        ></div>
+                    </div>
+                </div>
+            </Show>
+
+            <Show when=move || errors.with(|errors| !errors.is_empty())>
+                <div class="mt-5">
+                    <h2 class="pb-4 text-lg font-semibold text-gray-800 dark:text-gray-200">
+                        Rejected rows
+                    </h2>
+                    <Table headers=vec![
+                        "Line".to_string(),
+                        "Name".to_string(),
+                        "Reason".to_string(),
+                    ]>
+                        {move || {
+                            errors
+                                .get()
+                                .into_iter()
+                                .map(|error| {
+                                    view! {
+                                        <TableRow>
+                                            <span>{error.line}</span>
+                                            <span>{error.name}</span>
+                                            <span>{error.reason}</span>
+                                        </TableRow>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+
+                    </Table>
+                </div>
+            </Show>
+
+            <FormButtonBar>
+                <Button
+                    text="Download template"
+                    color=Color::Gray
+                    on_click=move |_| {
+                        let template = csv_row(COLUMNS)
+                            + &csv_row(["jdoe", "jdoe@example.org", "1073741824", "John Doe"]);
+                        if let Err(err) = download_file("accounts.csv", "text/csv", &template) {
+                            log::warn!("Failed to download template: {err:?}");
+                        }
+                    }
+                >
+
+                    <IconArrowDownTray attr:class="flex-shrink-0 size-4"/>
+                </Button>
+                <Button
+                    text=t_signal("button.cancel")
+                    color=Color::Gray
+                    on_click=move |_| {
+                        use_navigate()("/manage/directory/accounts", Default::default());
+                    }
+                />
+
+                <Button
+                    text=Signal::derive(move || {
+                        let total = pending.with(|pending| pending.len());
+                        if total > 0 {
+                            format!("Import {}", maybe_plural(total, "account", "accounts"))
+                        } else {
+                            "Import".to_string()
+                        }
+                    })
+
+                    color=Color::Blue
+                    on_click=Callback::new(move |_| {
+                        let principals = pending.try_update(std::mem::take).unwrap_or_default();
+                        if !principals.is_empty() {
+                            import_action.dispatch(Arc::new(principals));
+                        }
+                    })
+
+                    disabled=Signal::derive(move || {
+                        is_importing.get() || pending.with(|pending| pending.is_empty())
+                    })
+                />
+
+            </FormButtonBar>
+
+        </Form>
+    }
+}
+
+fn parse_accounts(
+    contents: &str,
+    schemas: &Schemas,
+) -> (Vec<(usize, Principal)>, Vec<ImportError>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+    let mut rows = parse_csv(contents).into_iter();
+
+    let Some((header_line, header)) = rows.next() else {
+        invalid.push(ImportError {
+            line: 1,
+            name: String::new(),
+            reason: "The file is empty.".to_string(),
+        });
+        return (valid, invalid);
+    };
+    let mut columns = [None; COLUMNS.len()];
+    for (pos, name) in header.iter().enumerate() {
+        if let Some(idx) = COLUMNS
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name.trim()))
+        {
+            columns[idx] = Some(pos);
+        }
+    }
+    if columns[0].is_none() {
+        invalid.push(ImportError {
+            line: header_line,
+            name: String::new(),
+            reason: "Missing 'name' column in header row.".to_string(),
+        });
+        return (valid, invalid);
+    }
+
+    let mut seen = AHashSet::new();
+    for (line, row) in rows {
+        let [name, email, quota, display_name] = columns.map(|column| {
+            column
+                .and_then(|column| row.get(column))
+                .map(|value| value.trim())
+                .unwrap_or_default()
+        });
+
+        let mut form = schemas.build_form("principals");
+        form.set("name", name);
+        form.set("email", email);
+        form.set("description", display_name);
+        let mut reasons = Vec::new();
+        if !form.validate_form() {
+            for (field, label) in [
+                ("name", "Name"),
+                ("email", "E-mail"),
+                ("description", "Display name"),
+            ] {
+                if let Some(error) = form.error_string(field) {
+                    reasons.push(format!("{label}: {error}"));
+                }
+            }
+        }
+        let quota = if !quota.is_empty() {
+            quota.parse::<u64>().ok().or_else(|| {
+                reasons.push(format!("Quota: '{quota}' is not a number of bytes"));
+                None
+            })
+        } else {
+            Some(0)
+        };
+
+        let name = form.value::<String>("name").unwrap_or_default();
+        if reasons.is_empty() && !seen.insert(name.clone()) {
+            reasons.push("Duplicate name in file".to_string());
+        }
+
+        if reasons.is_empty() {
+            valid.push((
+                line,
+                Principal {
+                    typ: Some(PrincipalType::Individual),
+                    quota: PrincipalValue::Integer(quota.unwrap_or_default()),
+                    name: PrincipalValue::String(name),
+                    description: PrincipalValue::String(
+                        form.value::<String>("description").unwrap_or_default(),
+                    ),
+                    emails: PrincipalValue::StringList(
+                        form.value::<String>("email").into_iter().collect(),
+                    ),
+                    roles: PrincipalValue::StringList(vec!["user".to_string()]),
+                    ..Default::default()
+                },
+            ));
+        } else {
+            invalid.push(ImportError {
+                line,
+                name,
+                reason: reasons.join("; "),
+            });
+        }
+    }
+
+    (valid, invalid)
+}
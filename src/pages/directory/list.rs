@@ -4,45 +4,58 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::{collections::HashSet, sync::Arc};
+use std::{cmp::Ordering, collections::HashSet, sync::Arc, time::Duration};
 
+use gloo_storage::{SessionStorage, Storage};
 use humansize::{format_size, DECIMAL};
 use leptos::*;
 use leptos_router::*;
+use web_sys::{wasm_bindgen::JsCast, HtmlElement};
 
 use crate::{
     components::{
         badge::Badge,
-        icon::{IconAdd, IconThreeDots, IconTrash},
+        form::input::Size,
+        icon::{IconAdd, IconArrowUpTray, IconCheck, IconThreeDots, IconTrash, IconUserGroup},
         list::{
-            header::ColumnList,
-            pagination::Pagination,
+            header::{ColumnList, ColumnSort, SortOrder},
+            pagination::{Pagination, PAGE_SIZES},
             row::SelectItem,
             toolbar::{SearchBox, ToolbarButton},
-            Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar, ZeroResults,
+            EmptyState, Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar,
         },
         messages::{
             alert::{use_alerts, Alert},
-            modal::{use_modals, Modal},
+            modal::{confirm_dangerous, use_modals, Modal},
         },
-        skeleton::Skeleton,
+        skeleton::{ListSkeleton, Skeleton},
         Color,
     },
     core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
+        api::{use_api, use_latest_api},
+        debounce::SEARCH_DEBOUNCE,
+        http,
+        i18n::t_signal,
+        list_state::use_list_state,
+        oauth::{use_authorization, use_is_viewer},
+        shortcuts::move_focus,
         url::UrlBuilder,
+        Permission,
     },
     pages::{
-        directory::{Principal, PrincipalType},
+        directory::{
+            guard_principal_type, Principal, PrincipalField, PrincipalType, PrincipalUpdate,
+            PrincipalValue,
+        },
         maybe_plural, List,
     },
 };
 
-const PAGE_SIZE: u32 = 10;
+const PAGE_SIZE_KEY: &str = "webadmin_directory_page_size";
 
 #[component]
 pub fn PrincipalList() -> impl IntoView {
+    let is_viewer = use_is_viewer();
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
     provide_context(selected);
 
@@ -67,41 +80,43 @@ pub fn PrincipalList() -> impl IntoView {
         }
     });
 
-    let query = use_query_map();
-    let page = create_memo(move |_| {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
-            .filter(|&page| page > 0)
-            .unwrap_or(1)
-    });
-    let filter = create_memo(move |_| {
-        query.with(|q| {
-            q.get("filter").and_then(|s| {
-                let s = s.trim();
-                if !s.is_empty() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            })
-        })
-    });
+    let state = use_list_state();
+    let page = state.page;
+    let filter = state.value("filter");
+    let sort = state.value("sort");
+    let sort = create_memo(move |_| sort.get().and_then(|sort| SortOrder::parse(&sort)));
+    let page_size = state.page_size(PAGE_SIZE_KEY, &PAGE_SIZES);
+    // The page size is always included, as it may come from the session
+    let list_url = move |key: &'static str, value: Option<String>| {
+        let url = state.url(
+            format!("/manage/directory/{}", selected_type.get().resource_name()),
+            key,
+            value,
+        );
+        if key != "limit" {
+            url.with_parameter("limit", page_size.get().to_string())
+        } else {
+            url
+        }
+    };
 
     let auth = use_authorization();
+    let api = use_api();
+    let latest = use_latest_api();
+    guard_principal_type(selected_type);
     let alert = use_alerts();
     let modal = use_modals();
 
     let principals = create_resource(
-        move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
+        move || (page.get(), page_size.get(), filter.get()),
+        move |(page, page_size, filter)| {
             let selected_type = selected_type.get();
 
             async move {
-                HttpRequest::get("/api/principal")
-                    .with_authorization(&auth)
+                latest
+                    .get("/api/principal")
                     .with_parameter("page", page.to_string())
-                    .with_parameter("limit", PAGE_SIZE.to_string())
+                    .with_parameter("limit", page_size.to_string())
                     .with_parameter("types", selected_type.id())
                     .with_optional_parameter("filter", filter)
                     .send::<List<Principal>>()
@@ -112,15 +127,10 @@ pub fn PrincipalList() -> impl IntoView {
 
     let delete_action = create_action(move |items: &Arc<HashSet<String>>| {
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             for item in items.iter() {
-                if let Err(err) = HttpRequest::delete(("/api/principal", item))
-                    .with_authorization(&auth)
-                    .send::<()>()
-                    .await
-                {
+                if let Err(err) = api.delete(("/api/principal", item)).send::<()>().await {
                     alert.set(Alert::from(err));
                     return;
                 }
@@ -136,13 +146,71 @@ pub fn PrincipalList() -> impl IntoView {
             )));
         }
     });
+    let domain_delete_action = create_action(move |domains: &Arc<HashSet<String>>| {
+        let domains = domains.clone();
+
+        async move {
+            // Count the principals with addresses on the domains being deleted
+            let mut accounts = 0;
+            let mut others = 0;
+            for domain in domains.iter() {
+                for (types, total) in [("individual", &mut accounts), ("group,list", &mut others)] {
+                    match api
+                        .get("/api/principal")
+                        .with_parameter("filter", domain)
+                        .with_parameter("fields", "name")
+                        .with_parameter("count", "1")
+                        .with_parameter("types", types)
+                        .send::<List<Principal>>()
+                        .await
+                    {
+                        Ok(list) => *total += list.total as usize,
+                        Err(err) => {
+                            alert.set(Alert::from(err));
+                            return;
+                        }
+                    }
+                }
+            }
+
+            let (text, confirm_text) = match domains.iter().next() {
+                Some(domain) if domains.len() == 1 => (domain.clone(), domain.clone()),
+                _ => {
+                    let text = maybe_plural(domains.len(), "domain", "domains");
+                    (text.clone(), format!("delete {text}"))
+                }
+            };
+            let confirmed = modal
+                .confirm(
+                    Modal::with_title("Delete domain")
+                        .with_message(format!(
+                            concat!(
+                                "Are you sure you want to delete {}? {} and {} use addresses ",
+                                "on this domain. This action cannot be undone."
+                            ),
+                            text,
+                            maybe_plural(accounts, "account", "accounts"),
+                            maybe_plural(others, "group or list", "groups or lists"),
+                        ))
+                        .with_button(format!("Delete {text}"))
+                        .with_confirmation_text(confirm_text)
+                        .dangerous(),
+                )
+                .await;
+            if confirmed {
+                selected.update(|selected| {
+                    selected.retain(|item| !domains.contains(item));
+                });
+                delete_action.dispatch(domains);
+            }
+        }
+    });
     let purge_action = create_action(move |item: &String| {
         let item = item.clone();
-        let auth = auth.get();
 
         async move {
-            match HttpRequest::get(("/api/store/purge/account", &item))
-                .with_authorization(&auth)
+            match api
+                .get(("/api/store/purge/account", &item))
                 .send::<()>()
                 .await
             {
@@ -196,22 +264,18 @@ pub fn PrincipalList() -> impl IntoView {
                 <Toolbar slot>
                     <SearchBox
                         value=filter
+                        debounce=SEARCH_DEBOUNCE
+                        latest
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new(
-                                        format!(
-                                            "/manage/directory/{}",
-                                            selected_type.get().resource_name(),
-                                        ),
-                                    )
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &list_url("filter", Some(value)).finish(),
                                 Default::default(),
                             );
                         }
                     />
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Delete ({ns})") } else { "Delete".to_string() }
@@ -220,30 +284,31 @@ pub fn PrincipalList() -> impl IntoView {
                         color=Color::Red
                         on_click=Callback::new(move |_| {
                             let to_delete = selected.get().len();
-                            if to_delete > 0 {
+                            if to_delete > 0 && selected_type.get() == PrincipalType::Domain {
+                                domain_delete_action.dispatch(Arc::new(selected.get()));
+                            } else if to_delete > 0 {
                                 let text = maybe_plural(
                                     to_delete,
                                     selected_type.get().item_name(false),
                                     selected_type.get().item_name(true),
                                 );
-                                modal
-                                    .set(
-                                        Modal::with_title("Confirm deletion")
-                                            .with_message(
-                                                format!(
-                                                    "Are you sure you want to delete {text}? This action cannot be undone.",
+                                let confirmed = confirm_dangerous(
+                                    "Confirm deletion",
+                                    format!(
+                                        "Are you sure you want to delete {text}? This action cannot be undone.",
+                                    ),
+                                    format!("Delete {text}"),
+                                );
+                                spawn_local(async move {
+                                    if confirmed.await {
+                                        delete_action
+                                            .dispatch(
+                                                Arc::new(
+                                                    selected.try_update(std::mem::take).unwrap_or_default(),
                                                 ),
-                                            )
-                                            .with_button(format!("Delete {text}"))
-                                            .with_dangerous_callback(move || {
-                                                delete_action
-                                                    .dispatch(
-                                                        Arc::new(
-                                                            selected.try_update(std::mem::take).unwrap_or_default(),
-                                                        ),
-                                                    );
-                                            }),
-                                    )
+                                            );
+                                    }
+                                });
                             }
                         })
                     >
@@ -251,7 +316,28 @@ pub fn PrincipalList() -> impl IntoView {
                         <IconTrash/>
                     </ToolbarButton>
 
+                    <Show when=move || {
+                        selected_type.get() == PrincipalType::Individual
+                            && auth
+                                .get()
+                                .permissions()
+                                .has_access(Permission::IndividualCreate)
+                    }>
+                        <ToolbarButton
+                            hide=is_viewer
+                            text=t_signal("button.import")
+                            color=Color::Gray
+                            on_click=move |_| {
+                                use_navigate()("/manage/directory/accounts/import", Default::default());
+                            }
+                        >
+
+                            <IconArrowUpTray attr:class="flex-shrink-0 size-3.5"/>
+                        </ToolbarButton>
+                    </Show>
+
                     <ToolbarButton
+                        hide=is_viewer
                         text=create_memo(move |_| {
                             format!("Create {}", selected_type.get().item_name(false))
                         })
@@ -273,7 +359,7 @@ pub fn PrincipalList() -> impl IntoView {
 
                 </Toolbar>
 
-                <Transition fallback=Skeleton>
+                <Transition fallback=|| view! { <ListSkeleton/> }>
                     {move || match principals.get() {
                         None => None,
                         Some(Err(http::Error::Unauthorized)) => {
@@ -285,9 +371,25 @@ pub fn PrincipalList() -> impl IntoView {
                             alert.set(Alert::from(err));
                             Some(view! { <Skeleton/> }.into_view())
                         }
-                        Some(Ok(principals)) if !principals.items.is_empty() => {
+                        Some(Ok(mut principals)) if !principals.items.is_empty() => {
                             total_results.set(Some(principals.total as u32));
+                            if let Some(sort) = sort.get() {
+                                principals.items.sort_by(|a, b| a.compare(b, &sort));
+                            }
                             let principals_ = principals.clone();
+                            let matches = filter
+                                .get()
+                                .map(|filter| {
+                                    let item_type = selected_type.get();
+                                    format!(
+                                        "{} matching \"{filter}\".",
+                                        maybe_plural(
+                                            principals.total as usize,
+                                            item_type.item_name(false),
+                                            item_type.item_name(true),
+                                        ),
+                                    )
+                                });
                             let headers = match selected_type.get() {
                                 PrincipalType::Individual => {
                                     vec![
@@ -359,8 +461,34 @@ pub fn PrincipalList() -> impl IntoView {
                             };
                             Some(
                                 view! {
+                                    {matches
+                                        .map(|matches| {
+                                            view! {
+                                                <p class="px-6 pt-3 text-sm text-gray-500 dark:text-gray-400" aria-live="polite">
+                                                    {matches}
+                                                </p>
+                                            }
+                                        })}
                                     <ColumnList
                                         headers=headers
+                                        sticky=true
+                                        sort=ColumnSort {
+                                            columns: vec![
+                                                ("Name", "name"),
+                                                ("Type", "type"),
+                                                ("E-mail", "email"),
+                                                ("Usage", "quota"),
+                                            ],
+                                            current: sort.into(),
+                                            on_sort: Callback::new(move |order: SortOrder| {
+                                                use_navigate()(
+                                                    &list_url("sort", Some(order.to_param()))
+                                                        .finish(),
+                                                    Default::default(),
+                                                );
+                                            }),
+                                        }
+
                                         select_all=Callback::new(move |_| {
                                             principals_
                                                 .items
@@ -380,8 +508,8 @@ pub fn PrincipalList() -> impl IntoView {
                                                 params=Parameters {
                                                     selected_type: selected_type.get(),
                                                     delete_action,
+                                                    domain_delete_action,
                                                     purge_action,
-                                                    modal,
                                                     show_dropdown,
                                                 }
                                             />
@@ -394,16 +522,35 @@ pub fn PrincipalList() -> impl IntoView {
                         }
                         Some(Ok(_)) => {
                             total_results.set(Some(0));
+                            let item_type = selected_type.get();
+                            let (title, subtitle, button_text) = if filter.get().is_some() {
+                                (
+                                    "No results".to_string(),
+                                    "Your search did not yield any results.".to_string(),
+                                    format!("Create a new {}", item_type.item_name(false)),
+                                )
+                            } else {
+                                (
+                                    format!("No {} yet", item_type.item_name(true)),
+                                    format!(
+                                        "There are no {} in the directory.",
+                                        item_type.item_name(true),
+                                    ),
+                                    format!("Create your first {}", item_type.item_name(false)),
+                                )
+                            };
                             Some(
                                 view! {
-                                    <ZeroResults
-                                        title="No results"
-                                        subtitle="Your search did not yield any results."
-                                        button_text=format!(
-                                            "Create a new {}",
-                                            selected_type.get().item_name(false),
-                                        )
+                                    <EmptyState
+                                        title=title
+                                        subtitle=subtitle
+                                        icon=if filter.get().is_some() {
+                                            None
+                                        } else {
+                                            Some(view! { <IconUserGroup attr:class="size-5"/> }.into_view())
+                                        }
 
+                                        button_text=button_text
                                         button_action=Callback::new(move |_| {
                                             use_navigate()(
                                                 &format!(
@@ -427,18 +574,21 @@ pub fn PrincipalList() -> impl IntoView {
                     <Pagination
                         current_page=page
                         total_results=total_results.read_only()
-                        page_size=PAGE_SIZE
+                        page_size=page_size
+                        jump_to_page=true
                         on_page_change=move |page: u32| {
                             use_navigate()(
-                                &UrlBuilder::new(
-                                        format!(
-                                            "/manage/directory/{}",
-                                            selected_type.get().resource_name(),
-                                        ),
-                                    )
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &list_url("page", Some(page.to_string())).finish(),
+                                Default::default(),
+                            );
+                        }
+
+                        on_page_size_change=move |page_size: u32| {
+                            if let Err(err) = SessionStorage::set(PAGE_SIZE_KEY, page_size) {
+                                log::warn!("Failed to save page size: {err}");
+                            }
+                            use_navigate()(
+                                &list_url("limit", Some(page_size.to_string())).finish(),
                                 Default::default(),
                             );
                         }
@@ -453,19 +603,81 @@ pub fn PrincipalList() -> impl IntoView {
 struct Parameters {
     selected_type: PrincipalType,
     delete_action: Action<Arc<HashSet<String>>, ()>,
+    domain_delete_action: Action<Arc<HashSet<String>>, ()>,
     purge_action: Action<String, ()>,
-    modal: RwSignal<Modal>,
     show_dropdown: RwSignal<String>,
 }
 
 #[component]
 fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
+    let auth = use_authorization();
+    let api = use_api();
+    let alert = use_alerts();
     let selected_type = params.selected_type;
     let show_dropdown = params.show_dropdown;
     let principal = RwSignal::new(principal);
+    let pending = create_rw_signal(false);
+    let can_edit = selected_type == PrincipalType::Individual
+        && auth
+            .get_untracked()
+            .permissions()
+            .has_access(Permission::IndividualUpdate);
+    let can_view_activity = selected_type == PrincipalType::Individual
+        && auth
+            .get_untracked()
+            .permissions()
+            .has_access(Permission::TracingList);
+    // Shown right away and reverted if the server refuses the change
+    let toggle_disabled = move || {
+        let name = principal.get_untracked().name_or_empty();
+        let disabled = !principal.get_untracked().is_disabled();
+        principal.update(|principal| principal.set_disabled(disabled));
+        pending.set(true);
+
+        spawn_local(async move {
+            let result = api
+                .patch(("/api/principal", &name))
+                .with_body(vec![PrincipalUpdate::disable(disabled)])
+                .unwrap()
+                .send::<()>()
+                .await;
+            if let Err(err) = result {
+                principal.try_update(|principal| principal.set_disabled(!disabled));
+                alert.set(Alert::from(err));
+            }
+            pending.try_set(false);
+        });
+    };
+    let menu_id = format!(
+        "principal-actions-{}",
+        principal.get_untracked().name_or_empty()
+    );
+    let toggle_ref = create_node_ref::<html::Button>();
+    let menu_ref = create_node_ref::<html::Div>();
+    let is_open = move || show_dropdown.get() == principal.get().name().unwrap_or_default();
+    let close_menu = move || {
+        show_dropdown.set(String::new());
+        if let Some(toggle) = toggle_ref.get_untracked() {
+            let _ = toggle.focus();
+        }
+    };
+    let focus_menu = move || {
+        request_animation_frame(move || {
+            if let Some(menu) = menu_ref.get_untracked() {
+                if let Some(item) = menu
+                    .query_selector("[role=menuitem]")
+                    .ok()
+                    .flatten()
+                    .and_then(|item| item.dyn_into::<HtmlElement>().ok())
+                {
+                    let _ = item.focus();
+                }
+            }
+        });
+    };
 
     view! {
-        <tr>
+        <tr class:opacity-60=pending aria-busy=move || pending.get().to_string()>
             <ListItem>
                 <label class="flex">
                     <SelectItem item_id=principal.get_untracked().name_or_empty()/>
@@ -487,12 +699,18 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                         </span>
                     </span>
                     <div class="grow">
-                        <span class="block text-sm font-semibold text-gray-800 dark:text-gray-200">
-                            {principal
-                                .get_untracked()
-                                .description_or_name()
-                                .unwrap_or_default()
-                                .to_string()}
+                        <span class="flex items-center gap-x-2 text-sm font-semibold text-gray-800 dark:text-gray-200">
+                            <InlineEdit principal field=InlineField::Description editable=can_edit>
+                                {move || {
+                                    principal
+                                        .with(|principal| {
+                                            principal.description_or_name().unwrap_or_default().to_string()
+                                        })
+                                }}
+                            </InlineEdit>
+                            <Show when=move || principal.with(|principal| principal.is_disabled())>
+                                <Badge color=Color::Gray>Disabled</Badge>
+                            </Show>
                         </span>
                         <span class="block text-sm text-gray-500">
                             {principal.get_untracked().name().unwrap_or("unknown").to_string()}
@@ -541,21 +759,19 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                 matches!(selected_type, PrincipalType::Individual | PrincipalType::Tenant)
             }>
                 <ListTextItem>
-                    {match (
-                        principal.get_untracked().quota.as_int_non_zero(),
-                        principal.get_untracked().used_quota.as_int_non_zero(),
-                    ) {
-                        (Some(quota), Some(used_quota)) => {
-                            format!(
-                                "{} ({}%)",
-                                format_size(used_quota, DECIMAL),
-                                (used_quota as f64 / quota as f64 * 100.0).round() as u8,
-                            )
-                        }
-                        (_, Some(used_quota)) => format_size(used_quota, DECIMAL).to_string(),
-                        _ => "N/A".to_string(),
-                    }}
-
+                    <InlineEdit principal field=InlineField::Quota editable=can_edit>
+                        {move || {
+                            principal
+                                .with(|principal| {
+                                    view! {
+                                        <QuotaBar
+                                            used=principal.used_quota.as_int().unwrap_or_default()
+                                            quota=principal.quota.as_int_non_zero()
+                                        />
+                                    }
+                                })
+                        }}
+                    </InlineEdit>
                 </ListTextItem>
             </Show>
             <Show when=move || {
@@ -614,8 +830,13 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
             <ListItem subclass="px-6 py-1.5">
                 <div class="hs-dropdown relative inline-block">
                     <button
-                        id="hs-table-dropdown-1"
+                        id=format!("{menu_id}-toggle")
                         type="button"
+                        node_ref=toggle_ref
+                        aria-label="Actions"
+                        aria-haspopup="menu"
+                        aria-expanded=move || is_open().to_string()
+                        aria-controls=menu_id.clone()
                         class="hs-dropdown-toggle py-1.5 px-2 inline-flex justify-center items-center gap-2 rounded-lg text-gray-700 align-middle disabled:opacity-50 disabled:pointer-events-none focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-offset-white focus:ring-blue-600 transition-all text-sm dark:text-neutral-400 dark:hover:text-white dark:focus:ring-offset-gray-800"
                         on:click=move |_| {
                             show_dropdown
@@ -628,27 +849,56 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                                     }
                                 });
                         }
+
+                        on:keydown=move |ev| {
+                            if ev.key() == "ArrowDown" {
+                                ev.prevent_default();
+                                show_dropdown.set(principal.get_untracked().name_or_empty());
+                                focus_menu();
+                            }
+                        }
                     >
 
-                        <IconThreeDots/>
+                        <IconThreeDots attr:aria-hidden="true"/>
                     </button>
                     <div
+                        id=menu_id.clone()
+                        node_ref=menu_ref
+                        role="menu"
                         class=move || {
-                            if show_dropdown.get() == principal.get().name().unwrap_or_default() {
+                            if is_open() {
                                 "hs-dropdown-menu transition-[opacity,margin] absolute top-full right-0 duration opacity-100 open block divide-y divide-gray-200 min-w-40 z-50 bg-white shadow-2xl rounded-lg p-2 mt-2 dark:divide-neutral-700 dark:bg-neutral-800 dark:border dark:border-neutral-700"
                             } else {
                                 "hs-dropdown-menu transition-[opacity,margin] duration hs-dropdown-open:opacity-100 opacity-0 hidden divide-y divide-gray-200 min-w-40 z-20 bg-white shadow-2xl rounded-lg p-2 mt-2 dark:divide-neutral-700 dark:bg-neutral-800 dark:border dark:border-neutral-700"
                             }
                         }
 
-                        aria-labelledby="hs-table-dropdown-1"
+                        aria-labelledby=format!("{menu_id}-toggle")
+                        on:keydown=move |ev| {
+                            match ev.key().as_str() {
+                                "Escape" => {
+                                    ev.stop_propagation();
+                                    close_menu();
+                                }
+                                "Tab" => show_dropdown.set(String::new()),
+                                _ => {
+                                    if let Some(menu) = menu_ref.get_untracked() {
+                                        move_focus(&menu, "[role=menuitem]", &ev);
+                                    }
+                                }
+                            }
+                        }
                     >
                         <div class="py-2 first:pt-0 last:pb-0">
-                            <span class="block py-2 px-3 text-xs font-medium uppercase text-gray-400 dark:text-neutral-600">
+                            <span
+                                class="block py-2 px-3 text-xs font-medium uppercase text-gray-400 dark:text-neutral-600"
+                                role="presentation"
+                            >
                                 Actions
                             </span>
                             <a
-                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:ring-2 focus:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                role="menuitem"
+                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
                                 href=move || {
                                     format!(
                                         "/manage/directory/{}/{}/edit",
@@ -661,7 +911,8 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                                 Edit
                             </a>
                             <a
-                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:ring-2 focus:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                role="menuitem"
+                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
                                 href=move || {
                                     format!(
                                         "/manage/dns/{}/view",
@@ -677,7 +928,48 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                                 DNS records
                             </a>
                             <a
-                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:ring-2 focus:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                role="menuitem"
+                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                href=move || {
+                                    format!(
+                                        "/manage/directory/accounts/{}/activity",
+                                        principal.get_untracked().name().unwrap_or_default(),
+                                    )
+                                }
+
+                                class:hidden=!can_view_activity
+                            >
+
+                                Activity
+                            </a>
+                            <button
+                                type="button"
+                                role="menuitem"
+                                class="w-full flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                disabled=pending
+                                on:click=move |_| {
+                                    show_dropdown.set(String::new());
+                                    toggle_disabled();
+                                }
+
+                                class:hidden=move || {
+                                    !matches!(selected_type, PrincipalType::Individual)
+                                }
+                            >
+
+                                {move || {
+                                    if principal.with(|principal| principal.is_disabled()) {
+                                        "Enable account"
+                                    } else {
+                                        "Disable account"
+                                    }
+                                }}
+
+                            </button>
+                            <button
+                                type="button"
+                                role="menuitem"
+                                class="w-full flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
                                 on:click=move |_| {
                                     show_dropdown.set(String::new());
                                     params
@@ -694,9 +986,10 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                             >
 
                                 Purge deleted
-                            </a>
+                            </button>
                             <a
-                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:ring-2 focus:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                                role="menuitem"
+                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-gray-800 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-neutral-400 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
                                 href=move || {
                                     UrlBuilder::new("/manage/undelete")
                                         .with_subpath(
@@ -717,30 +1010,36 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
                             </a>
                         </div>
                         <div class="py-2 first:pt-0 last:pb-0">
-                            <a
-                                class="flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-red-600 hover:bg-gray-100 focus:ring-2 focus:ring-blue-500 dark:text-red-500 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
+                            <button
+                                type="button"
+                                role="menuitem"
+                                class="w-full flex items-center gap-x-3 py-2 px-3 rounded-lg text-sm text-red-600 hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:text-red-500 dark:hover:bg-neutral-700 dark:hover:text-neutral-300"
                                 on:click=move |_| {
                                     let id = principal.get_untracked().name_or_empty();
                                     show_dropdown.set(String::new());
-                                    params
-                                        .modal
-                                        .set(
-                                            Modal::with_title("Confirm deletion")
-                                                .with_message(
-                                                    "Are you sure you want to delete this principal? This action cannot be undone.",
-                                                )
-                                                .with_button(format!("Delete {id}"))
-                                                .with_dangerous_callback(move || {
-                                                    params
-                                                        .delete_action
-                                                        .dispatch(Arc::new(HashSet::from_iter([id.clone()])));
-                                                }),
-                                        );
+                                    if selected_type == PrincipalType::Domain {
+                                        params
+                                            .domain_delete_action
+                                            .dispatch(Arc::new(HashSet::from_iter([id])));
+                                        return;
+                                    }
+                                    let confirmed = confirm_dangerous(
+                                        "Confirm deletion",
+                                        "Are you sure you want to delete this principal? This action cannot be undone.",
+                                        format!("Delete {id}"),
+                                    );
+                                    spawn_local(async move {
+                                        if confirmed.await {
+                                            params
+                                                .delete_action
+                                                .dispatch(Arc::new(HashSet::from_iter([id])));
+                                        }
+                                    });
                                 }
                             >
 
                                 Delete
-                            </a>
+                            </button>
                         </div>
                     </div>
                 </div>
@@ -750,3 +1049,292 @@ fn PrincipalItem(principal: Principal, params: Parameters) -> impl IntoView {
         </tr>
     }
 }
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InlineField {
+    Description,
+    Quota,
+}
+
+#[component]
+fn InlineEdit(
+    principal: RwSignal<Principal>,
+    field: InlineField,
+    #[prop(into)] editable: MaybeSignal<bool>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let api = use_api();
+    let editing = create_rw_signal(false);
+    let text = create_rw_signal(String::new());
+    let error = create_rw_signal(None::<String>);
+    let saved = create_rw_signal(false);
+    let pending = create_rw_signal(false);
+    let input_ref = create_node_ref::<html::Input>();
+    let children = store_value(children);
+
+    let start = move || {
+        text.set(principal.with_untracked(|principal| field.text(principal)));
+        error.set(None);
+        saved.set(false);
+        editing.set(true);
+        request_animation_frame(move || {
+            if let Some(input) = input_ref.get_untracked() {
+                let _ = input.focus();
+                input.select();
+            }
+        });
+    };
+    let save = move || {
+        if pending.get_untracked() || !editing.get_untracked() {
+            return;
+        }
+        let value = match field.parse(&text.get_untracked()) {
+            Ok(value) => value,
+            Err(err) => {
+                error.set(Some(err.to_string()));
+                return;
+            }
+        };
+        if principal.with_untracked(|principal| field.value(principal)) == value {
+            editing.set(false);
+            return;
+        }
+        let name = principal.get_untracked().name_or_empty();
+        pending.set(true);
+
+        spawn_local(async move {
+            let result = api
+                .patch(("/api/principal", &name))
+                .with_body(vec![PrincipalUpdate::set(field.into(), value.clone())])
+                .unwrap()
+                .send::<()>()
+                .await;
+            pending.try_set(false);
+            match result {
+                Ok(_) => {
+                    principal.try_update(|principal| field.apply(principal, value));
+                    editing.try_set(false);
+                    saved.try_set(true);
+                    set_timeout(
+                        move || {
+                            saved.try_set(false);
+                        },
+                        Duration::from_secs(2),
+                    );
+                }
+                Err(err) => {
+                    error.try_set(Some(err.to_string()));
+                }
+            }
+        });
+    };
+
+    view! {
+        <Show
+            when=move || editing.get()
+            fallback=move || {
+                view! {
+                    <div class="flex items-center gap-x-2">
+                        <Show
+                            when=move || editable.get()
+                            fallback=move || children.with_value(|children| children())
+                        >
+                            <button
+                                type="button"
+                                class="text-start rounded hover:bg-gray-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-500 dark:hover:bg-gray-800"
+                                title=field.title()
+                                on:click=move |_| start()
+                            >
+                                {children.with_value(|children| children())}
+                            </button>
+                        </Show>
+                        <Show when=move || saved.get()>
+                            <IconCheck attr:class="flex-shrink-0 size-3.5 text-green-600"/>
+                            <span class="sr-only">Saved</span>
+                        </Show>
+                    </div>
+                }
+            }
+        >
+
+            <div>
+                <input
+                    type="text"
+                    node_ref=input_ref
+                    class="py-1 px-2 block w-48 border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                    class=("border-red-500", move || error.with(Option::is_some))
+                    placeholder=field.placeholder()
+                    disabled=pending
+                    prop:value=text
+                    aria-invalid=move || error.with(Option::is_some).to_string()
+                    on:input=move |ev| {
+                        text.set(event_target_value(&ev));
+                        error.set(None);
+                    }
+                    on:keydown=move |ev| {
+                        match ev.key().as_str() {
+                            "Enter" => {
+                                ev.prevent_default();
+                                save();
+                            }
+                            "Escape" => {
+                                ev.stop_propagation();
+                                editing.set(false);
+                            }
+                            _ => {}
+                        }
+                    }
+                    on:blur=move |_| save()
+                />
+                {move || {
+                    error
+                        .get()
+                        .map(|error| {
+                            view! { <p class="mt-1 text-xs text-red-600">{error}</p> }
+                        })
+                }}
+
+            </div>
+        </Show>
+    }
+}
+
+impl InlineField {
+    fn value(&self, principal: &Principal) -> PrincipalValue {
+        match self {
+            InlineField::Description => principal.description.clone(),
+            InlineField::Quota => {
+                PrincipalValue::Integer(principal.quota.as_int().unwrap_or_default())
+            }
+        }
+    }
+
+    fn text(&self, principal: &Principal) -> String {
+        match self {
+            InlineField::Description => principal.description().unwrap_or_default().to_string(),
+            InlineField::Quota => principal
+                .quota
+                .as_int_non_zero()
+                .map(|quota| Size(quota).to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn parse(&self, text: &str) -> Result<PrincipalValue, &'static str> {
+        let text = text.trim();
+        match self {
+            InlineField::Description if text.chars().count() > 255 => {
+                Err("The display name is too long.")
+            }
+            InlineField::Description => Ok(PrincipalValue::String(text.to_string())),
+            InlineField::Quota if text.is_empty() => Ok(PrincipalValue::Integer(0)),
+            InlineField::Quota => text
+                .parse::<Size>()
+                .map(|size| PrincipalValue::Integer(size.0))
+                .map_err(|_| "Enter a size such as 500M or 2G."),
+        }
+    }
+
+    fn apply(&self, principal: &mut Principal, value: PrincipalValue) {
+        match self {
+            InlineField::Description => principal.description = value,
+            InlineField::Quota => principal.quota = value,
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            InlineField::Description => "Edit display name",
+            InlineField::Quota => "Edit quota",
+        }
+    }
+
+    fn placeholder(&self) -> &'static str {
+        match self {
+            InlineField::Description => "Display name",
+            InlineField::Quota => "Unlimited",
+        }
+    }
+}
+
+impl From<InlineField> for PrincipalField {
+    fn from(field: InlineField) -> Self {
+        match field {
+            InlineField::Description => PrincipalField::Description,
+            InlineField::Quota => PrincipalField::Quota,
+        }
+    }
+}
+
+#[component]
+fn QuotaBar(used: u64, quota: Option<u64>) -> impl IntoView {
+    match quota {
+        Some(quota) => {
+            let pct = (used as f64 / quota as f64 * 100.0).clamp(0.0, 100.0);
+            let color = if pct >= 90.0 {
+                "bg-red-500"
+            } else if pct >= 75.0 {
+                "bg-amber-500"
+            } else {
+                "bg-green-500"
+            };
+
+            view! {
+                <div
+                    class="flex items-center gap-x-3"
+                    title=format!(
+                        "{} of {} used",
+                        format_size(used, DECIMAL),
+                        format_size(quota, DECIMAL),
+                    )
+                >
+
+                    <div
+                        class="flex w-24 h-1.5 bg-gray-200 rounded-full overflow-hidden dark:bg-gray-700"
+                        role="progressbar"
+                        aria-valuenow=pct.round().to_string()
+                        aria-valuemin="0"
+                        aria-valuemax="100"
+                    >
+                        <div
+                            class=format!("flex flex-col justify-center overflow-hidden {color}")
+                            style=format!("width: {pct:.0}%")
+                        ></div>
+                    </div>
+                    <span class="text-xs">{format!("{pct:.0}%")}</span>
+                </div>
+            }
+                .into_view()
+        }
+        None => view! {
+            <div class="flex items-center gap-x-3" title="Unlimited quota">
+                <span class="text-sm">"∞"</span>
+                <span class="text-xs">{format_size(used, DECIMAL)}</span>
+            </div>
+        }
+        .into_view(),
+    }
+}
+
+impl Principal {
+    fn compare(&self, other: &Principal, order: &SortOrder) -> Ordering {
+        let result = match order.column.as_str() {
+            "name" => self.name().cmp(&other.name()),
+            "type" => self.typ.map(|t| t.name()).cmp(&other.typ.map(|t| t.name())),
+            "email" => self.email().cmp(&other.email()),
+            "quota" => self
+                .used_quota
+                .as_int()
+                .cmp(&other.used_quota.as_int())
+                .then_with(|| self.quota.as_int().cmp(&other.quota.as_int())),
+            _ => Ordering::Equal,
+        };
+
+        if order.ascending {
+            result
+        } else {
+            result.reverse()
+        }
+        .then_with(|| self.id.cmp(&other.id))
+    }
+}
@@ -12,10 +12,20 @@ use serde::{
 };
 
 use base64::{engine::general_purpose::STANDARD, Engine};
+use leptos::{create_effect, Memo, SignalGet};
+use leptos_router::use_navigate;
 
+use crate::{
+    core::{oauth::use_authorization, Permission},
+    pages::config::SettingChange,
+};
+
+pub mod delivery;
 pub mod dns;
 pub mod edit;
+pub mod import;
 pub mod list;
+pub mod setup;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Principal {
@@ -70,6 +80,10 @@ pub struct Principal {
     #[serde(rename = "members")]
     pub members: PrincipalValue,
 
+    #[serde(default, skip_serializing_if = "PrincipalValue::is_none")]
+    #[serde(rename = "externalMembers")]
+    pub external_members: PrincipalValue,
+
     #[serde(default, skip_serializing_if = "PrincipalValue::is_none")]
     #[serde(rename = "enabledPermissions")]
     pub enabled_permissions: PrincipalValue,
@@ -110,6 +124,7 @@ pub enum PrincipalField {
     Emails,
     MemberOf,
     Members,
+    ExternalMembers,
     Tenant,
     Roles,
     Lists,
@@ -126,6 +141,28 @@ pub struct PrincipalUpdate {
     value: PrincipalValue,
 }
 
+impl PrincipalUpdate {
+    pub fn set(field: PrincipalField, value: PrincipalValue) -> Self {
+        PrincipalUpdate {
+            action: PrincipalAction::Set,
+            field,
+            value,
+        }
+    }
+
+    pub fn disable(disabled: bool) -> Self {
+        PrincipalUpdate {
+            action: if disabled {
+                PrincipalAction::AddItem
+            } else {
+                PrincipalAction::RemoveItem
+            },
+            field: PrincipalField::DisabledPermissions,
+            value: PrincipalValue::String("authenticate".to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum PrincipalAction {
     #[serde(rename = "set")]
@@ -156,6 +193,58 @@ impl Principal {
             && self.description.is_none()
     }
 
+    // The quota usage changes whenever mail is delivered
+    pub fn changes_since(&self, loaded: &Principal) -> Vec<SettingChange> {
+        [
+            ("name", &loaded.name, &self.name),
+            ("description", &loaded.description, &self.description),
+            ("quota", &loaded.quota, &self.quota),
+            ("tenant", &loaded.tenant, &self.tenant),
+            ("picture", &loaded.picture, &self.picture),
+            ("secrets", &loaded.secrets, &self.secrets),
+            ("emails", &loaded.emails, &self.emails),
+            ("urls", &loaded.urls, &self.urls),
+            ("memberOf", &loaded.member_of, &self.member_of),
+            ("roles", &loaded.roles, &self.roles),
+            ("lists", &loaded.lists, &self.lists),
+            ("members", &loaded.members, &self.members),
+            (
+                "externalMembers",
+                &loaded.external_members,
+                &self.external_members,
+            ),
+            (
+                "enabledPermissions",
+                &loaded.enabled_permissions,
+                &self.enabled_permissions,
+            ),
+            (
+                "disabledPermissions",
+                &loaded.disabled_permissions,
+                &self.disabled_permissions,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(key, old, new)| {
+            let (old_value, new_value) = if key == "secrets" {
+                // Only tell that the credentials changed
+                (
+                    old.to_text().map(|_| "••••••••".to_string()),
+                    new.to_text().map(|_| "••••••••".to_string()),
+                )
+            } else {
+                (old.to_text(), new.to_text())
+            };
+            SettingChange {
+                key: key.to_string(),
+                old_value,
+                new_value,
+            }
+        })
+        .collect()
+    }
+
     pub fn into_updates(self, changes: Principal) -> Vec<PrincipalUpdate> {
         let current = self;
         let mut updates = vec![];
@@ -246,6 +335,11 @@ impl Principal {
                 changes.member_of,
             ),
             (PrincipalField::Members, current.members, changes.members),
+            (
+                PrincipalField::ExternalMembers,
+                current.external_members,
+                changes.external_members,
+            ),
             (PrincipalField::Roles, current.roles, changes.roles),
             (PrincipalField::Lists, current.lists, changes.lists),
             (
@@ -291,6 +385,22 @@ impl Principal {
         self.name.as_str()
     }
 
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_permissions
+            .as_string_list()
+            .iter()
+            .any(|permission| permission == "authenticate")
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        let mut permissions = std::mem::take(&mut self.disabled_permissions).unwrap_string_list();
+        permissions.retain(|permission| permission != "authenticate");
+        if disabled {
+            permissions.push("authenticate".to_string());
+        }
+        self.disabled_permissions = PrincipalValue::StringList(permissions);
+    }
+
     pub fn name_or_empty(&self) -> String {
         self.name.as_str().unwrap_or_default().to_string()
     }
@@ -313,6 +423,21 @@ impl PrincipalValue {
         matches!(self, PrincipalValue::String(s) if s.is_empty())
     }
 
+    pub fn to_text(&self) -> Option<String> {
+        match self {
+            PrincipalValue::String(s) if !s.is_empty() => Some(s.clone()),
+            PrincipalValue::StringList(l) if !l.is_empty() => Some(l.join(", ")),
+            PrincipalValue::Integer(i) => Some(i.to_string()),
+            PrincipalValue::IntegerList(l) if !l.is_empty() => Some(
+                l.iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            _ => None,
+        }
+    }
+
     pub fn unwrap_string(self) -> String {
         match self {
             PrincipalValue::String(s) => s,
@@ -462,6 +587,34 @@ impl PrincipalType {
             _ => unimplemented!("resource_name for {:?}", self),
         }
     }
+
+    pub fn list_permission(&self) -> Option<Permission> {
+        match self {
+            PrincipalType::Individual => Some(Permission::IndividualList),
+            PrincipalType::Group => Some(Permission::GroupList),
+            PrincipalType::List => Some(Permission::MailingListList),
+            PrincipalType::Domain => Some(Permission::DomainList),
+            PrincipalType::Tenant => Some(Permission::TenantList),
+            PrincipalType::Role => Some(Permission::RoleList),
+            PrincipalType::ApiKey => Some(Permission::ApiKeyList),
+            PrincipalType::OauthClient => Some(Permission::OauthClientList),
+            PrincipalType::Resource | PrincipalType::Location | PrincipalType::Other => None,
+        }
+    }
+}
+
+// The route guards only check that some principal type can be listed
+pub fn guard_principal_type(selected_type: Memo<PrincipalType>) {
+    let auth = use_authorization();
+    create_effect(move |_| {
+        let allowed = selected_type
+            .get()
+            .list_permission()
+            .is_some_and(|permission| auth.get().has_permission(permission));
+        if !allowed {
+            use_navigate()("/forbidden", Default::default());
+        }
+    });
 }
 
 impl FromStr for PrincipalType {
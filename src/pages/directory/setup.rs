@@ -0,0 +1,525 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::Arc;
+
+use gloo_storage::{LocalStorage, Storage};
+use leptos::*;
+use leptos_router::use_navigate;
+use pwhash::sha512_crypt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        clipboard::CopyButton,
+        form::{
+            button::Button,
+            input::{InputPassword, InputSwitch, InputText},
+            select::Select,
+            Form, FormButtonBar, FormElement, FormItem, FormSection,
+        },
+        icon::IconCheck,
+        list::table::{Table, TableRow},
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+        Color,
+    },
+    core::{
+        api::{use_api, ApiClient},
+        oauth::{take_login_redirect, use_authorization},
+        schema::{Builder, Schemas, SelectType, Source, Transformer, Type, Validator},
+        AccessToken, Permission,
+    },
+    pages::{
+        directory::{
+            dns::DnsRecord, edit::DkimSignature, Principal, PrincipalType, PrincipalValue,
+        },
+        List,
+    },
+};
+
+const SETUP_STORAGE_KEY: &str = "webadmin_setup";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum SetupStep {
+    #[default]
+    Domain,
+    Account,
+    Dkim,
+    Dns,
+    Done,
+}
+
+const STEPS: &[(SetupStep, &str)] = &[
+    (SetupStep::Domain, "Domain"),
+    (SetupStep::Account, "Account"),
+    (SetupStep::Dkim, "DKIM"),
+    (SetupStep::Dns, "DNS records"),
+];
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct SetupProgress {
+    step: SetupStep,
+    domain: String,
+}
+
+// Progress is kept per server, as this browser may manage several
+impl SetupProgress {
+    fn load(base_url: &str) -> Self {
+        LocalStorage::get(storage_key(base_url)).unwrap_or_default()
+    }
+
+    fn save(&self, base_url: &str) {
+        if let Err(err) = LocalStorage::set(storage_key(base_url), self) {
+            log::warn!("Failed to save setup progress: {}", err);
+        }
+    }
+}
+
+fn storage_key(base_url: &str) -> String {
+    format!("{SETUP_STORAGE_KEY}:{base_url}")
+}
+
+pub async fn landing_page(auth_token: RwSignal<AccessToken>, default_url: &str) -> String {
+    if let Some(redirect) = take_login_redirect() {
+        return redirect;
+    }
+    let auth = auth_token.get_untracked();
+    if auth
+        .permissions()
+        .has_access_all(&[Permission::DomainCreate, Permission::IndividualCreate])
+        && needs_setup(ApiClient::new(auth_token), &auth.base_url).await
+    {
+        "/manage/setup".to_string()
+    } else {
+        default_url.to_string()
+    }
+}
+
+// Offered until finished or skipped, when left halfway or the server has no domains or accounts
+async fn needs_setup(api: ApiClient, base_url: &str) -> bool {
+    match SetupProgress::load(base_url).step {
+        SetupStep::Done => false,
+        SetupStep::Domain => api
+            .get("/api/principal")
+            .with_parameter("types", "domain,individual")
+            .with_parameter("fields", "name")
+            .with_parameter("count", "1")
+            .send::<List<Principal>>()
+            .await
+            .is_ok_and(|list| list.total == 0),
+        _ => true,
+    }
+}
+
+#[component]
+pub fn SetupWizard() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let base_url = store_value(use_authorization().get_untracked().base_url);
+    let progress = create_rw_signal(SetupProgress::load(&base_url.get_value()));
+    let step = create_memo(move |_| progress.get().step);
+    let domain = create_memo(move |_| progress.get().domain);
+    let (pending, set_pending) = create_signal(false);
+
+    let schemas = expect_context::<Arc<Schemas>>();
+    let domain_data = schemas
+        .build_form("setup-domain")
+        .with_value("domain", domain.get_untracked())
+        .into_signal();
+    let account_data = schemas.build_form("setup-account").into_signal();
+    let dkim_data = schemas.build_form("setup-dkim").into_signal();
+
+    let advance = move |next: SetupStep| {
+        progress.update(|progress| {
+            progress.step = next;
+            progress.save(&base_url.get_value());
+        });
+    };
+    let finish = move |url: &str| {
+        advance(SetupStep::Done);
+        use_navigate()(url, Default::default());
+    };
+
+    let create_domain = create_action(move |name: &String| {
+        let name = name.clone();
+
+        async move {
+            set_pending.set(true);
+            let result = api
+                .post("/api/principal")
+                .with_body(Principal {
+                    typ: Some(PrincipalType::Domain),
+                    name: PrincipalValue::String(name.clone()),
+                    ..Default::default()
+                })
+                .unwrap()
+                .send::<u32>()
+                .await;
+            set_pending.set(false);
+
+            match result {
+                Ok(_) => {
+                    progress.update(|progress| {
+                        progress.domain = name;
+                        progress.step = SetupStep::Account;
+                        progress.save(&base_url.get_value());
+                    });
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let create_account = create_action(move |account: &Principal| {
+        let account = account.clone();
+
+        async move {
+            set_pending.set(true);
+            let result = api
+                .post("/api/principal")
+                .with_body(account)
+                .unwrap()
+                .send::<u32>()
+                .await;
+            set_pending.set(false);
+
+            match result {
+                Ok(_) => advance(SetupStep::Dkim),
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let create_dkim = create_action(move |signatures: &Vec<DkimSignature>| {
+        let signatures = signatures.clone();
+
+        async move {
+            set_pending.set(true);
+            for signature in &signatures {
+                if let Err(err) = api
+                    .post("/api/dkim")
+                    .with_body(signature)
+                    .unwrap()
+                    .send::<()>()
+                    .await
+                {
+                    set_pending.set(false);
+                    alert.set(Alert::from(err));
+                    return;
+                }
+            }
+            set_pending.set(false);
+            advance(SetupStep::Dns);
+        }
+    });
+
+    let records = create_resource(
+        move || (step.get() == SetupStep::Dns).then(|| domain.get()),
+        move |domain| async move {
+            match domain {
+                Some(domain) => {
+                    api.get(("/api/dns/records", &domain))
+                        .send::<Vec<DnsRecord>>()
+                        .await
+                }
+                None => Ok(vec![]),
+            }
+        },
+    );
+
+    view! {
+        <Form
+            title="Set up your mail server"
+            subtitle="Create the first domain and account to start receiving mail."
+        >
+            <ol class="mb-8 flex items-center gap-x-2">
+                {STEPS
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (this_step, label))| {
+                        let this_step = *this_step;
+                        let is_done = move || step.get() > this_step;
+                        let is_current = move || step.get() == this_step;
+                        view! {
+                            <li class="group flex items-center gap-x-2 flex-1">
+                                <span
+                                    class="size-7 flex justify-center items-center flex-shrink-0 rounded-full text-sm font-medium"
+                                    class=("bg-blue-600", move || is_done() || is_current())
+                                    class=("text-white", move || is_done() || is_current())
+                                    class=("bg-gray-100", move || !is_done() && !is_current())
+                                    class=("text-gray-800", move || !is_done() && !is_current())
+                                    class=("dark:bg-gray-700", move || !is_done() && !is_current())
+                                    class=("dark:text-white", move || !is_done() && !is_current())
+                                >
+                                    {move || {
+                                        if is_done() {
+                                            view! { <IconCheck attr:class="flex-shrink-0 size-3"/> }
+                                                .into_view()
+                                        } else {
+                                            (idx + 1).into_view()
+                                        }
+                                    }}
+
+                                </span>
+                                <span
+                                    class="text-sm text-gray-500 dark:text-gray-400"
+                                    class=("font-semibold", is_current)
+                                >
+                                    {*label}
+                                </span>
+                                <div class="w-full h-px flex-1 bg-gray-200 group-last:hidden dark:bg-gray-700"></div>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ol>
+
+            <FormSection hide=Signal::derive(move || step.get() != SetupStep::Domain)>
+                <FormItem
+                    label="Domain name"
+                    tooltip="The domain your addresses will be under, such as example.org."
+                >
+                    <InputText placeholder="example.org" element=FormElement::new("domain", domain_data)/>
+                </FormItem>
+            </FormSection>
+
+            <FormSection hide=Signal::derive(move || step.get() != SetupStep::Account)>
+                <FormItem label="Login name">
+                    <InputText placeholder="john" element=FormElement::new("name", account_data)/>
+                </FormItem>
+                <FormItem label="Name" is_optional=true>
+                    <InputText placeholder="John Doe" element=FormElement::new("description", account_data)/>
+                </FormItem>
+                <FormItem label="Password">
+                    <InputPassword element=FormElement::new("password", account_data)/>
+                </FormItem>
+                <FormItem label="Administrator">
+                    <InputSwitch
+                        label="Grant this account full access to the server"
+                        element=FormElement::new("admin", account_data)
+                    />
+                </FormItem>
+            </FormSection>
+
+            <FormSection hide=Signal::derive(move || step.get() != SetupStep::Dkim)>
+                <FormItem label="DKIM keys">
+                    <Select element=FormElement::new("dkim_algorithm", dkim_data)/>
+                </FormItem>
+                <FormItem
+                    label="DKIM selector"
+                    tooltip=concat!(
+                        "Leave empty to use a server-generated selector. ",
+                        "When generating both keys, 'e' and 'r' are appended ",
+                        "to tell them apart."
+                    )
+                    is_optional=true
+                    hide=Signal::derive(move || {
+                        dkim_data.get().value_as_str("dkim_algorithm") == Some("none")
+                    })
+                >
+                    <InputText placeholder="stalwart" element=FormElement::new("dkim_selector", dkim_data)/>
+                </FormItem>
+            </FormSection>
+
+            <Show when=move || step.get() == SetupStep::Dns>
+                <p class="mb-4 text-sm text-gray-600 dark:text-gray-400">
+                    "Publish these records in the DNS zone of " <span class="font-semibold">{domain}</span>
+                    " so that other servers can deliver mail to it and verify the messages it sends."
+                </p>
+                <Transition fallback=Skeleton>
+                    {move || match records.get() {
+                        None => None,
+                        Some(Err(err)) => {
+                            alert.set(Alert::from(err));
+                            Some(view! { <Skeleton/> }.into_view())
+                        }
+                        Some(Ok(records)) => Some(
+                            view! {
+                                <Table headers=vec![
+                                    "Type".to_string(),
+                                    "Name".to_string(),
+                                    "Contents".to_string(),
+                                ]>
+                                    {records
+                                        .into_iter()
+                                        .map(|record| {
+                                            view! {
+                                                <TableRow>
+                                                    <span>{record.typ}</span>
+                                                    <span>{record.name}</span>
+                                                    <div class="flex items-center gap-x-2">
+                                                        <span class="truncate max-w-md">{record.content.clone()}</span>
+                                                        <CopyButton value=record.content/>
+                                                    </div>
+                                                </TableRow>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </Table>
+                            }
+                                .into_view(),
+                        ),
+                    }}
+
+                </Transition>
+            </Show>
+
+            <FormButtonBar>
+                <Button
+                    text="Skip setup"
+                    color=Color::Gray
+                    on_click=move |_| finish("/manage/directory/domains")
+                    disabled=pending
+                />
+                <Show when=move || step.get() == SetupStep::Domain>
+                    <Button
+                        text="Create domain"
+                        color=Color::Blue
+                        on_click=move |_| {
+                            domain_data
+                                .update(|data| {
+                                    if data.validate_form() {
+                                        create_domain.dispatch(data.value::<String>("domain").unwrap_or_default());
+                                    }
+                                });
+                        }
+
+                        disabled=pending
+                    />
+                </Show>
+                <Show when=move || step.get() == SetupStep::Account>
+                    <Button
+                        text="Create account"
+                        color=Color::Blue
+                        on_click=move |_| {
+                            account_data
+                                .update(|data| {
+                                    if data.validate_form() {
+                                        let name = data.value::<String>("name").unwrap_or_default();
+                                        let is_admin = data.value::<bool>("admin").unwrap_or_default();
+                                        create_account
+                                            .dispatch(Principal {
+                                                typ: Some(PrincipalType::Individual),
+                                                emails: PrincipalValue::StringList(
+                                                    vec![format!("{name}@{}", domain.get_untracked())],
+                                                ),
+                                                name: PrincipalValue::String(name),
+                                                description: data
+                                                    .value::<String>("description")
+                                                    .map(PrincipalValue::String)
+                                                    .unwrap_or_default(),
+                                                secrets: PrincipalValue::StringList(
+                                                    data
+                                                        .value::<String>("password")
+                                                        .map(|password| sha512_crypt::hash(password).unwrap())
+                                                        .into_iter()
+                                                        .collect(),
+                                                ),
+                                                roles: PrincipalValue::StringList(
+                                                    vec![if is_admin { "admin" } else { "user" }.to_string()],
+                                                ),
+                                                ..Default::default()
+                                            });
+                                    }
+                                });
+                        }
+
+                        disabled=pending
+                    />
+                </Show>
+                <Show when=move || step.get() == SetupStep::Dkim>
+                    <Button
+                        text="Generate keys"
+                        color=Color::Blue
+                        on_click=move |_| {
+                            dkim_data
+                                .update(|data| {
+                                    if data.validate_form() {
+                                        create_dkim.dispatch(data.dkim_signatures(&domain.get_untracked()));
+                                    }
+                                });
+                        }
+
+                        disabled=pending
+                    />
+                </Show>
+                <Show when=move || step.get() == SetupStep::Dns>
+                    <Button
+                        text="Check DNS records"
+                        color=Color::Gray
+                        on_click=move |_| finish(&format!("/manage/dns/{}/view", domain.get_untracked()))
+                    />
+                    <Button
+                        text="Finish"
+                        color=Color::Blue
+                        on_click=move |_| finish("/manage/directory/accounts")
+                    />
+                </Show>
+            </FormButtonBar>
+        </Form>
+    }
+}
+
+impl Builder<Schemas, ()> {
+    pub fn build_setup(self) -> Self {
+        self.new_schema("setup-domain")
+            .new_field("domain")
+            .typ(Type::Input)
+            .input_check(
+                [Transformer::Trim, Transformer::Lowercase],
+                [Validator::Required, Validator::IsDomain],
+            )
+            .build()
+            .build()
+            .new_schema("setup-account")
+            .new_field("name")
+            .typ(Type::Input)
+            .input_check(
+                [Transformer::RemoveSpaces, Transformer::Lowercase],
+                [Validator::Required],
+            )
+            .build()
+            .new_field("description")
+            .typ(Type::Input)
+            .input_check([Transformer::Trim], [])
+            .build()
+            .new_field("password")
+            .typ(Type::Secret)
+            .input_check([], [Validator::Required])
+            .build()
+            .new_field("admin")
+            .typ(Type::Boolean)
+            .default("true")
+            .build()
+            .build()
+            .new_schema("setup-dkim")
+            .new_field("dkim_algorithm")
+            .typ(Type::Select {
+                typ: SelectType::Single,
+                source: Source::Static(&[
+                    ("all", "Generate Ed25519 and RSA keys"),
+                    ("ed25519", "Generate an Ed25519 key"),
+                    ("rsa", "Generate an RSA 2048 key"),
+                    ("none", "Do not generate keys"),
+                ]),
+            })
+            .default("all")
+            .build()
+            .new_field("dkim_selector")
+            .typ(Type::Input)
+            .input_check(
+                [Transformer::Trim, Transformer::Lowercase],
+                [Validator::IsId],
+            )
+            .build()
+            .build()
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::use_navigate;
+
+use crate::{components::icon::IconArrowLeft, core::oauth::use_authorization};
+
+#[component]
+pub fn Forbidden() -> impl IntoView {
+    let auth = use_authorization();
+    if !auth.get_untracked().is_logged_in() {
+        use_navigate()("/login", Default::default());
+    }
+    let home = move || match auth.get().default_url() {
+        "" => "/account/preferences",
+        url => url,
+    };
+    let username = move || auth.get().username.to_string();
+
+    view! {
+        <Html lang="en" class="h-full"/>
+        <Body class="dark:bg-slate-900 bg-gray-100 flex h-full items-center py-16"/>
+        <div class="max-w-[50rem] flex flex-col mx-auto size-full">
+            <header class="mb-auto flex justify-center z-50 w-full py-4">
+                <nav class="px-4 sm:px-6 lg:px-8" aria-label="Global">
+                    <a
+                        class="flex-none text-xl font-semibold sm:text-3xl dark:text-white"
+                        href="#"
+                        aria-label="Brand"
+                    >
+                        Stalwart
+                    </a>
+                </nav>
+            </header>
+
+            <div class="text-center py-10 px-4 sm:px-6 lg:px-8">
+                <h1 class="block text-7xl font-bold text-gray-800 sm:text-9xl dark:text-white">
+                    403
+                </h1>
+                <p class="mt-3 text-gray-600 dark:text-gray-400">
+                    You do not have permission to view this page.
+                </p>
+                <p class="text-gray-600 dark:text-gray-400">
+                    "You are signed in as "
+                    <span class="font-semibold">{username}</span>
+                    ", ask an administrator if you need access."
+                </p>
+                <div class="mt-5 flex flex-col justify-center items-center gap-2 sm:flex-row sm:gap-3">
+                    <a
+                        class="w-full sm:w-auto py-3 px-4 inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-lg border border-transparent text-blue-600 hover:text-blue-800 disabled:opacity-50 disabled:pointer-events-none dark:text-blue-500 dark:hover:text-blue-400 dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                        href=home
+                    >
+                        <IconArrowLeft/>
+                        Back to your pages
+                    </a>
+                    <a
+                        class="w-full sm:w-auto py-3 px-4 inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                        href="/login"
+                    >
+                        Sign in as someone else
+                    </a>
+                </div>
+            </div>
+
+            <footer class="mt-auto text-center py-5">
+                <div class="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8">
+                    <p class="text-sm text-gray-500">(c) Stalwart Labs Ltd.</p>
+                </div>
+            </footer>
+        </div>
+    }
+}
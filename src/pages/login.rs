@@ -18,13 +18,18 @@ use crate::{
             input::{InputPassword, InputText},
             FormElement,
         },
+        icon::{IconCheckCircle, IconExclamationCircle},
         messages::alert::{use_alerts, Alert, Alerts},
     },
     core::{
+        debounce::use_debounce,
+        http::HttpRequest,
         oauth::{oauth_authenticate, AuthenticationResult},
         schema::{Builder, Schemas, Transformer, Type, Validator},
+        sso::{start_sso_login, SsoSettings},
         AccessToken, Permissions,
     },
+    pages::directory::setup::landing_page,
     STATE_LOGIN_NAME_KEY, STATE_STORAGE_KEY,
 };
 
@@ -34,6 +39,15 @@ struct SavedSession {
     base_url: String,
 }
 
+const PROBE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ServerProbe {
+    InvalidUrl,
+    Unreachable,
+    Found,
+}
+
 #[component]
 pub fn Login() -> impl IntoView {
     let stored_data: Option<SavedSession> = LocalStorage::get(STATE_LOGIN_NAME_KEY).ok();
@@ -71,6 +85,7 @@ pub fn Login() -> impl IntoView {
                             auth_token.is_valid = true;
                             auth_token.permissions = permissions;
                             auth_token.is_enterprise = response.is_enterprise;
+                            auth_token.identity_provider = None;
 
                             if let Err(err) =
                                 SessionStorage::set(STATE_STORAGE_KEY, auth_token.clone())
@@ -96,7 +111,8 @@ pub fn Login() -> impl IntoView {
                             );
                         }
 
-                        use_navigate()(default_url, Default::default());
+                        let redirect = landing_page(auth_token, default_url).await;
+                        use_navigate()(&redirect, Default::default());
                     }
                     AuthenticationResult::TotpRequired => {
                         show_totp.set(true);
@@ -127,6 +143,52 @@ pub fn Login() -> impl IntoView {
                 .is_some())
             && !show_totp.get()
     });
+    // Links shared from the single sign-on settings carry the provider
+    let sso_settings =
+        query.with_untracked(
+            |query| match (query.get("sso-issuer"), query.get("sso-client-id")) {
+                (Some(issuer), Some(client_id)) => SsoSettings {
+                    issuer: issuer.clone(),
+                    client_id: client_id.clone(),
+                },
+                _ => SsoSettings::load(),
+            },
+        );
+    let show_sso =
+        create_rw_signal(query.with_untracked(|query| query.get("sso-issuer").is_some()));
+    let sso_data = expect_context::<Arc<Schemas>>()
+        .build_form("sso")
+        .with_value("issuer", sso_settings.issuer)
+        .with_value("client-id", sso_settings.client_id)
+        .into_signal();
+    let sso_action = create_action(move |(base_url, settings): &(String, SsoSettings)| {
+        let base_url = base_url.clone();
+        let settings = settings.clone();
+
+        async move {
+            if let Err(err) = start_sso_login(&base_url, settings).await {
+                alert.set(err);
+            }
+        }
+    });
+    // Probe once typing has paused rather than on every keystroke
+    let probed_url = create_rw_signal(None::<String>);
+    let probe = use_debounce(PROBE_DEBOUNCE, move |base_url| probed_url.set(base_url));
+    create_effect(move |_| {
+        probe.call(
+            data.with(|data| data.value::<String>("base-url"))
+                .filter(|base_url| !base_url.is_empty()),
+        );
+    });
+    let server_probe = create_local_resource(
+        move || probed_url.get(),
+        |base_url| async move {
+            match base_url {
+                Some(base_url) => Some(probe_server(base_url.trim_end_matches('/')).await),
+                None => None,
+            }
+        },
+    );
 
     view! {
         <Body class="dark:bg-slate-900 bg-gray-100 flex h-full items-center py-16"/>
@@ -151,6 +213,23 @@ pub fn Login() -> impl IntoView {
                                             placeholder="https://mail.example.org"
                                             element=FormElement::new("base-url", data)
                                         />
+                                        <div class="mt-2 text-xs" aria-live="polite">
+                                            {move || {
+                                                if server_probe.loading().get() {
+                                                    return Some(
+                                                        view! {
+                                                            <span class="text-gray-500">Checking server...</span>
+                                                        }
+                                                            .into_view(),
+                                                    );
+                                                }
+                                                server_probe
+                                                    .get()
+                                                    .flatten()
+                                                    .map(|probe| view! { <ServerProbeStatus probe/> })
+                                            }}
+
+                                        </div>
                                     </div>
                                 </Show>
                                 <Show when=move || !show_totp.get()>
@@ -223,6 +302,7 @@ pub fn Login() -> impl IntoView {
                                                 };
                                                 let base_url = data
                                                     .value::<String>("base-url")
+                                                    .map(|url| url.trim_end_matches('/').to_string())
                                                     .unwrap_or_default();
                                                 if remember_me.get() {
                                                     if let Err(err) = LocalStorage::set(
@@ -247,6 +327,68 @@ pub fn Login() -> impl IntoView {
 
                                     Sign in
                                 </button>
+
+                                <Show when=move || !show_totp.get()>
+                                    <div class="py-1 flex items-center text-xs text-gray-400 uppercase before:flex-1 before:border-t before:border-gray-200 before:me-6 after:flex-1 after:border-t after:border-gray-200 after:ms-6 dark:text-gray-500 dark:before:border-gray-600 dark:after:border-gray-600">
+                                        Or
+                                    </div>
+                                    <Show when=move || show_sso.get()>
+                                        <div>
+                                            <label class="block text-sm mb-2 dark:text-white">
+                                                Identity provider
+                                            </label>
+                                            <InputText
+                                                placeholder="https://sso.example.org/realms/example"
+                                                element=FormElement::new("issuer", sso_data)
+                                            />
+                                        </div>
+                                        <div>
+                                            <label class="block text-sm mb-2 dark:text-white">
+                                                Client ID
+                                            </label>
+                                            <InputText
+                                                placeholder="stalwart-webadmin"
+                                                element=FormElement::new("client-id", sso_data)
+                                            />
+                                        </div>
+                                    </Show>
+                                    <button
+                                        type="button"
+                                        class="w-full py-3 px-4 inline-flex justify-center items-center gap-x-2 text-sm font-semibold rounded-lg border border-gray-200 bg-white text-gray-800 shadow-sm hover:bg-gray-50 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                                        disabled=move || sso_action.pending().get()
+                                        on:click=move |_| {
+                                            if !show_sso.get() {
+                                                show_sso.set(true);
+                                                return;
+                                            }
+                                            let base_url = data
+                                                .get()
+                                                .value::<String>("base-url")
+                                                .map(|url| url.trim_end_matches('/').to_string())
+                                                .unwrap_or_default();
+                                            sso_data
+                                                .update(|sso_data| {
+                                                    if sso_data.validate_form() {
+                                                        sso_action
+                                                            .dispatch((
+                                                                base_url,
+                                                                SsoSettings {
+                                                                    issuer: sso_data
+                                                                        .value::<String>("issuer")
+                                                                        .unwrap_or_default(),
+                                                                    client_id: sso_data
+                                                                        .value::<String>("client-id")
+                                                                        .unwrap_or_default(),
+                                                                },
+                                                            ));
+                                                    }
+                                                });
+                                        }
+                                    >
+
+                                        Sign in with single sign-on
+                                    </button>
+                                </Show>
                             </div>
                         </form>
                     </div>
@@ -256,6 +398,58 @@ pub fn Login() -> impl IntoView {
     }
 }
 
+#[component]
+fn ServerProbeStatus(probe: ServerProbe) -> impl IntoView {
+    let (is_found, message) = match probe {
+        ServerProbe::Found => (true, "Server found".to_string()),
+        ServerProbe::Unreachable => (
+            false,
+            "Couldn't reach a Stalwart server at this URL".to_string(),
+        ),
+        ServerProbe::InvalidUrl => (
+            false,
+            "Enter the full address of the server, such as https://mail.example.org".to_string(),
+        ),
+    };
+
+    view! {
+        <span
+            class="inline-flex items-center gap-x-1.5"
+            class=("text-teal-600", is_found)
+            class=("dark:text-teal-500", is_found)
+            class=("text-yellow-600", !is_found)
+            class=("dark:text-yellow-500", !is_found)
+        >
+            {if is_found {
+                view! { <IconCheckCircle attr:class="flex-shrink-0 size-4"/> }
+            } else {
+                view! { <IconExclamationCircle attr:class="flex-shrink-0 size-4"/> }
+            }}
+            {message}
+        </span>
+    }
+}
+
+// Only informs the user, signing in is still allowed whatever the outcome
+async fn probe_server(base_url: &str) -> ServerProbe {
+    match web_sys::Url::new(base_url) {
+        Ok(url)
+            if matches!(url.protocol().as_str(), "http:" | "https:") && !url.host().is_empty() => {}
+        _ => return ServerProbe::InvalidUrl,
+    }
+
+    match HttpRequest::get(format!("{base_url}/healthz/live"))
+        .send_response()
+        .await
+    {
+        Ok(_) => ServerProbe::Found,
+        Err(err) => {
+            log::debug!("Failed to probe server at {base_url}: {err:?}");
+            ServerProbe::Unreachable
+        }
+    }
+}
+
 impl Builder<Schemas, ()> {
     pub fn build_login(self) -> Self {
         self.new_schema("login")
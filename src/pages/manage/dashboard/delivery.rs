@@ -0,0 +1,273 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::Arc;
+
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use leptos::*;
+use leptos_router::{use_navigate, use_query_map, A};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        chart::{Chart, ChartKind, ChartSeries},
+        list::table::{Table, TableRow},
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+        Color,
+    },
+    core::{api::use_api, http, url::UrlBuilder},
+};
+
+use super::{
+    fetch_metrics, parse_window, DashboardHeader, DashboardPanel, EmptyPanel, WindowSelector,
+};
+
+const WINDOWS: &[(&str, &str); 3] = &[("1h", "1 hour"), ("24h", "24 hours"), ("7d", "7 days")];
+const DEFAULT_WINDOW: &str = "24h";
+
+const QUEUED: &str = "queue.queue-message";
+const DELIVERED: &str = "delivery.completed";
+const DEFERRED: &str = "queue.rescheduled";
+const BOUNCED: &str = "delivery.dsn-perm-fail";
+
+// Delivery failures by the SMTP stage the remote server rejected
+const FAILURES: [(&str, &str); 10] = [
+    ("delivery.mx-lookup-failed", "MX lookup failed"),
+    ("delivery.connect-error", "Connection error"),
+    ("delivery.greeting-failed", "Greeting rejected"),
+    ("delivery.ehlo-rejected", "EHLO rejected"),
+    ("delivery.auth-failed", "Authentication failed"),
+    ("delivery.mail-from-rejected", "MAIL FROM rejected"),
+    ("delivery.rcpt-to-rejected", "RCPT TO rejected"),
+    ("delivery.rcpt-to-failed", "RCPT TO failed"),
+    ("delivery.message-rejected", "Message rejected"),
+    ("delivery.rate-limit-exceeded", "Rate limited"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Failure {
+    label: String,
+    count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FunnelStage {
+    label: String,
+    total: u64,
+    series: Vec<(DateTime<Utc>, f64)>,
+    href: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DeliveryStats {
+    stages: Vec<FunnelStage>,
+    failures: Vec<Failure>,
+    failures_href: String,
+}
+
+#[component]
+pub fn DeliveryDashboard() -> impl IntoView {
+    let query = use_query_map();
+    let window = create_memo(move |_| {
+        query
+            .with(|q| q.get("window").cloned())
+            .filter(|window| WINDOWS.iter().any(|(id, _)| id == window))
+            .unwrap_or_else(|| DEFAULT_WINDOW.to_string())
+    });
+    let api = use_api();
+    let alert = use_alerts();
+
+    let stats = create_resource(
+        move || window.get(),
+        move |window| async move {
+            let after = Utc::now() - parse_window(&window).unwrap_or(TimeDelta::hours(24));
+            let after_ = after.to_rfc3339_opts(SecondsFormat::Secs, true);
+            let metrics = fetch_metrics(api, after).await?;
+            let mut failures = FAILURES
+                .iter()
+                .map(|(id, label)| Failure {
+                    label: label.to_string(),
+                    count: metrics.total(id).unwrap_or_default() as u64,
+                })
+                .filter(|failure| failure.count > 0)
+                .collect::<Vec<_>>();
+            failures.sort_by_key(|failure| std::cmp::Reverse(failure.count));
+
+            let history = |failing: bool| {
+                UrlBuilder::new("/manage/tracing/delivery")
+                    .with_parameter("after", after_.clone())
+                    .with_optional_parameter("status", failing.then_some("failing"))
+            };
+            let stages = [
+                (QUEUED, "Queued", UrlBuilder::new("/manage/queue/messages")),
+                (DELIVERED, "Delivered", history(false)),
+                (DEFERRED, "Deferred", history(true)),
+                (BOUNCED, "Bounced", history(true)),
+            ]
+            .into_iter()
+            .map(|(id, label, href)| FunnelStage {
+                label: label.to_string(),
+                total: metrics.total(id).unwrap_or_default() as u64,
+                series: metrics.series(id),
+                href: href.finish(),
+            })
+            .collect();
+
+            Ok(Arc::new(DeliveryStats {
+                stages,
+                failures,
+                failures_href: history(true).finish(),
+            }))
+        },
+    );
+
+    view! {
+        <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+            <DashboardHeader
+                title="Delivery"
+                subtitle="Outbound messages by outcome and the stage where deliveries failed."
+                loading=stats.loading()
+                on_refresh=move |_| stats.refetch()
+            >
+                <WindowSelector
+                    options=WINDOWS
+                    value=window
+                    on_change=move |window: String| {
+                        use_navigate()(
+                            &UrlBuilder::new("/manage/dashboard/delivery")
+                                .with_parameter("window", window)
+                                .finish(),
+                            Default::default(),
+                        );
+                    }
+                />
+            </DashboardHeader>
+
+            <Transition fallback=Skeleton>
+                {move || match stats.get() {
+                    None => None,
+                    Some(Err(http::Error::Unauthorized)) => {
+                        use_navigate()("/login", Default::default());
+                        Some(view! { <div></div> }.into_view())
+                    }
+                    Some(Err(err)) => {
+                        alert.set(Alert::from(err));
+                        Some(view! { <Skeleton/> }.into_view())
+                    }
+                    Some(Ok(stats)) => {
+                        let stats = Arc::unwrap_or_clone(stats);
+                        let queued = stats.stages.first().map_or(0, |stage| stage.total).max(1);
+                        let outcomes = stats
+                            .stages
+                            .iter()
+                            .skip(1)
+                            .zip([Color::Green, Color::Yellow, Color::Red])
+                            .map(|(stage, color)| {
+                                ChartSeries::new(stage.label.clone(), stage.series.clone())
+                                    .with_color(color)
+                            })
+                            .collect::<Vec<_>>();
+                        Some(
+                            view! {
+                                <div class="grid sm:grid-cols-2 lg:grid-cols-4 gap-4 sm:gap-6 mb-6">
+                                    {stats
+                                        .stages
+                                        .into_iter()
+                                        .map(|stage| {
+                                            let share = stage.total * 100 / queued;
+                                            view! {
+                                                <A
+                                                    href=stage.href
+                                                    class="group flex flex-col p-4 md:p-5 bg-white border shadow-sm rounded-xl hover:shadow-md transition dark:bg-slate-900 dark:border-gray-800"
+                                                >
+                                                    <p class="text-xs uppercase tracking-wide text-gray-500">
+                                                        {stage.label}
+                                                    </p>
+                                                    <h3 class="mt-1 text-xl sm:text-2xl font-medium text-gray-800 group-hover:text-blue-600 dark:text-gray-200 dark:group-hover:text-blue-500">
+                                                        {stage.total}
+                                                    </h3>
+                                                    <div class="mt-2 h-1.5 bg-gray-200 rounded-full overflow-hidden dark:bg-gray-700">
+                                                        <div
+                                                            class="h-full bg-blue-600 rounded-full dark:bg-blue-500"
+                                                            style=format!("width: {}%", share.min(100))
+                                                        ></div>
+                                                    </div>
+                                                </A>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+
+                                <div class="mb-6">
+                                    <DashboardPanel title="Outcomes over time">
+                                        <Chart
+                                            series=Signal::derive(move || outcomes.clone())
+                                            kind=ChartKind::Bar
+                                            empty_text="No deliveries were attempted in this period."
+                                        />
+                                    </DashboardPanel>
+                                </div>
+
+                                <DashboardPanel title="Failures by stage">
+                                    {if stats.failures.is_empty() {
+                                        view! {
+                                            <EmptyPanel text="No delivery attempts failed in this period."/>
+                                        }
+                                            .into_view()
+                                    } else {
+                                        let failed = stats
+                                            .failures
+                                            .iter()
+                                            .map(|failure| failure.count)
+                                            .sum::<u64>()
+                                            .max(1);
+                                        let href = stats.failures_href;
+                                        view! {
+                                            <Table headers=vec![
+                                                "Stage".to_string(),
+                                                "Failures".to_string(),
+                                                "Share".to_string(),
+                                            ]>
+                                                {stats
+                                                    .failures
+                                                    .into_iter()
+                                                    .map(|failure| {
+                                                        let share = format!(
+                                                            "{:.1}%",
+                                                            failure.count as f64 * 100.0 / failed as f64,
+                                                        );
+                                                        let href = href.clone();
+                                                        view! {
+                                                            <TableRow>
+                                                                <A
+                                                                    href=href
+                                                                    class="text-blue-600 hover:underline dark:text-blue-500"
+                                                                >
+                                                                    {failure.label}
+                                                                </A>
+                                                                <span>{failure.count}</span>
+                                                                <span>{share}</span>
+                                                            </TableRow>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </Table>
+                                        }
+                                            .into_view()
+                                    }}
+
+                                </DashboardPanel>
+                            }
+                                .into_view(),
+                        )
+                    }
+                }}
+
+            </Transition>
+        </div>
+    }
+}
@@ -0,0 +1,310 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::collections::BTreeMap;
+
+use ahash::AHashMap;
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        chart::{Points, Sparkline},
+        icon::IconRefresh,
+        list::{refresh::AutoRefreshToggle, toolbar::ToolbarButton},
+        Color,
+    },
+    core::{api::ApiClient, http, i18n::t_signal},
+};
+
+pub mod delivery;
+pub mod network;
+pub mod overview;
+pub mod performance;
+pub mod security;
+
+const DASHBOARD_REFRESH_KEY: &str = "webadmin_dashboard_refresh";
+
+pub type Series = Points;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[serde(rename_all = "camelCase")]
+pub enum Metric {
+    Counter {
+        id: String,
+        timestamp: DateTime<Utc>,
+        value: u64,
+    },
+    Gauge {
+        id: String,
+        timestamp: DateTime<Utc>,
+        value: u64,
+    },
+    Histogram {
+        id: String,
+        timestamp: DateTime<Utc>,
+        count: u64,
+        sum: u64,
+        #[serde(default)]
+        p50: Option<u64>,
+        #[serde(default)]
+        p95: Option<u64>,
+        #[serde(default)]
+        p99: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Percentile {
+    P50,
+    P95,
+    P99,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricHistory {
+    series: AHashMap<String, BTreeMap<DateTime<Utc>, f64>>,
+}
+
+pub fn parse_window(window: &str) -> Option<TimeDelta> {
+    let (amount, unit) = window.split_at(window.len().checked_sub(1)?);
+    let amount = amount.parse::<i64>().ok()?;
+    match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        _ => None,
+    }
+}
+
+pub async fn fetch_metrics(api: ApiClient, after: DateTime<Utc>) -> http::Result<MetricHistory> {
+    api.get("/api/telemetry/metrics")
+        .conditional()
+        .with_parameter("after", after.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .send::<Vec<Metric>>()
+        .await
+        .map(MetricHistory::from)
+}
+
+impl From<Vec<Metric>> for MetricHistory {
+    fn from(metrics: Vec<Metric>) -> Self {
+        let mut history = MetricHistory::default();
+        for metric in metrics {
+            let (id, timestamp, value) = match metric {
+                Metric::Counter {
+                    id,
+                    timestamp,
+                    value,
+                }
+                | Metric::Gauge {
+                    id,
+                    timestamp,
+                    value,
+                } => (id, timestamp, value as f64),
+                // Histograms are reduced to their mean, percentiles are kept as separate series
+                Metric::Histogram {
+                    id,
+                    timestamp,
+                    count,
+                    sum,
+                    p50,
+                    p95,
+                    p99,
+                } => {
+                    for (percentile, value) in [
+                        (Percentile::P50, p50),
+                        (Percentile::P95, p95),
+                        (Percentile::P99, p99),
+                    ] {
+                        if let Some(value) = value {
+                            history
+                                .series
+                                .entry(percentile.series_id(&id))
+                                .or_default()
+                                .insert(timestamp, value as f64);
+                        }
+                    }
+                    (
+                        id,
+                        timestamp,
+                        if count > 0 {
+                            sum as f64 / count as f64
+                        } else {
+                            0.0
+                        },
+                    )
+                }
+            };
+            *history
+                .series
+                .entry(id)
+                .or_default()
+                .entry(timestamp)
+                .or_default() += value;
+        }
+        history
+    }
+}
+
+impl MetricHistory {
+    pub fn series_matching(&self, filter: impl Fn(&str) -> bool) -> Series {
+        let mut result: BTreeMap<DateTime<Utc>, f64> = BTreeMap::new();
+        for (_, series) in self.series.iter().filter(|(id, _)| filter(id)) {
+            for (timestamp, value) in series {
+                *result.entry(*timestamp).or_default() += value;
+            }
+        }
+        result.into_iter().collect()
+    }
+
+    pub fn series(&self, id: &str) -> Series {
+        self.series_matching(|metric| metric == id)
+    }
+
+    pub fn latest(&self, id: &str) -> Option<f64> {
+        self.series
+            .get(id)
+            .and_then(|series| series.values().next_back().copied())
+    }
+
+    pub fn total(&self, id: &str) -> Option<f64> {
+        self.series.get(id).map(|series| series.values().sum())
+    }
+
+    pub fn average(&self, id: &str) -> Option<f64> {
+        self.series
+            .get(id)
+            .filter(|series| !series.is_empty())
+            .map(|series| series.values().sum::<f64>() / series.len() as f64)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.series.contains_key(id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+}
+
+impl Percentile {
+    pub fn series_id(&self, id: &str) -> String {
+        format!("{id}:{}", self.as_str())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Percentile::P50 => "p50",
+            Percentile::P95 => "p95",
+            Percentile::P99 => "p99",
+        }
+    }
+}
+
+#[component]
+pub fn DashboardHeader(
+    #[prop(into)] title: String,
+    #[prop(into)] subtitle: String,
+    #[prop(into)] loading: Signal<bool>,
+    #[prop(into)] on_refresh: Callback<()>,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-wrap justify-between items-center gap-3 mb-6">
+            <div>
+                <h2 class="text-xl font-semibold text-gray-800 dark:text-gray-200">{title}</h2>
+                <p class="text-sm text-gray-600 dark:text-gray-400">{subtitle}</p>
+            </div>
+            <div class="inline-flex flex-wrap items-center gap-2">
+                {children.map(|children| children())}
+                <AutoRefreshToggle
+                    storage_key=DASHBOARD_REFRESH_KEY
+                    enabled=true
+                    paused=loading
+                    on_refresh=on_refresh
+                />
+                <ToolbarButton
+                    text=t_signal("button.refresh")
+                    color=Color::Gray
+                    on_click=Callback::new(move |_| on_refresh.call(()))
+                >
+                    <IconRefresh/>
+                </ToolbarButton>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn WindowSelector(
+    options: &'static [(&'static str, &'static str)],
+    #[prop(into)] value: Signal<String>,
+    #[prop(into)] on_change: Callback<String>,
+) -> impl IntoView {
+    view! {
+        <div class="inline-flex rounded-lg shadow-sm" role="group" aria-label="Time window">
+            {options
+                .iter()
+                .map(|(window, label)| {
+                    view! {
+                        <button
+                            type="button"
+                            class="py-2 px-3 inline-flex items-center text-sm font-medium first:rounded-s-lg last:rounded-e-lg border border-gray-200 text-gray-800 hover:bg-gray-50 -ms-px dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                            class:bg-gray-100=move || value.get() == *window
+                            class=("dark:bg-gray-800", move || value.get() == *window)
+                            aria-pressed=move || (value.get() == *window).to_string()
+                            on:click=move |_| on_change.call(window.to_string())
+                        >
+                            {*label}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn DashboardPanel(#[prop(into)] title: String, children: Children) -> impl IntoView {
+    view! {
+        <div class="p-4 md:p-5 bg-white border shadow-sm rounded-xl dark:bg-slate-900 dark:border-gray-800">
+            <h3 class="mb-4 text-sm font-semibold text-gray-800 dark:text-gray-200">{title}</h3>
+            {children()}
+        </div>
+    }
+}
+
+#[component]
+pub fn EmptyPanel(#[prop(into)] text: String) -> impl IntoView {
+    view! { <p class="text-sm text-gray-500 dark:text-gray-400">{text}</p> }
+}
+
+#[component]
+pub fn MetricCard(
+    #[prop(into)] title: String,
+    #[prop(into)] value: Signal<Option<String>>,
+    #[prop(optional, into)] subtitle: MaybeProp<String>,
+    #[prop(optional, into)] series: Option<Signal<Series>>,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-col bg-white border shadow-sm rounded-xl dark:bg-slate-900 dark:border-gray-800">
+            <div class="p-4 md:p-5">
+                <p class="text-xs uppercase tracking-wide text-gray-500">{title}</p>
+                <h3 class="mt-1 text-xl sm:text-2xl font-medium text-gray-800 dark:text-gray-200">
+                    {move || value.get().unwrap_or_else(|| "—".to_string())}
+                </h3>
+                {move || {
+                    subtitle
+                        .get()
+                        .map(|subtitle| view! { <p class="text-xs text-gray-500">{subtitle}</p> })
+                }}
+                {series.map(|series| view! { <Sparkline series/> })}
+            </div>
+        </div>
+    }
+}
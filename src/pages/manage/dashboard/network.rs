@@ -0,0 +1,390 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{cmp::Ordering, sync::Arc};
+
+use ahash::AHashMap;
+
+use chrono::{SecondsFormat, TimeDelta, Utc};
+use leptos::*;
+use leptos_router::use_navigate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        chart::{Chart, ChartSeries},
+        clipboard::CopyButton,
+        list::{
+            header::{ColumnList, ColumnSort, SortOrder},
+            table::{Table, TableRow},
+            ListItem,
+        },
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+    },
+    core::{api::use_api, http},
+    pages::{
+        manage::tracing::{SpanEvent, CONNECTION_SPAN},
+        List,
+    },
+};
+
+use super::{fetch_metrics, DashboardHeader, DashboardPanel, MetricHistory, Series};
+
+const LISTENERS: [(&str, &str); 5] = [
+    ("smtp", "SMTP"),
+    ("imap", "IMAP"),
+    ("pop3", "POP3"),
+    ("http", "HTTP & JMAP"),
+    ("manage-sieve", "ManageSieve"),
+];
+const TOP_IPS: usize = 25;
+const SAMPLE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RemoteIp {
+    ip: String,
+    connections: u64,
+    listeners: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ListenerStats {
+    name: String,
+    active: u64,
+    accepted: u64,
+    rejected: u64,
+    series: Series,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NetworkStats {
+    listeners: Vec<ListenerStats>,
+    remote_ips: Vec<RemoteIp>,
+}
+
+#[component]
+pub fn NetworkDashboard() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let sort = create_rw_signal(Some(SortOrder {
+        column: "connections".to_string(),
+        ascending: false,
+    }));
+
+    let stats = create_resource(
+        || (),
+        move |_| async move {
+            let after = Utc::now() - TimeDelta::hours(1);
+            let metrics = fetch_metrics(api, after).await?;
+            // The server has no per-IP counters, so the most recent SMTP
+            // connection spans are tallied here
+            let spans = api
+                .get("/api/telemetry/traces")
+                .with_parameter("type", CONNECTION_SPAN)
+                .with_parameter("page", "1")
+                .with_parameter("limit", SAMPLE_SIZE.to_string())
+                .with_parameter("values", "1")
+                .with_parameter("after", after.to_rfc3339_opts(SecondsFormat::Secs, true))
+                .send::<List<SpanEvent>>()
+                .await?;
+
+            Ok(Arc::new(NetworkStats {
+                listeners: ListenerStats::from_metrics(&metrics),
+                remote_ips: RemoteIp::from_spans(&spans.items),
+            }))
+        },
+    );
+
+    view! {
+        <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+            <DashboardHeader
+                title="Network"
+                subtitle="Connections per listener and busiest remote addresses over the last hour."
+                loading=stats.loading()
+                on_refresh=move |_| stats.refetch()
+            />
+
+            <Transition fallback=Skeleton>
+                {move || match stats.get() {
+                    None => None,
+                    Some(Err(http::Error::Unauthorized)) => {
+                        use_navigate()("/login", Default::default());
+                        Some(view! { <div></div> }.into_view())
+                    }
+                    Some(Err(err)) => {
+                        alert.set(Alert::from(err));
+                        Some(view! { <Skeleton/> }.into_view())
+                    }
+                    Some(Ok(stats)) => {
+                        let max_active = stats
+                            .listeners
+                            .iter()
+                            .map(|listener| listener.active)
+                            .max()
+                            .unwrap_or_default()
+                            .max(1);
+                        let connections = stats
+                            .listeners
+                            .iter()
+                            .filter(|listener| !listener.series.is_empty())
+                            .map(|listener| {
+                                ChartSeries::new(listener.name.clone(), listener.series.clone())
+                            })
+                            .collect::<Vec<_>>();
+                        let mut remote_ips = stats.remote_ips.clone();
+                        if let Some(sort) = sort.get() {
+                            remote_ips.sort_by(|a, b| a.compare(b, &sort));
+                        }
+                        Some(
+                            view! {
+                                <div class="grid lg:grid-cols-2 gap-4 sm:gap-6 mb-6">
+                                    <div class="p-4 md:p-5 bg-white border shadow-sm rounded-xl dark:bg-slate-900 dark:border-gray-800">
+                                        <h3 class="mb-4 text-sm font-semibold text-gray-800 dark:text-gray-200">
+                                            Active connections
+                                        </h3>
+                                        <ul class="space-y-3">
+                                            {stats
+                                                .listeners
+                                                .clone()
+                                                .into_iter()
+                                                .map(|listener| {
+                                                    let width = listener.active * 100 / max_active;
+                                                    view! {
+                                                        <li class="grid grid-cols-[8rem_1fr_3rem] items-center gap-x-3 text-sm">
+                                                            <span class="text-gray-800 dark:text-gray-200">
+                                                                {listener.name}
+                                                            </span>
+                                                            <div class="h-2 bg-gray-200 rounded-full overflow-hidden dark:bg-gray-700">
+                                                                <div
+                                                                    class="h-full bg-blue-600 rounded-full dark:bg-blue-500"
+                                                                    style=format!("width: {width}%")
+                                                                ></div>
+                                                            </div>
+                                                            <span class="text-end tabular-nums text-gray-500">
+                                                                {listener.active}
+                                                            </span>
+                                                        </li>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </ul>
+                                    </div>
+                                    <Table headers=vec![
+                                        "Listener".to_string(),
+                                        "Active".to_string(),
+                                        "Accepted".to_string(),
+                                        "Rejected".to_string(),
+                                        "Rejection rate".to_string(),
+                                    ]>
+                                        {stats
+                                            .listeners
+                                            .iter()
+                                            .map(|listener| {
+                                                let rejection_rate = listener.rejection_rate();
+                                                let ListenerStats {
+                                                    name,
+                                                    active,
+                                                    accepted,
+                                                    rejected,
+                                                    ..
+                                                } = listener.clone();
+                                                view! {
+                                                    <TableRow>
+                                                        <span>{name}</span>
+                                                        <span>{active}</span>
+                                                        <span>{accepted}</span>
+                                                        <span>{rejected}</span>
+                                                        <span>{rejection_rate}</span>
+                                                    </TableRow>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </Table>
+                                </div>
+
+                                <div class="mb-6">
+                                    <DashboardPanel title="Connections over time">
+                                        <Chart
+                                            series=Signal::derive(move || connections.clone())
+                                            empty_text="No connections in the last hour."
+                                        />
+                                    </DashboardPanel>
+                                </div>
+
+                                <div class="bg-white border border-gray-200 rounded-xl shadow-sm overflow-x-auto dark:bg-slate-900 dark:border-gray-700">
+                                    <div class="px-6 py-4 border-b border-gray-200 dark:border-gray-700">
+                                        <h3 class="text-sm font-semibold text-gray-800 dark:text-gray-200">
+                                            Top remote IPs
+                                        </h3>
+                                        <p class="text-xs text-gray-500">
+                                            {format!(
+                                                "Based on the last {SAMPLE_SIZE} SMTP connections",
+                                            )}
+
+                                        </p>
+                                    </div>
+                                    <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
+                                        <ColumnList
+                                            headers=vec![
+                                                "IP address".to_string(),
+                                                "Connections".to_string(),
+                                                "Listeners".to_string(),
+                                            ]
+
+                                            sort=ColumnSort {
+                                                columns: vec![
+                                                    ("IP address", "ip"),
+                                                    ("Connections", "connections"),
+                                                ],
+                                                current: sort.into(),
+                                                on_sort: Callback::new(move |order| {
+                                                    sort.set(Some(order))
+                                                }),
+                                            }
+                                        >
+
+                                            {if remote_ips.is_empty() {
+                                                view! {
+                                                    <tr>
+                                                        <td
+                                                            colspan="3"
+                                                            class="px-6 py-3 text-sm text-gray-500"
+                                                        >
+                                                            No connections in the last hour.
+                                                        </td>
+                                                    </tr>
+                                                }
+                                                    .into_view()
+                                            } else {
+                                                remote_ips
+                                                    .into_iter()
+                                                    .map(|remote_ip| {
+                                                        view! { <RemoteIpItem remote_ip/> }
+                                                    })
+                                                    .collect_view()
+                                            }}
+
+                                        </ColumnList>
+                                    </table>
+                                </div>
+                            }
+                                .into_view(),
+                        )
+                    }
+                }}
+
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn RemoteIpItem(remote_ip: RemoteIp) -> impl IntoView {
+    let ip = remote_ip.ip.clone();
+
+    view! {
+        <tr>
+            <ListItem>
+                <div class="flex items-center gap-x-2">
+                    <span class="font-mono text-sm text-gray-800 dark:text-gray-200">
+                        {remote_ip.ip}
+                    </span>
+                    <CopyButton value=ip/>
+                </div>
+            </ListItem>
+            <ListItem subclass="px-6 py-3">
+                <span class="text-sm tabular-nums text-gray-800 dark:text-gray-200">
+                    {remote_ip.connections}
+                </span>
+            </ListItem>
+            <ListItem subclass="px-6 py-3">
+                <span class="text-sm text-gray-500">{remote_ip.listeners.join(", ")}</span>
+            </ListItem>
+        </tr>
+    }
+}
+
+impl ListenerStats {
+    fn from_metrics(metrics: &MetricHistory) -> Vec<Self> {
+        LISTENERS
+            .iter()
+            .map(|(id, name)| ListenerStats {
+                name: name.to_string(),
+                active: metrics
+                    .latest(&format!("{id}.active-connections"))
+                    .unwrap_or_default() as u64,
+                accepted: metrics
+                    .total(&format!("{id}.connection-start"))
+                    .unwrap_or_default() as u64,
+                rejected: metrics
+                    .total(&format!("{id}.connection-rejected"))
+                    .unwrap_or_default() as u64,
+                series: metrics.series(&format!("{id}.active-connections")),
+            })
+            .collect()
+    }
+
+    fn rejection_rate(&self) -> String {
+        let total = self.accepted + self.rejected;
+        if total > 0 {
+            format!("{:.1}%", self.rejected as f64 * 100.0 / total as f64)
+        } else {
+            "—".to_string()
+        }
+    }
+}
+
+impl RemoteIp {
+    fn from_spans(spans: &[SpanEvent]) -> Vec<Self> {
+        let mut remote_ips: AHashMap<String, RemoteIp> = AHashMap::new();
+        for span in spans {
+            let Some(ip) = span.value("remoteIp") else {
+                continue;
+            };
+            let remote_ip = remote_ips.entry(ip.clone()).or_insert_with(|| RemoteIp {
+                ip,
+                connections: 0,
+                listeners: Vec::new(),
+            });
+            remote_ip.connections += 1;
+            if let Some(listener) = span.value("listenerId") {
+                if !remote_ip.listeners.contains(&listener) {
+                    remote_ip.listeners.push(listener);
+                }
+            }
+        }
+
+        let mut remote_ips = remote_ips.into_values().collect::<Vec<_>>();
+        remote_ips.sort_by(|a, b| {
+            b.connections
+                .cmp(&a.connections)
+                .then_with(|| a.ip.cmp(&b.ip))
+        });
+        remote_ips.truncate(TOP_IPS);
+        remote_ips
+    }
+
+    fn compare(&self, other: &RemoteIp, order: &SortOrder) -> Ordering {
+        let result = match order.column.as_str() {
+            "ip" => self
+                .ip
+                .parse::<std::net::IpAddr>()
+                .ok()
+                .cmp(&other.ip.parse().ok())
+                .then_with(|| self.ip.cmp(&other.ip)),
+            "connections" => self.connections.cmp(&other.connections),
+            _ => Ordering::Equal,
+        };
+
+        if order.ascending {
+            result
+        } else {
+            result.reverse()
+        }
+        .then_with(|| self.ip.cmp(&other.ip))
+    }
+}
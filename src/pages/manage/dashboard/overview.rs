@@ -0,0 +1,127 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{TimeDelta, Utc};
+use humansize::{format_size, DECIMAL};
+use leptos::*;
+use leptos_router::use_navigate;
+
+use crate::{
+    components::messages::alert::{use_alerts, Alert},
+    core::{api::use_api, http},
+};
+
+use super::{fetch_metrics, DashboardHeader, MetricCard, MetricHistory};
+
+const ACTIVE_CONNECTIONS_SUFFIX: &str = ".active-connections";
+const QUEUE_COUNT: &str = "queue.count";
+const DELIVERED: &str = "delivery.completed";
+const DEFERRED: &str = "queue.rescheduled";
+const BOUNCED: &str = "delivery.dsn-perm-fail";
+const STORAGE_USED: &str = "store.size";
+
+#[component]
+pub fn Overview() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+
+    let metrics = create_resource(
+        || (),
+        move |_| async move { fetch_metrics(api, Utc::now() - TimeDelta::hours(24)).await },
+    );
+    let history = create_memo(move |_| match metrics.get() {
+        Some(Ok(history)) => history,
+        Some(Err(http::Error::Unauthorized)) => {
+            use_navigate()("/login", Default::default());
+            MetricHistory::default()
+        }
+        Some(Err(err)) => {
+            alert.set(Alert::from(err));
+            MetricHistory::default()
+        }
+        None => MetricHistory::default(),
+    });
+
+    let is_session = |id: &str| id.ends_with(ACTIVE_CONNECTIONS_SUFFIX);
+    let counter = move |id: &'static str| {
+        Signal::derive(move || {
+            history.with(|history| history.total(id).map(|total| (total as u64).to_string()))
+        })
+    };
+    let series = move |id: &'static str| Signal::derive(move || history.with(|h| h.series(id)));
+
+    view! {
+        <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+            <DashboardHeader
+                title="Overview"
+                subtitle="Server activity over the last 24 hours."
+                loading=metrics.loading()
+                on_refresh=move |_| metrics.refetch()
+            />
+
+            <div class="grid sm:grid-cols-2 lg:grid-cols-3 gap-4 sm:gap-6">
+                <MetricCard
+                    title="Active sessions"
+                    value=Signal::derive(move || {
+                        history
+                            .with(|history| {
+                                history
+                                    .series_matching(is_session)
+                                    .last()
+                                    .map(|(_, value)| (*value as u64).to_string())
+                            })
+                    })
+
+                    subtitle="SMTP, IMAP, POP3, HTTP and ManageSieve"
+                    series=Signal::derive(move || history.with(|h| h.series_matching(is_session)))
+                />
+                <MetricCard
+                    title="Queue depth"
+                    value=Signal::derive(move || {
+                        history
+                            .with(|history| {
+                                history.latest(QUEUE_COUNT).map(|value| (value as u64).to_string())
+                            })
+                    })
+
+                    subtitle="Messages waiting for delivery"
+                    series=series(QUEUE_COUNT)
+                />
+                <MetricCard
+                    title="Storage usage"
+                    value=Signal::derive(move || {
+                        history
+                            .with(|history| {
+                                history
+                                    .latest(STORAGE_USED)
+                                    .map(|value| format_size(value as u64, DECIMAL))
+                            })
+                    })
+
+                    series=series(STORAGE_USED)
+                />
+                <MetricCard
+                    title="Delivered"
+                    value=counter(DELIVERED)
+                    subtitle="Last 24 hours"
+                    series=series(DELIVERED)
+                />
+                <MetricCard
+                    title="Deferred"
+                    value=counter(DEFERRED)
+                    subtitle="Last 24 hours"
+                    series=series(DEFERRED)
+                />
+                <MetricCard
+                    title="Bounced"
+                    value=counter(BOUNCED)
+                    subtitle="Last 24 hours"
+                    series=series(BOUNCED)
+                />
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,329 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{TimeDelta, Utc};
+use humansize::{format_size, DECIMAL};
+use leptos::*;
+use leptos_router::{use_navigate, use_query_map, A};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        chart::{Chart, ChartSeries, Sparkline},
+        list::table::{Table, TableRow},
+        messages::alert::{use_alerts, Alert},
+    },
+    core::{api::use_api, http, url::UrlBuilder},
+    pages::config::edit::FetchSettings,
+};
+
+use super::{
+    fetch_metrics, parse_window, DashboardHeader, DashboardPanel, MetricCard, MetricHistory,
+    Percentile, WindowSelector,
+};
+
+const WINDOWS: &[(&str, &str); 3] = &[("15m", "15 minutes"), ("1h", "1 hour"), ("24h", "24 hours")];
+const DEFAULT_WINDOW: &str = "1h";
+
+const REQUEST_LATENCY: &[(&str, &str)] = &[
+    ("SMTP", "smtp.request-time"),
+    ("IMAP", "imap.request-time"),
+    ("POP3", "pop3.request-time"),
+    ("HTTP", "http.request-time"),
+    ("Message ingestion", "message.ingestion-time"),
+    ("Delivery attempt", "delivery.attempt-time"),
+];
+const STORE_LATENCY: &[(&str, &str)] = &[
+    ("Data read", "store.data-read-time"),
+    ("Data write", "store.data-write-time"),
+    ("Blob read", "store.blob-read-time"),
+    ("Blob write", "store.blob-write-time"),
+    ("DNS lookup", "dns.lookup-time"),
+];
+const MEMORY: &str = "server.memory";
+const CPU: &str = "server.cpu-usage";
+const CACHE_HIT: &str = "store.cache-hit";
+const CACHE_MISS: &str = "store.cache-miss";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PerformanceStats {
+    history: MetricHistory,
+    // None when the settings could not be read
+    history_enabled: Option<bool>,
+    disabled_metrics: Vec<String>,
+}
+
+#[component]
+pub fn PerformanceDashboard() -> impl IntoView {
+    let query = use_query_map();
+    let window = create_memo(move |_| {
+        query
+            .with(|q| q.get("window").cloned())
+            .filter(|window| WINDOWS.iter().any(|(id, _)| id == window))
+            .unwrap_or_else(|| DEFAULT_WINDOW.to_string())
+    });
+    let api = use_api();
+    let alert = use_alerts();
+
+    let metrics = create_resource(
+        move || window.get(),
+        move |window| {
+            async move {
+                let after = Utc::now() - parse_window(&window).unwrap_or(TimeDelta::hours(1));
+                let history = fetch_metrics(api, after).await?;
+
+                // Reading the settings requires additional permissions, the
+                // dashboard still works without them.
+                let settings = api
+                    .get("/api/settings/list")
+                    .with_parameter("prefix", "metrics")
+                    .send::<FetchSettings>()
+                    .await
+                    .ok()
+                    .map(|settings| settings.items);
+
+                Ok(PerformanceStats {
+                    history,
+                    history_enabled: settings
+                        .as_ref()
+                        .map(|items| items.get("history.enable").is_some_and(|v| v == "true")),
+                    disabled_metrics: settings
+                        .map(|items| {
+                            items
+                                .into_iter()
+                                .filter(|(key, _)| key.starts_with("disabled-events."))
+                                .map(|(_, value)| value)
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+            }
+        },
+    );
+    let stats = create_memo(move |_| match metrics.get() {
+        Some(Ok(stats)) => stats,
+        Some(Err(http::Error::Unauthorized)) => {
+            use_navigate()("/login", Default::default());
+            PerformanceStats::default()
+        }
+        Some(Err(err)) => {
+            alert.set(Alert::from(err));
+            PerformanceStats::default()
+        }
+        None => PerformanceStats::default(),
+    });
+    let history = Signal::derive(move || stats.with(|stats| stats.history.clone()));
+    let missing = move |id: &'static str| {
+        Signal::derive(move || stats.with(|stats| stats.missing_reason(id)))
+    };
+
+    let cache_hit_rate = Signal::derive(move || {
+        history.with(|history| {
+            let hits = history.total(CACHE_HIT)?;
+            let total = hits + history.total(CACHE_MISS).unwrap_or_default();
+            (total > 0.0).then(|| format!("{:.1}%", hits * 100.0 / total))
+        })
+    });
+
+    view! {
+        <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+            <DashboardHeader
+                title="Performance"
+                subtitle="Latency, resource usage and cache efficiency."
+                loading=metrics.loading()
+                on_refresh=move |_| metrics.refetch()
+            >
+                <WindowSelector
+                    options=WINDOWS
+                    value=window
+                    on_change=move |window: String| {
+                        use_navigate()(
+                            &UrlBuilder::new("/manage/dashboard/performance")
+                                .with_parameter("window", window)
+                                .finish(),
+                            Default::default(),
+                        );
+                    }
+                />
+            </DashboardHeader>
+
+            <Show when=move || {
+                stats.with(|stats| stats.history_enabled == Some(false) && stats.history.is_empty())
+            }>
+                <div
+                    class="mb-6 bg-yellow-50 border border-yellow-200 text-sm text-yellow-800 rounded-lg p-4 dark:bg-yellow-800/10 dark:border-yellow-900 dark:text-yellow-500"
+                    role="alert"
+                >
+                    <span class="font-bold">Metrics history is disabled.</span>
+                    " Charts stay empty until the server is configured to keep a "
+                    <A href="/settings/telemetry-history/edit" class="underline font-medium">
+                        metrics history
+                    </A>
+                    "."
+                </div>
+            </Show>
+
+            <div class="grid sm:grid-cols-2 lg:grid-cols-3 gap-4 sm:gap-6 mb-6">
+                <MetricCard
+                    title="Memory usage"
+                    value=Signal::derive(move || {
+                        history
+                            .with(|history| {
+                                history.latest(MEMORY).map(|value| format_size(value as u64, DECIMAL))
+                            })
+                    })
+
+                    subtitle=missing(MEMORY)
+                    series=Signal::derive(move || history.with(|h| h.series(MEMORY)))
+                />
+                <MetricCard
+                    title="CPU usage"
+                    value=Signal::derive(move || {
+                        history
+                            .with(|history| history.latest(CPU).map(|value| format!("{value:.0}%")))
+                    })
+
+                    subtitle=missing(CPU)
+                    series=Signal::derive(move || history.with(|h| h.series(CPU)))
+                />
+                <MetricCard
+                    title="Cache hit rate"
+                    value=cache_hit_rate
+                    subtitle=missing(CACHE_HIT)
+                    series=Signal::derive(move || history.with(|h| h.series(CACHE_HIT)))
+                />
+            </div>
+
+            <div class="mb-6">
+                <DashboardPanel title="Request latency (p95)">
+                    <Chart
+                        series=Signal::derive(move || {
+                            history
+                                .with(|history| {
+                                    REQUEST_LATENCY
+                                        .iter()
+                                        .filter(|(_, id)| history.contains(id))
+                                        .map(|(label, id)| {
+                                            let p95 = history.series(&Percentile::P95.series_id(id));
+                                            if p95.is_empty() {
+                                                ChartSeries::new(format!("{label} (mean)"), history.series(id))
+                                            } else {
+                                                ChartSeries::new(*label, p95)
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()
+                                })
+                        })
+
+                        format_value=|value| format_latency(Some(value))
+                        empty_text="No request latency metrics were reported in this period."
+                    />
+                </DashboardPanel>
+            </div>
+
+            <div class="grid lg:grid-cols-2 gap-4 sm:gap-6">
+                <LatencyTable title="Request latency" metrics=REQUEST_LATENCY stats=stats/>
+                <LatencyTable title="Database and DNS timings" metrics=STORE_LATENCY stats=stats/>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn LatencyTable(
+    #[prop(into)] title: String,
+    metrics: &'static [(&'static str, &'static str)],
+    #[prop(into)] stats: Signal<PerformanceStats>,
+) -> impl IntoView {
+    let headers = vec![
+        "Metric".to_string(),
+        "p50".to_string(),
+        "p95".to_string(),
+        "p99".to_string(),
+        "Mean".to_string(),
+        "Trend".to_string(),
+    ];
+
+    view! {
+        <DashboardPanel title=title>
+            <Table headers=headers>
+                {metrics
+                    .iter()
+                    .map(|(label, id)| {
+                        let percentile = move |percentile: Percentile| {
+                            move || {
+                                stats
+                                    .with(|stats| {
+                                        format_latency(
+                                            stats.history.average(&percentile.series_id(id)),
+                                        )
+                                    })
+                            }
+                        };
+                        view! {
+                            <TableRow>
+                                <span class="font-medium">{*label}</span>
+                                <span>{percentile(Percentile::P50)}</span>
+                                <span>{percentile(Percentile::P95)}</span>
+                                <span>{percentile(Percentile::P99)}</span>
+                                <span>
+                                    {move || {
+                                        stats.with(|stats| format_latency(stats.history.average(id)))
+                                    }}
+
+                                </span>
+                                {move || match stats.with(|stats| stats.missing_reason(id)) {
+                                    Some(reason) => {
+                                        view! {
+                                            <A
+                                                href="/settings/metrics/edit"
+                                                class="text-xs text-gray-500 hover:text-blue-600 dark:text-gray-400 dark:hover:text-blue-500"
+                                            >
+                                                {reason}
+                                            </A>
+                                        }
+                                            .into_view()
+                                    }
+                                    None => {
+                                        view! {
+                                            <div class="w-24 -mt-3">
+                                                <Sparkline series=Signal::derive(move || {
+                                                    stats.with(|stats| stats.history.series(id))
+                                                })/>
+                                            </div>
+                                        }
+                                            .into_view()
+                                    }
+                                }}
+
+                            </TableRow>
+                        }
+                    })
+                    .collect_view()}
+            </Table>
+        </DashboardPanel>
+    }
+}
+
+impl PerformanceStats {
+    fn missing_reason(&self, id: &str) -> Option<String> {
+        if self.history.contains(id) {
+            None
+        } else if self.disabled_metrics.iter().any(|metric| metric == id) {
+            Some("Disabled in settings".to_string())
+        } else {
+            Some("Not reported by the server".to_string())
+        }
+    }
+}
+
+fn format_latency(value: Option<f64>) -> String {
+    match value {
+        Some(value) if value >= 1000.0 => format!("{:.2} s", value / 1000.0),
+        Some(value) => format!("{value:.0} ms"),
+        None => "—".to_string(),
+    }
+}
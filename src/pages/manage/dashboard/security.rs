@@ -0,0 +1,320 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use leptos::*;
+use leptos_router::use_navigate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        clipboard::CopyButton,
+        form::{
+            button::Button, input::InputText, FormButtonBar, FormElement, FormItem, FormSection,
+        },
+        list::table::{Table, TableRow},
+        messages::{
+            alert::{use_alerts, Alert},
+            modal::confirm_dangerous,
+        },
+        skeleton::Skeleton,
+        Color,
+    },
+    core::{
+        api::{use_api, ApiClient},
+        http,
+        oauth::use_authorization,
+        schema::{Builder, Schemas, Transformer, Type, Validator},
+        Permission,
+    },
+    pages::config::{edit::FetchSettings, ReloadSettings, UpdateSettings},
+};
+
+use super::{
+    fetch_metrics, DashboardHeader, DashboardPanel, EmptyPanel, MetricCard, MetricHistory,
+};
+
+const BLOCKED_IP_PREFIX: &str = "server.blocked-ip";
+const AUTH_FAILED: &str = "auth.failed";
+const AUTH_BANNED: &str = "security.authentication-ban";
+const BLOCKED_CONNECTIONS: &str = "security.ip-blocked";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SecurityStats {
+    metrics: MetricHistory,
+    blocked: Vec<String>,
+}
+
+#[component]
+pub fn SecurityDashboard() -> impl IntoView {
+    let auth = use_authorization();
+    let api = use_api();
+    let alert = use_alerts();
+    let can_block = auth
+        .get_untracked()
+        .permissions()
+        .has_access(Permission::SettingsUpdate);
+
+    let stats = create_resource(
+        || (),
+        move |_| async move {
+            let metrics = fetch_metrics(api, Utc::now() - TimeDelta::hours(24)).await?;
+            let mut blocked = api
+                .get("/api/settings/list")
+                .with_parameter("prefix", BLOCKED_IP_PREFIX)
+                .send::<FetchSettings>()
+                .await?
+                .items
+                .into_keys()
+                .collect::<Vec<_>>();
+            blocked.sort();
+
+            Ok(Arc::new(SecurityStats { metrics, blocked }))
+        },
+    );
+
+    let unblock = create_action(move |ip: &String| {
+        let ip = ip.clone();
+
+        async move {
+            match update_blocked_ips(
+                api,
+                UpdateSettings::Delete {
+                    keys: vec![format!("{BLOCKED_IP_PREFIX}.{ip}")],
+                },
+            )
+            .await
+            {
+                Ok(_) => {
+                    alert.set(Alert::success(format!("Unblocked {ip}")));
+                    stats.refetch();
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let history = create_memo(move |_| {
+        stats
+            .get()
+            .and_then(|stats| stats.ok())
+            .map(|stats| stats.metrics.clone())
+            .unwrap_or_default()
+    });
+    let counter = move |id: &'static str| {
+        Signal::derive(move || {
+            history.with(|history| history.total(id).map(|total| (total as u64).to_string()))
+        })
+    };
+    let series = move |id: &'static str| Signal::derive(move || history.with(|h| h.series(id)));
+
+    view! {
+        <div class="max-w-[85rem] px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
+            <DashboardHeader
+                title="Security"
+                subtitle="Authentication failures over the last 24 hours and blocked addresses."
+                loading=stats.loading()
+                on_refresh=move |_| stats.refetch()
+            />
+
+            <div class="grid sm:grid-cols-2 lg:grid-cols-3 gap-4 sm:gap-6 mb-6">
+                <MetricCard
+                    title="Authentication failures"
+                    value=counter(AUTH_FAILED)
+                    subtitle="Last 24 hours"
+                    series=series(AUTH_FAILED)
+                />
+                <MetricCard
+                    title="Addresses banned"
+                    value=counter(AUTH_BANNED)
+                    subtitle="After repeated authentication failures"
+                    series=series(AUTH_BANNED)
+                />
+                <MetricCard
+                    title="Blocked connections"
+                    value=counter(BLOCKED_CONNECTIONS)
+                    subtitle="Rejected from blocked addresses"
+                    series=series(BLOCKED_CONNECTIONS)
+                />
+            </div>
+
+            <Transition fallback=Skeleton>
+                {move || match stats.get() {
+                    None => None,
+                    Some(Err(http::Error::Unauthorized)) => {
+                        use_navigate()("/login", Default::default());
+                        Some(view! { <div></div> }.into_view())
+                    }
+                    Some(Err(err)) => {
+                        alert.set(Alert::from(err));
+                        Some(view! { <Skeleton/> }.into_view())
+                    }
+                    Some(Ok(stats)) => {
+                        let stats = Arc::unwrap_or_clone(stats);
+                        Some(
+                            view! {
+                                <div class="mb-6">
+                                    <DashboardPanel title="Blocked IP addresses">
+                                        {if stats.blocked.is_empty() {
+                                            view! { <EmptyPanel text="No addresses are currently blocked."/> }
+                                        } else {
+                                            view! {
+                                                <Table headers=vec![
+                                                    "IP address".to_string(),
+                                                    String::new(),
+                                                ]>
+                                                    {stats
+                                                        .blocked
+                                                        .into_iter()
+                                                        .map(|ip| {
+                                                            let ip_ = ip.clone();
+                                                            view! {
+                                                                <TableRow>
+                                                                    <span class="inline-flex items-center gap-x-2 font-mono">
+                                                                        {ip.clone()}
+                                                                        <CopyButton value=ip/>
+                                                                    </span>
+                                                                    <button
+                                                                        type="button"
+                                                                        class="text-sm font-semibold text-red-600 hover:text-red-800 disabled:opacity-50 disabled:pointer-events-none dark:text-red-500 dark:hover:text-red-400"
+                                                                        class:hidden=!can_block
+                                                                        disabled=move || unblock.pending().get()
+                                                                        on:click=move |_| {
+                                                                            let ip = ip_.clone();
+                                                                            let confirmed = confirm_dangerous(
+                                                                                "Unblock IP address",
+                                                                                format!("Connections from {ip} will be accepted again."),
+                                                                                "Unblock",
+                                                                            );
+                                                                            spawn_local(async move {
+                                                                                if confirmed.await {
+                                                                                    unblock.dispatch(ip);
+                                                                                }
+                                                                            });
+                                                                        }
+                                                                    >
+
+                                                                        Unblock
+                                                                    </button>
+                                                                </TableRow>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </Table>
+                                            }
+                                                .into_view()
+                                        }}
+
+                                    </DashboardPanel>
+                                </div>
+                            }
+                                .into_view(),
+                        )
+                    }
+                }}
+
+            </Transition>
+
+            <Show when=move || can_block>
+                <BlockIpForm on_blocked=move |_| stats.refetch()/>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn BlockIpForm(#[prop(into)] on_blocked: Callback<()>) -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+
+    let data = expect_context::<Arc<Schemas>>()
+        .build_form("block-ip")
+        .into_signal();
+
+    let block = create_action(move |ip: &String| {
+        let ip = ip.clone();
+
+        async move {
+            match update_blocked_ips(
+                api,
+                UpdateSettings::Insert {
+                    prefix: None,
+                    values: vec![(format!("{BLOCKED_IP_PREFIX}.{ip}"), String::new())],
+                    assert_empty: false,
+                },
+            )
+            .await
+            {
+                Ok(_) => {
+                    data.update(|data| {
+                        data.set("ip", "");
+                    });
+                    alert.set(Alert::success(format!("Blocked {ip}")));
+                    on_blocked.call(());
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    view! {
+        <div class="bg-white border border-gray-200 rounded-xl shadow-sm dark:bg-slate-900 dark:border-gray-700">
+            <FormSection title="Block IP address".to_string()>
+                <FormItem label="IP address" tooltip="An IP address or network in CIDR notation">
+                    <InputText element=FormElement::new("ip", data)/>
+                </FormItem>
+            </FormSection>
+            <FormButtonBar>
+                <Button
+                    text="Block"
+                    color=Color::Red
+                    on_click=Callback::new(move |_| {
+                        data.update(|data| {
+                            if data.validate_form() {
+                                block.dispatch(data.value("ip").unwrap());
+                            }
+                        });
+                    })
+
+                    disabled=block.pending()
+                />
+            </FormButtonBar>
+        </div>
+    }
+}
+
+impl Builder<Schemas, ()> {
+    pub fn build_security_dashboard(self) -> Self {
+        self.new_schema("block-ip")
+            .new_field("ip")
+            .typ(Type::Input)
+            .input_check(
+                [Transformer::Trim],
+                [Validator::Required, Validator::IsIpOrMask],
+            )
+            .build()
+            .build()
+    }
+}
+
+// Blocked addresses are settings, applied once the server reloads them
+async fn update_blocked_ips(api: ApiClient, update: UpdateSettings) -> http::Result<()> {
+    api.post("/api/settings")
+        .with_body(vec![update])
+        .unwrap()
+        .send::<()>()
+        .await?;
+    api.get(format!("/api/reload/{BLOCKED_IP_PREFIX}"))
+        .send::<ReloadSettings>()
+        .await
+        .map(|_| ())
+}
@@ -0,0 +1,677 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::cmp::Reverse;
+
+use chrono::{SecondsFormat, TimeDelta, Utc};
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::{
+        badge::Badge,
+        icon::IconArrowLeft,
+        list::{
+            header::ColumnList,
+            pagination::Pagination,
+            toolbar::{FilterInput, FilterSelect, ToolbarButton},
+            Filters, Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
+        },
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+        timestamp::{RelativeTime, Timestamp},
+        Color,
+    },
+    core::{api::use_api, http, i18n::t_signal, url::UrlBuilder},
+    pages::{
+        queue::messages::list::{from_local_input, query_value, to_local_input},
+        List,
+    },
+};
+
+use super::tracing::{SpanEvent, TraceEvent, TraceItem, CONNECTION_SPAN, DELIVERY_SPAN};
+
+const PAGE_SIZE: u32 = 25;
+const DEFAULT_RANGE_HOURS: i64 = 24;
+const MAX_ATTEMPTS: u32 = 1000;
+
+const DELIVERY_STATUSES: [(&str, &str); 2] =
+    [("", "All messages"), ("failing", "Currently failing")];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MessageAttempts {
+    queue_id: String,
+    attempts: Vec<SpanEvent>,
+    queued: bool,
+}
+
+#[component]
+pub fn ReceivedHistory() -> impl IntoView {
+    let query = use_query_map();
+    let page = create_memo(move |_| {
+        query
+            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
+            .filter(|&page| page > 0)
+            .unwrap_or(1)
+    });
+    let default_after = (Utc::now() - TimeDelta::hours(DEFAULT_RANGE_HOURS))
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+    let after = query_value(query, "after");
+    let after = create_memo(move |_| after.get().unwrap_or_else(|| default_after.clone()));
+    let before = query_value(query, "before");
+    let filter = query_value(query, "filter");
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        let mut url = UrlBuilder::new("/manage/tracing/received");
+        for (param, current) in [
+            ("after", Some(after.get())),
+            ("before", before.get()),
+            ("filter", filter.get()),
+        ] {
+            url = url.with_optional_parameter(
+                param,
+                if param == key { value.clone() } else { current }.filter(|v| !v.is_empty()),
+            );
+        }
+        url
+    };
+
+    let api = use_api();
+    let alert = use_alerts();
+
+    let sessions = create_resource(
+        move || (page.get(), after.get(), before.get(), filter.get()),
+        move |(page, after, before, filter)| async move {
+            api.get("/api/telemetry/traces")
+                .with_parameter("type", CONNECTION_SPAN)
+                .with_parameter("page", page.to_string())
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_parameter("values", "1")
+                .with_parameter("after", after)
+                .with_optional_parameter("before", before)
+                .with_optional_parameter("filter", filter)
+                .send::<List<SpanEvent>>()
+                .await
+        },
+    );
+
+    let total_results = create_rw_signal(None::<u32>);
+
+    view! {
+        <ListSection>
+            <ListTable
+                title="Received messages"
+                subtitle="Search the SMTP sessions recorded by the server"
+            >
+                <Toolbar slot>
+                    <ToolbarButton
+                        text=t_signal("button.refresh")
+                        color=Color::Gray
+                        on_click=move |_| {
+                            sessions.refetch();
+                        }
+                    />
+
+                </Toolbar>
+
+                <Filters slot>
+                    <FilterInput
+                        label="Received after"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || to_local_input(&after.get()))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("after", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Received before"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || before.get().and_then(|dt| to_local_input(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("before", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Search"
+                        placeholder="Address, domain or IP"
+                        value=filter
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("filter", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=Skeleton>
+                    {move || match sessions.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => {
+                            total_results.set(Some(0));
+                            alert.set(Alert::from(err));
+                            Some(view! { <Skeleton/> }.into_view())
+                        }
+                        Some(Ok(sessions)) if !sessions.items.is_empty() => {
+                            total_results.set(Some(sessions.total as u32));
+                            Some(
+                                view! {
+                                    <ColumnList headers=vec![
+                                        "Received".to_string(),
+                                        "Remote IP".to_string(),
+                                        "Listener".to_string(),
+                                        "".to_string(),
+                                    ]>
+
+                                        <For
+                                            each=move || sessions.items.clone()
+                                            key=|session| session.id.clone()
+                                            let:session
+                                        >
+                                            <ReceivedItem session/>
+                                        </For>
+
+                                    </ColumnList>
+                                }
+                                    .into_view(),
+                            )
+                        }
+                        Some(Ok(_)) => {
+                            total_results.set(Some(0));
+                            Some(
+                                view! {
+                                    <ZeroResults
+                                        title="No results"
+                                        subtitle="No SMTP sessions match the selected criteria."
+                                    />
+                                }
+                                    .into_view(),
+                            )
+                        }
+                    }}
+
+                </Transition>
+
+                <Footer slot>
+
+                    <Pagination
+                        current_page=page
+                        total_results=total_results.read_only()
+                        page_size=PAGE_SIZE
+                        on_page_change=move |page: u32| {
+                            use_navigate()(
+                                &filter_url("page", None)
+                                    .with_parameter("page", page.to_string())
+                                    .finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Footer>
+            </ListTable>
+        </ListSection>
+    }
+}
+
+#[component]
+fn ReceivedItem(session: SpanEvent) -> impl IntoView {
+    let remote_ip = session.value("remoteIp");
+    let listener = session.value("listenerId");
+
+    view! {
+        <tr>
+            <ListItem>
+                <span class="text-sm text-gray-500">
+                    <Timestamp value=session.created_at/>
+                </span>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm font-mono text-gray-800 dark:text-gray-200">{remote_ip}</span>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm text-gray-500">{listener}</span>
+            </ListItem>
+            <ListItem subclass="px-6 py-1.5">
+                <a
+                    class="inline-flex items-center gap-x-1 text-sm text-blue-600 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                    href=format!("/manage/tracing/span/{}", session.id)
+                >
+                    View trace
+                </a>
+            </ListItem>
+        </tr>
+    }
+}
+
+#[component]
+pub fn DeliveryHistory() -> impl IntoView {
+    let query = use_query_map();
+    let page = create_memo(move |_| {
+        query
+            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
+            .filter(|&page| page > 0)
+            .unwrap_or(1)
+    });
+    let default_after = (Utc::now() - TimeDelta::hours(DEFAULT_RANGE_HOURS))
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+    let after = query_value(query, "after");
+    let after = create_memo(move |_| after.get().unwrap_or_else(|| default_after.clone()));
+    let before = query_value(query, "before");
+    let filter = query_value(query, "filter");
+    let failing =
+        create_memo(move |_| query.with(|q| q.get("status").is_some_and(|s| s == "failing")));
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        let mut url = UrlBuilder::new("/manage/tracing/delivery");
+        for (param, current) in [
+            ("after", Some(after.get())),
+            ("before", before.get()),
+            ("filter", filter.get()),
+            ("status", failing.get().then(|| "failing".to_string())),
+        ] {
+            url = url.with_optional_parameter(
+                param,
+                if param == key { value.clone() } else { current }.filter(|v| !v.is_empty()),
+            );
+        }
+        url
+    };
+
+    let api = use_api();
+    let alert = use_alerts();
+
+    // The server pages by attempt and knows nothing of their outcome, so the
+    // most recent attempts are grouped by message here and checked against
+    // the messages still waiting in the queue
+    let attempts = create_resource(
+        move || (after.get(), before.get(), filter.get()),
+        move |(after, before, filter)| async move {
+            let attempts = api
+                .get("/api/telemetry/traces")
+                .with_parameter("type", DELIVERY_SPAN)
+                .with_parameter("page", "1")
+                .with_parameter("limit", MAX_ATTEMPTS.to_string())
+                .with_parameter("values", "1")
+                .with_parameter("after", after)
+                .with_optional_parameter("before", before)
+                .with_optional_parameter("filter", filter)
+                .send::<List<SpanEvent>>()
+                .await?;
+            let queued = api.get("/api/queue/messages").send::<List<u64>>().await?;
+
+            Ok((attempts.items, queued.items))
+        },
+    );
+
+    let total_results = create_rw_signal(None::<u32>);
+
+    view! {
+        <ListSection>
+            <ListTable
+                title="Delivery attempts"
+                subtitle=format!(
+                    "Review the last {MAX_ATTEMPTS} delivery attempts grouped by queued message",
+                )
+            >
+                <Toolbar slot>
+                    <ToolbarButton
+                        text=t_signal("button.refresh")
+                        color=Color::Gray
+                        on_click=move |_| {
+                            attempts.refetch();
+                        }
+                    />
+
+                </Toolbar>
+
+                <Filters slot>
+                    <FilterInput
+                        label="Attempted after"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || to_local_input(&after.get()))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("after", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Attempted before"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || before.get().and_then(|dt| to_local_input(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("before", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Search"
+                        placeholder="Address, domain or IP"
+                        value=filter
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("filter", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterSelect
+                        label="Status"
+                        value=Signal::derive(move || {
+                            if failing.get() { "failing" } else { "" }.to_string()
+                        })
+
+                        options=DELIVERY_STATUSES.to_vec()
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("status", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=Skeleton>
+                    {move || match attempts.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(err)) => {
+                            total_results.set(Some(0));
+                            alert.set(Alert::from(err));
+                            Some(view! { <Skeleton/> }.into_view())
+                        }
+                        Some(Ok((attempts, queued))) => {
+                            let messages = group_attempts(attempts, &queued)
+                                .into_iter()
+                                .filter(|message| !failing.get() || message.queued)
+                                .collect::<Vec<_>>();
+                            total_results.set(Some(messages.len() as u32));
+                            let messages = messages
+                                .into_iter()
+                                .skip(((page.get() - 1) * PAGE_SIZE) as usize)
+                                .take(PAGE_SIZE as usize)
+                                .collect::<Vec<_>>();
+                            if messages.is_empty() {
+                                return Some(
+                                    view! {
+                                        <ZeroResults
+                                            title="No results"
+                                            subtitle="No delivery attempts match the selected criteria."
+                                        />
+                                    }
+                                        .into_view(),
+                                );
+                            }
+                            Some(
+                                view! {
+                                    <ColumnList headers=vec![
+                                        "Envelope".to_string(),
+                                        "Last attempt".to_string(),
+                                        "Attempts".to_string(),
+                                        "Status".to_string(),
+                                        "".to_string(),
+                                    ]>
+
+                                        <For
+                                            each=move || messages.clone()
+                                            key=|message| message.queue_id.clone()
+                                            let:message
+                                        >
+                                            <DeliveryItem message/>
+                                        </For>
+
+                                    </ColumnList>
+                                }
+                                    .into_view(),
+                            )
+                        }
+                    }}
+
+                </Transition>
+
+                <Footer slot>
+
+                    <Pagination
+                        current_page=page
+                        total_results=total_results.read_only()
+                        page_size=PAGE_SIZE
+                        on_page_change=move |page: u32| {
+                            use_navigate()(
+                                &filter_url("page", None)
+                                    .with_parameter("page", page.to_string())
+                                    .finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Footer>
+            </ListTable>
+        </ListSection>
+    }
+}
+
+#[component]
+fn DeliveryItem(message: MessageAttempts) -> impl IntoView {
+    let expanded = create_rw_signal(false);
+    let latest = message.attempts[0].clone();
+    let total_attempts = message.attempts.len();
+    let to = latest.values("to");
+    let recipients = match to.len() {
+        0 => String::new(),
+        1 => to[0].clone(),
+        n => format!("{} and {} more", to[0], n - 1),
+    };
+    let sender = latest
+        .value("from")
+        .filter(|from| !from.is_empty())
+        .unwrap_or_else(|| "<>".to_string());
+    let status = if message.queued {
+        view! { <Badge color=Color::Yellow>Queued</Badge> }
+    } else {
+        view! { <Badge color=Color::Gray>Left queue</Badge> }
+    };
+    let attempts = message.attempts;
+
+    view! {
+        <tr>
+            <ListItem>
+                <span class="block text-sm font-semibold text-gray-800 dark:text-gray-200">
+                    {sender}
+                </span>
+                <span class="block text-sm text-gray-500">{recipients}</span>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm text-gray-500">
+                    <RelativeTime value=latest.created_at/>
+                </span>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm text-gray-500">{total_attempts}</span>
+            </ListItem>
+            <ListItem>{status}</ListItem>
+            <ListItem subclass="px-6 py-1.5">
+                <button
+                    class="inline-flex items-center gap-x-1 text-sm text-blue-600 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                    on:click=move |_| expanded.update(|expanded| *expanded = !*expanded)
+                >
+                    {move || if expanded.get() { "Hide attempts" } else { "Show attempts" }}
+                </button>
+            </ListItem>
+        </tr>
+        <Show when=move || expanded.get()>
+            <tr>
+                <td colspan="5" class="px-6 pb-4">
+                    <ol class="relative ms-3 border-s border-gray-200 dark:border-gray-700">
+
+                        {attempts
+                            .clone()
+                            .into_iter()
+                            .map(|attempt| {
+                                view! {
+                                    <li class="mb-4 ms-4">
+                                        <div class="absolute w-3 h-3 bg-gray-200 rounded-full mt-1.5 -start-1.5 border border-white dark:border-gray-900 dark:bg-gray-700"></div>
+                                        <time class="block text-xs text-gray-400">
+                                            <Timestamp value=attempt.created_at/>
+                                        </time>
+                                        <p class="text-sm text-gray-500 text-wrap">{attempt.text}</p>
+                                        <a
+                                            class="text-xs text-blue-600 decoration-2 hover:underline dark:text-blue-500"
+                                            href=format!("/manage/tracing/span/{}", attempt.id)
+                                        >
+                                            View trace
+                                        </a>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+
+                    </ol>
+                </td>
+            </tr>
+        </Show>
+    }
+}
+
+fn group_attempts(attempts: Vec<SpanEvent>, queued: &[u64]) -> Vec<MessageAttempts> {
+    let mut messages: Vec<MessageAttempts> = Vec::new();
+    for attempt in attempts {
+        let queue_id = attempt.value("queueId").unwrap_or_default();
+        if let Some(message) = messages
+            .iter_mut()
+            .find(|message| message.queue_id == queue_id)
+        {
+            message.attempts.push(attempt);
+        } else {
+            messages.push(MessageAttempts {
+                queued: queued.iter().any(|id| id.to_string() == queue_id),
+                queue_id,
+                attempts: vec![attempt],
+            });
+        }
+    }
+    for message in &mut messages {
+        message
+            .attempts
+            .sort_by_key(|attempt| Reverse(attempt.created_at));
+    }
+    messages.sort_by(|a, b| b.attempts[0].created_at.cmp(&a.attempts[0].created_at));
+    messages
+}
+
+#[component]
+pub fn TraceDisplay() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let params = use_params_map();
+
+    let trace = create_resource(
+        move || params.with(|p| p.get("id").cloned().unwrap_or_default()),
+        move |id| async move {
+            api.get(("/api/telemetry/trace", &id))
+                .send::<Vec<TraceEvent>>()
+                .await
+        },
+    );
+
+    view! {
+        <ListSection>
+            <ListTable
+                title="Session trace"
+                subtitle=Signal::derive(move || {
+                    params.with(|p| p.get("id").cloned().unwrap_or_default())
+                })
+            >
+
+                <Toolbar slot>
+                    <ToolbarButton
+                        text=t_signal("button.back")
+                        color=Color::Gray
+                        on_click=move |_| {
+                            use_navigate()("/manage/tracing/received", Default::default());
+                        }
+                    >
+
+                        <IconArrowLeft attr:class="flex-shrink-0 size-4"/>
+                    </ToolbarButton>
+
+                </Toolbar>
+
+                <Transition fallback=Skeleton>
+                    {move || match trace.get() {
+                        None => None,
+                        Some(Err(http::Error::Unauthorized)) => {
+                            use_navigate()("/login", Default::default());
+                            Some(view! { <div></div> }.into_view())
+                        }
+                        Some(Err(http::Error::NotFound)) => {
+                            Some(
+                                view! {
+                                    <ZeroResults
+                                        title="Trace not found"
+                                        subtitle="This trace has expired or does not exist."
+                                    />
+                                }
+                                    .into_view(),
+                            )
+                        }
+                        Some(Err(err)) => {
+                            alert.set(Alert::from(err));
+                            Some(view! { <Skeleton/> }.into_view())
+                        }
+                        Some(Ok(events)) => {
+                            Some(
+                                view! {
+                                    <ColumnList headers=vec![
+                                        "Date".to_string(),
+                                        "Level".to_string(),
+                                        "Event".to_string(),
+                                        "Details".to_string(),
+                                    ]>
+
+                                        {events
+                                            .into_iter()
+                                            .map(|event| view! { <TraceItem event/> })
+                                            .collect_view()}
+
+                                    </ColumnList>
+                                }
+                                    .into_view(),
+                            )
+                        }
+                    }}
+
+                </Transition>
+
+                <Footer slot>
+                    <div></div>
+                </Footer>
+            </ListTable>
+        </ListSection>
+    }
+}
@@ -4,35 +4,52 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use ahash::AHashSet;
 use chrono::{DateTime, Utc};
-use leptos::*;
+use gloo_storage::{SessionStorage, Storage};
+use leptos::{leptos_dom::helpers::IntervalHandle, *};
 use leptos_router::*;
 use serde::{Deserialize, Serialize};
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    time::Duration,
+};
 
 use crate::pages::queue::messages::deserialize_datetime;
 use crate::{
     components::{
         badge::Badge,
+        icon::IconArrowDownTray,
         list::{
-            header::ColumnList, pagination::Pagination, toolbar::SearchBox, Footer, ListItem,
-            ListSection, ListTable, Toolbar, ZeroResults,
+            header::ColumnList,
+            pagination::Pagination,
+            toolbar::{FilterSelect, SearchBox, ToolbarButton},
+            Filters, Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
         },
         messages::alert::{use_alerts, Alert},
         skeleton::Skeleton,
+        timestamp::RelativeTime,
         Color,
     },
     core::{
-        http::{self, HttpRequest},
+        api::{use_api, use_latest_api},
+        download::download_file,
+        http,
+        i18n::t_signal,
         oauth::use_authorization,
         url::UrlBuilder,
     },
-    pages::{FormatDateTime, List},
+    pages::List,
 };
 
+use super::{level_severity, LEVELS};
+
 const PAGE_SIZE: u32 = 50;
+const MAX_FOLLOW_ENTRIES: usize = 2000;
+const FOLLOW_INTERVAL_KEY: &str = "webadmin_logs_follow_interval";
+const FOLLOW_INTERVALS: [u32; 4] = [2, 5, 10, 30];
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 struct LogEntry {
     #[serde(deserialize_with = "deserialize_datetime")]
     pub timestamp: DateTime<Utc>,
@@ -64,25 +81,208 @@ pub fn Logs() -> impl IntoView {
         })
     });
 
+    let level = create_memo(move |_| {
+        query
+            .with(|q| {
+                q.get("level")
+                    .filter(|level| LEVELS.iter().any(|(id, _)| id == level))
+                    .cloned()
+            })
+            .unwrap_or_else(|| LEVELS[0].0.to_string())
+    });
+    let logs_url = move |page: Option<u32>, filter: Option<String>, level: String| {
+        UrlBuilder::new("/manage/logs")
+            .with_optional_parameter("page", page.map(|page| page.to_string()))
+            .with_optional_parameter("filter", filter)
+            .with_optional_parameter("level", Some(level).filter(|l| l != LEVELS[0].0))
+            .finish()
+    };
+
     let auth = use_authorization();
+    let api = use_api();
+    let latest = use_latest_api();
     let alert = use_alerts();
     let logs = create_resource(
         move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
-
-            async move {
-                HttpRequest::get("/api/logs")
-                    .with_authorization(&auth)
-                    .with_parameter("page", page.to_string())
-                    .with_parameter("limit", PAGE_SIZE.to_string())
-                    .with_optional_parameter("filter", filter)
-                    .send::<List<LogEntry>>()
-                    .await
-            }
+        move |(page, filter)| async move {
+            latest
+                .get("/api/logs")
+                .conditional()
+                .with_parameter("page", page.to_string())
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_optional_parameter("filter", filter)
+                .send::<List<LogEntry>>()
+                .await
         },
     );
 
+    // Follow mode keeps polling the newest entries and appends them in chronological order
+    let following = create_rw_signal(false);
+    let follow_interval = create_rw_signal(
+        SessionStorage::get::<u32>(FOLLOW_INTERVAL_KEY)
+            .ok()
+            .filter(|interval| FOLLOW_INTERVALS.contains(interval))
+            .unwrap_or(FOLLOW_INTERVALS[1]),
+    );
+    let tail = create_rw_signal(Vec::<LogEntry>::new());
+    let scroll_paused = create_rw_signal(false);
+    let new_entries = create_rw_signal(0usize);
+    let poll_handle = store_value(None::<IntervalHandle>);
+
+    let poll_action = create_action(move |_: &()| {
+        let filter = filter.get_untracked();
+
+        async move {
+            match api
+                .get("/api/logs")
+                .conditional()
+                .with_parameter("page", "1")
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_optional_parameter("filter", filter)
+                .send::<List<LogEntry>>()
+                .await
+            {
+                Ok(list) => {
+                    tail.update(|tail| {
+                        let seen = tail.iter().collect::<AHashSet<_>>();
+                        let mut added = list
+                            .items
+                            .into_iter()
+                            .filter(|log| !seen.contains(log))
+                            .collect::<Vec<_>>();
+                        if !tail.is_empty() && scroll_paused.get_untracked() {
+                            new_entries.update(|count| *count += added.len());
+                        }
+                        tail.append(&mut added);
+                        tail.sort_by_key(|log| log.timestamp);
+                        let excess = tail.len().saturating_sub(MAX_FOLLOW_ENTRIES);
+                        tail.drain(..excess);
+                    });
+                }
+                Err(http::Error::Unauthorized) => {
+                    following.set(false);
+                    use_navigate()("/login", Default::default());
+                }
+                Err(err) => {
+                    following.set(false);
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+
+    let stop_polling = move || {
+        if let Some(handle) = poll_handle.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+    };
+    create_effect(move |_| {
+        filter.track();
+        if following.get() {
+            tail.set(vec![]);
+            new_entries.set(0);
+            scroll_paused.set(false);
+        }
+    });
+    create_effect(move |_| {
+        let interval = follow_interval.get();
+        stop_polling();
+        if following.get() {
+            poll_action.dispatch(());
+            match set_interval_with_handle(
+                move || {
+                    if !poll_action.pending().get_untracked() {
+                        poll_action.dispatch(());
+                    }
+                },
+                Duration::from_secs(interval as u64),
+            ) {
+                Ok(handle) => poll_handle.set_value(Some(handle)),
+                Err(err) => log::warn!("Failed to start log polling: {err:?}"),
+            }
+        }
+    });
+    on_cleanup(stop_polling);
+
+    let tail_visible = create_memo(move |_| {
+        let min_level = level_severity(&level.get());
+        tail.with(|tail| {
+            tail.iter()
+                .filter(|log| level_severity(&log.level) >= min_level)
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    });
+    create_effect(move |_| {
+        tail_visible.track();
+        if following.get_untracked() && !scroll_paused.get_untracked() {
+            request_animation_frame(|| {
+                if let Some(element) = document().document_element() {
+                    window().scroll_to_with_x_and_y(0.0, element.scroll_height() as f64);
+                }
+            });
+        }
+    });
+
+    let page_visible = create_memo(move |_| {
+        let min_level = level_severity(&level.get());
+        logs.with(|logs| match logs {
+            Some(Ok(logs)) => logs
+                .items
+                .iter()
+                .filter(|log| level_severity(&log.level) >= min_level)
+                .cloned()
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        })
+    });
+    let download_logs = move |_| {
+        let entries = if following.get_untracked() {
+            tail_visible.get_untracked()
+        } else {
+            page_visible.get_untracked()
+        };
+        if entries.is_empty() {
+            alert.set(Alert::warning("There are no log entries to download."));
+            return;
+        }
+
+        let base_url = auth.with_untracked(|auth| {
+            if !auth.base_url.is_empty() {
+                auth.base_url.to_string()
+            } else {
+                window().location().origin().unwrap_or_default()
+            }
+        });
+        let now = Utc::now();
+        let mut contents = format!(
+            "# Stalwart log export\n# Server: {base_url}\n# Exported: {}\n# Level: {}\n",
+            now.to_rfc3339(),
+            level.get_untracked(),
+        );
+        if let Some(filter) = filter.get_untracked() {
+            contents.push_str(&format!("# Filter: {filter}\n"));
+        }
+        contents.push('\n');
+        for log in &entries {
+            contents.push_str(&format!(
+                "{} {:<5} {} {}\n",
+                log.timestamp.to_rfc3339(),
+                log.level,
+                log.event,
+                log.details
+            ));
+        }
+
+        if let Err(err) = download_file(
+            &format!("logs-{}.txt", now.format("%Y%m%d-%H%M%S")),
+            "text/plain",
+            &contents,
+        ) {
+            log::warn!("Failed to download logs: {err:?}");
+        }
+    };
+
     let total_results = create_rw_signal(None::<u32>);
 
     view! {
@@ -91,87 +291,229 @@ pub fn Logs() -> impl IntoView {
                 <Toolbar slot>
                     <SearchBox
                         value=filter
+                        latest
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/logs")
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &logs_url(None, Some(value), level.get()),
                                 Default::default(),
                             );
                         }
                     />
 
-                </Toolbar>
+                    <Show when=move || following.get()>
+                        <select
+                            class="py-2 px-3 pe-9 block border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400"
+                            on:change=move |ev| {
+                                if let Ok(interval) = event_target_value(&ev).parse::<u32>() {
+                                    follow_interval.set(interval);
+                                    if let Err(err) = SessionStorage::set(
+                                        FOLLOW_INTERVAL_KEY,
+                                        interval,
+                                    ) {
+                                        log::warn!("Failed to save polling interval: {err}");
+                                    }
+                                }
+                            }
+                        >
 
-                <Transition fallback=Skeleton>
-                    {move || match logs.get() {
-                        None => None,
-                        Some(Err(http::Error::Unauthorized)) => {
-                            use_navigate()("/login", Default::default());
-                            Some(view! { <div></div> }.into_view())
-                        }
-                        Some(Err(err)) => {
-                            total_results.set(Some(0));
-                            alert.set(Alert::from(err));
-                            Some(view! { <Skeleton/> }.into_view())
-                        }
-                        Some(Ok(logs)) if !logs.items.is_empty() => {
-                            total_results.set(Some(logs.total as u32));
-                            Some(
-                                view! {
-                                    <ColumnList headers=vec![
-                                        "Date".to_string(),
-                                        "Level".to_string(),
-                                        "Event".to_string(),
-                                        "Details".to_string(),
-                                    ]>
-
-                                        <For
-                                            each=move || logs.items.clone()
-                                            key=|log| log.id()
-                                            let:log
+                            {FOLLOW_INTERVALS
+                                .into_iter()
+                                .map(|interval| {
+                                    view! {
+                                        <option
+                                            value=interval.to_string()
+                                            selected=move || follow_interval.get() == interval
                                         >
-                                            <LogItem log/>
-                                        </For>
+                                            {format!("Every {interval}s")}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+
+                        </select>
 
-                                    </ColumnList>
+                        <ToolbarButton
+                            text=Signal::derive(move || {
+                                let count = new_entries.get();
+                                if !scroll_paused.get() {
+                                    "Pause".to_string()
+                                } else if count > 0 {
+                                    format!("Resume ({count} new)")
+                                } else {
+                                    "Resume".to_string()
                                 }
-                                    .into_view(),
-                            )
-                        }
-                        Some(Ok(_)) => {
-                            total_results.set(Some(0));
-                            Some(
-                                view! {
-                                    <ZeroResults
-                                        title="No results"
-                                        subtitle="No log entries were found with the selected criteria."
-                                    />
+                            })
+
+                            color=Color::Gray
+                            on_click=move |_| {
+                                if scroll_paused.get() {
+                                    new_entries.set(0);
+                                    scroll_paused.set(false);
+                                    tail.update(|_| ());
+                                } else {
+                                    scroll_paused.set(true);
                                 }
-                                    .into_view(),
-                            )
-                        }
-                    }}
+                            }
+                        />
+                    </Show>
 
-                </Transition>
+                    <ToolbarButton text=t_signal("button.download") color=Color::Gray on_click=download_logs>
+                        <IconArrowDownTray attr:class="flex-shrink-0 size-4"/>
+                    </ToolbarButton>
 
-                <Footer slot>
+                    <ToolbarButton
+                        text=Signal::derive(move || {
+                            if following.get() { "Stop following" } else { "Follow" }.to_string()
+                        })
 
-                    <Pagination
-                        current_page=page
-                        total_results=total_results.read_only()
-                        page_size=PAGE_SIZE
-                        on_page_change=move |page: u32| {
+                        color=Color::Blue
+                        on_click=move |_| {
+                            following.update(|following| *following = !*following);
+                        }
+                    />
+
+                </Toolbar>
+
+                <Filters slot>
+                    <FilterSelect
+                        label="Level"
+                        value=level
+                        options=LEVELS.to_vec()
+                        on_change=move |value: String| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/logs")
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &logs_url(Some(page.get()), filter.get(), value),
                                 Default::default(),
                             );
                         }
                     />
 
+                </Filters>
+
+                {move || {
+                    if following.get() {
+                        if tail_visible.with(|tail| !tail.is_empty()) {
+                            view! {
+                                <ColumnList headers=vec![
+                                    "Date".to_string(),
+                                    "Level".to_string(),
+                                    "Event".to_string(),
+                                    "Details".to_string(),
+                                ]>
+
+                                    <For
+                                        each=move || tail_visible.get()
+                                        key=|log| log.id()
+                                        let:log
+                                    >
+                                        <LogItem log highlight=filter/>
+                                    </For>
+
+                                </ColumnList>
+                            }
+                                .into_view()
+                        } else {
+                            view! {
+                                <ZeroResults
+                                    title="Waiting for entries"
+                                    subtitle="New log entries matching the selected criteria will appear here."
+                                />
+                            }
+                                .into_view()
+                        }
+                    } else {
+                        view! {
+                            <Transition fallback=Skeleton>
+                                {move || match logs.get() {
+                                    None => None,
+                                    Some(Err(http::Error::Unauthorized)) => {
+                                        use_navigate()("/login", Default::default());
+                                        Some(view! { <div></div> }.into_view())
+                                    }
+                                    Some(Err(err)) => {
+                                        total_results.set(Some(0));
+                                        alert.set(Alert::from(err));
+                                        Some(view! { <Skeleton/> }.into_view())
+                                    }
+                                    Some(Ok(logs)) if !logs.items.is_empty() => {
+                                        total_results.set(Some(logs.total as u32));
+                                        Some(
+                                            view! {
+                                                <ColumnList headers=vec![
+                                                    "Date".to_string(),
+                                                    "Level".to_string(),
+                                                    "Event".to_string(),
+                                                    "Details".to_string(),
+                                                ]>
+
+                                                    <For
+                                                        each=move || page_visible.get()
+                                                        key=|log| log.id()
+                                                        let:log
+                                                    >
+                                                        <LogItem log highlight=filter/>
+                                                    </For>
+
+                                                </ColumnList>
+                                            }
+                                                .into_view(),
+                                        )
+                                    }
+                                    Some(Ok(_)) => {
+                                        total_results.set(Some(0));
+                                        Some(
+                                            view! {
+                                                <ZeroResults
+                                                    title="No results"
+                                                    subtitle="No log entries were found with the selected criteria."
+                                                />
+                                            }
+                                                .into_view(),
+                                        )
+                                    }
+                                }}
+
+                            </Transition>
+                        }
+                            .into_view()
+                    }
+                }}
+
+                <Footer slot>
+
+                    <Show
+                        when=move || !following.get()
+                        fallback=move || {
+                            view! {
+                                <div class="px-6 py-4 border-t border-gray-200 dark:border-gray-700">
+                                    <p class="text-sm text-gray-600 dark:text-gray-400">
+                                        {move || {
+                                            format!(
+                                                "Following {} entries, refreshing every {}s",
+                                                tail_visible.with(|tail| tail.len()),
+                                                follow_interval.get(),
+                                            )
+                                        }}
+
+                                    </p>
+                                </div>
+                            }
+                        }
+                    >
+
+                        <Pagination
+                            current_page=page
+                            total_results=total_results.read_only()
+                            page_size=PAGE_SIZE
+                            on_page_change=move |page: u32| {
+                                use_navigate()(
+                                    &logs_url(Some(page), filter.get(), level.get()),
+                                    Default::default(),
+                                );
+                            }
+                        />
+
+                    </Show>
+
                 </Footer>
             </ListTable>
         </ListSection>
@@ -179,13 +521,22 @@ pub fn Logs() -> impl IntoView {
 }
 
 #[component]
-fn LogItem(log: LogEntry) -> impl IntoView {
-    let timestamp = log.timestamp.format_date_time();
+fn LogItem(log: LogEntry, #[prop(into)] highlight: Signal<Option<String>>) -> impl IntoView {
+    let timestamp = log.timestamp;
+    let row_class = match log.level.as_str() {
+        "ERROR" => "bg-red-50 dark:bg-red-800/10",
+        "WARN" => "bg-yellow-50 dark:bg-yellow-800/10",
+        _ => "",
+    };
+    let event = log.event.clone();
+    let details = log.details.clone();
 
     view! {
-        <tr>
+        <tr class=row_class>
             <ListItem>
-                <span class="text-sm text-gray-500">{timestamp}</span>
+                <span class="text-sm text-gray-500">
+                    <RelativeTime value=timestamp/>
+                </span>
             </ListItem>
 
             <ListItem>
@@ -204,22 +555,55 @@ fn LogItem(log: LogEntry) -> impl IntoView {
             </ListItem>
 
             <ListItem>
-                <span class="text-sm text-gray-500">{log.event}</span>
+                <span class="text-sm text-gray-500">
+                    {move || highlight_matches(&event, highlight.get().as_deref())}
+                </span>
             </ListItem>
             <ListItem>
-                <span class="text-sm text-gray-500 text-wrap">{log.details}</span>
+                <span class="text-sm text-gray-500 text-wrap">
+                    {move || highlight_matches(&details, highlight.get().as_deref())}
+                </span>
             </ListItem>
 
         </tr>
     }
 }
 
+fn highlight_matches(text: &str, needle: Option<&str>) -> View {
+    let Some(needle) = needle.filter(|needle| !needle.is_empty()) else {
+        return text.to_string().into_view();
+    };
+    // ASCII lowercasing preserves byte offsets, so positions map back onto `text`
+    let haystack = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut pos = 0;
+
+    while let Some(start) = haystack[pos..].find(&needle).map(|start| start + pos) {
+        let end = start + needle.len();
+        parts.push(text[pos..start].to_string().into_view());
+        parts.push(
+            view! {
+                <mark class="bg-yellow-200 text-gray-800 rounded dark:bg-yellow-500/40 dark:text-gray-200">
+                    {text[start..end].to_string()}
+                </mark>
+            }
+            .into_view(),
+        );
+        pos = end;
+    }
+    parts.push(text[pos..].to_string().into_view());
+
+    parts.collect_view()
+}
+
 impl LogEntry {
     pub fn id(&self) -> String {
         let mut hasher = DefaultHasher::new();
         self.level.hash(&mut hasher);
         self.event_id.hash(&mut hasher);
         self.timestamp.hash(&mut hasher);
+        self.details.hash(&mut hasher);
         hasher.finish().to_string()
     }
 }
@@ -4,6 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use std::sync::Arc;
+
+use ahash::AHashSet;
 use leptos::*;
 use leptos_router::use_navigate;
 
@@ -11,16 +14,13 @@ use crate::{
     components::{
         icon::{
             IconCheckCircle, IconComputerDesktop, IconDocumentMagnifyingGlass, IconPower,
-            IconRefresh, IconShieldCheck,
+            IconRefresh, IconScale, IconShieldCheck, IconTrash,
         },
-        messages::alert::{use_alerts, Alert, Alerts},
-    },
-    core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-        Permission,
+        list::table::{Table, TableRow},
+        messages::alert::{use_alerts, Alert},
     },
-    pages::config::ReloadSettings,
+    core::{api::use_api, http, oauth::use_authorization, schema::Schemas, Permission},
+    pages::config::{diff_settings, fetch_all_settings, ReloadSettings, SettingChange},
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -79,50 +79,103 @@ const ACTIONS: &[Action] = &[
         description: "Rebuilds the full-text search index for all accounts. This may take some time.",
         icon: "document_magnifying_glass",
         url: "/api/store/reindex",
-        success_message: "Successfully requested FTS reindex",
+        success_message: "Full-text search index rebuilt",
         permission: Permission::FtsReindex,
     },
-
+    Action {
+        title: "Purge blobs",
+        description: "Deletes blobs that are no longer referenced by any message or account.",
+        icon: "trash",
+        url: "/api/store/purge/blob",
+        success_message: "Unreferenced blobs purged",
+        permission: Permission::PurgeBlobStore,
+    },
+    Action {
+        title: "Recalculate accounts",
+        description: "Removes expired data and recalculates the quota usage of all accounts.",
+        icon: "scale",
+        url: "/api/store/purge/account",
+        success_message: "Account usage recalculated",
+        permission: Permission::PurgeAccount,
+    },
 ];
 
 #[component]
 pub fn Maintenance() -> impl IntoView {
     let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
-    let (pending, set_pending) = create_signal(false);
+    let running = create_rw_signal(AHashSet::<usize>::new());
+    let reload_changes = create_rw_signal(None::<Arc<Vec<SettingChange>>>);
+    let schemas = expect_context::<Arc<Schemas>>();
 
     let execute = create_action(move |idx: &usize| {
-        let auth = auth.get();
-        let action = ACTIONS[*idx];
+        let idx = *idx;
+        let action = ACTIONS[idx];
+        let schemas = schemas.clone();
 
         async move {
-            set_pending.set(true);
+            running.update(|running| {
+                running.insert(idx);
+            });
+            let finished = move || {
+                running.try_update(|running| running.remove(&idx));
+            };
 
             let err = if action.url.starts_with("/api/reload") {
-                match HttpRequest::get(action.url)
-                    .with_authorization(&auth)
-                    .send::<ReloadSettings>()
-                    .await
-                {
+                let is_dry_run = action.url.contains("dry-run");
+                let before = if !is_dry_run {
+                    fetch_all_settings(api).await.ok()
+                } else {
+                    None
+                };
+                match api.get(action.url).send::<ReloadSettings>().await {
                     Ok(result) => {
-                        set_pending.set(false);
+                        let changes = match before {
+                            Some(before) => fetch_all_settings(api)
+                                .await
+                                .ok()
+                                .map(|after| diff_settings(&before, &after)),
+                            None => None,
+                        };
+                        finished();
                         if result.errors.is_empty() && result.warnings.is_empty() {
-                            alert.set(Alert::success(action.success_message).without_timeout());
+                            let message = match changes.as_ref().map(|changes| changes.len()) {
+                                Some(0) => {
+                                    "Configuration reloaded, no settings changed".to_string()
+                                }
+                                Some(1) => "Configuration reloaded, 1 setting changed".to_string(),
+                                Some(total) => {
+                                    format!("Configuration reloaded, {total} settings changed")
+                                }
+                                None => action.success_message.to_string(),
+                            };
+                            alert.set(Alert::success(message).without_timeout());
                         } else {
                             alert.set(Alert::from(result));
                         }
+                        if let Some(mut changes) = changes {
+                            for change in &mut changes {
+                                if schemas.is_secret_setting(&change.key) {
+                                    for value in [&mut change.old_value, &mut change.new_value]
+                                        .into_iter()
+                                        .flatten()
+                                    {
+                                        *value = "••••••••".to_string();
+                                    }
+                                }
+                            }
+                            reload_changes.set((!changes.is_empty()).then(|| Arc::new(changes)));
+                        }
                         return;
                     }
                     Err(err) => err,
                 }
             } else {
-                match HttpRequest::get(action.url)
-                    .with_authorization(&auth)
-                    .send::<Option<String>>()
-                    .await
-                {
+                // Tasks report no progress, so they stay running until the call returns
+                match api.get(action.url).send::<Option<String>>().await {
                     Ok(_) => {
-                        set_pending.set(false);
+                        finished();
                         alert.set(Alert::success(action.success_message).without_timeout());
                         return;
                     }
@@ -135,7 +188,7 @@ pub fn Maintenance() -> impl IntoView {
                     use_navigate()("/login", Default::default());
                 }
                 err => {
-                    set_pending.set(false);
+                    finished();
                     alert.set(Alert::from(err));
                 }
             }
@@ -143,6 +196,7 @@ pub fn Maintenance() -> impl IntoView {
     });
 
     let permissions = auth.get_untracked().permissions().clone();
+    let is_running = move |idx: usize| running.with(|running| running.contains(&idx));
     let actions = ACTIONS.iter().enumerate().filter_map(|(idx, action)| {
         let icon_class = "mt-1 flex-shrink-0 size-5 text-gray-800 dark:text-gray-200";
         let icon = match action.icon {
@@ -152,6 +206,8 @@ pub fn Maintenance() -> impl IntoView {
             "shield_check" => view! { <IconShieldCheck attr:class=icon_class/> },
             "computer_desktop" => view! { <IconComputerDesktop attr:class=icon_class/> },
             "document_magnifying_glass" => view! { <IconDocumentMagnifyingGlass attr:class=icon_class/> },
+            "trash" => view! { <IconTrash attr:class=icon_class/> },
+            "scale" => view! { <IconScale attr:class=icon_class/> },
             _ => unreachable!("No icon specified"),
         };
 
@@ -159,11 +215,14 @@ pub fn Maintenance() -> impl IntoView {
             <a
                 class="group flex flex-col bg-white border shadow-sm rounded-xl hover:shadow-md transition dark:bg-slate-900 dark:border-gray-800"
                 href="#"
+                class:opacity-60=move || is_running(idx)
+                class:pointer-events-none=move || is_running(idx)
+                aria-busy=move || is_running(idx).to_string()
                 on:click=move |_| {
-                    execute.dispatch(idx);
+                    if !is_running(idx) {
+                        execute.dispatch(idx);
+                    }
                 }
-
-                disabled=move || pending.get()
             >
 
                 <div class="p-4 md:p-5">
@@ -173,6 +232,9 @@ pub fn Maintenance() -> impl IntoView {
                                 {action.title}
                             </h3>
                             <p class="text-sm text-gray-500">{action.description}</p>
+                            <Show when=move || is_running(idx)>
+                                <TaskProgress/>
+                            </Show>
                         </div>
                     </div>
                 </div>
@@ -183,13 +245,67 @@ pub fn Maintenance() -> impl IntoView {
 
     view! {
         <div class="max-w-5xl px-4 py-10 sm:px-6 lg:px-8 lg:py-14 mx-auto">
-            <Alerts/>
             <div class="grid sm:grid-cols-2 lg:grid-cols-3 gap-3 sm:gap-6">
 
                 {actions}
 
             </div>
 
+            {move || reload_changes.get().map(|changes| view! { <ReloadChanges changes/> })}
+
+        </div>
+    }
+}
+
+#[component]
+fn ReloadChanges(changes: Arc<Vec<SettingChange>>) -> impl IntoView {
+    let count = |filter: fn(&SettingChange) -> bool| changes.iter().filter(|c| filter(c)).count();
+    let summary = format!(
+        "{} added, {} removed, {} modified.",
+        count(|change| change.old_value.is_none()),
+        count(|change| change.new_value.is_none()),
+        count(|change| change.old_value.is_some() && change.new_value.is_some()),
+    );
+
+    view! {
+        <div class="mt-10">
+            <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
+                Changes applied by the last reload
+            </h2>
+            <p class="mb-4 text-sm text-gray-600 dark:text-gray-400">{summary}</p>
+            <Table headers=vec![
+                "Setting".to_string(),
+                "Previous value".to_string(),
+                "New value".to_string(),
+            ]>
+                {changes
+                    .iter()
+                    .cloned()
+                    .map(|change| {
+                        view! {
+                            <TableRow>
+                                <span class="font-mono">{change.key}</span>
+                                <span class="font-mono text-red-800 dark:text-red-500">
+                                    {change.old_value.unwrap_or_else(|| "(not set)".to_string())}
+                                </span>
+                                <span class="font-mono text-teal-800 dark:text-teal-500">
+                                    {change.new_value.unwrap_or_else(|| "(removed)".to_string())}
+                                </span>
+                            </TableRow>
+                        }
+                    })
+                    .collect_view()}
+            </Table>
+        </div>
+    }
+}
+
+#[component]
+fn TaskProgress() -> impl IntoView {
+    view! {
+        <div class="mt-3 inline-flex items-center gap-x-2 text-xs text-gray-500" role="status">
+            <span class="animate-spin inline-block size-3.5 border-2 border-current border-t-transparent text-blue-600 rounded-full dark:text-blue-500"></span>
+            Running...
         </div>
     }
 }
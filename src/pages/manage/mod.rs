@@ -4,6 +4,27 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod dashboard;
+pub mod history;
 pub mod logs;
 pub mod maintenance;
 pub mod spam;
+pub mod tracing;
+
+pub const LEVELS: [(&str, &str); 5] = [
+    ("TRACE", "All levels"),
+    ("DEBUG", "Debug and above"),
+    ("INFO", "Info and above"),
+    ("WARN", "Warnings and errors"),
+    ("ERROR", "Errors only"),
+];
+
+pub fn level_severity(level: &str) -> u8 {
+    match level {
+        "ERROR" => 4,
+        "WARN" => 3,
+        "INFO" => 2,
+        "DEBUG" => 1,
+        _ => 0,
+    }
+}
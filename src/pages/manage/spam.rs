@@ -4,15 +4,19 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::{net::IpAddr, sync::Arc};
+use std::{collections::BTreeMap, net::IpAddr, rc::Rc, sync::Arc};
 
 use ahash::AHashMap;
+use gloo_storage::{LocalStorage, Storage};
 use leptos::*;
 use leptos_router::use_navigate;
 use serde::{Deserialize, Serialize};
 
+use web_sys::{DragEvent, File, FileList, HtmlInputElement};
+
 use crate::{
     components::{
+        badge::Badge,
         form::{
             button::Button,
             input::{InputSwitch, InputText, TextArea},
@@ -20,17 +24,38 @@ use crate::{
             stacked_input::StackedInput,
             Form, FormButtonBar, FormElement, FormItem, FormSection,
         },
-        messages::alert::{use_alerts, Alert},
+        icon::{IconArrowDownTray, IconArrowUpTray},
+        list::table::{Table, TableRow},
+        messages::alert::{error_reason, use_alerts, Alert},
         Color,
     },
     core::{
-        form::FormValue,
-        http::{Error, HttpRequest},
-        oauth::use_authorization,
+        api::use_api,
+        download::download_file,
+        form::{FormData, FormValue},
+        http::Error,
+        i18n::t_signal,
         schema::{Builder, Schemas, SelectType, Source, Transformer, Type, Validator},
+        upload::read_file,
     },
+    pages::maybe_plural,
 };
 
+const SAMPLES_KEY: &str = "webadmin_spam_samples";
+
+#[derive(Debug, Clone)]
+struct TrainError {
+    file: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TrainProgress {
+    completed: usize,
+    trained: usize,
+    total: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "action")]
 #[serde(rename_all = "lowercase")]
@@ -70,27 +95,77 @@ pub enum Envelope {
     Envid,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+struct SpamReport {
+    score: f64,
+    is_spam: bool,
+    rules: Vec<SpamRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SpamRule {
+    name: String,
+    score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SpamSample {
+    name: String,
+    values: BTreeMap<String, SampleValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SampleValue {
+    Value(String),
+    Array(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct SampleResult {
+    name: String,
+    verdict: SampleVerdict,
+}
+
+#[derive(Debug, Clone)]
+enum SampleVerdict {
+    Spam(f64),
+    Ham(f64),
+    Accepted,
+    Rejected(String),
+    Discarded,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleSort {
+    Name { ascending: bool },
+    Score { ascending: bool },
+}
+
 #[component]
 pub fn SpamTest() -> impl IntoView {
-    let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
 
     let (pending, set_pending) = create_signal(false);
+    let report = create_rw_signal(None::<SpamReport>);
 
-    let mut data = expect_context::<Arc<Schemas>>().build_form("spam-test");
+    let schemas = expect_context::<Arc<Schemas>>();
+    let mut data = schemas.build_form("spam-test");
     data.apply_defaults(false);
     let data = data.into_signal();
 
     let save_changes = create_action(
         move |(variables, message): &(AHashMap<String, String>, String)| {
-            let auth = auth.get();
             let variables = variables.clone();
             let message = message.clone();
 
             async move {
                 set_pending.set(true);
-                let result = HttpRequest::post("/api/sieve/spam-filter")
-                    .with_authorization(&auth)
+                report.set(None);
+                let result = api
+                    .post("/api/sieve/spam-filter")
                     .with_parameters(variables)
                     .with_raw_body(message)
                     .send::<Response>()
@@ -103,6 +178,7 @@ pub fn SpamTest() -> impl IntoView {
                         Response::Accept { modifications }
                         | Response::Replace { modifications, .. },
                     ) => {
+                        report.set(SpamReport::from_modifications(&modifications));
                         alert.set(
                             Alert::success("Message accepted by filter")
                                 .with_details_list(modifications.into_iter().filter_map(
@@ -137,6 +213,101 @@ pub fn SpamTest() -> impl IntoView {
         },
     );
 
+    // Saved samples for regression testing
+    let samples =
+        create_rw_signal(LocalStorage::get::<Vec<SpamSample>>(SAMPLES_KEY).unwrap_or_default());
+    let selected_sample = create_rw_signal(String::new());
+    let sample_name = create_rw_signal(String::new());
+    let rerun_results = create_rw_signal(Vec::<SampleResult>::new());
+    let import_input = create_node_ref::<html::Input>();
+    let store_samples = move |updated: Vec<SpamSample>| {
+        if let Err(err) = LocalStorage::set(SAMPLES_KEY, &updated) {
+            log::warn!("Failed to save spam test samples: {err}");
+        }
+        samples.set(updated);
+    };
+
+    let rerun_action = create_action(move |samples: &Vec<SpamSample>| {
+        let samples = samples.clone();
+        let schemas = schemas.clone();
+
+        async move {
+            rerun_results.set(vec![]);
+            for sample in samples {
+                let mut form = schemas.build_form("spam-test");
+                sample.apply(&mut form);
+                let verdict = match api
+                    .post("/api/sieve/spam-filter")
+                    .with_parameters(test_variables(&form))
+                    .with_raw_body(form.value::<String>("message").unwrap_or_default())
+                    .send::<Response>()
+                    .await
+                {
+                    Ok(
+                        Response::Accept { modifications }
+                        | Response::Replace { modifications, .. },
+                    ) => match SpamReport::from_modifications(&modifications) {
+                        Some(report) if report.is_spam => SampleVerdict::Spam(report.score),
+                        Some(report) => SampleVerdict::Ham(report.score),
+                        None => SampleVerdict::Accepted,
+                    },
+                    Ok(Response::Reject { reason }) => SampleVerdict::Rejected(reason),
+                    Ok(Response::Discard) => SampleVerdict::Discarded,
+                    Err(Error::Unauthorized) => {
+                        use_navigate()("/login", Default::default());
+                        return;
+                    }
+                    Err(err) => SampleVerdict::Failed(error_reason(err)),
+                };
+                rerun_results.update(|results| {
+                    results.push(SampleResult {
+                        name: sample.name,
+                        verdict,
+                    });
+                });
+            }
+        }
+    });
+    let is_rerunning = rerun_action.pending();
+
+    let on_import = move |ev: ev::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+
+        spawn_local(async move {
+            let imported = match read_file(file).await {
+                Ok(contents) => serde_json::from_str::<Vec<SpamSample>>(&contents)
+                    .map_err(|err| format!("Invalid samples file: {err}")),
+                Err(err) => Err(format!(
+                    "Failed to read file: {}",
+                    err.as_string().unwrap_or_default()
+                )),
+            };
+            match imported {
+                Ok(imported) => {
+                    let total = imported.len();
+                    let mut updated = samples.get_untracked();
+                    for sample in imported {
+                        updated.retain(|existing| existing.name != sample.name);
+                        updated.push(sample);
+                    }
+                    updated.sort_by(|a, b| a.name.cmp(&b.name));
+                    store_samples(updated);
+                    alert.set(Alert::success(format!(
+                        "Imported {}.",
+                        maybe_plural(total, "sample", "samples")
+                    )));
+                }
+                Err(err) => {
+                    alert.set(Alert::error(err));
+                }
+            }
+        });
+    };
+
     view! {
         <Form title="">
 
@@ -219,37 +390,7 @@ pub fn SpamTest() -> impl IntoView {
                     on_click=Callback::new(move |_| {
                         data.update(|data| {
                             if data.validate_form() {
-                                let mut message = String::new();
-                                let mut variables = AHashMap::new();
-                                for (key, value) in data.values.iter() {
-                                    let value = match value {
-                                        FormValue::Value(value) if !value.is_empty() => value,
-                                        FormValue::Array(values) if !values.is_empty() => {
-                                            for (i, value) in values.iter().enumerate() {
-                                                variables.insert(format!("{key}_{i}"), value.clone());
-                                            }
-                                            continue;
-                                        }
-                                        _ => continue,
-                                    };
-                                    match key.as_str() {
-                                        "message" => {
-                                            message.clone_from(value);
-                                            continue;
-                                        }
-                                        "remote_ip" => {
-                                            if let Ok(ip) = value.parse::<IpAddr>() {
-                                                variables
-                                                    .insert(
-                                                        "remote_ip.reverse".to_string(),
-                                                        to_reverse_name(ip),
-                                                    );
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                    variables.insert(key.clone(), value.clone());
-                                }
+                                let variables = test_variables(data);
                                 save_changes.dispatch((variables, data.value("message").unwrap()));
                             }
                         });
@@ -259,13 +400,487 @@ pub fn SpamTest() -> impl IntoView {
                 />
             </FormButtonBar>
 
+            {move || report.get().map(|report| view! { <SpamScoreReport report/> })}
+
+            <FormSection title="Saved samples".to_string()>
+                <FormItem label="Sample" tooltip="Reload a previously saved test input">
+                    <div class="flex gap-x-2">
+                        <select
+                            class="py-3 px-4 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                            on:change=move |ev| selected_sample.set(event_target_value(&ev))
+                        >
+                            <option value="" selected=move || selected_sample.get().is_empty()>
+                                "Select a sample"
+                            </option>
+                            {move || {
+                                samples
+                                    .get()
+                                    .into_iter()
+                                    .map(|sample| {
+                                        let name = sample.name.clone();
+                                        view! {
+                                            <option
+                                                value=sample.name.clone()
+                                                selected=move || selected_sample.get() == name
+                                            >
+                                                {sample.name}
+                                            </option>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+
+                        </select>
+                        <Button
+                            text="Load"
+                            color=Color::Gray
+                            on_click=move |_| {
+                                let name = selected_sample.get();
+                                if let Some(sample) = samples
+                                    .with(|samples| {
+                                        samples.iter().find(|sample| sample.name == name).cloned()
+                                    })
+                                {
+                                    data.update(|data| {
+                                        data.reset();
+                                        sample.apply(data);
+                                    });
+                                    sample_name.set(sample.name);
+                                    report.set(None);
+                                }
+                            }
+
+                            disabled=Signal::derive(move || selected_sample.get().is_empty())
+                        />
+                        <Button
+                            text=t_signal("button.delete")
+                            color=Color::Red
+                            on_click=move |_| {
+                                let name = selected_sample.get();
+                                let mut updated = samples.get();
+                                updated.retain(|sample| sample.name != name);
+                                store_samples(updated);
+                                selected_sample.set(String::new());
+                            }
+
+                            disabled=Signal::derive(move || selected_sample.get().is_empty())
+                        />
+                    </div>
+                </FormItem>
+                <FormItem label="Save as" tooltip="Save the current test input under this name">
+                    <div class="flex gap-x-2">
+                        <input
+                            type="text"
+                            class="py-3 px-4 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                            placeholder="Sample name"
+                            prop:value=sample_name
+                            on:input=move |ev| sample_name.set(event_target_value(&ev))
+                        />
+                        <Button
+                            text=t_signal("button.save")
+                            color=Color::Gray
+                            on_click=move |_| {
+                                let name = sample_name.get().trim().to_string();
+                                let sample = data
+                                    .with_untracked(|data| SpamSample::from_form(name.clone(), data));
+                                let mut updated = samples.get();
+                                updated.retain(|existing| existing.name != name);
+                                updated.push(sample);
+                                updated.sort_by(|a, b| a.name.cmp(&b.name));
+                                store_samples(updated);
+                                selected_sample.set(name);
+                                alert.set(Alert::success("Sample saved."));
+                            }
+
+                            disabled=Signal::derive(move || sample_name.get().trim().is_empty())
+                        />
+                    </div>
+                </FormItem>
+            </FormSection>
+
+            <Show when=move || rerun_results.with(|results| !results.is_empty())>
+                <div class="mt-5">
+                    <Table headers=vec![
+                        "Sample".to_string(),
+                        "Verdict".to_string(),
+                        "Details".to_string(),
+                    ]>
+                        {move || {
+                            rerun_results
+                                .get()
+                                .into_iter()
+                                .map(|result| {
+                                    let (color, verdict, details) = result.verdict.describe();
+                                    view! {
+                                        <TableRow>
+                                            <span>{result.name}</span>
+                                            <Badge color=color>{verdict}</Badge>
+                                            <span>{details}</span>
+                                        </TableRow>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+
+                    </Table>
+                </div>
+            </Show>
+
+            <FormButtonBar>
+                <input
+                    type="file"
+                    accept=".json,application/json"
+                    class="hidden"
+                    node_ref=import_input
+                    on:change=on_import
+                />
+                <Button
+                    text=t_signal("button.import")
+                    color=Color::Gray
+                    on_click=move |_| {
+                        if let Some(input) = import_input.get() {
+                            input.click();
+                        }
+                    }
+                >
+
+                    <IconArrowUpTray attr:class="flex-shrink-0 size-4"/>
+                </Button>
+                <Button
+                    text=t_signal("button.export")
+                    color=Color::Gray
+                    on_click=move |_| {
+                        if let Err(err) = download_file(
+                            "spam-samples.json",
+                            "application/json",
+                            &serde_json::to_string_pretty(&samples.get()).unwrap_or_default(),
+                        ) {
+                            log::warn!("Failed to download samples: {err:?}");
+                        }
+                    }
+
+                    disabled=Signal::derive(move || samples.with(|samples| samples.is_empty()))
+                >
+
+                    <IconArrowDownTray attr:class="flex-shrink-0 size-4"/>
+                </Button>
+                <Button
+                    text="Re-run all samples"
+                    color=Color::Blue
+                    on_click=move |_| {
+                        rerun_action.dispatch(samples.get());
+                    }
+
+                    disabled=Signal::derive(move || {
+                        is_rerunning.get() || samples.with(|samples| samples.is_empty())
+                    })
+                />
+            </FormButtonBar>
+
         </Form>
     }
 }
 
+fn test_variables(data: &FormData) -> AHashMap<String, String> {
+    let mut variables = AHashMap::new();
+    for (key, value) in data.values.iter() {
+        let value = match value {
+            FormValue::Value(value) if !value.is_empty() => value,
+            FormValue::Array(values) if !values.is_empty() => {
+                for (i, value) in values.iter().enumerate() {
+                    variables.insert(format!("{key}_{i}"), value.clone());
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        match key.as_str() {
+            "message" => {
+                continue;
+            }
+            "remote_ip" => {
+                if let Ok(ip) = value.parse::<IpAddr>() {
+                    variables.insert("remote_ip.reverse".to_string(), to_reverse_name(ip));
+                }
+            }
+            _ => {}
+        }
+        variables.insert(key.clone(), value.clone());
+    }
+    variables
+}
+
+#[component]
+fn SpamScoreReport(report: SpamReport) -> impl IntoView {
+    let sort = create_rw_signal(RuleSort::Score { ascending: false });
+    let rules = report.rules;
+    let sorted_rules = move || {
+        let mut rules = rules.clone();
+        match sort.get() {
+            RuleSort::Name { ascending } => {
+                rules.sort_by(|a, b| a.name.cmp(&b.name));
+                if !ascending {
+                    rules.reverse();
+                }
+            }
+            RuleSort::Score { ascending } => {
+                rules.sort_by(|a, b| {
+                    a.score
+                        .abs()
+                        .total_cmp(&b.score.abs())
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                if !ascending {
+                    rules.reverse();
+                }
+            }
+        }
+        rules
+    };
+    let sort_label = move |column: &'static str| {
+        let arrow = match (column, sort.get()) {
+            ("Rule", RuleSort::Name { ascending }) | ("Score", RuleSort::Score { ascending }) => {
+                if ascending {
+                    " ▲"
+                } else {
+                    " ▼"
+                }
+            }
+            _ => "",
+        };
+        format!("{column}{arrow}")
+    };
+    let (verdict, verdict_color) = if report.is_spam {
+        ("SPAM", Color::Red)
+    } else {
+        ("HAM", Color::Green)
+    };
+
+    view! {
+        <div class="mt-5">
+            <div class="flex items-center gap-x-3 pb-4">
+                <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
+                    {format!("Score {:.2}", report.score)}
+                </h2>
+                <Badge color=verdict_color>{verdict}</Badge>
+            </div>
+            <div class="border rounded-lg shadow overflow-hidden dark:border-gray-700 dark:shadow-gray-900">
+                <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
+                    <thead>
+                        <tr>
+                            <th scope="col" class="px-6 py-3 text-start">
+                                <button
+                                    class="text-xs font-medium text-gray-500 uppercase hover:text-gray-800 dark:hover:text-gray-200"
+                                    on:click=move |_| {
+                                        sort.update(|sort| {
+                                            *sort = match *sort {
+                                                RuleSort::Name { ascending } => {
+                                                    RuleSort::Name {
+                                                        ascending: !ascending,
+                                                    }
+                                                }
+                                                _ => RuleSort::Name { ascending: true },
+                                            };
+                                        })
+                                    }
+                                >
+
+                                    {move || sort_label("Rule")}
+                                </button>
+                            </th>
+                            <th scope="col" class="px-6 py-3 text-start">
+                                <button
+                                    class="text-xs font-medium text-gray-500 uppercase hover:text-gray-800 dark:hover:text-gray-200"
+                                    on:click=move |_| {
+                                        sort.update(|sort| {
+                                            *sort = match *sort {
+                                                RuleSort::Score { ascending } => {
+                                                    RuleSort::Score {
+                                                        ascending: !ascending,
+                                                    }
+                                                }
+                                                _ => RuleSort::Score { ascending: false },
+                                            };
+                                        })
+                                    }
+                                >
+
+                                    {move || sort_label("Score")}
+                                </button>
+                            </th>
+                            <th
+                                scope="col"
+                                class="px-6 py-3 text-start text-xs font-medium text-gray-500 uppercase"
+                            >
+                                Description
+                            </th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-200 dark:divide-gray-700">
+                        {move || {
+                            sorted_rules()
+                                .into_iter()
+                                .map(|rule| {
+                                    let score_class = if rule.score > 0.0 {
+                                        "text-red-600 dark:text-red-500"
+                                    } else if rule.score < 0.0 {
+                                        "text-green-600 dark:text-green-500"
+                                    } else {
+                                        "text-gray-500"
+                                    };
+                                    view! {
+                                        <tr>
+                                            <td class="px-6 py-2 whitespace-nowrap text-sm font-mono text-gray-800 dark:text-gray-200">
+                                                {rule.name.clone()}
+                                            </td>
+                                            <td class=format!(
+                                                "px-6 py-2 whitespace-nowrap text-sm {score_class}",
+                                            )>{format!("{:+.2}", rule.score)}</td>
+                                            <td class="px-6 py-2 text-sm text-gray-500">
+                                                {rule_description(&rule.name)}
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+impl SpamReport {
+    fn from_modifications(modifications: &[Modification]) -> Option<Self> {
+        let mut rules = None;
+        let mut status = None;
+        for modification in modifications {
+            if let Modification::AddHeader { name, value } = modification {
+                if name.eq_ignore_ascii_case("X-Spam-Result") {
+                    rules = Some(parse_spam_result(value));
+                } else if name.eq_ignore_ascii_case("X-Spam-Status") {
+                    status = Some(value.as_str());
+                }
+            }
+        }
+
+        let rules = rules?;
+        let score = status
+            .and_then(|status| status.split_once("score="))
+            .and_then(|(_, score)| {
+                score
+                    .split(|ch: char| ch == ',' || ch.is_whitespace())
+                    .next()
+                    .and_then(|score| score.parse::<f64>().ok())
+            })
+            .unwrap_or_else(|| rules.iter().map(|rule| rule.score).sum());
+        let is_spam = status.is_some_and(|status| {
+            status
+                .trim_start()
+                .get(..3)
+                .is_some_and(|verdict| verdict.eq_ignore_ascii_case("yes"))
+        });
+
+        Some(SpamReport {
+            score,
+            is_spam,
+            rules,
+        })
+    }
+}
+
+fn parse_spam_result(value: &str) -> Vec<SpamRule> {
+    value
+        .split(',')
+        .filter_map(|rule| {
+            let rule = rule.trim();
+            let (name, score) = match rule.split_once('(') {
+                Some((name, score)) => (name.trim(), score.trim_end_matches(')').trim()),
+                None => (rule, "0"),
+            };
+            (!name.is_empty()).then(|| SpamRule {
+                name: name.to_string(),
+                score: score.parse().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+fn rule_description(name: &str) -> &'static str {
+    match name.split('_').next().unwrap_or_default() {
+        "ARC" => "ARC authentication result",
+        "DKIM" | "R" if name.contains("DKIM") => "DKIM signature validation",
+        "SPF" | "R" if name.contains("SPF") => "SPF authentication result",
+        "DMARC" => "DMARC policy evaluation",
+        "BAYES" => "Bayesian classifier verdict",
+        "RBL" | "RCVD" if name.contains("RBL") => "Sender listed in a DNS blocklist",
+        "RCVD" => "Received headers analysis",
+        "FROM" | "FORGED" => "Sender address analysis",
+        "TO" | "RCPT" => "Recipient analysis",
+        "SUBJ" | "SUBJECT" => "Subject line analysis",
+        "MIME" | "CTE" | "CT" => "MIME structure analysis",
+        "HTML" | "HAS" => "Message content analysis",
+        "URI" | "URL" | "DBL" | "SURBL" => "Links in the message body",
+        "MID" => "Message-ID header analysis",
+        "DATE" | "MISSING" => "Missing or malformed headers",
+        "REPLY" | "REPLYTO" => "Reply-To header analysis",
+        "HELO" | "HFILTER" => "EHLO hostname checks",
+        "IP" | "IPREV" | "RDNS" => "Reverse DNS of the remote host",
+        "TRUSTED" | "ALLOWLIST" | "WHITELIST" => "Sender is trusted",
+        "LLM" => "LLM classifier verdict",
+        _ => "",
+    }
+}
+
+impl SpamSample {
+    fn from_form(name: String, data: &FormData) -> Self {
+        SpamSample {
+            name,
+            values: data
+                .values
+                .iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        FormValue::Value(value) => SampleValue::Value(value.clone()),
+                        FormValue::Array(values) => SampleValue::Array(values.clone()),
+                        FormValue::Expression(_) => return None,
+                    };
+                    Some((key.clone(), value))
+                })
+                .collect(),
+        }
+    }
+
+    fn apply(&self, data: &mut FormData) {
+        for (key, value) in &self.values {
+            match value {
+                SampleValue::Value(value) => data.set(key.as_str(), value.clone()),
+                SampleValue::Array(values) => {
+                    data.set(key.as_str(), FormValue::Array(values.clone()))
+                }
+            }
+        }
+    }
+}
+
+impl SampleVerdict {
+    fn describe(self) -> (Color, &'static str, String) {
+        match self {
+            SampleVerdict::Spam(score) => (Color::Red, "SPAM", format!("Score {score:.2}")),
+            SampleVerdict::Ham(score) => (Color::Green, "HAM", format!("Score {score:.2}")),
+            SampleVerdict::Accepted => (Color::Green, "Accepted", String::new()),
+            SampleVerdict::Rejected(reason) => (Color::Yellow, "Rejected", reason),
+            SampleVerdict::Discarded => (Color::Yellow, "Discarded", String::new()),
+            SampleVerdict::Failed(reason) => (Color::Gray, "Error", reason),
+        }
+    }
+}
+
 #[component]
 pub fn SpamTrain() -> impl IntoView {
-    let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
 
     let (pending, set_pending) = create_signal(false);
@@ -275,14 +890,13 @@ pub fn SpamTrain() -> impl IntoView {
     let data = data.into_signal();
 
     let save_changes = create_action(move |(train, message): &(String, String)| {
-        let auth = auth.get();
         let train = train.clone();
         let message = message.clone();
 
         async move {
             set_pending.set(true);
-            let result = HttpRequest::post("/api/sieve/train")
-                .with_authorization(&auth)
+            let result = api
+                .post("/api/sieve/train")
                 .with_parameter("train", train)
                 .with_raw_body(message)
                 .send::<Response>()
@@ -313,6 +927,98 @@ pub fn SpamTrain() -> impl IntoView {
         }
     });
 
+    // Batch training of .eml files
+    let batch_class = create_rw_signal("spam".to_string());
+    let batch_files = create_rw_signal(Vec::<File>::new());
+    let batch_errors = create_rw_signal(Vec::<TrainError>::new());
+    let batch_progress = create_rw_signal(TrainProgress::default());
+    let is_dragging = create_rw_signal(false);
+
+    let train_batch = create_action(move |(train, files): &(String, Rc<Vec<File>>)| {
+        let train = train.clone();
+        let files = files.clone();
+
+        async move {
+            batch_errors.set(vec![]);
+            batch_progress.set(TrainProgress {
+                total: files.len(),
+                ..Default::default()
+            });
+
+            for file in files.iter() {
+                let reason = match read_file(file.clone()).await {
+                    Ok(message) if message.trim().is_empty() => Some("File is empty".to_string()),
+                    Ok(message) => match api
+                        .post("/api/sieve/train")
+                        .with_parameter("train", train.clone())
+                        .with_raw_body(message)
+                        .send::<Response>()
+                        .await
+                    {
+                        Ok(Response::Accept { .. }) => None,
+                        Ok(Response::Reject { reason }) => Some(reason),
+                        Ok(_) => Some("Unexpected server response".to_string()),
+                        Err(Error::Unauthorized) => {
+                            use_navigate()("/login", Default::default());
+                            return;
+                        }
+                        Err(err) => Some(error_reason(err)),
+                    },
+                    Err(err) => Some(format!(
+                        "Failed to read file: {}",
+                        err.as_string().unwrap_or_default()
+                    )),
+                };
+
+                batch_progress.update(|progress| {
+                    progress.completed += 1;
+                    if reason.is_none() {
+                        progress.trained += 1;
+                    }
+                });
+                if let Some(reason) = reason {
+                    batch_errors.update(|errors| {
+                        errors.push(TrainError {
+                            file: file.name(),
+                            reason,
+                        });
+                    });
+                }
+            }
+
+            let progress = batch_progress.get_untracked();
+            let failed = progress.total - progress.trained;
+            if failed == 0 {
+                alert.set(Alert::success(format!(
+                    "Trained {}.",
+                    maybe_plural(progress.trained, "message", "messages")
+                )));
+            } else {
+                alert.set(Alert::warning(format!(
+                    "Trained {}, {} failed.",
+                    maybe_plural(progress.trained, "message", "messages"),
+                    failed
+                )));
+            }
+        }
+    });
+    let is_training = train_batch.pending();
+
+    let add_files = move |files: Option<FileList>| {
+        let Some(files) = files else {
+            return;
+        };
+        batch_files.update(|batch| {
+            for file in (0..files.length()).filter_map(|idx| files.get(idx)) {
+                if !batch.iter().any(|existing| existing.name() == file.name()) {
+                    batch.push(file);
+                }
+            }
+        });
+        batch_progress.set(TrainProgress::default());
+        batch_errors.set(vec![]);
+    };
+
     view! {
         <Form title="Train SPAM filter" subtitle="Train the SPAM filter classifier">
 
@@ -347,6 +1053,178 @@ pub fn SpamTrain() -> impl IntoView {
                 />
             </FormButtonBar>
 
+            <FormSection title="Batch training".to_string()>
+                <FormItem label="Train">
+                    <select
+                        class="py-3 px-4 pe-9 block w-full border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 disabled:opacity-50 disabled:pointer-events-none dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        disabled=move || is_training.get()
+                        on:change=move |ev| batch_class.set(event_target_value(&ev))
+                    >
+                        <option value="spam" selected=move || batch_class.get() == "spam">
+                            SPAM
+                        </option>
+                        <option value="ham" selected=move || batch_class.get() == "ham">
+                            HAM
+                        </option>
+                    </select>
+                </FormItem>
+                <FormItem label="Messages">
+                    <label
+                        class=move || {
+                            format!(
+                                "flex flex-col items-center justify-center w-full h-32 border-2 border-dashed rounded-lg cursor-pointer {}",
+                                if is_dragging.get() {
+                                    "border-blue-500 bg-blue-50 dark:bg-blue-800/10"
+                                } else {
+                                    "border-gray-200 bg-gray-50 hover:bg-gray-100 dark:bg-slate-900 dark:border-gray-700 dark:hover:bg-gray-800"
+                                },
+                            )
+                        }
+
+                        on:dragover=move |ev: DragEvent| {
+                            ev.prevent_default();
+                            is_dragging.set(true);
+                        }
+
+                        on:dragleave=move |_| is_dragging.set(false)
+                        on:drop=move |ev: DragEvent| {
+                            ev.prevent_default();
+                            is_dragging.set(false);
+                            if !is_training.get_untracked() {
+                                add_files(ev.data_transfer().and_then(|data| data.files()));
+                            }
+                        }
+                    >
+
+                        <p class="text-sm text-gray-500 dark:text-gray-400">
+                            <span class="font-semibold">"Drop .eml files here"</span>
+                            " or click to select"
+                        </p>
+                        <p class="text-xs text-gray-500 dark:text-gray-400">
+                            {move || {
+                                let total = batch_files.with(|files| files.len());
+                                if total > 0 {
+                                    format!("{} selected", maybe_plural(total, "file", "files"))
+                                } else {
+                                    String::new()
+                                }
+                            }}
+
+                        </p>
+                        <input
+                            type="file"
+                            accept=".eml,message/rfc822"
+                            multiple
+                            class="hidden"
+                            disabled=move || is_training.get()
+                            on:change=move |ev| {
+                                let input = event_target::<HtmlInputElement>(&ev);
+                                add_files(input.files());
+                                input.set_value("");
+                            }
+                        />
+
+                    </label>
+                </FormItem>
+            </FormSection>
+
+            <Show when=move || { batch_progress.get().total > 0 }>
+                <div class="mt-5">
+                    <div class="flex justify-between items-center mb-2 text-sm text-gray-800 dark:text-white">
+                        <span>
+                            {move || {
+                                let progress = batch_progress.get();
+                                format!("Processed {} of {}", progress.completed, progress.total)
+                            }}
+
+                        </span>
+                        <span>
+                            {move || {
+                                let progress = batch_progress.get();
+                                format!("{} trained", progress.trained)
+                            }}
+
+                        </span>
+                    </div>
+                    <div
+                        class="flex w-full h-2 bg-gray-200 rounded-full overflow-hidden dark:bg-gray-700"
+                        role="progressbar"
+                    >
+                        <div
+                            class="flex flex-col justify-center rounded-full overflow-hidden bg-blue-600 transition duration-500"
+                            style=move || {
+                                let progress = batch_progress.get();
+                                format!(
+                                    "width: {}%",
+                                    progress.completed * 100 / std::cmp::max(progress.total, 1),
+                                )
+                            }
+                        ></div>
+                    </div>
+                </div>
+            </Show>
+
+            <Show when=move || batch_errors.with(|errors| !errors.is_empty())>
+                <div class="mt-5">
+                    <h2 class="pb-4 text-lg font-semibold text-gray-800 dark:text-gray-200">
+                        Failed files
+                    </h2>
+                    <Table headers=vec!["File".to_string(), "Reason".to_string()]>
+                        {move || {
+                            batch_errors
+                                .get()
+                                .into_iter()
+                                .map(|error| {
+                                    view! {
+                                        <TableRow>
+                                            <span>{error.file}</span>
+                                            <span>{error.reason}</span>
+                                        </TableRow>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+
+                    </Table>
+                </div>
+            </Show>
+
+            <FormButtonBar>
+                <Button
+                    text=t_signal("button.clear")
+                    color=Color::Gray
+                    on_click=move |_| {
+                        batch_files.set(vec![]);
+                        batch_errors.set(vec![]);
+                        batch_progress.set(TrainProgress::default());
+                    }
+
+                    disabled=is_training
+                />
+                <Button
+                    text=Signal::derive(move || {
+                        let total = batch_files.with(|files| files.len());
+                        if total > 0 {
+                            format!("Train {}", maybe_plural(total, "message", "messages"))
+                        } else {
+                            "Train batch".to_string()
+                        }
+                    })
+
+                    color=Color::Blue
+                    on_click=Callback::new(move |_| {
+                        let files = batch_files.try_update(std::mem::take).unwrap_or_default();
+                        if !files.is_empty() {
+                            train_batch.dispatch((batch_class.get(), Rc::new(files)));
+                        }
+                    })
+
+                    disabled=Signal::derive(move || {
+                        is_training.get() || batch_files.with(|files| files.is_empty())
+                    })
+                />
+            </FormButtonBar>
+
         </Form>
     }
 }
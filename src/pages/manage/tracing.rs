@@ -0,0 +1,552 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{collections::VecDeque, time::Duration};
+
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
+use leptos::{leptos_dom::helpers::TimeoutHandle, *};
+use leptos_router::use_navigate;
+use serde::{Deserialize, Serialize};
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast},
+    Event, EventSource, MessageEvent,
+};
+
+use crate::{
+    components::{
+        badge::Badge,
+        icon::IconTrash,
+        list::{
+            header::ColumnList,
+            toolbar::{FilterInput, FilterSelect, ToolbarButton},
+            Filters, Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
+        },
+        messages::alert::{use_alerts, Alert, AlertQueue},
+        timestamp::Timestamp,
+        Color,
+    },
+    core::{api::ApiClient, http, i18n::t_signal, oauth::use_authorization, AccessToken},
+    pages::queue::messages::deserialize_datetime,
+};
+
+use super::{level_severity, LEVELS};
+
+pub const CONNECTION_SPAN: &str = "smtp.connection-start";
+pub const DELIVERY_SPAN: &str = "delivery.attempt-start";
+
+const MAX_EVENTS: usize = 5000;
+const MAX_BACKOFF_SECS: u64 = 30;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(super) struct TraceEvent {
+    #[serde(deserialize_with = "deserialize_datetime")]
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub event: String,
+    #[serde(default)]
+    pub details: String,
+}
+
+// Starting event of a span, as listed by /api/telemetry/traces
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpanEvent {
+    pub id: String,
+    #[serde(deserialize_with = "deserialize_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub typ: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub data: AHashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TraceMessage {
+    Batch(Vec<TraceEvent>),
+    Single(TraceEvent),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TraceEntry {
+    id: u64,
+    event: TraceEvent,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConnectionState {
+    Connecting,
+    Connected,
+    Reconnecting { attempt: u32, delay: u64 },
+}
+
+struct LiveSource {
+    source: EventSource,
+    _on_open: Closure<dyn FnMut(Event)>,
+    on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(Event)>,
+}
+
+impl LiveSource {
+    fn close(self) {
+        let _ = self
+            .source
+            .remove_event_listener_with_callback("event", self.on_message.as_ref().unchecked_ref());
+        self.source.set_onopen(None);
+        self.source.set_onmessage(None);
+        self.source.set_onerror(None);
+        self.source.close();
+    }
+}
+
+#[derive(Clone, Copy)]
+struct LiveTrace {
+    auth: RwSignal<AccessToken>,
+    state: RwSignal<ConnectionState>,
+    events: RwSignal<VecDeque<TraceEntry>>,
+    held: RwSignal<VecDeque<TraceEntry>>,
+    paused: RwSignal<bool>,
+    source: StoredValue<Option<LiveSource>>,
+    retry: StoredValue<Option<TimeoutHandle>>,
+    attempt: StoredValue<u32>,
+    next_id: StoredValue<u64>,
+    disposed: StoredValue<bool>,
+}
+
+#[component]
+pub fn LiveTracing() -> impl IntoView {
+    let alert = use_alerts();
+    let live = LiveTrace {
+        auth: use_authorization(),
+        state: create_rw_signal(ConnectionState::Connecting),
+        events: create_rw_signal(VecDeque::new()),
+        held: create_rw_signal(VecDeque::new()),
+        paused: create_rw_signal(false),
+        source: store_value(None),
+        retry: store_value(None),
+        attempt: store_value(0),
+        next_id: store_value(0),
+        disposed: store_value(false),
+    };
+    let level = create_rw_signal(LEVELS[0].0.to_string());
+    let event_type = create_rw_signal(None::<String>);
+    let search = create_rw_signal(None::<String>);
+    let auto_scroll = create_rw_signal(true);
+
+    live.connect(alert);
+    on_cleanup(move || live.close());
+
+    let filtered = create_memo(move |_| {
+        let min_level = level_severity(&level.get());
+        let event_type = event_type.get().map(|s| s.to_lowercase());
+        let search = search.get().map(|s| s.to_lowercase());
+
+        live.events.with(|events| {
+            events
+                .iter()
+                .filter(|entry| {
+                    let event = &entry.event;
+                    level_severity(&event.level) >= min_level
+                        && event_type
+                            .as_ref()
+                            .is_none_or(|typ| event.event.to_lowercase().starts_with(typ))
+                        && search.as_ref().is_none_or(|search| {
+                            event.event.to_lowercase().contains(search)
+                                || event.details.to_lowercase().contains(search)
+                        })
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    });
+
+    create_effect(move |_| {
+        filtered.track();
+        if auto_scroll.get_untracked() {
+            request_animation_frame(|| {
+                if let Some(element) = document().document_element() {
+                    window().scroll_to_with_x_and_y(0.0, element.scroll_height() as f64);
+                }
+            });
+        }
+    });
+
+    view! {
+        <ListSection>
+            <ListTable
+                title="Live tracing"
+                subtitle="Follow server events as they happen"
+            >
+                <Toolbar slot>
+                    {move || {
+                        let (color, text) = match live.state.get() {
+                            ConnectionState::Connecting => (Color::Gray, "Connecting".to_string()),
+                            ConnectionState::Connected => (Color::Green, "Connected".to_string()),
+                            ConnectionState::Reconnecting { attempt, delay } => {
+                                (
+                                    Color::Red,
+                                    format!("Disconnected, retry {attempt} in {delay}s"),
+                                )
+                            }
+                        };
+                        view! {
+                            <div class="inline-flex items-center">
+                                <Badge color=color>{text}</Badge>
+                            </div>
+                        }
+                    }}
+
+                    <ToolbarButton
+                        text=Signal::derive(move || {
+                            let held = live.held.with(|held| held.len());
+                            if live.paused.get() {
+                                if held > 0 {
+                                    format!("Resume ({held} new)")
+                                } else {
+                                    "Resume".to_string()
+                                }
+                            } else {
+                                "Pause".to_string()
+                            }
+                        })
+
+                        color=Color::Gray
+                        on_click=move |_| {
+                            if live.paused.get() {
+                                live.resume();
+                            } else {
+                                live.paused.set(true);
+                            }
+                        }
+                    />
+
+                    <ToolbarButton
+                        text=Signal::derive(move || {
+                            if auto_scroll.get() {
+                                "Auto-scroll: on".to_string()
+                            } else {
+                                "Auto-scroll: off".to_string()
+                            }
+                        })
+
+                        color=Color::Gray
+                        on_click=move |_| {
+                            auto_scroll.update(|v| *v = !*v);
+                        }
+                    />
+
+                    <ToolbarButton
+                        text=t_signal("button.clear")
+                        color=Color::Red
+                        on_click=move |_| {
+                            live.events.update(|events| events.clear());
+                            live.held.update(|held| held.clear());
+                        }
+                    >
+
+                        <IconTrash/>
+                    </ToolbarButton>
+
+                </Toolbar>
+
+                <Filters slot>
+                    <FilterSelect
+                        label="Level"
+                        value=level
+                        options=LEVELS.to_vec()
+                        on_change=move |value: String| level.set(value)
+                    />
+
+                    <FilterInput
+                        label="Event type"
+                        placeholder="smtp."
+                        value=event_type
+                        on_change=move |value: String| {
+                            event_type.set(Some(value).filter(|v| !v.is_empty()));
+                        }
+                    />
+
+                    <FilterInput
+                        label="Search"
+                        placeholder="Text in event or details"
+                        value=search
+                        on_change=move |value: String| {
+                            search.set(Some(value).filter(|v| !v.is_empty()));
+                        }
+                    />
+
+                </Filters>
+
+                {move || {
+                    if filtered.with(|events| !events.is_empty()) {
+                        view! {
+                            <ColumnList headers=vec![
+                                "Date".to_string(),
+                                "Level".to_string(),
+                                "Event".to_string(),
+                                "Details".to_string(),
+                            ]>
+
+                                <For
+                                    each=move || filtered.get()
+                                    key=|entry| entry.id
+                                    let:entry
+                                >
+                                    <TraceItem event=entry.event/>
+                                </For>
+
+                            </ColumnList>
+                        }
+                            .into_view()
+                    } else {
+                        view! {
+                            <ZeroResults
+                                title="No events"
+                                subtitle="Waiting for events matching the selected filters."
+                            />
+                        }
+                            .into_view()
+                    }
+                }}
+
+                <Footer slot>
+                    <div class="px-6 py-4 flex justify-between items-center border-t border-gray-200 dark:border-gray-700">
+                        <p class="text-sm text-gray-600 dark:text-gray-400">
+                            {move || {
+                                format!(
+                                    "Showing {} of {} buffered events (last {MAX_EVENTS} kept)",
+                                    filtered.with(|events| events.len()),
+                                    live.events.with(|events| events.len()),
+                                )
+                            }}
+
+                        </p>
+                        <Show when=move || live.paused.get()>
+                            <span class="text-sm text-yellow-600 dark:text-yellow-500">
+                                "Paused, new events are held until resumed"
+                            </span>
+                        </Show>
+                    </div>
+                </Footer>
+            </ListTable>
+        </ListSection>
+    }
+}
+
+#[component]
+pub(super) fn TraceItem(event: TraceEvent) -> impl IntoView {
+    let timestamp = event.timestamp;
+    let color = match event.level.as_str() {
+        "ERROR" => Color::Red,
+        "WARN" => Color::Yellow,
+        "INFO" => Color::Green,
+        "DEBUG" => Color::Blue,
+        _ => Color::Gray,
+    };
+
+    view! {
+        <tr>
+            <ListItem>
+                <span class="text-sm text-gray-500">
+                    <Timestamp value=timestamp/>
+                </span>
+            </ListItem>
+            <ListItem>
+                <Badge color=color>{event.level}</Badge>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm text-gray-500">{event.event}</span>
+            </ListItem>
+            <ListItem>
+                <span class="text-sm text-gray-500 text-wrap">{event.details}</span>
+            </ListItem>
+        </tr>
+    }
+}
+
+impl SpanEvent {
+    pub fn value(&self, key: &str) -> Option<String> {
+        match self.data.get(key)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            serde_json::Value::Null => None,
+            value => Some(value.to_string()),
+        }
+    }
+
+    pub fn values(&self, key: &str) -> Vec<String> {
+        match self.data.get(key) {
+            Some(serde_json::Value::Array(values)) => values
+                .iter()
+                .filter_map(|value| value.as_str().map(|value| value.to_string()))
+                .collect(),
+            _ => self.value(key).into_iter().collect(),
+        }
+    }
+}
+
+impl LiveTrace {
+    fn connect(self, alert: AlertQueue) {
+        if self.is_disposed() {
+            return;
+        }
+        self.state.set(ConnectionState::Connecting);
+        let auth = self.auth.get_untracked();
+
+        spawn_local(async move {
+            // Browsers cannot send headers with an EventSource, so a
+            // short-lived token is requested and passed in the URL instead.
+            let token = match ApiClient::new(self.auth)
+                .get("/api/telemetry/live/tracing-token")
+                .send::<String>()
+                .await
+            {
+                Ok(token) => token,
+                Err(http::Error::Unauthorized) => {
+                    use_navigate()("/login", Default::default());
+                    return;
+                }
+                Err(err) => {
+                    if self.attempt.try_get_value() == Some(0) {
+                        alert.set(Alert::from(err));
+                    }
+                    self.schedule_reconnect(alert);
+                    return;
+                }
+            };
+            if self.is_disposed() {
+                return;
+            }
+
+            match EventSource::new(&live_url(&auth, &token)) {
+                Ok(source) => self.attach(source, alert),
+                Err(err) => {
+                    log::warn!("Failed to open tracing stream: {err:?}");
+                    self.schedule_reconnect(alert);
+                }
+            }
+        });
+    }
+
+    fn attach(self, source: EventSource, alert: AlertQueue) {
+        let on_open = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+            self.attempt.set_value(0);
+            self.state.set(ConnectionState::Connected);
+        });
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |ev: MessageEvent| {
+            let Some(text) = ev.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<TraceMessage>(&text) {
+                Ok(TraceMessage::Batch(events)) => self.push(events),
+                Ok(TraceMessage::Single(event)) => self.push(vec![event]),
+                Err(err) => log::debug!("Ignoring malformed trace event: {err}"),
+            }
+        });
+        // The browser would reconnect on its own with the same token, which
+        // has expired by then, so the stream is reopened with a new one
+        let on_error = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+            self.schedule_reconnect(alert);
+        });
+        let _ =
+            source.add_event_listener_with_callback("event", on_message.as_ref().unchecked_ref());
+        source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        // The page may be gone by now, in which case nothing else would close it
+        if let Some(Some(live)) = self.source.try_set_value(Some(LiveSource {
+            source,
+            _on_open: on_open,
+            on_message,
+            _on_error: on_error,
+        })) {
+            live.close();
+        }
+    }
+
+    fn push(self, events: Vec<TraceEvent>) {
+        let mut next_id = self.next_id.get_value();
+        let entries = events.into_iter().map(|event| {
+            next_id += 1;
+            TraceEntry { id: next_id, event }
+        });
+        let target = if self.paused.get_untracked() {
+            self.held
+        } else {
+            self.events
+        };
+        target.update(|target| {
+            target.extend(entries);
+            let excess = target.len().saturating_sub(MAX_EVENTS);
+            target.drain(..excess);
+        });
+        self.next_id.set_value(next_id);
+    }
+
+    fn resume(self) {
+        let held = self.held.try_update(std::mem::take).unwrap_or_default();
+        self.events.update(|events| {
+            events.extend(held);
+            let excess = events.len().saturating_sub(MAX_EVENTS);
+            events.drain(..excess);
+        });
+        self.paused.set(false);
+    }
+
+    fn schedule_reconnect(self, alert: AlertQueue) {
+        if self.is_disposed() {
+            return;
+        }
+        self.drop_source();
+        let Some(attempt) = self.attempt.try_get_value().map(|attempt| attempt + 1) else {
+            return;
+        };
+        let delay = std::cmp::min(1u64 << std::cmp::min(attempt - 1, 5), MAX_BACKOFF_SECS);
+        self.attempt.set_value(attempt);
+        self.state
+            .set(ConnectionState::Reconnecting { attempt, delay });
+
+        match set_timeout_with_handle(move || self.connect(alert), Duration::from_secs(delay)) {
+            Ok(handle) => self.retry.set_value(Some(handle)),
+            Err(err) => log::warn!("Failed to schedule reconnect: {err:?}"),
+        }
+    }
+
+    fn drop_source(self) {
+        if let Some(live) = self.source.try_update_value(Option::take).flatten() {
+            live.close();
+        }
+    }
+
+    fn is_disposed(self) -> bool {
+        self.disposed.try_get_value().unwrap_or(true)
+    }
+
+    fn close(self) {
+        self.disposed.set_value(true);
+        if let Some(handle) = self.retry.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+        self.drop_source();
+    }
+}
+
+fn live_url(auth: &AccessToken, token: &str) -> String {
+    let base_url = if auth.base_url.is_empty() {
+        window().location().origin().unwrap_or_default()
+    } else {
+        auth.base_url.to_string()
+    };
+
+    format!(
+        "{}/api/telemetry/traces/live?token={}",
+        base_url.trim_end_matches('/'),
+        form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>()
+    )
+}
@@ -4,14 +4,17 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::core::datetime::{self, DateTimeStyle};
+
 pub mod account;
 pub mod authorize;
 pub mod config;
 pub mod directory;
 pub mod enterprise;
+pub mod forbidden;
 pub mod login;
 pub mod manage;
 pub mod notfound;
@@ -36,22 +39,23 @@ pub trait FormatDateTime {
     fn format_date_time(&self) -> String;
     fn format_date(&self) -> String;
     fn format_time(&self) -> String;
+    fn format_relative(&self) -> String;
 }
 
 impl FormatDateTime for DateTime<Utc> {
     fn format_date_time(&self) -> String {
-        self.with_timezone(&Local)
-            .format("%a, %d %b %Y %H:%M:%S")
-            .to_string()
+        datetime::format_datetime(self, DateTimeStyle::DateTime)
     }
 
     fn format_date(&self) -> String {
-        self.with_timezone(&Local)
-            .format("%a, %d %b %Y")
-            .to_string()
+        datetime::format_datetime(self, DateTimeStyle::Date)
     }
 
     fn format_time(&self) -> String {
-        self.with_timezone(&Local).format("%H:%M:%S").to_string()
+        datetime::format_datetime(self, DateTimeStyle::Time)
+    }
+
+    fn format_relative(&self) -> String {
+        datetime::format_relative(self, &Utc::now())
     }
 }
@@ -12,26 +12,31 @@ use crate::{
     components::{
         badge::Badge,
         icon::{
-            IconAlertTriangle, IconCancel, IconCheckCircle, IconClock, IconLaunch, IconRefresh,
+            IconAlertTriangle, IconCancel, IconCheckCircle, IconClock, IconInbox, IconLaunch,
+            IconRefresh,
         },
         list::{
             header::ColumnList,
             pagination::Pagination,
+            refresh::AutoRefreshToggle,
             row::SelectItem,
-            toolbar::{SearchBox, ToolbarButton},
-            Footer, ListItem, ListSection, ListTable, Toolbar, ZeroResults,
+            toolbar::{FilterInput, SearchBox, ToolbarButton},
+            EmptyState, Filters, Footer, ListItem, ListSection, ListTable, Toolbar,
         },
         messages::{
             alert::{use_alerts, Alert},
             modal::{use_modals, Modal},
         },
-        skeleton::Skeleton,
+        skeleton::{ListSkeleton, Skeleton},
+        timestamp::RelativeTime,
         Color,
     },
     core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-        url::UrlBuilder,
+        api::{use_api, use_latest_api},
+        http,
+        i18n::t_signal,
+        list_state::use_list_state,
+        oauth::use_is_viewer,
     },
     pages::{
         maybe_plural,
@@ -40,66 +45,64 @@ use crate::{
     },
 };
 
-use chrono_humanize::HumanTime;
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat, TimeZone, Utc};
 
 const PAGE_SIZE: u32 = 10;
+const AUTO_REFRESH_KEY: &str = "webadmin_queue_refresh";
 
 #[component]
 pub fn QueueList() -> impl IntoView {
-    let query = use_query_map();
-    let page = create_memo(move |_| {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
-            .filter(|&page| page > 0)
-            .unwrap_or(1)
-    });
-    let filter = create_memo(move |_| {
-        query.with(|q| {
-            q.get("filter").and_then(|s| {
-                let s = s.trim();
-                if !s.is_empty() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            })
-        })
+    let is_viewer = use_is_viewer();
+    let state = use_list_state();
+    let page = state.page;
+    let filter = state.value("filter");
+    let from = state.value("from");
+    let to = state.value("to");
+    let before = state.value("before");
+    let has_filters = create_memo(move |_| {
+        filter.get().is_some()
+            || from.get().is_some()
+            || to.get().is_some()
+            || before.get().is_some()
     });
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        state.url("/manage/queue/messages", key, value)
+    };
 
-    let auth = use_authorization();
+    let api = use_api();
+    let latest = use_latest_api();
     let alert = use_alerts();
     let modal = use_modals();
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
     provide_context(selected);
 
     let messages = create_resource(
-        move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
-
-            async move {
-                HttpRequest::get("/api/queue/messages")
-                    .with_authorization(&auth)
-                    .with_parameter("page", page.to_string())
-                    .with_parameter("limit", PAGE_SIZE.to_string())
-                    .with_parameter("values", "1")
-                    .with_parameter("max-total", "100")
-                    .with_optional_parameter("text", filter)
-                    .send::<List<Message>>()
-                    .await
-            }
+        move || (page.get(), filter.get(), from.get(), to.get(), before.get()),
+        move |(page, filter, from, to, before)| async move {
+            latest
+                .get("/api/queue/messages")
+                .conditional()
+                .with_parameter("page", page.to_string())
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_parameter("values", "1")
+                .with_parameter("max-total", "100")
+                .with_optional_parameter("text", filter)
+                .with_optional_parameter("from", from)
+                .with_optional_parameter("to", to)
+                .with_optional_parameter("before", before)
+                .send::<List<Message>>()
+                .await
         },
     );
 
     let cancel_action = create_action(move |items: &HashSet<String>| {
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             let mut total_deleted = 0;
             for id in items {
-                match HttpRequest::delete(("/api/queue/messages", &id))
-                    .with_authorization(&auth)
+                match api
+                    .delete(("/api/queue/messages", &id))
                     .send::<bool>()
                     .await
                 {
@@ -125,16 +128,11 @@ pub fn QueueList() -> impl IntoView {
     });
     let retry_action = create_action(move |items: &HashSet<String>| {
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             let mut total_rescheduled = 0;
             for id in items {
-                match HttpRequest::patch(("/api/queue/messages", &id))
-                    .with_authorization(&auth)
-                    .send::<bool>()
-                    .await
-                {
+                match api.patch(("/api/queue/messages", &id)).send::<bool>().await {
                     Ok(true) => {
                         total_rescheduled += 1;
                     }
@@ -162,20 +160,31 @@ pub fn QueueList() -> impl IntoView {
         <ListSection>
             <ListTable title="Message Queue" subtitle="View, cancel or reschedule queued messages">
                 <Toolbar slot>
+                    <AutoRefreshToggle
+                        storage_key=AUTO_REFRESH_KEY
+                        paused=Signal::derive(move || {
+                            cancel_action.pending().get() || retry_action.pending().get()
+                                || messages.loading().get()
+                        })
+
+                        on_refresh=move |_| {
+                            messages.refetch();
+                        }
+                    />
+
                     <SearchBox
                         value=filter
+                        latest
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/queue/messages")
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &filter_url("filter", Some(value)).finish(),
                                 Default::default(),
                             );
                         }
                     />
 
                     <ToolbarButton
-                        text="Refresh"
+                        text=t_signal("button.refresh")
 
                         color=Color::Gray
                         on_click=Callback::new(move |_| {
@@ -187,6 +196,7 @@ pub fn QueueList() -> impl IntoView {
                     </ToolbarButton>
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Retry ({ns})") } else { "Retry".to_string() }
@@ -208,6 +218,7 @@ pub fn QueueList() -> impl IntoView {
                     </ToolbarButton>
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Cancel ({ns})") } else { "Cancel".to_string() }
@@ -243,7 +254,46 @@ pub fn QueueList() -> impl IntoView {
 
                 </Toolbar>
 
-                <Transition fallback=Skeleton>
+                <Filters slot>
+                    <FilterInput
+                        label="Sender domain"
+                        placeholder="example.org"
+                        value=from
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("from", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Recipient"
+                        placeholder="user@example.org"
+                        value=to
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("to", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Due before"
+                        input_type="datetime-local"
+                        value=Signal::derive(move || before.get().and_then(|dt| to_local_input(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("before", from_local_input(&value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=|| view! { <ListSkeleton/> }>
                     {move || match messages.get() {
                         None => None,
                         Some(Err(http::Error::Unauthorized)) => {
@@ -295,10 +345,22 @@ pub fn QueueList() -> impl IntoView {
                             total_results.set(Some(0));
                             Some(
                                 view! {
-                                    <ZeroResults
-                                        title="No results"
-                                        subtitle="No queued messages were found with the selected criteria."
-                                    />
+                                    {if has_filters.get() {
+                                        view! {
+                                            <EmptyState
+                                                title="No results"
+                                                subtitle="No queued messages match the selected filters. Clear one or more filters to widen the search."
+                                            />
+                                        }
+                                    } else {
+                                        view! {
+                                            <EmptyState
+                                                title="The queue is empty"
+                                                subtitle="There are no messages waiting to be delivered."
+                                                icon=Some(view! { <IconInbox attr:class="size-5"/> }.into_view())
+                                            />
+                                        }
+                                    }}
                                 }
                                     .into_view(),
                             )
@@ -315,10 +377,7 @@ pub fn QueueList() -> impl IntoView {
                         page_size=PAGE_SIZE
                         on_page_change=move |page: u32| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/queue/messages")
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &filter_url("page", Some(page.to_string())).finish(),
                                 Default::default(),
                             );
                         }
@@ -359,10 +418,8 @@ fn QueueItem(message: Message) -> impl IntoView {
         }
     }
 
-    let next_retry = message
-        .next_retry()
-        .map(|dt| HumanTime::from(dt).to_string());
-    let next_dsn = message.next_dsn().map(|dt| HumanTime::from(dt).to_string());
+    let next_retry = message.next_retry();
+    let next_dsn = message.next_dsn();
     let return_path = message.return_path().to_string();
     let recipients = if total_recipients > 0 {
         format!("{first_recipient} and {total_recipients} more",)
@@ -409,11 +466,15 @@ fn QueueItem(message: Message) -> impl IntoView {
             </td>
 
             <ListItem>
-                <span class="text-sm text-gray-500">{next_retry}</span>
+                <span class="text-sm text-gray-500">
+                    {next_retry.map(|value| view! { <RelativeTime value/> })}
+                </span>
             </ListItem>
 
             <ListItem>
-                <span class="text-sm text-gray-500">{next_dsn}</span>
+                <span class="text-sm text-gray-500">
+                    {next_dsn.map(|value| view! { <RelativeTime value/> })}
+                </span>
             </ListItem>
 
             <ListItem subclass="px-6 py-1.5">
@@ -428,6 +489,37 @@ fn QueueItem(message: Message) -> impl IntoView {
     }
 }
 
+pub fn query_value(query: Memo<ParamsMap>, key: &'static str) -> Memo<Option<String>> {
+    create_memo(move |_| {
+        query.with(|q| {
+            q.get(key)
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        })
+    })
+}
+
+// Date filters are kept in the URL as UTC RFC3339 timestamps, which is what
+// the server expects, while the inputs work in local time.
+pub fn from_local_input(value: &str) -> Option<String> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .ok()
+        .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+        .map(|dt| {
+            dt.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true)
+        })
+}
+
+pub fn to_local_input(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value).ok().map(|dt| {
+        dt.with_timezone(&Local)
+            .format("%Y-%m-%dT%H:%M")
+            .to_string()
+    })
+}
+
 impl IntoView for Status {
     fn into_view(self) -> View {
         match self {
@@ -6,8 +6,7 @@
 
 use std::{collections::HashSet, vec};
 
-use chrono::Utc;
-use chrono_humanize::HumanTime;
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
 use humansize::{format_size, DECIMAL};
 use leptos::*;
 use leptos_router::{use_navigate, use_params_map};
@@ -25,40 +24,40 @@ use crate::{
             ListTable, ListTextItem, Toolbar,
         },
         messages::{
-            alert::{use_alerts, Alert, Alerts},
-            modal::{use_modals, Modal},
+            alert::{use_alerts, Alert},
+            modal::{use_modals, Modal, ModalStack},
         },
         skeleton::Skeleton,
         Color,
     },
-    core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-    },
+    core::{api::use_api, datetime::use_datetime, http, oauth::use_is_viewer, url::UrlBuilder},
     pages::{
         maybe_plural,
-        queue::messages::{Message, Status},
+        queue::messages::{
+            list::{from_local_input, to_local_input},
+            Message, Recipient, Status,
+        },
         FormatDateTime, List,
     },
 };
 
 #[component]
 pub fn QueueManage() -> impl IntoView {
-    let auth = use_authorization();
+    let is_viewer = use_is_viewer();
+    let api = use_api();
     let alert = use_alerts();
     let modal = use_modals();
     let params = use_params_map();
+    let datetime = use_datetime();
     let blob_hash = RwSignal::new(String::new());
     let fetch_headers = RwSignal::new(true);
     let fetch_message = create_resource(
         move || params.get().get("id").cloned().unwrap_or_default(),
         move |id| {
-            let auth = auth.get_untracked();
             let id = id.clone();
 
             async move {
-                HttpRequest::get(("/api/queue/messages", &id))
-                    .with_authorization(&auth)
+                api.get(("/api/queue/messages", &id))
                     .send::<Message>()
                     .await
             }
@@ -68,14 +67,12 @@ pub fn QueueManage() -> impl IntoView {
     let fetch_contents = create_resource(
         move || (blob_hash.get(), fetch_headers.get()),
         move |(blob_hash, fetch_headers)| {
-            let auth = auth.get_untracked();
             let blob_hash = blob_hash.clone();
 
             async move {
                 if !blob_hash.is_empty() {
-                    HttpRequest::get(("/api/store/blobs", &blob_hash))
+                    api.get(("/api/store/blobs", &blob_hash))
                         .with_optional_parameter("limit", fetch_headers.then_some("10240"))
-                        .with_authorization(&auth)
                         .send_raw()
                         .await
                         .map(|bytes| {
@@ -115,12 +112,11 @@ pub fn QueueManage() -> impl IntoView {
     let cancel_action = create_action(move |items: &Vec<String>| {
         let id = params.get().get("id").cloned().unwrap_or_default();
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             for item in items {
-                match HttpRequest::delete(("/api/queue/messages", &id))
-                    .with_authorization(&auth)
+                match api
+                    .delete(("/api/queue/messages", &id))
                     .with_parameter("filter", item)
                     .send::<bool>()
                     .await
@@ -140,12 +136,11 @@ pub fn QueueManage() -> impl IntoView {
     let retry_action = create_action(move |items: &Vec<String>| {
         let id = params.get().get("id").cloned().unwrap_or_default();
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             for item in items {
-                match HttpRequest::patch(("/api/queue/messages", &id))
-                    .with_authorization(&auth)
+                match api
+                    .patch(("/api/queue/messages", &id))
                     .with_parameter("filter", item)
                     .send::<bool>()
                     .await
@@ -166,7 +161,6 @@ pub fn QueueManage() -> impl IntoView {
     provide_context(selected);
 
     view! {
-        <Alerts/>
         <Transition fallback=Skeleton>
 
             {move || match fetch_message.get() {
@@ -179,12 +173,10 @@ pub fn QueueManage() -> impl IntoView {
                     use_navigate()("/manage/queue/messages", Default::default());
                     Some(view! { <div></div> }.into_view())
                 }
-                Some(Err(err)) => {
-                    alert.set(Alert::from(err));
-                    Some(view! { <div></div> }.into_view())
-                }
+                Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
                 Some(Ok(message)) => {
                     blob_hash.set(message.blob_hash.clone());
+                    let message_id = message.id;
                     let return_path = message.return_path().to_string();
                     let num_recipients = message
                         .domains
@@ -200,13 +192,14 @@ pub fn QueueManage() -> impl IntoView {
                         .domains
                         .into_iter()
                         .flat_map(|d| {
+                            let route = d.route();
                             d.recipients
                                 .into_iter()
                                 .map(move |mut r| {
                                     if r.status == Status::Scheduled {
                                         r.status = d.status.clone();
                                     }
-                                    (r, d.next_retry)
+                                    (r, d.next_retry, route.clone())
                                 })
                         });
                     Some(
@@ -227,7 +220,7 @@ pub fn QueueManage() -> impl IntoView {
                                 </CardItem>
                                 <CardItem
                                     title="Sent"
-                                    contents=HumanTime::from(message.created).to_string()
+                                    contents=datetime.relative_text(Some(message.created))
                                     subcontents=message.created.format_date_time()
                                 >
 
@@ -258,9 +251,7 @@ pub fn QueueManage() -> impl IntoView {
                             <Card>
                                 <CardItem
                                     title="Next Retry"
-                                    contents=next_retry
-                                        .map(|dt| HumanTime::from(dt).to_string())
-                                        .unwrap_or("N/A".to_string())
+                                    contents=datetime.relative_text(next_retry)
                                     subcontents=next_retry
                                         .map(|dt| { dt.format_date_time() })
                                         .unwrap_or("N/A".to_string())
@@ -271,9 +262,7 @@ pub fn QueueManage() -> impl IntoView {
                                 </CardItem>
                                 <CardItem
                                     title="Next notification"
-                                    contents=next_dsn
-                                        .map(|dt| HumanTime::from(dt).to_string())
-                                        .unwrap_or("N/A".to_string())
+                                    contents=datetime.relative_text(next_dsn)
                                     subcontents=next_dsn
                                         .map(|dt| { dt.format_date_time() })
                                         .unwrap_or("N/A".to_string())
@@ -284,9 +273,7 @@ pub fn QueueManage() -> impl IntoView {
                                 </CardItem>
                                 <CardItem
                                     title="Last attempt"
-                                    contents=expires
-                                        .map(|dt| HumanTime::from(dt).to_string())
-                                        .unwrap_or("N/A".to_string())
+                                    contents=datetime.relative_text(expires)
                                     subcontents=expires
                                         .map(|dt| { dt.format_date_time() })
                                         .unwrap_or("N/A".to_string())
@@ -309,11 +296,24 @@ pub fn QueueManage() -> impl IntoView {
                                 </CardItem>
 
                             </Card>
+                            {next_retry
+                                .filter(|_| !is_viewer.get_untracked())
+                                .map(|next_retry| {
+                                    view! {
+                                        <RescheduleRetry
+                                            message_id
+                                            next_retry
+                                            on_done=move |_| fetch_message.refetch()
+                                        />
+                                    }
+                                })}
+
 
                             <div class="max-w-[85rem] px-4 py-8 sm:px-6 lg:px-8 lg:py-10 mx-auto">
                                 <ListTable title="Recipients" subtitle="Retry or cancel delivery">
                                     <Toolbar slot>
                                         <ToolbarButton
+                                            hide=is_viewer
                                             text=Signal::derive(move || {
                                                 let ns = selected.get().len();
                                                 if ns > 0 {
@@ -354,6 +354,7 @@ pub fn QueueManage() -> impl IntoView {
                                             <IconLaunch/>
                                         </ToolbarButton>
                                         <ToolbarButton
+                                            hide=is_viewer
                                             text=Signal::derive(move || {
                                                 let ns = selected.get().len();
                                                 if ns > 0 {
@@ -406,7 +407,9 @@ pub fn QueueManage() -> impl IntoView {
                                             "Recipient".to_string(),
                                             "Status".to_string(),
                                             "Server Response".to_string(),
+                                            "Route".to_string(),
                                             "Next/Last Retry".to_string(),
+                                            "".to_string(),
                                         ]
 
                                         select_all=Callback::new(move |_| {
@@ -423,83 +426,16 @@ pub fn QueueManage() -> impl IntoView {
                                         <For
                                             each=move || { recipients.clone() }
 
-                                            key=|(recipient, _)| recipient.address.clone()
-                                            children=move |(recipient, next_retry)| {
-                                                let item_id = recipient.address.clone();
-                                                let mut status_details = recipient
-                                                    .status
-                                                    .clone()
-                                                    .unwrap_message();
-                                                let mut status_response = String::new();
-                                                if let Some((code, message)) = status_details
-                                                    .split_once(", Message: ")
-                                                {
-                                                    status_response = code.to_string();
-                                                    status_details = message.to_string();
-                                                }
-                                                let next_retry = next_retry
-                                                    .map(|dt| {
-                                                        if !matches!(recipient.status, Status::Completed(_))
-                                                            || dt < Utc::now()
-                                                        {
-                                                            format!(
-                                                                "{} ({})",
-                                                                HumanTime::from(dt),
-                                                                dt.format_date_time(),
-                                                            )
-                                                        } else {
-                                                            "".to_string()
-                                                        }
-                                                    })
-                                                    .unwrap_or_default();
-                                                let display_status = match &recipient.status {
-                                                    Status::Completed(_) => {
-                                                        Status::Completed("Delivered".into())
-                                                    }
-                                                    Status::TemporaryFailure(_) => {
-                                                        Status::TemporaryFailure("Pending".into())
-                                                    }
-                                                    Status::PermanentFailure(_) => {
-                                                        Status::PermanentFailure("Failed".into())
-                                                    }
-                                                    Status::Scheduled => {
-                                                        Status::TemporaryFailure("Scheduled".into())
-                                                    }
-                                                };
+                                            key=|(recipient, _, _)| recipient.address.clone()
+                                            children=move |(recipient, next_retry, route)| {
                                                 view! {
-                                                    <tr>
-                                                        <ListItem>
-                                                            <label class="flex">
-                                                                <SelectItem item_id=item_id/>
-
-                                                                <span class="sr-only">Checkbox</span>
-                                                            </label>
-                                                        </ListItem>
-                                                        <ListItem subclass="ps-6 lg:ps-3 xl:ps-0 pe-6 py-3">
-                                                            <div class="flex items-center gap-x-3">
-                                                                <div class="grow">
-                                                                    <span class="block text-sm font-normal text-gray-800 dark:text-gray-200">
-                                                                        {recipient.address}
-                                                                    </span>
-                                                                </div>
-                                                            </div>
-                                                        </ListItem>
-
-                                                        <ListItem>{display_status}</ListItem>
-
-                                                        <ListItem class="h-px w-72 text-wrap">
-                                                            <span class="block text-sm font-semibold text-gray-800 dark:text-gray-200">
-                                                                {status_response}
-                                                            </span>
-                                                            <span class="block text-sm text-gray-500">
-                                                                {status_details}
-                                                            </span>
-
-                                                        </ListItem>
-
-                                                        <ListTextItem>{next_retry}</ListTextItem>
-
-                                                    </tr>
+                                                    <RecipientItem
+                                                        message_id
+                                                        recipient
+                                                        next_retry
+                                                        route
+                                                        modal
+                                                    />
                                                 }
                                             }
                                         />
@@ -548,22 +484,27 @@ pub fn QueueManage() -> impl IntoView {
                                             </h2>
 
                                         </div>
-                                        <Show when=move || fetch_headers.get()>
-                                            <div class="inline-flex gap-x-2">
-
-                                                <Button
-                                                    text="View Contents"
-                                                    color=Color::Gray
-                                                    on_click=move |_| {
-                                                        fetch_headers.set(false);
+                                        <div class="inline-flex gap-x-2">
+
+                                            <Button
+                                                text=Signal::derive(move || {
+                                                    if fetch_headers.get() {
+                                                        "View Contents".to_string()
+                                                    } else {
+                                                        "View Headers".to_string()
                                                     }
-                                                >
+                                                })
+
+                                                color=Color::Gray
+                                                on_click=move |_| {
+                                                    fetch_headers.update(|headers| *headers = !*headers);
+                                                }
+                                            >
 
-                                                    <IconEnvelope/>
-                                                </Button>
+                                                <IconEnvelope/>
+                                            </Button>
 
-                                            </div>
-                                        </Show>
+                                        </div>
                                     </div>
 
                                     <div
@@ -584,3 +525,283 @@ pub fn QueueManage() -> impl IntoView {
         </Transition>
     }
 }
+
+#[component]
+fn RecipientItem(
+    message_id: u64,
+    recipient: Recipient,
+    next_retry: Option<DateTime<Utc>>,
+    route: String,
+    modal: ModalStack,
+) -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let is_viewer = use_is_viewer();
+    let address = recipient.address;
+    let status = create_rw_signal(recipient.status);
+    let pending = create_rw_signal(false);
+    let is_delivered = move || status.with(|status| matches!(status, Status::Completed(_)));
+    let is_failed = move || status.with(|status| matches!(status, Status::PermanentFailure(_)));
+
+    let item_id = address.clone();
+    let address_ = address.clone();
+    let filter = address.clone();
+
+    // Applied right away and reverted if the server refuses the change
+    let apply = store_value(move |action: RecipientAction| {
+        let filter = filter.clone();
+        let prev_status = status.get_untracked();
+        status.set(match action {
+            RecipientAction::Retry => Status::Scheduled,
+            RecipientAction::Cancel => {
+                Status::PermanentFailure("Cancelled by an administrator".to_string())
+            }
+        });
+        pending.set(true);
+
+        spawn_local(async move {
+            let url = UrlBuilder::new("/api/queue/messages").with_subpath(message_id.to_string());
+            let request = match action {
+                RecipientAction::Retry => api.patch(url),
+                RecipientAction::Cancel => api.delete(url),
+            };
+            let result = request
+                .with_parameter("filter", filter)
+                .send::<bool>()
+                .await;
+            pending.try_set(false);
+            match result {
+                Ok(true) => {}
+                Ok(false) | Err(http::Error::NotFound) => {
+                    status.try_set(prev_status);
+                    alert.set(Alert::warning(
+                        "The recipient is no longer queued for delivery.",
+                    ));
+                }
+                Err(err) => {
+                    status.try_set(prev_status);
+                    alert.set(Alert::from(err));
+                }
+            }
+        });
+    });
+
+    view! {
+        <tr
+            class:opacity-60=move || is_delivered() || pending.get()
+            class=("bg-red-50", is_failed)
+            class=("dark:bg-red-900/10", is_failed)
+            aria-busy=move || pending.get().to_string()
+        >
+            <ListItem>
+                <label class="flex">
+                    <SelectItem item_id=item_id/>
+
+                    <span class="sr-only">Checkbox</span>
+                </label>
+            </ListItem>
+            <ListItem subclass="ps-6 lg:ps-3 xl:ps-0 pe-6 py-3">
+                <div class="flex items-center gap-x-3">
+                    <div class="grow">
+                        <span class="block text-sm font-normal text-gray-800 dark:text-gray-200">
+                            {address}
+                        </span>
+                    </div>
+                </div>
+            </ListItem>
+
+            <ListItem>
+                {move || match status.get() {
+                    Status::Completed(_) => Status::Completed("Delivered".into()),
+                    Status::TemporaryFailure(_) => Status::TemporaryFailure("Pending".into()),
+                    Status::PermanentFailure(_) => Status::PermanentFailure("Failed".into()),
+                    Status::Scheduled => Status::TemporaryFailure("Scheduled".into()),
+                }}
+            </ListItem>
+
+            <ListItem class="h-px w-72 text-wrap">
+                {move || {
+                    let mut status_details = status.get().unwrap_message();
+                    let mut status_response = String::new();
+                    if let Some((code, message)) = status_details.split_once(", Message: ") {
+                        status_response = code.to_string();
+                        status_details = message.to_string();
+                    }
+                    view! {
+                        <span class="block text-sm font-semibold text-gray-800 dark:text-gray-200">
+                            {status_response}
+                        </span>
+                        <span class="block text-sm text-gray-500">{status_details}</span>
+                    }
+                }}
+
+            </ListItem>
+
+            <ListTextItem>{route}</ListTextItem>
+
+            <ListTextItem>
+
+                {move || {
+                    next_retry
+                        .filter(|dt| !is_delivered() || *dt < Utc::now())
+                        .map(|dt| format!("{} ({})", dt.format_relative(), dt.format_date_time()))
+                        .unwrap_or_default()
+                }}
+
+            </ListTextItem>
+
+            <ListItem subclass="px-6 py-1.5">
+                <Show when=move || !is_delivered() && !is_failed() && !is_viewer.get()>
+                    <div class="flex items-center gap-x-3 text-sm font-medium whitespace-nowrap">
+                        <button
+                            type="button"
+                            class="text-blue-600 decoration-2 hover:underline disabled:opacity-50 dark:text-blue-500"
+                            disabled=pending
+                            on:click=move |_| apply.with_value(|apply| apply(RecipientAction::Retry))
+                        >
+                            Retry
+                        </button>
+                        <button
+                            type="button"
+                            class="text-red-600 decoration-2 hover:underline disabled:opacity-50 dark:text-red-500"
+                            disabled=pending
+                            on:click={
+                                let address = address_.clone();
+                                move |_| {
+                                    modal
+                                        .set(
+                                            Modal::with_title("Cancel delivery")
+                                                .with_message(
+                                                    format!(
+                                                        "Delivery to {address} will be cancelled and the recipient removed from the queue. This action cannot be undone.",
+                                                    ),
+                                                )
+                                                .with_button("Cancel delivery")
+                                                .with_dangerous_callback(move || {
+                                                    apply.with_value(|apply| apply(RecipientAction::Cancel));
+                                                }),
+                                        );
+                                }
+                            }
+                        >
+
+                            Cancel
+                        </button>
+                    </div>
+                </Show>
+            </ListItem>
+
+        </tr>
+    }
+}
+
+#[component]
+fn RescheduleRetry(
+    message_id: u64,
+    next_retry: DateTime<Utc>,
+    #[prop(into)] on_done: Callback<()>,
+) -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let at = create_rw_signal(
+        to_local_input(&next_retry.to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap_or_default(),
+    );
+    let error = create_rw_signal(None::<&'static str>);
+
+    let reschedule = create_action(move |at: &Option<String>| {
+        let at = at.clone();
+
+        async move {
+            match api
+                .patch(("/api/queue/messages", message_id.to_string()))
+                .with_optional_parameter("at", at.clone())
+                .send::<bool>()
+                .await
+            {
+                Ok(true) => {
+                    alert.set(Alert::success(if at.is_some() {
+                        "Next delivery attempt rescheduled."
+                    } else {
+                        "Successfully requested immediate delivery."
+                    }));
+                    on_done.call(());
+                }
+                Ok(false) | Err(http::Error::NotFound) => {
+                    alert.set(Alert::warning("The message is no longer in the queue."));
+                    on_done.call(());
+                }
+                Err(err) => {
+                    alert.set(Alert::from(err));
+                }
+            }
+        }
+    });
+    let schedule_in = move |hours: i64| {
+        let dt = Utc::now() + TimeDelta::hours(hours);
+        at.set(to_local_input(&dt.to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap_or_default());
+        error.set(None);
+    };
+    let apply = move || match from_local_input(&at.get_untracked())
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+    {
+        Some(dt) if dt > Utc::now() => {
+            reschedule.dispatch(Some(dt.to_utc().to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+        Some(_) => error.set(Some("Choose a time in the future.")),
+        None => error.set(Some("Enter a valid date and time.")),
+    };
+
+    view! {
+        <div class="max-w-[85rem] px-4 sm:px-6 lg:px-8 mx-auto">
+            <div class="bg-white rounded-xl shadow p-4 sm:p-7 dark:bg-slate-900">
+                <h2 class="text-lg font-semibold text-gray-800 dark:text-gray-200">
+                    Reschedule delivery
+                </h2>
+                <p class="text-sm text-gray-500">
+                    Override the retry backoff once the cause of the deferral is resolved.
+                </p>
+                <div class="mt-4 flex flex-wrap items-center gap-2">
+                    <Button
+                        text="Retry now"
+                        color=Color::Blue
+                        on_click=move |_| reschedule.dispatch(None)
+                        disabled=reschedule.pending()
+                    />
+                    <Button text="In 1 hour" color=Color::Gray on_click=move |_| schedule_in(1)/>
+                    <Button text="In 2 hours" color=Color::Gray on_click=move |_| schedule_in(2)/>
+                    <input
+                        type="datetime-local"
+                        class="py-2 px-3 block border-gray-200 rounded-lg text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-slate-900 dark:border-gray-700 dark:text-gray-400 dark:focus:ring-gray-600"
+                        class=("border-red-500", move || error.with(Option::is_some))
+                        prop:value=at
+                        on:input=move |ev| {
+                            at.set(event_target_value(&ev));
+                            error.set(None);
+                        }
+                    />
+
+                    <Button
+                        text="Apply"
+                        color=Color::Gray
+                        on_click=move |_| apply()
+                        disabled=reschedule.pending()
+                    />
+                </div>
+                {move || {
+                    error
+                        .get()
+                        .map(|error| {
+                            view! { <p class="mt-2 text-sm text-red-600">{error}</p> }
+                        })
+                }}
+
+            </div>
+        </div>
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RecipientAction {
+    Retry,
+    Cancel,
+}
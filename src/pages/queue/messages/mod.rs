@@ -37,6 +37,8 @@ pub struct Domain {
     pub next_notify: Option<DateTime<Utc>>,
     #[serde(deserialize_with = "deserialize_datetime")]
     pub expires: DateTime<Utc>,
+    #[serde(default)]
+    pub next_hop: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -129,6 +131,14 @@ impl Message {
     }
 }
 
+impl Domain {
+    pub fn route(&self) -> String {
+        self.next_hop
+            .clone()
+            .unwrap_or_else(|| format!("MX of {}", self.name))
+    }
+}
+
 impl Status {
     pub fn unwrap_message(self) -> String {
         match self {
@@ -18,6 +18,7 @@ use crate::{
         report::{ReportItem, ReportSection, ReportTextValue, ReportView},
         Color,
     },
+    core::i18n::t_signal,
     pages::{
         queue::reports::{DeliveryResult, FeedbackType, IdentityAlignment},
         FormatDateTime,
@@ -181,7 +182,7 @@ pub fn ArfReportDisplay(
             <div class="flex justify-end">
 
                 <Button
-                    text="Close"
+                    text=t_signal("button.close")
                     color=Color::Blue
                     on_click=move |_| {
                         use_navigate()(&back_url, Default::default());
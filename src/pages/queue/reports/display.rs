@@ -10,14 +10,8 @@ use leptos::*;
 use leptos_router::{use_navigate, use_params_map};
 
 use crate::{
-    components::{
-        messages::alert::{use_alerts, Alert, Alerts},
-        skeleton::Skeleton,
-    },
-    core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-    },
+    components::skeleton::Skeleton,
+    core::{api::use_api, http},
     pages::queue::reports::{dmarc::DmarcReportDisplay, tls::TlsReportDisplay, AggregateReport},
 };
 
@@ -25,13 +19,11 @@ pub(super) const PAGE_SIZE: u32 = 10;
 
 #[component]
 pub fn ReportDisplay() -> impl IntoView {
-    let auth = use_authorization();
-    let alert = use_alerts();
+    let api = use_api();
     let params = use_params_map();
     let fetch_report = create_resource(
         move || params.get().get("id").cloned().unwrap_or_default(),
         move |id| {
-            let auth = auth.get_untracked();
             let id = id.clone();
 
             async move {
@@ -63,8 +55,7 @@ pub fn ReportDisplay() -> impl IntoView {
                     }
                 }
 
-                HttpRequest::get(("/api/queue/reports", &id))
-                    .with_authorization(&auth)
+                api.get(("/api/queue/reports", &id))
                     .send::<AggregateReport>()
                     .await
             }
@@ -74,7 +65,6 @@ pub fn ReportDisplay() -> impl IntoView {
     provide_context(create_rw_signal::<HashSet<String>>(HashSet::new()));
 
     view! {
-        <Alerts/>
         <Transition fallback=Skeleton>
 
             {move || match fetch_report.get() {
@@ -87,10 +77,7 @@ pub fn ReportDisplay() -> impl IntoView {
                     use_navigate()("/manage/queue/reports", Default::default());
                     Some(view! { <div></div> }.into_view())
                 }
-                Some(Err(err)) => {
-                    alert.set(Alert::from(err));
-                    Some(view! { <div></div> }.into_view())
-                }
+                Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
                 Some(Ok(report)) => {
                     match report {
                         AggregateReport::Tls { report, rua, .. } => {
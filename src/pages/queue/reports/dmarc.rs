@@ -19,19 +19,23 @@ use crate::{
         card::{Card, CardItem},
         form::button::Button,
         icon::{
-            IconAlertTriangle, IconArrowRightCircle, IconCancel, IconCheckCircle, IconClock,
-            IconEnvelope, IconId,
+            IconAlertTriangle, IconArrowDownTray, IconArrowRightCircle, IconCancel,
+            IconCheckCircle, IconClock, IconEnvelope, IconId,
         },
         list::{
             header::ColumnList,
             pagination::{ItemPagination, Pagination},
             table::{Table, TableRow},
-            toolbar::SearchBox,
+            toolbar::{SearchBox, ToolbarButton},
             Footer, ListItem, ListTable, ListTextItem, Toolbar,
         },
         report::{ReportItem, ReportSection, ReportTextValue, ReportView},
         Color,
     },
+    core::{
+        download::{csv_row, download_file},
+        i18n::t_signal,
+    },
     pages::{
         queue::reports::{display::PAGE_SIZE, ActionDisposition, Report},
         FormatDateTime,
@@ -64,6 +68,15 @@ pub fn DmarcReportDisplay(
             .map(|dt| dt.format_time())
             .unwrap_or_else(|| "N/A".to_string());
     let domain = report.policy_published.domain.clone();
+    let csv_name = format!(
+        "dmarc-{}-{}.csv",
+        report.policy_published.domain, report.report_metadata.report_id
+    )
+    .replace(
+        |c: char| !c.is_ascii_alphanumeric() && !['.', '-', '_'].contains(&c),
+        "_",
+    );
+    let csv_records = report.record.clone();
 
     let mut total_pass = 0;
     let mut total_quarantine = 0;
@@ -251,6 +264,23 @@ pub fn DmarcReportDisplay(
                             }
                         />
 
+                        <ToolbarButton
+                            text="Download CSV"
+                            color=Color::Gray
+                            on_click=move |_| {
+                                if let Err(err) = download_file(
+                                    &csv_name,
+                                    "text/csv",
+                                    &Record::to_csv(&csv_records),
+                                ) {
+                                    log::warn!("Failed to download CSV: {err:?}");
+                                }
+                            }
+                        >
+
+                            <IconArrowDownTray/>
+                        </ToolbarButton>
+
                     </Toolbar>
                     <ColumnList headers=vec![
                         "From".to_string(),
@@ -322,7 +352,7 @@ pub fn DmarcReportDisplay(
             <div class="flex justify-end">
 
                 <Button
-                    text="Close"
+                    text=t_signal("button.close")
                     color=Color::Blue
                     on_click=move |_| {
                         use_navigate()(&back_url, Default::default());
@@ -490,7 +520,7 @@ pub fn DmarcReportDisplay(
                                 </div>
 
                                 <Button
-                                    text="Close"
+                                    text=t_signal("button.close")
                                     color=Color::Blue
                                     on_click=move |_| {
                                         display_record.set(false);
@@ -718,6 +748,32 @@ impl Record {
                 .map_or(false, |ip| ip.to_string().contains(filter))
     }
 
+    pub fn to_csv(records: &[Record]) -> String {
+        let mut csv = csv_row([
+            "Source IP",
+            "Count",
+            "Disposition",
+            "DKIM",
+            "SPF",
+            "Header From",
+        ]);
+        for record in records {
+            csv.push_str(&csv_row([
+                record
+                    .row
+                    .source_ip
+                    .map(|ip| ip.to_string())
+                    .unwrap_or_default(),
+                record.row.count.to_string(),
+                record.row.policy_evaluated.disposition.to_string(),
+                record.row.policy_evaluated.dkim.to_string(),
+                record.row.policy_evaluated.spf.to_string(),
+                record.identifiers.header_from.clone(),
+            ]));
+        }
+        csv
+    }
+
     pub fn id(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.hash(&mut hasher);
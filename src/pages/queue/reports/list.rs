@@ -11,25 +11,27 @@ use std::collections::HashSet;
 use crate::{
     components::{
         badge::Badge,
-        icon::{IconCancel, IconEnvelope, IconRefresh, IconShieldCheck},
+        icon::{IconCancel, IconDocumentChartBar, IconEnvelope, IconRefresh, IconShieldCheck},
         list::{
             header::ColumnList,
             pagination::Pagination,
             row::SelectItem,
-            toolbar::{SearchBox, ToolbarButton},
-            Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar, ZeroResults,
+            toolbar::{FilterSelect, SearchBox, ToolbarButton},
+            EmptyState, Filters, Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar,
         },
         messages::{
             alert::{use_alerts, Alert},
             modal::{use_modals, Modal},
         },
-        skeleton::Skeleton,
+        skeleton::{ListSkeleton, Skeleton},
         Color,
     },
     core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-        url::UrlBuilder,
+        api::{use_api, use_latest_api},
+        http,
+        i18n::t_signal,
+        list_state::use_list_state,
+        oauth::use_is_viewer,
     },
     pages::{
         maybe_plural,
@@ -38,33 +40,31 @@ use crate::{
     },
 };
 
+use chrono::{TimeDelta, Utc};
 use chrono_humanize::{Accuracy, HumanTime, Tense};
 
 const PAGE_SIZE: u32 = 10;
+const FETCH_ALL_PAGE_SIZE: u32 = 100;
+
+const OLDER_THAN: [(&str, &str); 4] = [
+    ("", "Select reports..."),
+    ("30", "Older than 30 days"),
+    ("60", "Older than 60 days"),
+    ("90", "Older than 90 days"),
+];
 
 #[component]
 pub fn ReportList() -> impl IntoView {
-    let query = use_query_map();
-    let page = create_memo(move |_| {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
-            .filter(|&page| page > 0)
-            .unwrap_or(1)
-    });
-    let filter = create_memo(move |_| {
-        query.with(|q| {
-            q.get("filter").and_then(|s| {
-                let s = s.trim();
-                if !s.is_empty() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            })
-        })
-    });
-
-    let auth = use_authorization();
+    let is_viewer = use_is_viewer();
+    let state = use_list_state();
+    let page = state.page;
+    let filter = state.value("filter");
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        state.url("/manage/queue/reports", key, value)
+    };
+
+    let api = use_api();
+    let latest = use_latest_api();
     let alert = use_alerts();
     let modal = use_modals();
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
@@ -72,48 +72,39 @@ pub fn ReportList() -> impl IntoView {
 
     let reports = create_resource(
         move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
-
-            async move {
-                HttpRequest::get("/api/queue/reports")
-                    .with_authorization(&auth)
-                    .with_parameter("page", page.to_string())
-                    .with_parameter("limit", PAGE_SIZE.to_string())
-                    .with_parameter("max-total", "100")
-                    .with_optional_parameter("domain", filter)
-                    .send::<List<String>>()
-                    .await
-                    .map(|list| {
-                        let mut response = List {
-                            items: Vec::with_capacity(list.items.len()),
-                            total: list.total,
-                        };
-                        for item in list.items {
-                            if let Some(item) = AggregateReportId::parse(item.clone()) {
-                                response.items.push(item);
-                            } else {
-                                log::warn!("Invalid report id: {item}");
-                            }
+        move |(page, filter)| async move {
+            latest
+                .get("/api/queue/reports")
+                .with_parameter("page", page.to_string())
+                .with_parameter("limit", PAGE_SIZE.to_string())
+                .with_parameter("max-total", "100")
+                .with_optional_parameter("domain", filter)
+                .send::<List<String>>()
+                .await
+                .map(|list| {
+                    let mut response = List {
+                        items: Vec::with_capacity(list.items.len()),
+                        total: list.total,
+                    };
+                    for item in list.items {
+                        if let Some(item) = AggregateReportId::parse(item.clone()) {
+                            response.items.push(item);
+                        } else {
+                            log::warn!("Invalid report id: {item}");
                         }
-                        response
-                    })
-            }
+                    }
+                    response
+                })
         },
     );
 
     let cancel_action = create_action(move |items: &HashSet<String>| {
         let items = items.clone();
-        let auth = auth.get();
 
         async move {
             let mut total_deleted = 0;
             for id in items {
-                match HttpRequest::delete(("/api/queue/reports", &id))
-                    .with_authorization(&auth)
-                    .send::<bool>()
-                    .await
-                {
+                match api.delete(("/api/queue/reports", &id)).send::<bool>().await {
                     Ok(true) => {
                         total_deleted += 1;
                     }
@@ -135,6 +126,58 @@ pub fn ReportList() -> impl IntoView {
         }
     });
 
+    // Selects the reports created before the cutoff across all pages
+    let older_than = create_rw_signal(String::new());
+    let select_older_action = create_action(move |days: &i64| {
+        let cutoff = Utc::now() - TimeDelta::days(*days);
+        let days = *days;
+
+        async move {
+            let mut aged = HashSet::new();
+            let mut page = 1;
+            loop {
+                let ids = match api
+                    .get("/api/queue/reports")
+                    .with_parameter("page", page.to_string())
+                    .with_parameter("limit", FETCH_ALL_PAGE_SIZE.to_string())
+                    .with_optional_parameter("domain", filter.get_untracked())
+                    .send::<List<String>>()
+                    .await
+                {
+                    Ok(ids) => ids,
+                    Err(err) => {
+                        alert.set(Alert::from(err));
+                        return;
+                    }
+                };
+                let fetched = ids.items.len();
+                aged.extend(
+                    ids.items
+                        .into_iter()
+                        .filter_map(AggregateReportId::parse)
+                        .filter(|report| report.created < cutoff)
+                        .map(|report| report.id),
+                );
+                if fetched < FETCH_ALL_PAGE_SIZE as usize {
+                    break;
+                }
+                page += 1;
+            }
+
+            if aged.is_empty() {
+                alert.set(Alert::warning(format!(
+                    "No reports are older than {days} days."
+                )));
+            } else {
+                alert.set(Alert::success(format!(
+                    "Selected {} older than {days} days.",
+                    maybe_plural(aged.len(), "report", "reports")
+                )));
+            }
+            selected.set(aged);
+        }
+    });
+
     let total_results = create_rw_signal(None::<u32>);
 
     view! {
@@ -146,18 +189,17 @@ pub fn ReportList() -> impl IntoView {
                 <Toolbar slot>
                     <SearchBox
                         value=filter
+                        latest
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/queue/reports")
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &filter_url("filter", Some(value)).finish(),
                                 Default::default(),
                             );
                         }
                     />
 
                     <ToolbarButton
-                        text="Refresh"
+                        text=t_signal("button.refresh")
 
                         color=Color::Gray
                         on_click=Callback::new(move |_| {
@@ -169,6 +211,7 @@ pub fn ReportList() -> impl IntoView {
                     </ToolbarButton>
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Cancel ({ns})") } else { "Cancel".to_string() }
@@ -204,7 +247,22 @@ pub fn ReportList() -> impl IntoView {
 
                 </Toolbar>
 
-                <Transition fallback=Skeleton>
+                <Filters slot>
+                    <FilterSelect
+                        label="Bulk selection"
+                        value=older_than
+                        options=OLDER_THAN.to_vec()
+                        on_change=move |value: String| {
+                            if let Ok(days) = value.parse::<i64>() {
+                                select_older_action.dispatch(days);
+                            }
+                            older_than.set(value);
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=|| view! { <ListSkeleton/> }>
                     {move || match reports.get() {
                         None => None,
                         Some(Err(http::Error::Unauthorized)) => {
@@ -256,10 +314,22 @@ pub fn ReportList() -> impl IntoView {
                             total_results.set(Some(0));
                             Some(
                                 view! {
-                                    <ZeroResults
-                                        title="No results"
-                                        subtitle="No reports were found with the selected criteria."
-                                    />
+                                    {if filter.get().is_some() {
+                                        view! {
+                                            <EmptyState
+                                                title="No results"
+                                                subtitle="No reports were found with the selected criteria."
+                                            />
+                                        }
+                                    } else {
+                                        view! {
+                                            <EmptyState
+                                                title="No reports yet"
+                                                subtitle="There are no aggregate reports scheduled for delivery."
+                                                icon=Some(view! { <IconDocumentChartBar attr:class="size-5"/> }.into_view())
+                                            />
+                                        }
+                                    }}
                                 }
                                     .into_view(),
                             )
@@ -276,10 +346,7 @@ pub fn ReportList() -> impl IntoView {
                         page_size=PAGE_SIZE
                         on_page_change=move |page: u32| {
                             use_navigate()(
-                                &UrlBuilder::new("/manage/queue/reports")
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &filter_url("page", Some(page.to_string())).finish(),
                                 Default::default(),
                             );
                         }
@@ -344,7 +411,7 @@ fn ReportItem(report: AggregateReportId) -> impl IntoView {
             </td>
 
             <ListTextItem>
-                {format!("{} ({})", HumanTime::from(report.due), report.due.format_date_time())}
+                {format!("{} ({})", report.due.format_relative(), report.due.format_date_time())}
 
             </ListTextItem>
 
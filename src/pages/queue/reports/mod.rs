@@ -548,6 +548,16 @@ impl Display for ActionDisposition {
     }
 }
 
+impl Display for DmarcResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DmarcResult::Pass => "Pass",
+            DmarcResult::Fail => "Fail",
+            DmarcResult::Unspecified => "Unspecified",
+        })
+    }
+}
+
 impl Display for PolicyOverride {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
@@ -25,13 +25,15 @@ use crate::{
         report::{ReportItem, ReportSection, ReportTextValue, ReportView},
         Color,
     },
+    core::i18n::t_signal,
     pages::{
+        maybe_plural,
         queue::reports::{display::PAGE_SIZE, Policy},
         FormatDateTime,
     },
 };
 
-use super::{FailureDetails, PolicyType, TlsReport};
+use super::{FailureDetails, PolicyType, ResultType, TlsReport};
 
 #[derive(Clone, Debug, PartialEq, Copy)]
 enum CurrentView {
@@ -233,7 +235,7 @@ pub fn TlsReportDisplay(
             <div class="flex justify-end">
 
                 <Button
-                    text="Close"
+                    text=t_signal("button.close")
                     color=Color::Blue
                     on_click=move |_| {
                         use_navigate()(&back_url, Default::default());
@@ -247,6 +249,7 @@ pub fn TlsReportDisplay(
             selected_policy
                 .get()
                 .map(|policy| {
+                    let reasons = policy.failures_by_type();
                     view! {
                         <ReportView hide=Signal::derive(move || {
                             current_view.get() != CurrentView::Policy
@@ -272,6 +275,34 @@ pub fn TlsReportDisplay(
                                         .to_string()/>
                                 </ReportItem>
                             </ReportSection>
+                            {(!reasons.is_empty())
+                                .then(|| {
+                                    view! {
+                                        <ReportSection title="Failure Reasons">
+                                            {reasons
+                                                .into_iter()
+                                                .map(|(result_type, sessions)| {
+                                                    view! {
+                                                        <ReportItem label=result_type.to_string()>
+                                                            <div class="mt-2.5 text-sm">
+                                                                <p class="font-semibold text-gray-800 dark:text-gray-200">
+                                                                    {maybe_plural(
+                                                                        sessions as usize,
+                                                                        "failed session",
+                                                                        "failed sessions",
+                                                                    )}
+                                                                </p>
+                                                                <p class="text-gray-500">
+                                                                    {result_type.description()}
+                                                                </p>
+                                                            </div>
+                                                        </ReportItem>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </ReportSection>
+                                    }
+                                })}
                             {if !policy.failure_details.is_empty() {
                                 let total_failures = policy.failure_details.len() as u32;
                                 let fetch_failures = create_memo(move |_| {
@@ -391,7 +422,7 @@ pub fn TlsReportDisplay(
 
                             <div class="mt-5 flex justify-end gap-x-2">
                                 <Button
-                                    text="Close"
+                                    text=t_signal("button.close")
                                     color=Color::Blue
                                     on_click=move |_| {
                                         current_view.set(CurrentView::Main);
@@ -417,6 +448,12 @@ pub fn TlsReportDisplay(
                                 <ReportItem label="Type">
                                     <ReportTextValue value=failure.result_type.to_string()/>
                                 </ReportItem>
+                                <ReportItem label="Reason">
+                                    <ReportTextValue value=failure
+                                        .result_type
+                                        .description()
+                                        .to_string()/>
+                                </ReportItem>
                                 <ReportItem
                                     label="Sending MTA IP"
                                     hide=failure.sending_mta_ip.is_none()
@@ -475,7 +512,7 @@ pub fn TlsReportDisplay(
                             </ReportSection>
                             <div class="mt-5 flex justify-end gap-x-2">
                                 <Button
-                                    text="Close"
+                                    text=t_signal("button.close")
                                     color=Color::Blue
                                     on_click=move |_| {
                                         current_view.set(CurrentView::Policy);
@@ -522,6 +559,21 @@ impl Policy {
         self.hash(&mut hasher);
         hasher.finish()
     }
+
+    pub fn failures_by_type(&self) -> Vec<(ResultType, u32)> {
+        let mut failures: Vec<(ResultType, u32)> = Vec::new();
+        for failure in &self.failure_details {
+            match failures
+                .iter_mut()
+                .find(|(result_type, _)| *result_type == failure.result_type)
+            {
+                Some((_, sessions)) => *sessions += failure.failed_session_count,
+                None => failures.push((failure.result_type, failure.failed_session_count)),
+            }
+        }
+        failures.sort_by_key(|(_, sessions)| std::cmp::Reverse(*sessions));
+        failures
+    }
 }
 
 impl FailureDetails {
@@ -573,3 +625,44 @@ impl IntoView for PolicyType {
         }
     }
 }
+
+impl ResultType {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ResultType::StartTlsNotSupported => {
+                "The receiving MX did not offer STARTTLS, so the session could not be encrypted."
+            }
+            ResultType::CertificateHostMismatch => {
+                "The certificate presented by the receiving MX does not match its hostname."
+            }
+            ResultType::CertificateExpired => {
+                "The certificate presented by the receiving MX has expired."
+            }
+            ResultType::CertificateNotTrusted => {
+                "The certificate presented by the receiving MX is not signed by a trusted authority."
+            }
+            ResultType::ValidationFailure => {
+                "The TLS session could not be established for a reason not covered by other types."
+            }
+            ResultType::TlsaInvalid => {
+                "The TLSA records published for the receiving MX are invalid."
+            }
+            ResultType::DnssecInvalid => {
+                "The DNSSEC signatures of the TLSA records could not be validated."
+            }
+            ResultType::DaneRequired => {
+                "DANE was required by the sender but no usable TLSA records were found."
+            }
+            ResultType::StsPolicyFetchError => {
+                "The MTA-STS policy could not be retrieved from the policy domain."
+            }
+            ResultType::StsPolicyInvalid => {
+                "The MTA-STS policy was retrieved but could not be parsed."
+            }
+            ResultType::StsWebpkiInvalid => {
+                "The certificate of the MTA-STS policy host could not be validated."
+            }
+            ResultType::Other => "The reporting organization did not provide a known result type.",
+        }
+    }
+}
@@ -15,10 +15,7 @@ use crate::{
         messages::alert::{use_alerts, Alert, Alerts},
         skeleton::Skeleton,
     },
-    core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-    },
+    core::{api::use_api, http},
     pages::{
         queue::reports::{
             arf::ArfReportDisplay, dmarc::DmarcReportDisplay, tls::TlsReportDisplay, Feedback,
@@ -28,7 +25,7 @@ use crate::{
     },
 };
 
-use super::{parse_report_date, ReportType};
+use super::{parse_report_date, ReportType, ReviewedReports};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
@@ -40,8 +37,7 @@ enum ReportWrapper {
 
 #[component]
 pub fn IncomingReportDisplay() -> impl IntoView {
-    let auth = use_authorization();
-    let alert = use_alerts();
+    let api = use_api();
     let params = use_params_map();
     let report_type = create_memo(move |_| {
         match params
@@ -59,24 +55,23 @@ pub fn IncomingReportDisplay() -> impl IntoView {
     let fetch_report = create_resource(
         move || params.get().get("id").cloned().unwrap_or_default(),
         move |id| {
-            let auth = auth.get_untracked();
             let id = id.clone();
             let report_type = report_type.get();
 
             async move {
                 match report_type {
-                    ReportType::Dmarc => HttpRequest::get(format!("/api/reports/dmarc/{id}"))
-                        .with_authorization(&auth)
+                    ReportType::Dmarc => api
+                        .get(format!("/api/reports/dmarc/{id}"))
                         .send::<IncomingReport<Report>>()
                         .await
                         .map(ReportWrapper::Dmarc),
-                    ReportType::Tls => HttpRequest::get(format!("/api/reports/tls/{id}"))
-                        .with_authorization(&auth)
+                    ReportType::Tls => api
+                        .get(format!("/api/reports/tls/{id}"))
                         .send::<IncomingReport<TlsReport>>()
                         .await
                         .map(ReportWrapper::Tls),
-                    ReportType::Arf => HttpRequest::get(format!("/api/reports/arf/{id}"))
-                        .with_authorization(&auth)
+                    ReportType::Arf => api
+                        .get(format!("/api/reports/arf/{id}"))
                         .send::<IncomingReport<Feedback>>()
                         .await
                         .map(ReportWrapper::Arf),
@@ -88,6 +83,30 @@ pub fn IncomingReportDisplay() -> impl IntoView {
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
     provide_context(selected);
 
+    // Opening a report marks it as reviewed, unless it already was
+    let alert = use_alerts();
+    let reviewed = ReviewedReports::load();
+    create_effect(move |_| {
+        if !fetch_report.with(|report| matches!(report, Some(Ok(_)))) {
+            return;
+        }
+        let id = params.with_untracked(|params| params.get("id").cloned().unwrap_or_default());
+        if !untrack(|| reviewed.is_reviewed(&id)) {
+            reviewed.set_reviewed(&id, true);
+            alert.set(
+                Alert::success("Report marked as reviewed.").with_details(view! {
+                    <button
+                        type="button"
+                        class="font-medium underline hover:no-underline"
+                        on:click=move |_| reviewed.set_reviewed(&id, false)
+                    >
+                        Undo
+                    </button>
+                }),
+            );
+        }
+    });
+
     view! {
         <Alerts/>
         <Transition fallback=Skeleton>
@@ -105,10 +124,7 @@ pub fn IncomingReportDisplay() -> impl IntoView {
                     );
                     Some(view! { <div></div> }.into_view())
                 }
-                Some(Err(err)) => {
-                    alert.set(Alert::from(err));
-                    Some(view! { <div></div> }.into_view())
-                }
+                Some(Err(err)) => Some(Err::<View, _>(err).into_view()),
                 Some(Ok(report)) => {
                     match report {
                         ReportWrapper::Tls(report) => {
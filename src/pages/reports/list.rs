@@ -6,63 +6,67 @@
 
 use leptos::*;
 use leptos_router::*;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use super::ReportType;
+use super::{tls::TlsReportSummary, ReportType, ReviewedReports};
 use crate::{
     components::{
-        icon::{IconCancel, IconRefresh},
+        chart::{format_compact, Chart, ChartKind, ChartSeries},
+        icon::{IconCancel, IconDocumentChartBar, IconRefresh},
         list::{
             header::ColumnList,
             pagination::Pagination,
             row::SelectItem,
-            toolbar::{SearchBox, ToolbarButton},
-            Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar, ZeroResults,
+            toolbar::{FilterInput, FilterSelect, SearchBox, ToolbarButton},
+            EmptyState, Filters, Footer, ListItem, ListSection, ListTable, ListTextItem, Toolbar,
         },
         messages::{
             alert::{use_alerts, Alert},
             modal::{use_modals, Modal},
         },
-        skeleton::Skeleton,
+        skeleton::{ListSkeleton, Skeleton},
         Color,
     },
-    core::{
-        http::{self, HttpRequest},
-        oauth::use_authorization,
-        url::UrlBuilder,
-    },
+    core::{api::use_api, http, i18n::t_signal, list_state::use_list_state, oauth::use_is_viewer},
     pages::{
         maybe_plural,
-        queue::reports::{Feedback, Report, TlsReport},
+        queue::{
+            messages::list::{from_local_input, to_local_input},
+            reports::{Feedback, Report, TlsReport},
+        },
         reports::{IncomingReport, IncomingReportSummary},
         FormatDateTime, List,
     },
 };
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use chrono_humanize::{Accuracy, HumanTime, Tense};
 
 const PAGE_SIZE: u32 = 10;
+const MAX_TOTAL: u32 = 100;
+
+const DMARC_RESULTS: [(&str, &str); 3] = [
+    ("", "Any result"),
+    ("pass", "All passed"),
+    ("fail", "Any failure"),
+];
 
 #[component]
 pub fn IncomingReportList() -> impl IntoView {
-    let query = use_query_map();
-    let page = create_memo(move |_| {
-        query
-            .with(|q| q.get("page").and_then(|page| page.parse::<u32>().ok()))
-            .filter(|&page| page > 0)
-            .unwrap_or(1)
-    });
-    let filter = create_memo(move |_| {
-        query.with(|q| {
-            q.get("filter").and_then(|s| {
-                let s = s.trim();
-                if !s.is_empty() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            })
-        })
+    let is_viewer = use_is_viewer();
+    let state = use_list_state();
+    let page = state.page;
+    let filter = state.value("filter");
+    let organization = state.value("org");
+    let after = state.value("after");
+    let before = state.value("before");
+    let result = state.value("result");
+    let result = create_memo(move |_| {
+        result
+            .get()
+            .filter(|result| DMARC_RESULTS.iter().any(|(id, _)| id == result))
+            .unwrap_or_default()
     });
+    let unreviewed = state.flag("unreviewed");
     let params = use_params_map();
     let report_type = create_memo(move |_| {
         match params
@@ -78,25 +82,56 @@ pub fn IncomingReportList() -> impl IntoView {
         }
     });
 
-    let auth = use_authorization();
+    let api = use_api();
     let alert = use_alerts();
     let modal = use_modals();
     let selected = create_rw_signal::<HashSet<String>>(HashSet::new());
     provide_context(selected);
+    let reviewed = ReviewedReports::load();
+    provide_context(reviewed);
+
+    let filter_url = move |key: &'static str, value: Option<String>| {
+        state.url(
+            format!("/manage/reports/{}", report_type.get().as_str()),
+            key,
+            value,
+        )
+    };
+    let has_filters = create_memo(move |_| {
+        report_type.get() == ReportType::Dmarc
+            && (organization.get().is_some()
+                || after.get().is_some()
+                || before.get().is_some()
+                || !result.get().is_empty())
+    });
 
     let reports = create_resource(
-        move || (page.get(), filter.get()),
-        move |(page, filter)| {
-            let auth = auth.get_untracked();
+        move || {
+            let dmarc_filters = (report_type.get() == ReportType::Dmarc).then(|| {
+                (
+                    organization.get(),
+                    after.get(),
+                    before.get(),
+                    Some(result.get()).filter(|result| !result.is_empty()),
+                )
+            });
+            (page.get(), filter.get(), dmarc_filters)
+        },
+        move |(page, filter, dmarc_filters)| {
             let report_type = report_type.get();
+            let (organization, after, before, result) = dmarc_filters.unwrap_or_default();
 
             async move {
-                let ids = HttpRequest::get(format!("/api/reports/{}", report_type.as_str()))
-                    .with_authorization(&auth)
+                let ids = api
+                    .get(format!("/api/reports/{}", report_type.as_str()))
                     .with_parameter("page", page.to_string())
                     .with_parameter("limit", PAGE_SIZE.to_string())
-                    .with_parameter("max-total", "100")
+                    .with_parameter("max-total", MAX_TOTAL.to_string())
                     .with_optional_parameter("filter", filter)
+                    .with_optional_parameter("org", organization)
+                    .with_optional_parameter("after", after)
+                    .with_optional_parameter("before", before)
+                    .with_optional_parameter("result", result)
                     .send::<List<String>>()
                     .await?;
                 let mut result = List {
@@ -106,18 +141,18 @@ pub fn IncomingReportList() -> impl IntoView {
 
                 for id in ids.items {
                     let report = match report_type {
-                        ReportType::Dmarc => HttpRequest::get(format!("/api/reports/dmarc/{id}"))
-                            .with_authorization(&auth)
+                        ReportType::Dmarc => api
+                            .get(format!("/api/reports/dmarc/{id}"))
                             .try_send::<IncomingReport<Report>>()
                             .await?
                             .map(|report| IncomingReportSummary::dmarc(id, report)),
-                        ReportType::Tls => HttpRequest::get(format!("/api/reports/tls/{id}"))
-                            .with_authorization(&auth)
+                        ReportType::Tls => api
+                            .get(format!("/api/reports/tls/{id}"))
                             .try_send::<IncomingReport<TlsReport>>()
                             .await?
                             .map(|report| IncomingReportSummary::tls(id, report)),
-                        ReportType::Arf => HttpRequest::get(format!("/api/reports/arf/{id}"))
-                            .with_authorization(&auth)
+                        ReportType::Arf => api
+                            .get(format!("/api/reports/arf/{id}"))
                             .try_send::<IncomingReport<Feedback>>()
                             .await?
                             .map(|report| IncomingReportSummary::arf(id, report)),
@@ -134,18 +169,18 @@ pub fn IncomingReportList() -> impl IntoView {
 
     let delete_action = create_action(move |items: &HashSet<String>| {
         let items = items.clone();
-        let auth = auth.get();
         let report_class = report_type.get().as_str();
 
         async move {
             let mut total_deleted = 0;
             for id in items {
-                match HttpRequest::delete(format!("/api/reports/{report_class}/{id}"))
-                    .with_authorization(&auth)
+                match api
+                    .delete(format!("/api/reports/{report_class}/{id}"))
                     .send::<bool>()
                     .await
                 {
                     Ok(true) => {
+                        reviewed.set_reviewed(&id, false);
                         total_deleted += 1;
                     }
                     Ok(false) | Err(http::Error::NotFound) => {}
@@ -177,6 +212,17 @@ pub fn IncomingReportList() -> impl IntoView {
         .to_string()
     });
     let subtitle = create_memo(move |_| {
+        if has_filters.get() {
+            if let Some(total) = total_results.get() {
+                let count = maybe_plural(total as usize, "report", "reports");
+                return if total >= MAX_TOTAL {
+                    format!("More than {count} match the selected filters")
+                } else {
+                    format!("{count} match the selected filters")
+                };
+            }
+        }
+
         match report_type.get() {
             ReportType::Dmarc => "View received DMARC aggregate reports",
             ReportType::Tls => "View received TLS aggregate reports",
@@ -185,26 +231,80 @@ pub fn IncomingReportList() -> impl IntoView {
         .to_string()
     });
 
+    let trend = Signal::derive(move || {
+        if report_type.get() == ReportType::Dmarc {
+            reports
+                .get()
+                .and_then(|reports| reports.ok())
+                .map(|reports| DmarcTrend::from_reports(&reports.items))
+                .unwrap_or_default()
+        } else {
+            vec![]
+        }
+    });
+
     view! {
         <ListSection>
+            <Show when=move || !trend.get().is_empty()>
+                <DmarcTrendChart trend=trend/>
+            </Show>
+            <Show when=move || report_type.get() == ReportType::Tls>
+                <TlsReportSummary/>
+            </Show>
             <ListTable title=title subtitle=subtitle>
                 <Toolbar slot>
                     <SearchBox
                         value=filter
                         on_search=move |value| {
                             use_navigate()(
-                                &UrlBuilder::new(
-                                        format!("/manage/reports/{}", report_type.get().as_str()),
-                                    )
-                                    .with_parameter("filter", value)
-                                    .finish(),
+                                &filter_url("filter", Some(value)).finish(),
                                 Default::default(),
                             );
                         }
                     />
 
+                    <Show when=move || report_type.get() == ReportType::Dmarc>
+                        <ToolbarButton
+                            text=Signal::derive(move || {
+                                if result.get() == "fail" {
+                                    "All results".to_string()
+                                } else {
+                                    "Failures only".to_string()
+                                }
+                            })
+
+                            color=Color::Gray
+                            on_click=Callback::new(move |_| {
+                                let value = (result.get() != "fail").then(|| "fail".to_string());
+                                use_navigate()(
+                                    &filter_url("result", value).finish(),
+                                    Default::default(),
+                                );
+                            })
+                        />
+                    </Show>
+
+                    <ToolbarButton
+                        text=Signal::derive(move || {
+                            if unreviewed.get() {
+                                "All reports".to_string()
+                            } else {
+                                "Unreviewed only".to_string()
+                            }
+                        })
+
+                        color=Color::Gray
+                        on_click=Callback::new(move |_| {
+                            let value = (!unreviewed.get()).then(|| "true".to_string());
+                            use_navigate()(
+                                &filter_url("unreviewed", value).finish(),
+                                Default::default(),
+                            );
+                        })
+                    />
+
                     <ToolbarButton
-                        text="Reload"
+                        text=t_signal("button.reload")
 
                         color=Color::Gray
                         on_click=Callback::new(move |_| {
@@ -216,6 +316,7 @@ pub fn IncomingReportList() -> impl IntoView {
                     </ToolbarButton>
 
                     <ToolbarButton
+                        hide=is_viewer
                         text=Signal::derive(move || {
                             let ns = selected.get().len();
                             if ns > 0 { format!("Delete ({ns})") } else { "Delete".to_string() }
@@ -251,7 +352,60 @@ pub fn IncomingReportList() -> impl IntoView {
 
                 </Toolbar>
 
-                <Transition fallback=Skeleton>
+                <Filters slot hide=Signal::derive(move || report_type.get() != ReportType::Dmarc)>
+                    <FilterInput
+                        label="Reporting organization"
+                        placeholder="google.com"
+                        value=organization
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("org", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Reports from"
+                        input_type="date"
+                        value=Signal::derive(move || after.get().and_then(|dt| to_local_date(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("after", from_local_input(&format!("{value}T00:00")))
+                                    .finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterInput
+                        label="Reports until"
+                        input_type="date"
+                        value=Signal::derive(move || before.get().and_then(|dt| to_local_date(&dt)))
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("before", from_local_input(&format!("{value}T23:59")))
+                                    .finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                    <FilterSelect
+                        label="Result"
+                        value=result
+                        options=DMARC_RESULTS.to_vec()
+                        on_change=move |value: String| {
+                            use_navigate()(
+                                &filter_url("result", Some(value)).finish(),
+                                Default::default(),
+                            );
+                        }
+                    />
+
+                </Filters>
+
+                <Transition fallback=|| view! { <ListSkeleton/> }>
                     {move || match reports.get() {
                         None => None,
                         Some(Err(http::Error::Unauthorized)) => {
@@ -314,7 +468,17 @@ pub fn IncomingReportList() -> impl IntoView {
                                     >
 
                                         <For
-                                            each=move || reports.items.clone()
+                                            each=move || {
+                                                reports
+                                                    .items
+                                                    .iter()
+                                                    .filter(|report| {
+                                                        !unreviewed.get() || !reviewed.is_reviewed(report.id())
+                                                    })
+                                                    .cloned()
+                                                    .collect::<Vec<_>>()
+                                            }
+
                                             key=|report| report.id().to_string()
                                             let:report
                                         >
@@ -330,10 +494,22 @@ pub fn IncomingReportList() -> impl IntoView {
                             total_results.set(Some(0));
                             Some(
                                 view! {
-                                    <ZeroResults
-                                        title="No results"
-                                        subtitle="No reports were found with the selected criteria."
-                                    />
+                                    {if filter.get().is_some() || has_filters.get() || unreviewed.get() {
+                                        view! {
+                                            <EmptyState
+                                                title="No results"
+                                                subtitle="No reports were found with the selected criteria."
+                                            />
+                                        }
+                                    } else {
+                                        view! {
+                                            <EmptyState
+                                                title="No reports yet"
+                                                subtitle="No reports of this type have been received."
+                                                icon=Some(view! { <IconDocumentChartBar attr:class="size-5"/> }.into_view())
+                                            />
+                                        }
+                                    }}
                                 }
                                     .into_view(),
                             )
@@ -350,12 +526,7 @@ pub fn IncomingReportList() -> impl IntoView {
                         page_size=PAGE_SIZE
                         on_page_change=move |page: u32| {
                             use_navigate()(
-                                &UrlBuilder::new(
-                                        format!("/manage/reports/{}", report_type.get().as_str()),
-                                    )
-                                    .with_parameter("page", page.to_string())
-                                    .with_optional_parameter("filter", filter.get())
-                                    .finish(),
+                                &filter_url("page", Some(page.to_string())).finish(),
                                 Default::default(),
                             );
                         }
@@ -367,6 +538,144 @@ pub fn IncomingReportList() -> impl IntoView {
     }
 }
 
+fn to_local_date(value: &str) -> Option<String> {
+    to_local_input(value).and_then(|dt| dt.get(..10).map(|date| date.to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DmarcTrend {
+    day: DateTime<Utc>,
+    passed: f64,
+    failed: f64,
+}
+
+impl DmarcTrend {
+    fn from_reports(reports: &[IncomingReportSummary]) -> Vec<Self> {
+        let mut days: BTreeMap<NaiveDate, (u32, u32)> = BTreeMap::new();
+        for report in reports {
+            if let IncomingReportSummary::Dmarc {
+                range_from,
+                messages_passed,
+                messages_failed,
+                ..
+            } = report
+            {
+                let day = days
+                    .entry(range_from.with_timezone(&Local).date_naive())
+                    .or_default();
+                day.0 += messages_passed;
+                day.1 += messages_failed;
+            }
+        }
+
+        days.into_iter()
+            .filter_map(|(day, (passed, failed))| {
+                Local
+                    .from_local_datetime(&day.and_hms_opt(0, 0, 0)?)
+                    .earliest()
+                    .map(|day| DmarcTrend {
+                        day: day.with_timezone(&Utc),
+                        passed: passed as f64,
+                        failed: failed as f64,
+                    })
+            })
+            .collect()
+    }
+
+    fn as_percentage(&self) -> Self {
+        let total = self.passed + self.failed;
+        if total > 0.0 {
+            DmarcTrend {
+                day: self.day,
+                passed: self.passed * 100.0 / total,
+                failed: self.failed * 100.0 / total,
+            }
+        } else {
+            self.clone()
+        }
+    }
+}
+
+#[component]
+fn DmarcTrendChart(#[prop(into)] trend: Signal<Vec<DmarcTrend>>) -> impl IntoView {
+    let as_percentage = create_rw_signal(false);
+    let series = Signal::derive(move || {
+        let percentage = as_percentage.get();
+        trend.with(|trend| {
+            let trend = trend
+                .iter()
+                .map(|trend| {
+                    if percentage {
+                        trend.as_percentage()
+                    } else {
+                        trend.clone()
+                    }
+                })
+                .collect::<Vec<_>>();
+            vec![
+                ChartSeries::new(
+                    "Pass",
+                    trend
+                        .iter()
+                        .map(|trend| (trend.day, trend.passed))
+                        .collect(),
+                )
+                .with_color(Color::Green),
+                ChartSeries::new(
+                    "Fail",
+                    trend
+                        .iter()
+                        .map(|trend| (trend.day, trend.failed))
+                        .collect(),
+                )
+                .with_color(Color::Red),
+            ]
+        })
+    });
+    let format_value = move |value: f64| {
+        if as_percentage.get_untracked() {
+            format!("{value:.0}%")
+        } else {
+            format_compact(value)
+        }
+    };
+
+    view! {
+        <div class="mb-5 p-4 bg-white border border-gray-200 rounded-xl shadow-sm dark:bg-slate-900 dark:border-gray-700">
+            <div class="flex justify-between items-center pb-2">
+                <h2 class="text-sm font-semibold text-gray-800 dark:text-gray-200">
+                    {move || {
+                        if as_percentage.get() {
+                            "Authentication pass rate per day"
+                        } else {
+                            "Messages passing and failing authentication per day"
+                        }
+                    }}
+
+                </h2>
+                <div class="inline-flex rounded-lg shadow-sm">
+                    {[("Count", false), ("Pass rate", true)]
+                        .into_iter()
+                        .map(|(label, percentage)| {
+                            view! {
+                                <button
+                                    type="button"
+                                    class="py-1.5 px-3 inline-flex items-center text-xs font-medium first:rounded-s-lg last:rounded-e-lg border border-gray-200 text-gray-800 hover:bg-gray-50 -ms-px dark:border-gray-700 dark:text-white dark:hover:bg-gray-800"
+                                    class:bg-gray-100=move || as_percentage.get() == percentage
+                                    on:click=move |_| as_percentage.set(percentage)
+                                >
+                                    {label}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+            <Chart series=series kind=ChartKind::Bar format_value=format_value/>
+        </div>
+    }
+}
+
 enum Item {
     Single(String),
     Double((String, String)),
@@ -374,6 +683,7 @@ enum Item {
 
 #[component]
 fn ReportItem(report: IncomingReportSummary) -> impl IntoView {
+    let reviewed = expect_context::<ReviewedReports>();
     let (show_url, item_id, columns) = match report {
         IncomingReportSummary::Dmarc {
             id,
@@ -499,8 +809,11 @@ fn ReportItem(report: IncomingReportSummary) -> impl IntoView {
         })
         .collect_view();
 
+    let id = store_value(item_id.clone());
+    let is_reviewed = move || reviewed.is_reviewed(&id.get_value());
+
     view! {
-        <tr>
+        <tr class:opacity-50=is_reviewed>
             <ListItem>
                 <label class="flex">
                     <SelectItem item_id=item_id/>
@@ -511,13 +824,20 @@ fn ReportItem(report: IncomingReportSummary) -> impl IntoView {
 
             {columns}
 
-            <ListItem subclass="px-6 py-1.5">
+            <ListItem subclass="px-6 py-1.5 flex gap-x-3">
                 <a
                     class="inline-flex items-center gap-x-1 text-sm text-blue-600 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
                     href=show_url
                 >
                     Show
                 </a>
+                <button
+                    type="button"
+                    class="inline-flex items-center gap-x-1 text-sm text-gray-500 decoration-2 hover:underline font-medium dark:focus:outline-none dark:focus:ring-1 dark:focus:ring-gray-600"
+                    on:click=move |_| reviewed.set_reviewed(&id.get_value(), !is_reviewed())
+                >
+                    {move || if is_reviewed() { "Mark unreviewed" } else { "Mark reviewed" }}
+                </button>
             </ListItem>
         </tr>
     }
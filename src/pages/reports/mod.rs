@@ -4,13 +4,22 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
+use leptos::{create_rw_signal, RwSignal, SignalUpdate, SignalWith};
 use serde::{Deserialize, Serialize};
 
-use super::queue::reports::{ActionDisposition, Feedback, FeedbackType, Report, TlsReport};
+use super::queue::reports::{
+    ActionDisposition, DmarcResult, Feedback, FeedbackType, Report, TlsReport,
+};
 
 pub mod display;
 pub mod list;
+pub mod tls;
+
+const REVIEWED_KEY: &str = "webadmin_reviewed_reports";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReportType {
@@ -31,6 +40,8 @@ pub enum IncomingReportSummary {
         total_passes: u32,
         total_rejects: u32,
         total_quarantined: u32,
+        messages_passed: u32,
+        messages_failed: u32,
     },
     Tls {
         id: String,
@@ -86,8 +97,17 @@ impl IncomingReportSummary {
         let mut total_passes = 0;
         let mut total_quarantined = 0;
         let mut total_rejects = 0;
+        let mut messages_passed = 0;
+        let mut messages_failed = 0;
 
         for record in report.report.record {
+            let policy = &record.row.policy_evaluated;
+            if policy.dkim == DmarcResult::Pass || policy.spf == DmarcResult::Pass {
+                messages_passed += record.row.count;
+            } else {
+                messages_failed += record.row.count;
+            }
+
             match record.row.policy_evaluated.disposition {
                 ActionDisposition::Pass
                 | ActionDisposition::None
@@ -115,6 +135,8 @@ impl IncomingReportSummary {
             total_passes,
             total_rejects,
             total_quarantined,
+            messages_passed,
+            messages_failed,
         }
     }
 
@@ -169,3 +191,32 @@ pub(super) fn parse_report_date(id: &str) -> DateTime<Utc> {
     )
     .unwrap_or_else(Utc::now)
 }
+
+// The server keeps no read flag for reports, so their ids are stored in the browser
+#[derive(Clone, Copy)]
+pub struct ReviewedReports(RwSignal<HashSet<String>>);
+
+impl ReviewedReports {
+    pub fn load() -> Self {
+        Self(create_rw_signal(
+            LocalStorage::get(REVIEWED_KEY).unwrap_or_default(),
+        ))
+    }
+
+    pub fn is_reviewed(&self, id: &str) -> bool {
+        self.0.with(|reviewed| reviewed.contains(id))
+    }
+
+    pub fn set_reviewed(&self, id: &str, reviewed: bool) {
+        self.0.update(|ids| {
+            if reviewed {
+                ids.insert(id.to_string());
+            } else {
+                ids.remove(id);
+            }
+            if let Err(err) = LocalStorage::set(REVIEWED_KEY, &*ids) {
+                log::warn!("Failed to save reviewed reports: {err}");
+            }
+        });
+    }
+}
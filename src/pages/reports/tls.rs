@@ -0,0 +1,305 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs Ltd <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use leptos_router::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        chart::{Chart, ChartKind, ChartSeries, Points},
+        list::table::{Table, TableRow},
+        messages::alert::{use_alerts, Alert},
+        skeleton::Skeleton,
+        Color,
+    },
+    core::{api::use_api, http, url::UrlBuilder},
+    pages::{
+        maybe_plural,
+        queue::reports::{ResultType, TlsReport},
+        List,
+    },
+};
+
+use super::IncomingReport;
+
+const SUMMARY_SIZE: u32 = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct TlsSummary {
+    total_reports: usize,
+    success: Points,
+    failure: Points,
+    domains: Vec<DomainSummary>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DomainSummary {
+    domain: String,
+    total_success: u32,
+    total_failure: u32,
+    failures: Vec<(ResultType, u32)>,
+}
+
+#[component]
+pub fn TlsReportSummary() -> impl IntoView {
+    let api = use_api();
+    let alert = use_alerts();
+    let selected = create_rw_signal(None::<String>);
+
+    let summary = create_resource(
+        || (),
+        move |_| async move {
+            let ids = api
+                .get("/api/reports/tls")
+                .with_parameter("page", "1")
+                .with_parameter("limit", SUMMARY_SIZE.to_string())
+                .send::<List<String>>()
+                .await?;
+            let mut summary = TlsSummary::default();
+
+            for id in ids.items {
+                if let Some(report) = api
+                    .get(format!("/api/reports/tls/{id}"))
+                    .try_send::<IncomingReport<TlsReport>>()
+                    .await?
+                {
+                    summary.add(report.report);
+                }
+            }
+            summary.finish();
+
+            Ok(summary)
+        },
+    );
+
+    view! {
+        <Transition fallback=Skeleton>
+            {move || match summary.get() {
+                None => None,
+                Some(Err(http::Error::Unauthorized)) => {
+                    use_navigate()("/login", Default::default());
+                    Some(view! { <div></div> }.into_view())
+                }
+                Some(Err(err)) => {
+                    alert.set(Alert::from(err));
+                    None
+                }
+                Some(Ok(summary)) if summary.total_reports == 0 => None,
+                Some(Ok(summary)) => {
+                    let sessions = vec![
+                        ChartSeries::new("Successful sessions", summary.success)
+                            .with_color(Color::Green),
+                        ChartSeries::new("Failed sessions", summary.failure)
+                            .with_color(Color::Red),
+                    ];
+                    let domains = summary.domains;
+                    let drill_down = domains.clone();
+                    Some(
+                        view! {
+                            <div class="mb-5 p-4 bg-white border border-gray-200 rounded-xl shadow-sm dark:bg-slate-900 dark:border-gray-700">
+                                <h2 class="pb-2 text-sm font-semibold text-gray-800 dark:text-gray-200">
+                                    {format!(
+                                        "TLS sessions in the last {}",
+                                        maybe_plural(summary.total_reports, "report", "reports"),
+                                    )}
+                                </h2>
+                                <Chart
+                                    series=Signal::derive(move || sessions.clone())
+                                    kind=ChartKind::Bar
+                                />
+                                <div class="mt-4">
+                                    <Table headers=vec![
+                                        "Policy domain".to_string(),
+                                        "Successes".to_string(),
+                                        "Failures".to_string(),
+                                        "Failure rate".to_string(),
+                                        "".to_string(),
+                                    ]>
+                                        {domains
+                                            .into_iter()
+                                            .map(|domain| {
+                                                let href = UrlBuilder::new("/manage/reports/tls")
+                                                    .with_parameter("filter", domain.domain.clone())
+                                                    .finish();
+                                                let failure_rate = domain.failure_rate();
+                                                let has_failures = !domain.failures.is_empty();
+                                                let name = store_value(domain.domain.clone());
+                                                let is_selected = move || {
+                                                    selected.get() == Some(name.get_value())
+                                                };
+                                                view! {
+                                                    <TableRow>
+                                                        <A
+                                                            href=href
+                                                            class="text-blue-600 hover:underline dark:text-blue-500"
+                                                        >
+                                                            {domain.domain}
+                                                        </A>
+                                                        <span>{domain.total_success}</span>
+                                                        <span>{domain.total_failure}</span>
+                                                        <span>{failure_rate}</span>
+                                                        <button
+                                                            type="button"
+                                                            class="text-sm text-blue-600 decoration-2 hover:underline font-medium dark:text-blue-500"
+                                                            class:hidden=!has_failures
+                                                            on:click=move |_| {
+                                                                let name = (!is_selected()).then(|| name.get_value());
+                                                                selected.set(name);
+                                                            }
+                                                        >
+
+                                                            {move || {
+                                                                if is_selected() { "Hide failures" } else { "Show failures" }
+                                                            }}
+
+                                                        </button>
+                                                    </TableRow>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </Table>
+                                </div>
+                                {move || {
+                                    let selected = selected.get()?;
+                                    let domain = drill_down
+                                        .iter()
+                                        .find(|domain| domain.domain == selected)?
+                                        .clone();
+                                    Some(
+                                        view! {
+                                            <div class="mt-4">
+                                                <h3 class="pb-2 text-sm font-semibold text-gray-800 dark:text-gray-200">
+                                                    {format!("Failures for {}", &domain.domain)}
+                                                </h3>
+                                                <Table headers=vec![
+                                                    "Result".to_string(),
+                                                    "Sessions".to_string(),
+                                                    "Share".to_string(),
+                                                    "Description".to_string(),
+                                                ]>
+                                                    {domain
+                                                        .failures
+                                                        .iter()
+                                                        .map(|(result_type, sessions)| {
+                                                            let share = domain.failure_share(*sessions);
+                                                            let result_type = *result_type;
+                                                            let sessions = *sessions;
+                                                            view! {
+                                                                <TableRow>
+                                                                    <span class="font-medium">
+                                                                        {result_type.to_string()}
+                                                                    </span>
+                                                                    <span>{sessions}</span>
+                                                                    <span>{share}</span>
+                                                                    <span class="block max-w-md truncate text-gray-500">
+                                                                        {result_type.description()}
+                                                                    </span>
+                                                                </TableRow>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </Table>
+                                            </div>
+                                        },
+                                    )
+                                }}
+
+                            </div>
+                        }
+                            .into_view(),
+                    )
+                }
+            }}
+
+        </Transition>
+    }
+}
+
+impl TlsSummary {
+    fn add(&mut self, report: TlsReport) {
+        let date = report.date_range.start_datetime;
+        let mut total_success = 0;
+        let mut total_failure = 0;
+
+        for policy in report.policies {
+            total_success += policy.summary.total_success;
+            total_failure += policy.summary.total_failure;
+
+            let failures = policy.failures_by_type();
+            let domain = match self
+                .domains
+                .iter()
+                .position(|domain| domain.domain == policy.policy.policy_domain)
+            {
+                Some(idx) => &mut self.domains[idx],
+                None => {
+                    self.domains.push(DomainSummary {
+                        domain: policy.policy.policy_domain,
+                        ..Default::default()
+                    });
+                    self.domains.last_mut().unwrap()
+                }
+            };
+            domain.total_success += policy.summary.total_success;
+            domain.total_failure += policy.summary.total_failure;
+            for (result_type, sessions) in failures {
+                match domain
+                    .failures
+                    .iter_mut()
+                    .find(|(other, _)| *other == result_type)
+                {
+                    Some((_, total)) => *total += sessions,
+                    None => domain.failures.push((result_type, sessions)),
+                }
+            }
+        }
+
+        self.success.push((date, total_success as f64));
+        self.failure.push((date, total_failure as f64));
+        self.total_reports += 1;
+    }
+
+    fn finish(&mut self) {
+        self.success.sort_by_key(|(date, _)| *date);
+        self.failure.sort_by_key(|(date, _)| *date);
+        // Domains with the most failures first
+        self.domains.sort_by(|a, b| {
+            b.total_failure
+                .cmp(&a.total_failure)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        for domain in &mut self.domains {
+            domain
+                .failures
+                .sort_by_key(|(_, sessions)| std::cmp::Reverse(*sessions));
+        }
+    }
+}
+
+impl DomainSummary {
+    fn failure_rate(&self) -> String {
+        let total = self.total_success + self.total_failure;
+        if total > 0 {
+            format!("{:.1}%", self.total_failure as f64 * 100.0 / total as f64)
+        } else {
+            "-".to_string()
+        }
+    }
+
+    fn failure_share(&self, sessions: u32) -> String {
+        let total = self
+            .failures
+            .iter()
+            .map(|(_, sessions)| sessions)
+            .sum::<u32>();
+        if total > 0 {
+            format!("{:.1}%", sessions as f64 * 100.0 / total as f64)
+        } else {
+            "-".to_string()
+        }
+    }
+}
@@ -16,8 +16,13 @@ module.exports = {
       //https://play.tailwindcss.com/VCZwwz1e3R
       animation: {
         text: 'text 5s ease infinite',
+        'toast-in': 'toast-in 200ms ease-out',
       },
       keyframes: {
+        'toast-in': {
+          '0%': { opacity: '0', transform: 'translateY(-0.5rem)' },
+          '100%': { opacity: '1', transform: 'translateY(0)' },
+        },
         text: {
           '0%, 100%': {
             'background-size': '200% 200%',